@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use diskann_impl::{IndexBuilder, VamanaConfig};
+use diskann_impl::{compute_groundtruth, recall_at_k, IndexBuilder, VamanaConfig};
 use diskann_traits::{distance::EuclideanDistance, search::{Search, SearchBuffer}};
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
@@ -176,38 +176,38 @@ fn bench_recall_quality(c: &mut Criterion) {
     let dimension = 128;
     let num_queries = 50;
     let k = 10;
-    
+
     let vectors = generate_test_data(num_vectors, dimension, 42);
     let queries = generate_queries(num_queries, dimension, 42);
-    
+
+    // Exact brute-force ground truth, computed once against the same base
+    // set and queries the index below was built and searched with.
+    let groundtruth = compute_groundtruth(&vectors, &queries, k, &EuclideanDistance);
+
     let config = VamanaConfig {
         max_degree: 64,
         search_list_size: 100,
         alpha: 1.2,
         seed: 42,
     };
-    
+
     let index = build_index(vectors, config);
-    
+    let beam_width = 64;
+
     c.bench_function("recall_at_10", |b| {
         b.iter(|| {
-            let mut total_recall = 0.0;
-            for query in &queries {
-                // For this benchmark, we'll compare different beam widths
-                let baseline_results = index.search_with_beam(query, k * 2, 128).unwrap();
-                let test_results = index.search_with_beam(query, k, 64).unwrap();
-                
-                // Calculate recall (simplified)
-                let baseline_ids: std::collections::HashSet<_> = 
-                    baseline_results.iter().take(k).map(|r| r.id).collect();
-                let test_ids: std::collections::HashSet<_> = 
-                    test_results.iter().take(k).map(|r| r.id).collect();
-                
-                let intersection = baseline_ids.intersection(&test_ids).count();
-                let recall = intersection as f64 / k as f64;
-                total_recall += recall;
-            }
-            black_box(total_recall / queries.len() as f64)
+            let results: Vec<_> = queries
+                .iter()
+                .map(|query| {
+                    index
+                        .search_with_beam(query, k, beam_width)
+                        .unwrap()
+                        .into_iter()
+                        .map(|r| r.id)
+                        .collect()
+                })
+                .collect();
+            black_box(recall_at_k(&groundtruth, &results, k, false))
         });
     });
 }
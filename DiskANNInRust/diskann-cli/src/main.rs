@@ -9,9 +9,14 @@ use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
 
-use diskann_impl::IndexBuilder;
-use diskann_traits::{distance::EuclideanDistance, index::Index, search::Search};
-use diskann_io::{write_vectors_f32, read_vectors_f32};
+use diskann_impl::{DiskVamanaConfig, DiskVamanaIndex, IndexBuilder};
+use diskann_traits::{
+    distance::{CosineDistance, EuclideanDistance, InnerProductDistance, ManhattanDistance},
+    index::Index,
+    search::Search,
+};
+use diskann_io::read_vectors_f32;
+use diskann_io::{write_lz4_container, CompressionMode, Lz4ContainerOpts};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -20,6 +25,47 @@ struct Cli {
     command: Commands,
 }
 
+/// `--compress` choices for `Commands::Build`, mapped to `diskann_io::CompressionMode`
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum CompressArg {
+    /// Don't write a compressed vector container
+    None,
+    /// LZ4-compress the vector container at the standard effort level
+    Lz4,
+    /// LZ4-compress the vector container at a higher effort level
+    Lz4hc,
+}
+
+impl From<CompressArg> for CompressionMode {
+    fn from(arg: CompressArg) -> Self {
+        match arg {
+            CompressArg::None => CompressionMode::None,
+            CompressArg::Lz4 => CompressionMode::Lz4,
+            CompressArg::Lz4hc => CompressionMode::Lz4Hc,
+        }
+    }
+}
+
+/// `--metric` choices for `Commands::Build`
+///
+/// `DiskVamanaIndex`'s on-disk node records are always `f32`, so only the metrics that
+/// implement `Distance<f32>` can actually build and save an index; `Hamming` is accepted as a
+/// flag value (it's a real `Distance<u8>` impl in `diskann_traits::distance`) but rejected at
+/// build time with an explanation, since there's no bit-packed on-disk format for it yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum MetricArg {
+    /// Euclidean (L2) distance
+    L2,
+    /// Inner product (maximum inner product search)
+    Ip,
+    /// Cosine distance
+    Cosine,
+    /// Manhattan (L1) distance
+    L1,
+    /// Hamming distance over bit-packed vectors (not yet supported end-to-end, see above)
+    Hamming,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Build an index from vector data
@@ -42,6 +88,13 @@ enum Commands {
         /// Random seed for deterministic behavior
         #[arg(long, default_value_t = 42)]
         seed: u64,
+        /// Distance metric to build the index with
+        #[arg(long, value_enum, default_value_t = MetricArg::L2)]
+        metric: MetricArg,
+        /// Also write the input vectors to `<output>.vectors.lz4` as a compressed,
+        /// randomly-accessible block container (see `diskann_io::lz4_container`)
+        #[arg(long, value_enum, default_value_t = CompressArg::None)]
+        compress: CompressArg,
     },
     /// Search for nearest neighbors
     Search {
@@ -145,53 +198,85 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Build { 
-            input, 
-            output, 
+        Commands::Build {
+            input,
+            output,
             max_degree,
             search_list_size,
             alpha,
             seed,
+            metric,
+            compress,
         } => {
             info!("Building index from {} to {}", input, output);
-            info!("Parameters: max_degree={}, search_list_size={}, alpha={}, seed={}", 
-                  max_degree, search_list_size, alpha, seed);
+            info!("Parameters: max_degree={}, search_list_size={}, alpha={}, seed={}, metric={:?}",
+                  max_degree, search_list_size, alpha, seed, metric);
+
+            if metric == MetricArg::Hamming {
+                bail!(
+                    "--metric hamming isn't supported by `build` yet: DiskVamanaIndex's on-disk \
+                     node records are f32, and Hamming distance operates on bit-packed u8 vectors"
+                );
+            }
 
             // Load vectors
             let vectors = load_vectors_from_file(&input)
                 .context("Failed to load input vectors")?;
-            
+
             info!("Loaded {} vectors", vectors.len());
 
-            // Build index
-            let distance_fn = EuclideanDistance;
-            
-            // Clone vectors for saving before building index
-            let vector_data_for_save: Vec<Vec<f32>> = vectors.iter().map(|(_, v)| v.clone()).collect();
-            
-            let index = IndexBuilder::new(distance_fn)
-                .max_degree(max_degree)
-                .search_list_size(search_list_size)
-                .alpha(alpha)
-                .seed(seed)
-                .build(vectors)
-                .context("Failed to build index")?;
-
-            info!("Built index with {} nodes, average degree: {:.2}", 
-                  index.size(), index.average_degree());
-
-            // Save index to file - for now, we'll save the original vectors
-            // In the future, this would save the full graph structure
-            info!("Saving index to {}", output);
-            let output_file = File::create(&output)
-                .with_context(|| format!("Failed to create output file: {}", output))?;
-            let mut writer = std::io::BufWriter::new(output_file);
-            
-            // Save the original vectors (simple approach for demo)
-            write_vectors_f32(&mut writer, &vector_data_for_save)
-                .context("Failed to save index to file")?;
-            
+            let container_vectors: Vec<Vec<f32>> = if matches!(compress, CompressArg::None) {
+                Vec::new()
+            } else {
+                vectors.iter().map(|(_, vector)| vector.clone()).collect()
+            };
+
+            // Build and persist the real graph structure (vectors + adjacency lists), not just
+            // the raw vectors, so `search` can mmap it back without rebuilding the index from
+            // scratch. The chosen metric's name is recorded in the saved file's header (see
+            // `MetricTag::from_name`), so `search` picks the same one back up automatically.
+            macro_rules! build_and_save {
+                ($distance_fn:expr) => {{
+                    let index = IndexBuilder::new($distance_fn)
+                        .max_degree(max_degree)
+                        .search_list_size(search_list_size)
+                        .alpha(alpha)
+                        .seed(seed)
+                        .build(vectors)
+                        .context("Failed to build index")?;
+
+                    info!("Built index with {} nodes, average degree: {:.2}",
+                          index.size(), index.average_degree());
+
+                    info!("Saving index to {}", output);
+                    index.save(Path::new(&output))
+                        .with_context(|| format!("Failed to save index to {}", output))?;
+                }};
+            }
+
+            match metric {
+                MetricArg::L2 => build_and_save!(EuclideanDistance),
+                MetricArg::Ip => build_and_save!(InnerProductDistance),
+                MetricArg::Cosine => build_and_save!(CosineDistance),
+                MetricArg::L1 => build_and_save!(ManhattanDistance),
+                MetricArg::Hamming => unreachable!("rejected above"),
+            }
+
             info!("Index saved successfully to {}", output);
+
+            if !matches!(compress, CompressArg::None) {
+                let vectors_path = format!("{}.vectors.lz4", output);
+                let mut file = File::create(&vectors_path)
+                    .with_context(|| format!("Failed to create {}", vectors_path))?;
+                write_lz4_container(
+                    &mut file,
+                    &container_vectors,
+                    Lz4ContainerOpts { mode: compress.into(), ..Lz4ContainerOpts::default() },
+                )
+                .with_context(|| format!("Failed to write compressed vectors to {}", vectors_path))?;
+                info!("Compressed vectors saved to {}", vectors_path);
+            }
+
             println!("Index building completed successfully!");
         }
         Commands::Search { 
@@ -204,31 +289,23 @@ fn main() -> Result<()> {
             info!("Searching index {} with query {} for {} neighbors (beam={})", 
                   index_path, query_path, k, beam);
 
-            // Load index from file
-            let index = if Path::new(&index_path).exists() {
+            // Load query
+            let query = load_query_from_file(&query_path)
+                .context("Failed to load query vector")?;
+
+            info!("Loaded query vector of dimension {}", query.len());
+
+            // Mmap the saved graph back and search it directly, rather than rebuilding the
+            // index from its raw vectors on every search.
+            let result_pairs: Vec<(u32, f32)> = if Path::new(&index_path).exists() {
                 info!("Loading index from {}", index_path);
-                let index_file = File::open(&index_path)
+                let disk_index = DiskVamanaIndex::open(Path::new(&index_path), DiskVamanaConfig::default())
                     .with_context(|| format!("Failed to open index file: {}", index_path))?;
-                let mut reader = BufReader::new(index_file);
-                
-                // Load vectors and rebuild index
-                let loaded_vectors = read_vectors_f32(&mut reader)
-                    .context("Failed to load vectors from index file")?;
-                
-                info!("Loaded {} vectors from index", loaded_vectors.len());
-                
-                let distance_fn = EuclideanDistance;
-                let vector_data: Vec<(u32, Vec<f32>)> = loaded_vectors
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, v)| (i as u32, v))
-                    .collect();
-                
-                IndexBuilder::new(distance_fn)
-                    .max_degree(64)
-                    .search_list_size(128)
-                    .build(vector_data)
-                    .context("Failed to rebuild index from loaded vectors")?
+
+                info!("Loaded disk index with {} nodes", disk_index.size());
+
+                disk_index.search(&query, k, beam)
+                    .context("Search failed")?
             } else {
                 // Create demo index if file doesn't exist
                 info!("Index file not found, creating demo index for testing");
@@ -241,30 +318,24 @@ fn main() -> Result<()> {
                     (4, vec![0.0, 0.5, 0.5]),
                 ];
 
-                IndexBuilder::new(distance_fn)
+                let index = IndexBuilder::new(distance_fn)
                     .max_degree(32)
                     .search_list_size(64)
                     .build(demo_vectors)
-                    .context("Failed to create demo index")?
-            };
-
-            // Load query
-            let query = load_query_from_file(&query_path)
-                .context("Failed to load query vector")?;
-            
-            info!("Loaded query vector of dimension {}", query.len());
+                    .context("Failed to create demo index")?;
 
-            // Perform search
-            let results = index.search_with_beam(&query, k, beam)
-                .context("Search failed")?;
+                index.search_with_beam(&query, k, beam)
+                    .context("Search failed")?
+                    .into_iter()
+                    .map(|result| (result.id, result.distance))
+                    .collect()
+            };
 
             // Display results
             println!("Search Results:");
             println!("ID\tDistance");
-            let mut result_pairs = Vec::new();
-            for (i, result) in results.iter().enumerate() {
-                println!("{}\t{:.6}", result.id, result.distance);
-                result_pairs.push((result.id, result.distance));
+            for (i, (id, distance)) in result_pairs.iter().enumerate() {
+                println!("{}\t{:.6}", id, distance);
                 if i >= k - 1 {
                     break;
                 }
@@ -41,6 +41,28 @@ fn benchmark_math_functions(c: &mut Criterion) {
     });
 }
 
+/// Compares the generic scalar fold against the `simd`-feature dispatch path
+/// for `dot_product`/`l2_norm_squared` at several sizes -- both call through
+/// the same public functions, so this measures whatever `Float::simd_*`
+/// resolves to for the running build (SIMD kernel with `simd` enabled,
+/// scalar fold otherwise).
+fn benchmark_math_simd_dispatch(c: &mut Criterion) {
+    let sizes = [64, 128, 256, 512, 1024];
+
+    for &size in &sizes {
+        let vector_a: Vec<f32> = (0..size).map(|i| (i as f32 * 0.1).sin()).collect();
+        let vector_b: Vec<f32> = (0..size).map(|i| (i as f32 * 0.1).cos()).collect();
+
+        c.bench_function(&format!("dot_product_dispatch_{}", size), |b| {
+            b.iter(|| dot_product(black_box(&vector_a), black_box(&vector_b)))
+        });
+
+        c.bench_function(&format!("l2_norm_squared_dispatch_{}", size), |b| {
+            b.iter(|| l2_norm_squared(black_box(&vector_a)))
+        });
+    }
+}
+
 fn benchmark_distance_functions(c: &mut Criterion) {
     let euclidean = EuclideanDistance;
     let manhattan = ManhattanDistance;
@@ -112,15 +134,19 @@ fn benchmark_utils_functions(c: &mut Criterion) {
 }
 
 fn benchmark_aligned_allocation(c: &mut Criterion) {
+    // `aligned_vec!` returns `Vec<f32>` normally, or a genuinely-aligned
+    // `AlignedBuffer<f32>` when the `simd` feature is on -- leave the
+    // binding's type to be inferred rather than annotating it, so this
+    // benchmark builds either way.
     c.bench_function("aligned_vec_f32_128", |b| {
         b.iter(|| {
-            let _vec: Vec<f32> = aligned_vec![f32; black_box(128)];
+            let _vec = aligned_vec![f32; black_box(128)];
         })
     });
-    
+
     c.bench_function("aligned_vec_f32_1024", |b| {
         b.iter(|| {
-            let _vec: Vec<f32> = aligned_vec![f32; black_box(1024)];
+            let _vec = aligned_vec![f32; black_box(1024)];
         })
     });
     
@@ -137,6 +163,7 @@ fn benchmark_simd_functions(c: &mut Criterion) {
         l2_squared_distance_scalar, l2_squared_distance_dispatch,
         inner_product_distance_scalar, inner_product_distance_dispatch,
         l2_squared_distance_simd, inner_product_distance_simd,
+        l1_distance_scalar, l1_distance_dispatch, l1_distance_simd,
     };
     
     // Test different vector sizes to see SIMD effectiveness
@@ -171,6 +198,19 @@ fn benchmark_simd_functions(c: &mut Criterion) {
         c.bench_function(&format!("inner_product_simd_{}", size), |b| {
             b.iter(|| inner_product_distance_simd(black_box(&vector_a), black_box(&vector_b)))
         });
+
+        // Benchmark L1 (Manhattan) distance
+        c.bench_function(&format!("l1_scalar_{}", size), |b| {
+            b.iter(|| l1_distance_scalar(black_box(&vector_a), black_box(&vector_b)))
+        });
+
+        c.bench_function(&format!("l1_dispatch_{}", size), |b| {
+            b.iter(|| l1_distance_dispatch(black_box(&vector_a), black_box(&vector_b)))
+        });
+
+        c.bench_function(&format!("l1_simd_{}", size), |b| {
+            b.iter(|| l1_distance_simd(black_box(&vector_a), black_box(&vector_b)))
+        });
     }
     
     // AVX2-specific benchmarks if available
@@ -192,7 +232,16 @@ fn benchmark_simd_functions(c: &mut Criterion) {
             c.bench_function("inner_product_avx2_512", |b| {
                 b.iter(|| unsafe {
                     diskann_core::simd::inner_product_distance_avx2(
-                        black_box(&vector_a), 
+                        black_box(&vector_a),
+                        black_box(&vector_b)
+                    )
+                })
+            });
+
+            c.bench_function("l1_avx2_512", |b| {
+                b.iter(|| unsafe {
+                    diskann_core::simd::l1_distance_avx2(
+                        black_box(&vector_a),
                         black_box(&vector_b)
                     )
                 })
@@ -207,8 +256,9 @@ fn benchmark_simd_functions(_c: &mut Criterion) {
 }
 
 criterion_group!(
-    benches, 
+    benches,
     benchmark_math_functions,
+    benchmark_math_simd_dispatch,
     benchmark_distance_functions,
     benchmark_utils_functions,
     benchmark_aligned_allocation,
@@ -2,6 +2,14 @@
 //!
 //! This module provides macros for creating vectors with specific memory alignment
 //! without using unsafe code, ensuring 32-byte alignment for SIMD operations.
+//!
+//! [`create_aligned_vec`] is best-effort: it copies the over-allocated data
+//! into a fresh `Vec`, whose own address is whatever the global allocator
+//! hands back, so the vector it returns isn't actually guaranteed to be
+//! aligned. When the `simd` feature is enabled -- the same feature that
+//! relaxes this crate's `forbid(unsafe_code)` (see `lib.rs`) -- [`aligned_vec!`]
+//! instead builds an [`AlignedBuffer`], whose alignment is a real guarantee
+//! backed by `align_offset` against its own allocation.
 
 #[cfg(not(feature = "std"))]
 use alloc::{vec::Vec, vec};
@@ -28,15 +36,39 @@ use crate::utils::round_up;
 macro_rules! aligned_vec {
     // aligned_vec![T; n] - create vector of n default elements
     ($t:ty; $n:expr) => {{
-        $crate::alignment::create_aligned_vec::<$t>($n, None)
+        #[cfg(feature = "simd")]
+        {
+            $crate::alignment::AlignedBuffer::<$t>::with_capacity($n)
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            $crate::alignment::create_aligned_vec::<$t>($n, None)
+        }
     }};
-    
+
     // aligned_vec![value; n] - create vector of n copies of value
     ($value:expr; $n:expr) => {{
-        $crate::alignment::create_aligned_vec($n, Some($value))
+        #[cfg(feature = "simd")]
+        {
+            $crate::alignment::AlignedBuffer::from_slice(&$crate::alignment::__aligned_vec_fill($value, $n))
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            $crate::alignment::create_aligned_vec($n, Some($value))
+        }
     }};
 }
 
+/// Helper for the [`aligned_vec!`] `[value; n]` arm: builds the plain `Vec<T>`
+/// of `n` clones of `value` that [`AlignedBuffer::from_slice`] then copies
+/// into aligned storage. Not part of the public API; only exported so the
+/// macro can reach it from callers' crates.
+#[doc(hidden)]
+#[cfg(feature = "simd")]
+pub fn __aligned_vec_fill<T: Clone>(value: T, n: usize) -> Vec<T> {
+    vec![value; n]
+}
+
 /// Internal function to create aligned vectors
 /// 
 /// This function creates a vector with 32-byte alignment by using padding
@@ -92,6 +124,216 @@ pub fn create_aligned_vec<T: Clone + Default>(
     aligned_vec
 }
 
+/// Marker for types where every bit pattern is a valid value -- the
+/// guarantee [`AlignedBuffer`] needs to reinterpret freshly allocated (or
+/// zeroed) bytes as `&[T]`/`&mut [T]` without ever materializing a value
+/// that isn't actually legal for `T`. Implemented for the scalar
+/// vector-element types this crate stores; deliberately not implemented for
+/// composite types, where padding bytes or a field with its own validity
+/// invariants would make the reinterpretation unsound.
+///
+/// # Safety
+/// Implementors must be valid for any bit pattern of their size: no padding
+/// bytes, and no value ranges narrower than the full bit pattern.
+#[cfg(feature = "simd")]
+pub unsafe trait FromBytes: Copy {}
+
+#[cfg(feature = "simd")]
+unsafe impl FromBytes for f32 {}
+#[cfg(feature = "simd")]
+unsafe impl FromBytes for u8 {}
+#[cfg(feature = "simd")]
+unsafe impl FromBytes for i8 {}
+#[cfg(feature = "simd")]
+unsafe impl FromBytes for u16 {}
+
+/// A `Vec<u8>`-backed buffer whose element view starts at a genuinely
+/// `ALIGN`-byte-aligned address.
+///
+/// Unlike [`create_aligned_vec`], which copies into a fresh `Vec` whose
+/// address the allocator controls (and so isn't actually guaranteed to be
+/// aligned), `AlignedBuffer` allocates `len * size_of::<T>() + ALIGN` bytes,
+/// computes [`align_offset`](pointer::align_offset) against that allocation,
+/// and keeps the offset alongside the backing bytes so `as_slice`/
+/// `as_mut_slice` can hand back a view that starts at the aligned address.
+/// `ALIGN` defaults to 32 (AVX2 width); pass 64 for AVX-512-width kernels.
+#[cfg(feature = "simd")]
+pub struct AlignedBuffer<T: FromBytes, const ALIGN: usize = 32> {
+    bytes: Vec<u8>,
+    align_offset: usize,
+    len: usize,
+    _marker: core::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "simd")]
+impl<T: FromBytes, const ALIGN: usize> AlignedBuffer<T, ALIGN> {
+    /// Wrap `bytes` (at least `len * size_of::<T>() + ALIGN` bytes long),
+    /// recomputing `align_offset` against its actual address -- the offset
+    /// found for one allocation doesn't carry over to another, which is why
+    /// `clone` calls this again rather than copying `align_offset` verbatim.
+    fn from_backing(bytes: Vec<u8>, len: usize) -> Self {
+        debug_assert!(ALIGN.is_power_of_two());
+        debug_assert!(ALIGN >= core::mem::align_of::<T>());
+        debug_assert!(bytes.len() >= len * core::mem::size_of::<T>() + ALIGN);
+
+        let align_offset = bytes.as_ptr().align_offset(ALIGN);
+        Self {
+            bytes,
+            align_offset,
+            len,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// `len` zero-initialized elements of `T`. Allocates with `vec![0u8;
+    /// ..]` rather than `Vec::with_capacity` plus a manual fill, so the
+    /// allocator can hand back already-zeroed pages instead of
+    /// malloc-then-memset.
+    pub fn zeroed(len: usize) -> Self {
+        let byte_len = len * core::mem::size_of::<T>() + ALIGN;
+        Self::from_backing(vec![0u8; byte_len], len)
+    }
+
+    /// `len` default-initialized elements of `T`.
+    pub fn with_capacity(len: usize) -> Self
+    where
+        T: Default,
+    {
+        let mut buffer = Self::zeroed(len);
+        for slot in buffer.as_mut_slice() {
+            *slot = T::default();
+        }
+        buffer
+    }
+
+    /// Copy `data` into freshly aligned storage.
+    pub fn from_slice(data: &[T]) -> Self {
+        let mut buffer = Self::zeroed(data.len());
+        buffer.as_mut_slice().copy_from_slice(data);
+        buffer
+    }
+
+    /// Number of elements the buffer holds.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer holds zero elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The `ALIGN`-aligned element view.
+    pub fn as_slice(&self) -> &[T] {
+        let ptr = self.bytes[self.align_offset..].as_ptr() as *const T;
+        // SAFETY: `align_offset` was computed via `align_offset(ALIGN)` on
+        // this exact allocation (recomputed in `from_backing`, never carried
+        // over from a different one), `ALIGN` is a power of two no smaller
+        // than `align_of::<T>()` so the resulting pointer is `T`-aligned,
+        // `bytes` holds `len * size_of::<T>() + ALIGN` bytes so `len`
+        // elements of `T` fit past the aligned offset, and `T: FromBytes`
+        // guarantees every bit pattern in those bytes is a valid `T`.
+        unsafe { core::slice::from_raw_parts(ptr, self.len) }
+    }
+
+    /// The `ALIGN`-aligned element view, mutable.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let ptr = self.bytes[self.align_offset..].as_mut_ptr() as *mut T;
+        // SAFETY: see `as_slice`; `&mut self` gives exclusive access to `bytes`.
+        unsafe { core::slice::from_raw_parts_mut(ptr, self.len) }
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<T: FromBytes, const ALIGN: usize> Clone for AlignedBuffer<T, ALIGN> {
+    fn clone(&self) -> Self {
+        // A fresh allocation may land at a different address than the one
+        // `self.align_offset` was computed against, so it has to be
+        // recomputed rather than copied verbatim.
+        Self::from_backing(self.bytes.clone(), self.len)
+    }
+}
+
+#[cfg(feature = "simd")]
+impl<T: FromBytes, const ALIGN: usize> core::ops::Deref for AlignedBuffer<T, ALIGN> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+/// SIMD-lane-width store helpers, specialized to `f32` since that's the
+/// element type every distance/PQ kernel in [`crate::simd`] actually works
+/// in -- [`wide::f32x8`], the same stable 8-lane (32-byte) vector type
+/// `simd.rs`'s scalar-dispatch kernels build from, is the "lane vector"
+/// these helpers check alignment against.
+#[cfg(feature = "simd")]
+impl<const ALIGN: usize> AlignedBuffer<f32, ALIGN> {
+    /// Number of `f32` lanes in a [`wide::f32x8`].
+    pub const LANE_COUNT: usize = 8;
+
+    /// Whether this buffer's element view currently starts at an address
+    /// aligned to `lane_bytes`. `ALIGN` is only the alignment this buffer
+    /// was *constructed* to guarantee -- checking the real pointer here lets
+    /// callers fall back to scalar writes if, say, `lane_bytes` asks for
+    /// 64-byte (AVX-512) alignment on a buffer built with `ALIGN = 32`.
+    pub fn is_simd_aligned(&self, lane_bytes: usize) -> bool {
+        self.as_slice().as_ptr() as usize % lane_bytes == 0
+    }
+
+    /// Write `values` into the buffer starting at element index
+    /// `lane_offset * `[`Self::LANE_COUNT`]`, asserting that `values`'s
+    /// length is a whole number of lanes and that the destination is
+    /// aligned to `align_of::<f32x8>()`. Panics if either invariant doesn't
+    /// hold; see [`Self::write_lanes_unchecked`] for a hot-loop variant that
+    /// only `debug_assert`s them.
+    pub fn write_lanes_aligned(&mut self, lane_offset: usize, values: &[f32]) {
+        assert_eq!(
+            values.len() % Self::LANE_COUNT,
+            0,
+            "write_lanes_aligned: {} values is not a multiple of the lane count ({})",
+            values.len(),
+            Self::LANE_COUNT
+        );
+
+        let start = lane_offset * Self::LANE_COUNT;
+        let dest = &mut self.as_mut_slice()[start..start + values.len()];
+        assert_eq!(
+            dest.as_ptr().align_offset(core::mem::align_of::<wide::f32x8>()),
+            0,
+            "write_lanes_aligned: destination at element {start} is not aligned to align_of::<f32x8>()"
+        );
+
+        dest.copy_from_slice(values);
+    }
+
+    /// Same as [`Self::write_lanes_aligned`], but only `debug_assert`s its
+    /// invariants instead of asserting them unconditionally -- for hot loops
+    /// that have already established alignment and lane-count invariants
+    /// hold via [`Self::is_simd_aligned`] and don't want to pay for the
+    /// check on every write in release builds.
+    pub fn write_lanes_unchecked(&mut self, lane_offset: usize, values: &[f32]) {
+        debug_assert_eq!(
+            values.len() % Self::LANE_COUNT,
+            0,
+            "write_lanes_unchecked: {} values is not a multiple of the lane count ({})",
+            values.len(),
+            Self::LANE_COUNT
+        );
+
+        let start = lane_offset * Self::LANE_COUNT;
+        let dest = &mut self.as_mut_slice()[start..start + values.len()];
+        debug_assert_eq!(
+            dest.as_ptr().align_offset(core::mem::align_of::<wide::f32x8>()),
+            0,
+            "write_lanes_unchecked: destination at element {start} is not aligned to align_of::<f32x8>()"
+        );
+
+        dest.copy_from_slice(values);
+    }
+}
+
 /// Check if a vector's data is aligned to the specified boundary
 /// 
 /// # Examples
@@ -246,4 +488,81 @@ mod tests {
         assert_eq!(vec_u64.len(), 32);
         assert!(vec_u64.iter().all(|&x| x == 0xDEADBEEF));
     }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_is_genuinely_aligned() {
+        let buffer = AlignedBuffer::<f32>::zeroed(100);
+        assert_eq!(buffer.len(), 100);
+        assert_eq!(buffer.as_slice().as_ptr() as usize % 32, 0);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_with_capacity_is_default_initialized() {
+        let buffer = AlignedBuffer::<f32>::with_capacity(50);
+        assert_eq!(buffer.len(), 50);
+        assert!(buffer.as_slice().iter().all(|&x| x == 0.0));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_from_slice_copies_values() {
+        let data = [1.0f32, 2.0, 3.0, 4.0];
+        let buffer = AlignedBuffer::<f32>::from_slice(&data);
+        assert_eq!(buffer.as_slice(), &data);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_clone_is_independently_aligned() {
+        let mut original = AlignedBuffer::<f32>::from_slice(&[1.0, 2.0, 3.0]);
+        let mut cloned = original.clone();
+        cloned.as_mut_slice()[0] = 99.0;
+
+        assert_eq!(original.as_slice()[0], 1.0);
+        assert_eq!(cloned.as_slice()[0], 99.0);
+        assert_eq!(cloned.as_slice().as_ptr() as usize % 32, 0);
+        assert_eq!(original.as_slice().as_ptr() as usize % 32, 0);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_64_byte_alignment() {
+        let buffer = AlignedBuffer::<u16, 64>::zeroed(40);
+        assert_eq!(buffer.as_slice().as_ptr() as usize % 64, 0);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_is_simd_aligned_for_its_own_align() {
+        let buffer = AlignedBuffer::<f32>::zeroed(16);
+        assert!(buffer.is_simd_aligned(32));
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_write_lanes_aligned_writes_one_lane() {
+        let mut buffer = AlignedBuffer::<f32>::zeroed(16);
+        let lane = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        buffer.write_lanes_aligned(0, &lane);
+        assert_eq!(&buffer.as_slice()[0..8], &lane);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    #[should_panic(expected = "multiple of the lane count")]
+    fn aligned_buffer_write_lanes_aligned_rejects_partial_lane() {
+        let mut buffer = AlignedBuffer::<f32>::zeroed(16);
+        buffer.write_lanes_aligned(0, &[1.0, 2.0, 3.0]);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn aligned_buffer_write_lanes_unchecked_writes_at_offset() {
+        let mut buffer = AlignedBuffer::<f32>::zeroed(16);
+        let lane = [9.0f32, 9.0, 9.0, 9.0, 9.0, 9.0, 9.0, 9.0];
+        buffer.write_lanes_unchecked(1, &lane);
+        assert_eq!(&buffer.as_slice()[8..16], &lane);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,215 @@
+//! Dense, word-packed bitset for visited-node tracking in graph traversal
+//!
+//! Beam search's inner loop needs a fast "have I seen this node id before"
+//! check over a dense range of ids; a `HashSet` pays for hashing and an
+//! allocation per entry where a packed bit per id is both smaller and more
+//! cache-friendly. [`Bitset`] packs flags into `u64` words and drives
+//! [`Bitset::count`] and [`Bitset::iter_set`] off the hardware-backed
+//! [`popcount_hw`]/[`trailing_zeros_hw`] helpers in [`crate::utils`] rather
+//! than looping bit-by-bit.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::utils::{div_round_up, popcount_hw, trailing_zeros_hw};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A dense bitset over the range `0..capacity`, packed into `u64` words
+///
+/// # Examples
+/// ```
+/// use diskann_core::bitset::Bitset;
+///
+/// let mut visited = Bitset::new(128);
+/// visited.set(5);
+/// visited.set(64);
+/// assert!(visited.contains(5));
+/// assert!(!visited.contains(6));
+/// assert_eq!(visited.count(), 2);
+/// assert_eq!(visited.iter_set().collect::<Vec<_>>(), vec![5, 64]);
+/// ```
+pub struct Bitset {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl Bitset {
+    /// Create a bitset capable of holding ids in `0..capacity`, all
+    /// initially unset
+    pub fn new(capacity: usize) -> Self {
+        let word_count = div_round_up(capacity as u64, WORD_BITS as u64) as usize;
+        Self {
+            words: vec![0u64; word_count],
+            capacity,
+        }
+    }
+
+    /// The number of ids this bitset can hold (the `capacity` passed to
+    /// [`Self::new`])
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Mark `idx` as set
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.capacity()`.
+    pub fn set(&mut self, idx: usize) {
+        assert!(idx < self.capacity, "index {} out of bounds for capacity {}", idx, self.capacity);
+        self.words[idx / WORD_BITS] |= 1u64 << (idx % WORD_BITS);
+    }
+
+    /// Check whether `idx` is set
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx >= self.capacity()`.
+    pub fn contains(&self, idx: usize) -> bool {
+        assert!(idx < self.capacity, "index {} out of bounds for capacity {}", idx, self.capacity);
+        self.words[idx / WORD_BITS] & (1u64 << (idx % WORD_BITS)) != 0
+    }
+
+    /// The number of set bits, computed word-wise via [`popcount_hw`]
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|&word| popcount_hw(word) as usize).sum()
+    }
+
+    /// Clear every bit, without shrinking the backing storage
+    pub fn clear_all(&mut self) {
+        for word in &mut self.words {
+            *word = 0;
+        }
+    }
+
+    /// Iterate the indices of set bits in ascending order
+    ///
+    /// Walks each word with [`trailing_zeros_hw`], clearing the lowest set
+    /// bit (`x &= x - 1`) after yielding it, so each word costs one
+    /// trailing-zeros instruction per set bit rather than a full bit-by-bit
+    /// scan.
+    pub fn iter_set(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(word_idx, &word)| BitsetWordIter {
+                word,
+                base: word_idx * WORD_BITS,
+            })
+    }
+}
+
+struct BitsetWordIter {
+    word: u64,
+    base: usize,
+}
+
+impl Iterator for BitsetWordIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let tz = trailing_zeros_hw(self.word)?;
+        self.word &= self.word - 1;
+        Some(self.base + tz as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_is_empty() {
+        let bitset = Bitset::new(100);
+        assert_eq!(bitset.count(), 0);
+        assert!(!bitset.contains(0));
+        assert_eq!(bitset.iter_set().collect::<Vec<_>>(), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_set_and_contains() {
+        let mut bitset = Bitset::new(100);
+        bitset.set(0);
+        bitset.set(63);
+        bitset.set(64);
+        bitset.set(99);
+
+        assert!(bitset.contains(0));
+        assert!(bitset.contains(63));
+        assert!(bitset.contains(64));
+        assert!(bitset.contains(99));
+        assert!(!bitset.contains(1));
+        assert!(!bitset.contains(65));
+    }
+
+    #[test]
+    fn test_count() {
+        let mut bitset = Bitset::new(200);
+        for idx in [0, 10, 63, 64, 127, 128, 199] {
+            bitset.set(idx);
+        }
+        assert_eq!(bitset.count(), 7);
+    }
+
+    #[test]
+    fn test_clear_all() {
+        let mut bitset = Bitset::new(100);
+        bitset.set(5);
+        bitset.set(90);
+        assert_eq!(bitset.count(), 2);
+
+        bitset.clear_all();
+        assert_eq!(bitset.count(), 0);
+        assert!(!bitset.contains(5));
+        assert!(!bitset.contains(90));
+    }
+
+    #[test]
+    fn test_iter_set_is_ordered_across_words() {
+        let mut bitset = Bitset::new(200);
+        let indices = [3, 1, 130, 64, 199, 0];
+        for &idx in &indices {
+            bitset.set(idx);
+        }
+
+        let mut expected: Vec<usize> = indices.to_vec();
+        expected.sort_unstable();
+
+        assert_eq!(bitset.iter_set().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_set_idempotent() {
+        let mut bitset = Bitset::new(10);
+        bitset.set(3);
+        bitset.set(3);
+        assert_eq!(bitset.count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_set_out_of_bounds_panics() {
+        let mut bitset = Bitset::new(10);
+        bitset.set(10);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_contains_out_of_bounds_panics() {
+        let bitset = Bitset::new(10);
+        bitset.contains(10);
+    }
+
+    #[test]
+    fn test_capacity_rounds_up_to_word_boundary() {
+        let bitset = Bitset::new(65);
+        assert_eq!(bitset.capacity(), 65);
+        // Should not panic: 65 requires 2 words of backing storage.
+        let mut bitset = bitset;
+        bitset.set(64);
+        assert!(bitset.contains(64));
+    }
+}
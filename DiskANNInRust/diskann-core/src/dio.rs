@@ -0,0 +1,241 @@
+//! Aligned byte buffer allocator for O_DIRECT / unbuffered disk I/O
+//!
+//! [`METADATA_SIZE`](crate::utils::METADATA_SIZE) and
+//! [`BUFFER_SIZE_FOR_CACHED_IO`](crate::utils::BUFFER_SIZE_FOR_CACHED_IO),
+//! along with [`is_512_aligned`](crate::utils::is_512_aligned) and
+//! [`is_4096_aligned`](crate::utils::is_4096_aligned), imply that the disk
+//! layer needs genuinely aligned buffers for unbuffered reads of the
+//! on-disk index -- but nothing produced one. [`AlignedBuffer`] does, via a
+//! real `Layout`-based allocation rather than the over-allocate-and-hope
+//! trick [`crate::alignment::create_aligned_vec`] uses; it's otherwise
+//! unrelated to [`crate::alignment::AlignedBuffer`], which is a typed,
+//! SIMD-lane-sized buffer rather than a raw byte one for disk I/O.
+//!
+//! This module's allocation and [`copy_aligned`]'s non-temporal stores both
+//! need unsafe code, so like the rest of this crate it's gated behind the
+//! `simd` feature, which is what relaxes `forbid(unsafe_code)` (see
+//! `lib.rs`).
+
+#[cfg(feature = "std")]
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+
+#[cfg(not(feature = "std"))]
+use alloc::alloc::{alloc_zeroed, dealloc, Layout};
+
+use core::slice;
+
+use crate::utils::{is_aligned, round_up};
+
+/// A heap buffer whose starting address is guaranteed aligned to `align`
+/// bytes, suitable for O_DIRECT / unbuffered disk I/O
+pub struct AlignedBuffer {
+    ptr: *mut u8,
+    len: usize,
+    layout: Layout,
+}
+
+// Safety: `AlignedBuffer` owns its allocation exclusively; nothing else
+// holds a pointer into it, so it's sound to send or share across threads
+// the same way a `Vec<u8>` would be.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocate a zeroed buffer of `len` bytes, aligned to `align` bytes
+    /// (typically 512 or 4096 -- see
+    /// [`is_512_aligned`](crate::utils::is_512_aligned)/
+    /// [`is_4096_aligned`](crate::utils::is_4096_aligned) -- for O_DIRECT
+    /// reads of the on-disk index).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two, or if the allocation fails.
+    pub fn new(len: usize, align: usize) -> Self {
+        assert!(
+            align.is_power_of_two(),
+            "alignment must be a power of two, got {}",
+            align
+        );
+
+        let alloc_len = round_up(len as u64, align as u64) as usize;
+        let layout = Layout::from_size_align(alloc_len.max(align), align)
+            .expect("invalid layout for aligned buffer");
+
+        // Safety: `layout.size()` is non-zero (at least `align`).
+        let ptr = unsafe { alloc_zeroed(layout) };
+        assert!(!ptr.is_null(), "allocation of {} bytes failed", layout.size());
+        debug_assert!(is_aligned(ptr as u64, align as u64));
+
+        Self { ptr, len, layout }
+    }
+
+    /// The number of usable bytes (the `len` passed to [`Self::new`]; the
+    /// backing allocation may be slightly larger, rounded up to `align`)
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this buffer holds zero usable bytes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The alignment this buffer was allocated with
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Borrow the buffer's usable bytes
+    pub fn as_slice(&self) -> &[u8] {
+        // Safety: `ptr` is valid for `layout.size() >= len` bytes for the
+        // lifetime of `self`.
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    /// Mutably borrow the buffer's usable bytes
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // Safety: `ptr` is valid for `layout.size() >= len` bytes for the
+        // lifetime of `self`, and `self` is borrowed mutably.
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // Safety: `self.ptr`/`self.layout` are exactly the pointer and
+        // layout `alloc_zeroed` returned in `Self::new`.
+        unsafe { dealloc(self.ptr, self.layout) };
+    }
+}
+
+/// Copy `src` into `dst` using non-temporal (cache-bypassing) stores on the
+/// bulk, 32-byte-aligned body, falling back to a scalar byte copy for the
+/// tail and on hosts without AVX.
+///
+/// Bypassing the cache is a win here because disk I/O buffers are typically
+/// written once and not immediately re-read, so polluting the cache with
+/// them would only evict data the caller is more likely to reuse.
+///
+/// # Panics
+///
+/// Panics if `dst.len() != src.len()`.
+///
+/// # Debug assertions
+///
+/// Debug builds assert `dst` is 32-byte aligned (via
+/// [`is_aligned`](crate::utils::is_aligned)) before taking the SIMD path,
+/// since [`AlignedBuffer::new`] is expected to be the source of `dst`.
+pub fn copy_aligned(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "copy_aligned requires equal-length slices");
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        debug_assert!(
+            is_aligned(dst.as_ptr() as u64, 32),
+            "dst should be 32-byte aligned for non-temporal stores"
+        );
+
+        if is_x86_feature_detected!("avx") {
+            // Safety: AVX support was just checked at runtime.
+            unsafe { copy_aligned_avx(dst, src) };
+            return;
+        }
+    }
+
+    dst.copy_from_slice(src);
+}
+
+/// AVX non-temporal-store copy kernel backing [`copy_aligned`], 32 bytes
+/// (one `__m256`) per iteration with a scalar tail for the remainder
+///
+/// # Safety
+///
+/// Caller must ensure the host supports AVX (checked at runtime by
+/// [`copy_aligned`] via `is_x86_feature_detected!("avx")`).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn copy_aligned_avx(dst: &mut [u8], src: &[u8]) {
+    use core::arch::x86_64::{_mm256_loadu_ps, _mm256_stream_ps, _mm_sfence};
+
+    let len = dst.len();
+    let chunks = len / 32;
+
+    for i in 0..chunks {
+        let offset = i * 32;
+        // Safety: `offset + 32 <= len` for every iteration of this loop;
+        // the load is unaligned (`src` isn't guaranteed aligned) but the
+        // non-temporal store requires `dst` to be 32-byte aligned, which
+        // `copy_aligned` debug-asserts before calling here.
+        let v = _mm256_loadu_ps(src.as_ptr().add(offset) as *const f32);
+        _mm256_stream_ps(dst.as_mut_ptr().add(offset) as *mut f32, v);
+    }
+
+    for i in (chunks * 32)..len {
+        dst[i] = src[i];
+    }
+
+    // Non-temporal stores are weakly ordered; fence so the copy is visible
+    // to the caller before this function returns.
+    _mm_sfence();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_is_zeroed_and_aligned() {
+        let buf = AlignedBuffer::new(100, 512);
+        assert_eq!(buf.len(), 100);
+        assert_eq!(buf.alignment(), 512);
+        assert!(buf.as_slice().iter().all(|&b| b == 0));
+        assert!(is_aligned(buf.as_slice().as_ptr() as u64, 512));
+    }
+
+    #[test]
+    fn test_4096_alignment() {
+        let buf = AlignedBuffer::new(4096, 4096);
+        assert!(is_aligned(buf.as_slice().as_ptr() as u64, 4096));
+    }
+
+    #[test]
+    fn test_as_mut_slice_roundtrips() {
+        let mut buf = AlignedBuffer::new(16, 512);
+        buf.as_mut_slice().copy_from_slice(&[7u8; 16]);
+        assert_eq!(buf.as_slice(), &[7u8; 16]);
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn test_non_power_of_two_alignment_panics() {
+        AlignedBuffer::new(100, 100);
+    }
+
+    #[test]
+    fn test_copy_aligned_matches_source() {
+        let src: Vec<u8> = (0..200).map(|i| (i % 256) as u8).collect();
+        let mut dst = AlignedBuffer::new(src.len(), 32);
+
+        copy_aligned(dst.as_mut_slice(), &src);
+
+        assert_eq!(dst.as_slice(), src.as_slice());
+    }
+
+    #[test]
+    fn test_copy_aligned_handles_tail_not_multiple_of_32() {
+        let src: Vec<u8> = (0..77).collect();
+        let mut dst = AlignedBuffer::new(src.len(), 32);
+
+        copy_aligned(dst.as_mut_slice(), &src);
+
+        assert_eq!(dst.as_slice(), src.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "equal-length")]
+    fn test_copy_aligned_rejects_length_mismatch() {
+        let src = vec![1u8; 10];
+        let mut dst = AlignedBuffer::new(20, 32);
+        copy_aligned(dst.as_mut_slice(), &src);
+    }
+}
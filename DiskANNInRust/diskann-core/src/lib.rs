@@ -47,6 +47,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(not(feature = "simd"), forbid(unsafe_code))]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 #![deny(warnings)]
 #![warn(missing_docs)]
 
@@ -74,10 +75,17 @@ pub mod utils;
 /// Aligned vector allocation macros
 pub mod alignment;
 
+/// Dense, word-packed bitset for visited-node tracking in graph traversal
+pub mod bitset;
+
 /// SIMD-accelerated distance computations
 #[cfg(feature = "simd")]
 pub mod simd;
 
+/// Aligned byte buffer allocator for O_DIRECT / unbuffered disk I/O
+#[cfg(feature = "simd")]
+pub mod dio;
+
 pub use error::*;
 
 #[cfg(test)]
@@ -2,12 +2,21 @@
 //!
 //! This module provides pure Rust implementations of mathematical functions
 //! optimized for vector operations in the DiskANN system.
+//!
+//! [`Float::sqrt`] uses the standard library's `sqrt` when the `std` feature
+//! is enabled. Under `#![no_std]`, enable the `libm` feature to route it
+//! through the portable, pure-Rust `libm` crate instead; without either
+//! feature, `no_std` builds won't have a `sqrt` implementation to link
+//! against.
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
 use core::ops::{Add, Div, Mul, Sub};
 
+#[cfg(feature = "simd")]
+use wide::{f32x8, f64x4};
+
 /// Trait for numeric types that support basic mathematical operations
 pub trait Float: 
     Copy 
@@ -34,14 +43,43 @@ pub trait Float:
     
     /// Returns three value
     fn three() -> Self;
+
+    /// Fused multiply-add: `self * a + b`, rounded once instead of twice.
+    /// Maps to `f32::mul_add`/`f64::mul_add` under `std`, `libm::fmaf`/
+    /// `libm::fma` under `no_std` with the `libm` feature; other `Float`
+    /// implementers fall back to a plain multiply-then-add.
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    /// Dispatch hook behind [`dot_product`]: defaults to the generic scalar
+    /// fold below, overridden for `f32`/`f64` with a `wide`-backed SIMD
+    /// kernel when the `simd` feature is enabled.
+    fn simd_dot_product(a: &[Self], b: &[Self]) -> Self {
+        a.iter().zip(b.iter()).fold(Self::zero(), |acc, (&x, &y)| x.mul_add(y, acc))
+    }
+
+    /// Dispatch hook behind [`l2_norm_squared`] (and so [`l2_norm`]): defaults
+    /// to the generic scalar fold below, overridden for `f32`/`f64` with a
+    /// `wide`-backed SIMD kernel when the `simd` feature is enabled.
+    fn simd_l2_norm_squared(vector: &[Self]) -> Self {
+        vector.iter().fold(Self::zero(), |acc, &x| x.mul_add(x, acc))
+    }
+
+    /// Dispatch hook behind [`l1_norm`]: defaults to the generic scalar fold
+    /// below, overridden for `f32`/`f64` with a `wide`-backed SIMD kernel
+    /// when the `simd` feature is enabled.
+    fn simd_l1_norm(vector: &[Self]) -> Self {
+        vector.iter().map(|&x| x.abs()).fold(Self::zero(), |acc, x| acc + x)
+    }
 }
 
 impl Float for f32 {
     fn sqrt(self) -> Self {
         #[cfg(feature = "std")]
         return self.sqrt();
-        
-        #[cfg(not(feature = "std"))]
+
+        #[cfg(all(not(feature = "std"), feature = "libm"))]
         return libm::sqrtf(self);
     }
     
@@ -53,25 +91,231 @@ impl Float for f32 {
     fn one() -> Self { 1.0 }
     fn two() -> Self { 2.0 }
     fn three() -> Self { 3.0 }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        #[cfg(feature = "std")]
+        return self.mul_add(a, b);
+
+        #[cfg(all(not(feature = "std"), feature = "libm"))]
+        return libm::fmaf(self, a, b);
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_dot_product(a: &[Self], b: &[Self]) -> Self {
+        dot_product_simd_f32(a, b)
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_l2_norm_squared(vector: &[Self]) -> Self {
+        dot_product_simd_f32(vector, vector)
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_l1_norm(vector: &[Self]) -> Self {
+        l1_norm_simd_f32(vector)
+    }
 }
 
 impl Float for f64 {
     fn sqrt(self) -> Self {
         #[cfg(feature = "std")]
         return self.sqrt();
-        
-        #[cfg(not(feature = "std"))]
+
+        #[cfg(all(not(feature = "std"), feature = "libm"))]
         return libm::sqrt(self);
     }
-    
+
     fn abs(self) -> Self {
         if self < 0.0 { -self } else { self }
     }
-    
+
     fn zero() -> Self { 0.0 }
     fn one() -> Self { 1.0 }
     fn two() -> Self { 2.0 }
     fn three() -> Self { 3.0 }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        #[cfg(feature = "std")]
+        return self.mul_add(a, b);
+
+        #[cfg(all(not(feature = "std"), feature = "libm"))]
+        return libm::fma(self, a, b);
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_dot_product(a: &[Self], b: &[Self]) -> Self {
+        dot_product_simd_f64(a, b)
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_l2_norm_squared(vector: &[Self]) -> Self {
+        dot_product_simd_f64(vector, vector)
+    }
+
+    #[cfg(feature = "simd")]
+    fn simd_l1_norm(vector: &[Self]) -> Self {
+        l1_norm_simd_f64(vector)
+    }
+}
+
+/// Number of lanes per SIMD register used by the `f32` kernels below ([`wide::f32x8`])
+#[cfg(feature = "simd")]
+const SIMD_LEN_F32: usize = 8;
+
+/// Number of lanes per SIMD register used by the `f64` kernels below ([`wide::f64x4`])
+#[cfg(feature = "simd")]
+const SIMD_LEN_F64: usize = 4;
+
+/// Number of independent accumulators the SIMD kernels below keep in flight,
+/// to break the fold's serial dependency chain (each accumulator only
+/// depends on its own previous value, so the CPU can pipeline all four)
+#[cfg(feature = "simd")]
+const SIMD_UNROLL: usize = 4;
+
+#[cfg(feature = "simd")]
+#[inline]
+fn load_f32x8(slice: &[f32], i: usize) -> f32x8 {
+    f32x8::from([
+        slice[i], slice[i + 1], slice[i + 2], slice[i + 3],
+        slice[i + 4], slice[i + 5], slice[i + 6], slice[i + 7],
+    ])
+}
+
+#[cfg(feature = "simd")]
+#[inline]
+fn load_f64x4(slice: &[f64], i: usize) -> f64x4 {
+    f64x4::from([slice[i], slice[i + 1], slice[i + 2], slice[i + 3]])
+}
+
+/// SIMD dot product of two `f32` slices, used as [`Float::simd_dot_product`]
+/// and (via `dot_product_simd_f32(v, v)`) [`Float::simd_l2_norm_squared`]
+/// for `f32`
+///
+/// Keeps [`SIMD_UNROLL`] independent `f32x8` accumulators live across the
+/// main loop so each one's multiply-add doesn't have to wait on the others',
+/// then horizontally reduces all four, a lane-width block at a time, and
+/// scalar-folds whatever's left over.
+#[cfg(feature = "simd")]
+fn dot_product_simd_f32(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len());
+    let block = SIMD_LEN_F32 * SIMD_UNROLL;
+    let unrolled_len = len - (len % block);
+
+    let mut acc = [f32x8::ZERO; SIMD_UNROLL];
+    let mut i = 0;
+    while i < unrolled_len {
+        for (lane, acc_lane) in acc.iter_mut().enumerate() {
+            let offset = i + lane * SIMD_LEN_F32;
+            *acc_lane = load_f32x8(a, offset).mul_add(load_f32x8(b, offset), *acc_lane);
+        }
+        i += block;
+    }
+    let mut sum = acc.iter().fold(f32x8::ZERO, |total, &x| total + x).to_array().iter().sum::<f32>();
+
+    let mut j = unrolled_len;
+    while j + SIMD_LEN_F32 <= len {
+        sum += (load_f32x8(a, j) * load_f32x8(b, j)).to_array().iter().sum::<f32>();
+        j += SIMD_LEN_F32;
+    }
+
+    for k in j..len {
+        sum = a[k].mul_add(b[k], sum);
+    }
+    sum
+}
+
+/// SIMD L1 (Manhattan) norm of an `f32` slice, used as [`Float::simd_l1_norm`]
+/// for `f32`; see [`dot_product_simd_f32`] for the unrolled-accumulator
+/// approach this mirrors.
+#[cfg(feature = "simd")]
+fn l1_norm_simd_f32(vector: &[f32]) -> f32 {
+    let len = vector.len();
+    let block = SIMD_LEN_F32 * SIMD_UNROLL;
+    let unrolled_len = len - (len % block);
+
+    let mut acc = [f32x8::ZERO; SIMD_UNROLL];
+    let mut i = 0;
+    while i < unrolled_len {
+        for (lane, acc_lane) in acc.iter_mut().enumerate() {
+            let offset = i + lane * SIMD_LEN_F32;
+            *acc_lane += load_f32x8(vector, offset).abs();
+        }
+        i += block;
+    }
+    let mut sum = acc.iter().fold(f32x8::ZERO, |total, &x| total + x).to_array().iter().sum::<f32>();
+
+    let mut j = unrolled_len;
+    while j + SIMD_LEN_F32 <= len {
+        sum += load_f32x8(vector, j).abs().to_array().iter().sum::<f32>();
+        j += SIMD_LEN_F32;
+    }
+
+    for &x in &vector[j..len] {
+        sum += x.abs();
+    }
+    sum
+}
+
+/// SIMD dot product of two `f64` slices; see [`dot_product_simd_f32`] for the
+/// unrolled-accumulator approach this mirrors at `f64x4` width.
+#[cfg(feature = "simd")]
+fn dot_product_simd_f64(a: &[f64], b: &[f64]) -> f64 {
+    let len = a.len().min(b.len());
+    let block = SIMD_LEN_F64 * SIMD_UNROLL;
+    let unrolled_len = len - (len % block);
+
+    let mut acc = [f64x4::ZERO; SIMD_UNROLL];
+    let mut i = 0;
+    while i < unrolled_len {
+        for (lane, acc_lane) in acc.iter_mut().enumerate() {
+            let offset = i + lane * SIMD_LEN_F64;
+            *acc_lane = load_f64x4(a, offset).mul_add(load_f64x4(b, offset), *acc_lane);
+        }
+        i += block;
+    }
+    let mut sum = acc.iter().fold(f64x4::ZERO, |total, &x| total + x).to_array().iter().sum::<f64>();
+
+    let mut j = unrolled_len;
+    while j + SIMD_LEN_F64 <= len {
+        sum += (load_f64x4(a, j) * load_f64x4(b, j)).to_array().iter().sum::<f64>();
+        j += SIMD_LEN_F64;
+    }
+
+    for k in j..len {
+        sum = a[k].mul_add(b[k], sum);
+    }
+    sum
+}
+
+/// SIMD L1 (Manhattan) norm of an `f64` slice; see [`l1_norm_simd_f32`] for
+/// the unrolled-accumulator approach this mirrors at `f64x4` width.
+#[cfg(feature = "simd")]
+fn l1_norm_simd_f64(vector: &[f64]) -> f64 {
+    let len = vector.len();
+    let block = SIMD_LEN_F64 * SIMD_UNROLL;
+    let unrolled_len = len - (len % block);
+
+    let mut acc = [f64x4::ZERO; SIMD_UNROLL];
+    let mut i = 0;
+    while i < unrolled_len {
+        for (lane, acc_lane) in acc.iter_mut().enumerate() {
+            let offset = i + lane * SIMD_LEN_F64;
+            *acc_lane += load_f64x4(vector, offset).abs();
+        }
+        i += block;
+    }
+    let mut sum = acc.iter().fold(f64x4::ZERO, |total, &x| total + x).to_array().iter().sum::<f64>();
+
+    let mut j = unrolled_len;
+    while j + SIMD_LEN_F64 <= len {
+        sum += load_f64x4(vector, j).abs().to_array().iter().sum::<f64>();
+        j += SIMD_LEN_F64;
+    }
+
+    for &x in &vector[j..len] {
+        sum += x.abs();
+    }
+    sum
 }
 
 /// Fast inverse square root implementation using Newton-Raphson method
@@ -102,14 +346,20 @@ pub fn fast_inv_sqrt<T: Float, const ITERATIONS: usize>(x: T) -> T {
     let half_x = x / T::two();
     let three_half = T::three() / T::two();
     
-    // Newton-Raphson iterations: y = y * (1.5 - 0.5 * x * y * y)
+    // Newton-Raphson iterations: y = y * (1.5 - 0.5 * x * y * y), expressed as
+    // two fused multiply-adds so each step only rounds three times instead of
+    // four (the two fused operations plus the final `y * ...` multiply):
+    // `y_squared = y.mul_add(y, 0)`, then
+    // `three_half - half_x * y_squared` folds its multiply and subtract into
+    // `(-half_x).mul_add(y_squared, three_half)`.
+    let neg_half_x = T::zero() - half_x;
     let mut i = 0;
     while i < ITERATIONS {
-        let y_squared = y * y;
-        y = y * (three_half - half_x * y_squared);
+        let y_squared = y.mul_add(y, T::zero());
+        y = y * neg_half_x.mul_add(y_squared, three_half);
         i += 1;
     }
-    
+
     y
 }
 
@@ -129,79 +379,287 @@ pub fn fast_inv_sqrt_runtime<T: Float>(x: T, iterations: usize) -> T {
     let half_x = x / T::two();
     let three_half = T::three() / T::two();
     
+    let neg_half_x = T::zero() - half_x;
     for _ in 0..iterations {
-        let y_squared = y * y;
-        y = y * (three_half - half_x * y_squared);
+        let y_squared = y.mul_add(y, T::zero());
+        y = y * neg_half_x.mul_add(y_squared, three_half);
     }
-    
+
     y
 }
 
 /// Compute the L2 (Euclidean) norm of a vector
-/// 
+///
+/// Delegates to [`l2_norm_squared`], so `f32`/`f64` automatically take its
+/// SIMD fast path when the `simd` feature is enabled.
+///
 /// # Examples
 /// ```
 /// use diskann_core::math::l2_norm;
-/// 
+///
 /// let vector = vec![3.0f32, 4.0f32];
 /// let norm = l2_norm(&vector);
 /// assert!((norm - 5.0f32).abs() < 0.001f32);
 /// ```
 pub fn l2_norm<T: Float>(vector: &[T]) -> T {
-    let sum_squares = vector.iter()
-        .map(|&x| x * x)
-        .fold(T::zero(), |acc, x| acc + x);
-    sum_squares.sqrt()
+    l2_norm_squared(vector).sqrt()
+}
+
+/// Compute the L2 (Euclidean) norm using scaled accumulation to avoid
+/// overflow/underflow
+///
+/// [`l2_norm`]'s naive `sum(x*x)` overflows to infinity once any component
+/// approaches `sqrt(T::MAX)` and silently underflows tiny components to
+/// zero. This instead finds `m = max(|x_i|)`, accumulates
+/// `sum += (x_i / m)^2` (every term now in `[0, 1]`), and returns
+/// `m * sum.sqrt()` -- mathematically the same norm, but computed entirely
+/// within a safe magnitude range regardless of the input's dynamic range.
+///
+/// # Examples
+/// ```
+/// use diskann_core::math::l2_norm_stable;
+///
+/// let vector = vec![3.0f32, 4.0f32];
+/// let norm = l2_norm_stable(&vector);
+/// assert!((norm - 5.0f32).abs() < 0.001f32);
+/// ```
+pub fn l2_norm_stable<T: Float>(vector: &[T]) -> T {
+    let max_abs = vector.iter()
+        .fold(T::zero(), |acc, &x| {
+            let ax = x.abs();
+            if ax > acc { ax } else { acc }
+        });
+
+    if max_abs == T::zero() {
+        return T::zero();
+    }
+
+    let scaled_sum_squares = vector.iter()
+        .fold(T::zero(), |acc, &x| {
+            let scaled = x / max_abs;
+            acc + scaled * scaled
+        });
+
+    max_abs * scaled_sum_squares.sqrt()
+}
+
+/// Base block size for [`dot_product_pairwise`]/[`l2_norm_squared_pairwise`]:
+/// below this many terms, the reduction falls back to a plain left-fold that
+/// stays register-friendly for the compiler to auto-vectorize.
+const PAIRWISE_BASE_BLOCK: usize = 128;
+
+/// Recursively halve `[start, start + len)` down to [`PAIRWISE_BASE_BLOCK`]-sized
+/// blocks, summing each block with a left-fold and adding the two halves'
+/// partial sums back up -- this is the classic pairwise (cascade) summation
+/// trick: it bounds rounding error growth to `O(log n)` instead of the
+/// `O(n)` a single left-fold accumulates.
+fn pairwise_reduce<T: Float>(start: usize, len: usize, term: &impl Fn(usize) -> T) -> T {
+    if len <= PAIRWISE_BASE_BLOCK {
+        let mut sum = T::zero();
+        for i in start..start + len {
+            sum = sum + term(i);
+        }
+        return sum;
+    }
+
+    let half = len / 2;
+    pairwise_reduce(start, half, term) + pairwise_reduce(start + half, len - half, term)
+}
+
+/// Compute dot product of two vectors using pairwise (cascade) summation
+///
+/// Behaves like [`dot_product`] but accumulates via [`pairwise_reduce`]
+/// instead of a single left-fold, which keeps rounding error from growing
+/// linearly with dimension -- worthwhile for the thousand-plus-dimension
+/// vectors this crate deals with.
+///
+/// # Examples
+/// ```
+/// use diskann_core::math::dot_product_pairwise;
+///
+/// let a = vec![1.0f32, 2.0f32, 3.0f32];
+/// let b = vec![4.0f32, 5.0f32, 6.0f32];
+/// let dot = dot_product_pairwise(&a, &b);
+/// assert!((dot - 32.0f32).abs() < 0.001f32);
+/// ```
+pub fn dot_product_pairwise<T: Float>(a: &[T], b: &[T]) -> T {
+    let len = a.len().min(b.len());
+    pairwise_reduce(0, len, &|i| a[i] * b[i])
+}
+
+/// Compute the squared L2 norm of a vector using pairwise (cascade) summation
+///
+/// Behaves like [`l2_norm_squared`] but accumulates via [`pairwise_reduce`]
+/// instead of a single left-fold, for the same accuracy reasons as
+/// [`dot_product_pairwise`].
+///
+/// # Examples
+/// ```
+/// use diskann_core::math::l2_norm_squared_pairwise;
+///
+/// let vector = vec![3.0f32, 4.0f32];
+/// let norm_sq = l2_norm_squared_pairwise(&vector);
+/// assert!((norm_sq - 25.0f32).abs() < 0.001f32);
+/// ```
+pub fn l2_norm_squared_pairwise<T: Float>(vector: &[T]) -> T {
+    pairwise_reduce(0, vector.len(), &|i| vector[i] * vector[i])
+}
+
+/// Tile size for [`l2_distance_matrix`]'s blocked cross-term loop: small
+/// enough that a tile's query/database rows stay resident in L1/L2 cache
+/// across the inner dot products, mirroring how GEMM kernels block for
+/// cache reuse.
+const DISTANCE_MATRIX_BLOCK: usize = 64;
+
+/// Fill `out` with the pairwise Euclidean distance between every query in
+/// `queries` and every vector in `database`, `out[i * database.len() + j]`
+/// holding `distance(queries[i], database[j])`
+///
+/// Uses the identity `‖a-b‖² = ‖a‖² + ‖b‖² - 2·(a·b)` so each query's and
+/// each database vector's squared norm is computed once and reused across
+/// the whole cross product, rather than re-deriving it inside every
+/// distance call. The `(a·b)` cross term is still the dominant cost, so
+/// it's computed in 64x64 tiles (see [`DISTANCE_MATRIX_BLOCK`]) to keep
+/// each tile's operands cache-resident instead of streaming the full
+/// `database` once per query. Floating-point error in the expansion can
+/// push a near-zero squared distance slightly negative, which is clamped
+/// to zero before the final `sqrt`.
+///
+/// # Panics
+/// Panics if `out.len() != queries.len() * database.len()`.
+///
+/// # Examples
+/// ```
+/// use diskann_core::math::l2_distance_matrix;
+///
+/// let q0 = vec![0.0f32, 0.0];
+/// let d0 = vec![3.0f32, 4.0];
+/// let d1 = vec![0.0f32, 0.0];
+/// let queries: Vec<&[f32]> = vec![&q0];
+/// let database: Vec<&[f32]> = vec![&d0, &d1];
+///
+/// let mut out = vec![0.0f32; queries.len() * database.len()];
+/// l2_distance_matrix(&queries, &database, &mut out);
+/// assert!((out[0] - 5.0).abs() < 0.001);
+/// assert!((out[1] - 0.0).abs() < 0.001);
+/// ```
+pub fn l2_distance_matrix<T: Float>(queries: &[&[T]], database: &[&[T]], out: &mut [T]) {
+    let num_queries = queries.len();
+    let num_db = database.len();
+    assert_eq!(out.len(), num_queries * num_db, "out must hold queries.len() * database.len() entries");
+
+    let query_norms: Vec<T> = queries.iter().map(|q| l2_norm_squared(q)).collect();
+    let db_norms: Vec<T> = database.iter().map(|d| l2_norm_squared(d)).collect();
+
+    let mut qi_block = 0;
+    while qi_block < num_queries {
+        let qi_end = if qi_block + DISTANCE_MATRIX_BLOCK < num_queries {
+            qi_block + DISTANCE_MATRIX_BLOCK
+        } else {
+            num_queries
+        };
+
+        let mut dj_block = 0;
+        while dj_block < num_db {
+            let dj_end = if dj_block + DISTANCE_MATRIX_BLOCK < num_db {
+                dj_block + DISTANCE_MATRIX_BLOCK
+            } else {
+                num_db
+            };
+
+            for qi in qi_block..qi_end {
+                for dj in dj_block..dj_end {
+                    let dot = dot_product(queries[qi], database[dj]);
+                    let mut dist_sq = query_norms[qi] + db_norms[dj] - T::two() * dot;
+                    if dist_sq < T::zero() {
+                        dist_sq = T::zero();
+                    }
+                    out[qi * num_db + dj] = dist_sq.sqrt();
+                }
+            }
+
+            dj_block = dj_end;
+        }
+
+        qi_block = qi_end;
+    }
 }
 
 /// Compute the squared L2 norm of a vector (avoids sqrt computation)
-/// 
+///
+/// `f32`/`f64` take a SIMD fast path when the `simd` feature is enabled; see
+/// [`Float::simd_l2_norm_squared`].
+///
 /// # Examples
 /// ```
 /// use diskann_core::math::l2_norm_squared;
-/// 
+///
 /// let vector = vec![3.0f32, 4.0f32];
 /// let norm_sq = l2_norm_squared(&vector);
 /// assert!((norm_sq - 25.0f32).abs() < 0.001f32);
 /// ```
 pub fn l2_norm_squared<T: Float>(vector: &[T]) -> T {
-    vector.iter()
-        .map(|&x| x * x)
-        .fold(T::zero(), |acc, x| acc + x)
+    T::simd_l2_norm_squared(vector)
 }
 
 /// Compute the L1 (Manhattan) norm of a vector
-/// 
+///
+/// `f32`/`f64` take a SIMD fast path when the `simd` feature is enabled; see
+/// [`Float::simd_l1_norm`].
+///
 /// # Examples
 /// ```
 /// use diskann_core::math::l1_norm;
-/// 
+///
 /// let vector = vec![3.0f32, -4.0f32];
 /// let norm = l1_norm(&vector);
 /// assert!((norm - 7.0f32).abs() < 0.001f32);
 /// ```
 pub fn l1_norm<T: Float>(vector: &[T]) -> T {
-    vector.iter()
-        .map(|&x| x.abs())
-        .fold(T::zero(), |acc, x| acc + x)
+    T::simd_l1_norm(vector)
 }
 
 /// Compute dot product of two vectors
-/// 
+///
+/// `f32`/`f64` take a SIMD fast path when the `simd` feature is enabled; see
+/// [`Float::simd_dot_product`].
+///
 /// # Examples
 /// ```
 /// use diskann_core::math::dot_product;
-/// 
+///
 /// let a = vec![1.0f32, 2.0f32, 3.0f32];
 /// let b = vec![4.0f32, 5.0f32, 6.0f32];
 /// let dot = dot_product(&a, &b);
 /// assert!((dot - 32.0f32).abs() < 0.001f32);
 /// ```
 pub fn dot_product<T: Float>(a: &[T], b: &[T]) -> T {
-    a.iter()
-        .zip(b.iter())
-        .map(|(&x, &y)| x * y)
-        .fold(T::zero(), |acc, x| acc + x)
+    T::simd_dot_product(a, b)
+}
+
+/// Count differing bits between two bit-packed vectors
+///
+/// Each `u8` holds 8 bits of the vector; `a` and `b` are compared byte by byte via
+/// `u8::count_ones` on their XOR, so the result is the number of bit positions that differ
+/// between the two vectors. Mismatched lengths are treated as differing in every bit of the
+/// longer vector's extra bytes.
+///
+/// # Examples
+/// ```
+/// use diskann_core::math::hamming_distance;
+///
+/// let a = vec![0b1010_1010u8];
+/// let b = vec![0b1010_0010u8];
+/// assert_eq!(hamming_distance(&a, &b), 1);
+/// ```
+pub fn hamming_distance(a: &[u8], b: &[u8]) -> u32 {
+    let common = a.iter().zip(b.iter()).map(|(&x, &y)| (x ^ y).count_ones()).sum::<u32>();
+    let tail_bits = if a.len() > b.len() {
+        a[b.len()..].iter().map(|&x| x.count_ones()).sum::<u32>()
+    } else {
+        b[a.len()..].iter().map(|&x| x.count_ones()).sum::<u32>()
+    };
+    common + tail_bits
 }
 
 /// Normalize a vector to unit length
@@ -219,11 +677,22 @@ pub fn dot_product<T: Float>(a: &[T], b: &[T]) -> T {
 /// assert!((normalized[1] - 0.8f32).abs() < 0.001f32);
 /// ```
 pub fn normalize<T: Float>(vector: &[T]) -> Vec<T> {
-    let norm = l2_norm(vector);
+    normalize_opts(vector, false)
+}
+
+/// Normalize a vector to unit length, optionally computing the norm via
+/// [`l2_norm_stable`] instead of [`l2_norm`]
+///
+/// Set `robust` when the input may have components with a wide dynamic
+/// range (e.g. un-normalized embeddings with large magnitudes), where the
+/// naive norm could overflow or underflow; leave it unset for the common
+/// case, where the naive norm is cheaper and accurate enough.
+pub fn normalize_opts<T: Float>(vector: &[T], robust: bool) -> Vec<T> {
+    let norm = if robust { l2_norm_stable(vector) } else { l2_norm(vector) };
     if norm == T::zero() {
         return vector.to_vec();
     }
-    
+
     vector.iter()
         .map(|&x| x / norm)
         .collect()
@@ -231,11 +700,17 @@ pub fn normalize<T: Float>(vector: &[T]) -> Vec<T> {
 
 /// In-place normalization of a vector
 pub fn normalize_in_place<T: Float>(vector: &mut [T]) {
-    let norm = l2_norm(vector);
+    normalize_in_place_opts(vector, false)
+}
+
+/// In-place normalization of a vector, optionally computing the norm via
+/// [`l2_norm_stable`] instead of [`l2_norm`] (see [`normalize_opts`])
+pub fn normalize_in_place_opts<T: Float>(vector: &mut [T], robust: bool) {
+    let norm = if robust { l2_norm_stable(vector) } else { l2_norm(vector) };
     if norm == T::zero() {
         return;
     }
-    
+
     for x in vector.iter_mut() {
         *x = *x / norm;
     }
@@ -254,6 +729,250 @@ mod tests {
         assert!((norm - 5.0).abs() < 0.001);
     }
 
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_dot_product_simd_f32_matches_naive_across_tail_sizes() {
+        for &len in &[0usize, 1, 7, 8, 9, 31, 32, 33, 100, 137] {
+            let a: Vec<f32> = (0..len).map(|i| i as f32 * 0.1).collect();
+            let b: Vec<f32> = (0..len).map(|i| (i as f32 * 0.2) + 1.0).collect();
+            let naive = a.iter().zip(b.iter()).map(|(&x, &y)| x * y).fold(0.0f32, |acc, x| acc + x);
+            let simd = dot_product_simd_f32(&a, &b);
+            assert!((simd - naive).abs() < 0.01, "len {} naive {} simd {}", len, naive, simd);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_l1_norm_simd_f32_matches_naive_across_tail_sizes() {
+        for &len in &[0usize, 1, 7, 8, 9, 31, 32, 33, 100, 137] {
+            let vector: Vec<f32> = (0..len).map(|i| if i % 2 == 0 { i as f32 } else { -(i as f32) }).collect();
+            let naive = vector.iter().map(|&x| x.abs()).fold(0.0f32, |acc, x| acc + x);
+            let simd = l1_norm_simd_f32(&vector);
+            assert!((simd - naive).abs() < 0.01, "len {} naive {} simd {}", len, naive, simd);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_dot_product_simd_f64_matches_naive_across_tail_sizes() {
+        for &len in &[0usize, 1, 3, 4, 5, 15, 16, 17, 100, 137] {
+            let a: Vec<f64> = (0..len).map(|i| i as f64 * 0.1).collect();
+            let b: Vec<f64> = (0..len).map(|i| (i as f64 * 0.2) + 1.0).collect();
+            let naive = a.iter().zip(b.iter()).map(|(&x, &y)| x * y).fold(0.0f64, |acc, x| acc + x);
+            let simd = dot_product_simd_f64(&a, &b);
+            assert!((simd - naive).abs() < 0.01, "len {} naive {} simd {}", len, naive, simd);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_l1_norm_simd_f64_matches_naive_across_tail_sizes() {
+        for &len in &[0usize, 1, 3, 4, 5, 15, 16, 17, 100, 137] {
+            let vector: Vec<f64> = (0..len).map(|i| if i % 2 == 0 { i as f64 } else { -(i as f64) }).collect();
+            let naive = vector.iter().map(|&x| x.abs()).fold(0.0f64, |acc, x| acc + x);
+            let simd = l1_norm_simd_f64(&vector);
+            assert!((simd - naive).abs() < 0.01, "len {} naive {} simd {}", len, naive, simd);
+        }
+    }
+
+    #[test]
+    fn test_fma_dot_product_more_accurate_than_plain_multiply_on_cancellation_input() {
+        // Alternating large-times-one / one-times-negative-large products,
+        // so the running sum repeatedly swings between roughly equal and
+        // opposite magnitudes -- the classic setup where a single rounding
+        // per accumulate step (fma) beats two (plain multiply then add).
+        let len = 4096usize;
+        let a: Vec<f32> = (0..len).map(|i| if i % 2 == 0 { 1.0e4 } else { 1.0 }).collect();
+        let b: Vec<f32> = (0..len).map(|i| if i % 2 == 0 { 1.0 } else { -1.0e4 }).collect();
+
+        let reference: f64 = a.iter().zip(b.iter()).map(|(&x, &y)| (x as f64) * (y as f64)).sum();
+
+        let plain_multiply = a.iter().zip(b.iter()).fold(0.0f32, |acc, (&x, &y)| acc + x * y);
+        let fma_based = a.iter().zip(b.iter()).fold(0.0f32, |acc, (&x, &y)| x.mul_add(y, acc));
+
+        let plain_error = (plain_multiply as f64 - reference).abs();
+        let fma_error = (fma_based as f64 - reference).abs();
+
+        assert!(
+            fma_error <= plain_error,
+            "fma error {} should not exceed plain-multiply error {}",
+            fma_error,
+            plain_error
+        );
+    }
+
+    #[test]
+    fn test_dot_product_uses_mul_add_fold() {
+        let a = vec![1.0f32, 2.0, 3.0];
+        let b = vec![4.0f32, 5.0, 6.0];
+        assert!((dot_product(&a, &b) - 32.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_l2_norm_stable_matches_naive_norm() {
+        let vector = vec![3.0f32, 4.0f32];
+        let norm = l2_norm_stable(&vector);
+        assert!((norm - 5.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_l2_norm_stable_zero_vector() {
+        let vector = vec![0.0f32, 0.0];
+        assert_eq!(l2_norm_stable(&vector), 0.0);
+    }
+
+    #[test]
+    fn test_l2_norm_stable_avoids_overflow() {
+        // Each component alone is far below f32::MAX, but squaring it naively
+        // overflows to infinity; the stable norm should not.
+        let huge = (f32::MAX).sqrt() * 10.0;
+        let vector = vec![huge, huge];
+
+        assert!(l2_norm(&vector).is_infinite());
+        assert!(l2_norm_stable(&vector).is_finite());
+    }
+
+    #[test]
+    fn test_l2_norm_stable_avoids_underflow() {
+        // Each component alone is far below f32's smallest positive
+        // (subnormal) value once squared, so the naive sum-of-squares
+        // underflows to zero.
+        let tiny = f32::MIN_POSITIVE.sqrt() / 1.0e4;
+        let vector = vec![tiny, tiny];
+
+        assert_eq!(l2_norm_squared(&vector), 0.0);
+        assert!(l2_norm_stable(&vector) > 0.0);
+    }
+
+    #[test]
+    fn test_normalize_opts_robust_matches_naive_on_ordinary_input() {
+        let vector = vec![3.0f32, 4.0];
+        let naive = normalize_opts(&vector, false);
+        let robust = normalize_opts(&vector, true);
+
+        for (a, b) in naive.iter().zip(robust.iter()) {
+            assert!((a - b).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_dot_product_pairwise_matches_naive_on_ordinary_input() {
+        let a = vec![1.0f32, 2.0, 3.0];
+        let b = vec![4.0f32, 5.0, 6.0];
+        assert!((dot_product_pairwise(&a, &b) - dot_product(&a, &b)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_l2_norm_squared_pairwise_matches_naive_on_ordinary_input() {
+        let vector = vec![3.0f32, 4.0];
+        assert!((l2_norm_squared_pairwise(&vector) - l2_norm_squared(&vector)).abs() < 0.001);
+    }
+
+    /// Alternating large/small magnitudes, the classic case that defeats a
+    /// single left-fold: each large term repeatedly swamps the accumulator
+    /// so the small terms' contributions round away to nothing.
+    fn adversarial_vector(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| if i % 2 == 0 { 1.0e4f32 } else { 1.0e-4f32 })
+            .collect()
+    }
+
+    #[test]
+    fn test_dot_product_pairwise_is_more_accurate_than_naive_on_adversarial_input() {
+        let a = adversarial_vector(4096);
+        let b = adversarial_vector(4096);
+
+        let reference: f64 = a.iter().zip(b.iter()).map(|(&x, &y)| (x as f64) * (y as f64)).sum();
+
+        let naive_error = (dot_product(&a, &b) as f64 - reference).abs();
+        let pairwise_error = (dot_product_pairwise(&a, &b) as f64 - reference).abs();
+
+        assert!(
+            pairwise_error <= naive_error,
+            "pairwise error {} should not exceed naive error {}",
+            pairwise_error,
+            naive_error
+        );
+    }
+
+    #[test]
+    fn test_l2_norm_squared_pairwise_is_more_accurate_than_naive_on_adversarial_input() {
+        let vector = adversarial_vector(4096);
+
+        let reference: f64 = vector.iter().map(|&x| (x as f64) * (x as f64)).sum();
+
+        let naive_error = (l2_norm_squared(&vector) as f64 - reference).abs();
+        let pairwise_error = (l2_norm_squared_pairwise(&vector) as f64 - reference).abs();
+
+        assert!(
+            pairwise_error <= naive_error,
+            "pairwise error {} should not exceed naive error {}",
+            pairwise_error,
+            naive_error
+        );
+    }
+
+    #[test]
+    fn test_l2_distance_matrix_matches_brute_force() {
+        let q0 = vec![0.0f32, 0.0];
+        let q1 = vec![1.0f32, 1.0];
+        let d0 = vec![3.0f32, 4.0];
+        let d1 = vec![0.0f32, 0.0];
+        let d2 = vec![1.0f32, 1.0];
+
+        let queries: Vec<&[f32]> = vec![&q0, &q1];
+        let database: Vec<&[f32]> = vec![&d0, &d1, &d2];
+
+        let mut out = vec![0.0f32; queries.len() * database.len()];
+        l2_distance_matrix(&queries, &database, &mut out);
+
+        for (i, q) in queries.iter().enumerate() {
+            for (j, d) in database.iter().enumerate() {
+                let diff: Vec<f32> = q.iter().zip(d.iter()).map(|(&a, &b)| a - b).collect();
+                let expected = l2_norm(&diff);
+                assert!((out[i * database.len() + j] - expected).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_l2_distance_matrix_clamps_negative_squared_distance_to_zero() {
+        let q0 = vec![1.0f32, 2.0];
+        let queries: Vec<&[f32]> = vec![&q0];
+        let database: Vec<&[f32]> = vec![&q0];
+
+        let mut out = vec![0.0f32; 1];
+        l2_distance_matrix(&queries, &database, &mut out);
+        assert_eq!(out[0], 0.0);
+    }
+
+    #[test]
+    fn test_l2_distance_matrix_handles_blocks_larger_than_tile_size() {
+        // Exercise more than one 64-wide tile in each dimension.
+        let queries_owned: Vec<Vec<f32>> = (0..130).map(|i| vec![i as f32, 0.0]).collect();
+        let database_owned: Vec<Vec<f32>> = (0..70).map(|i| vec![0.0, i as f32]).collect();
+        let queries: Vec<&[f32]> = queries_owned.iter().map(|v| v.as_slice()).collect();
+        let database: Vec<&[f32]> = database_owned.iter().map(|v| v.as_slice()).collect();
+
+        let mut out = vec![0.0f32; queries.len() * database.len()];
+        l2_distance_matrix(&queries, &database, &mut out);
+
+        let i = 129;
+        let j = 69;
+        let expected = ((129.0f32 * 129.0) + (69.0f32 * 69.0)).sqrt();
+        assert!((out[i * database.len() + j] - expected).abs() < 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_l2_distance_matrix_panics_on_wrong_output_length() {
+        let q0 = vec![0.0f32, 0.0];
+        let queries: Vec<&[f32]> = vec![&q0];
+        let database: Vec<&[f32]> = vec![&q0];
+        let mut out = vec![0.0f32; 3];
+        l2_distance_matrix(&queries, &database, &mut out);
+    }
+
     #[test]
     fn test_l2_norm_squared() {
         let vector = vec![3.0f32, 4.0f32];
@@ -2,10 +2,33 @@
 //!
 //! This module provides SIMD implementations for distance calculations
 //! with runtime CPU feature detection and fallback to scalar implementations.
+//!
+//! The `*_dispatch` functions' runtime CPU feature probing
+//! (`is_x86_feature_detected!`/`is_aarch64_feature_detected!`) is a
+//! std-only facility, so the AVX2/NEON branches are additionally gated on
+//! `feature = "std"`; under `no_std` they fall through to the portable-SIMD
+//! or scalar path instead. The scalar cosine kernels' `sqrt` similarly
+//! routes through `libm` under `no_std` (enable the `libm` feature) rather
+//! than assuming `f32::sqrt` -- see [`crate::math`] for the same split.
 
 #[cfg(feature = "simd")]
 use wide::f32x8;
 
+/// `f32::sqrt`, routed through `libm` when `std` is unavailable. Mirrors
+/// [`crate::math::Float::sqrt`]'s std/libm split so this module's cosine
+/// kernels compile under `no_std` too (with the `libm` feature enabled).
+#[cfg(feature = "std")]
+#[inline]
+fn sqrtf(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+#[inline]
+fn sqrtf(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
 /// Runtime dispatch for L2 (Euclidean) squared distance computation
 /// 
 /// Automatically selects the best available implementation based on CPU features:
@@ -31,20 +54,55 @@ pub fn l2_squared_distance_dispatch(a: &[f32], b: &[f32]) -> f32 {
     
     #[cfg(feature = "simd")]
     {
-        #[cfg(target_arch = "x86_64")]
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
         {
+            if is_x86_feature_detected!("avx512f") {
+                // SAFETY: We've just checked that AVX-512F is available at runtime
+                return unsafe { l2_squared_distance_avx512(a, b) };
+            }
+
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                // SAFETY: We've just checked that AVX2 and FMA are available at runtime
+                return unsafe { l2_squared_distance_avx2_fma(a, b) };
+            }
+
             if is_x86_feature_detected!("avx2") {
                 // SAFETY: We've just checked that AVX2 is available at runtime
                 return unsafe { l2_squared_distance_avx2(a, b) };
             }
         }
-        
+
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if is_aarch64_feature_detected!("neon") {
+                // SAFETY: We've just checked that NEON is available at runtime
+                return unsafe { l2_squared_distance_neon(a, b) };
+            }
+        }
+
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            // SAFETY: `simd128` is enabled at compile time via the cfg guard above
+            return unsafe { l2_squared_distance_wasm(a, b) };
+        }
+
+        // On non-x86_64 targets (aarch64, wasm, ...) prefer the nightly
+        // `core::simd`-backed kernel over the `wide`-based one below, since it
+        // lowers to the target's native vector instructions (NEON, WASM
+        // SIMD128, ...) rather than `wide`'s generic fallback.
+        #[cfg(all(feature = "portable_simd", not(target_arch = "x86_64")))]
+        {
+            if a.len() >= 8 {
+                return l2_squared_distance_portable::<8>(a, b);
+            }
+        }
+
         // Use portable SIMD if supported
         if a.len() >= 8 {
             return l2_squared_distance_simd(a, b);
         }
     }
-    
+
     // Fallback to scalar implementation
     l2_squared_distance_scalar(a, b)
 }
@@ -72,24 +130,199 @@ pub fn inner_product_distance_dispatch(a: &[f32], b: &[f32]) -> f32 {
     
     #[cfg(feature = "simd")]
     {
-        #[cfg(target_arch = "x86_64")]
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
         {
+            if is_x86_feature_detected!("avx512f") {
+                // SAFETY: We've just checked that AVX-512F is available at runtime
+                return unsafe { inner_product_distance_avx512(a, b) };
+            }
+
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                // SAFETY: We've just checked that AVX2 and FMA are available at runtime
+                return unsafe { inner_product_distance_avx2_fma(a, b) };
+            }
+
             if is_x86_feature_detected!("avx2") {
                 // SAFETY: We've just checked that AVX2 is available at runtime
                 return unsafe { inner_product_distance_avx2(a, b) };
             }
         }
-        
+
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if is_aarch64_feature_detected!("neon") {
+                // SAFETY: We've just checked that NEON is available at runtime
+                return unsafe { inner_product_distance_neon(a, b) };
+            }
+        }
+
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            // SAFETY: `simd128` is enabled at compile time via the cfg guard above
+            return unsafe { inner_product_distance_wasm(a, b) };
+        }
+
+        // See the equivalent branch in `l2_squared_distance_dispatch` for why
+        // this is tried before the `wide`-based kernel on non-x86_64 targets.
+        #[cfg(all(feature = "portable_simd", not(target_arch = "x86_64")))]
+        {
+            if a.len() >= 8 {
+                return inner_product_distance_portable::<8>(a, b);
+            }
+        }
+
         // Use portable SIMD if supported
         if a.len() >= 8 {
             return inner_product_distance_simd(a, b);
         }
     }
-    
+
     // Fallback to scalar implementation
     inner_product_distance_scalar(a, b)
 }
 
+/// Runtime dispatch for cosine distance computation
+///
+/// Fuses the dot product `Σ aᵢbᵢ` with both squared norms `Σ aᵢ²` and `Σ bᵢ²`
+/// into a single pass over SIMD accumulators, then returns
+/// `1 - dot / (sqrt(na) * sqrt(nb))`. Distance is defined as `1.0` when either
+/// vector has zero norm, to avoid dividing by zero and producing a NaN.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::cosine_distance_dispatch;
+///
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0];
+/// let distance = cosine_distance_dispatch(&a, &b);
+/// assert!((distance - 1.0).abs() < 1e-6);
+/// ```
+pub fn cosine_distance_dispatch(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return f32::INFINITY;
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                // SAFETY: We've just checked that AVX2 is available at runtime
+                return unsafe { cosine_distance_avx2(a, b) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if is_aarch64_feature_detected!("neon") {
+                // SAFETY: We've just checked that NEON is available at runtime
+                return unsafe { cosine_distance_neon(a, b) };
+            }
+        }
+
+        // See the equivalent branch in `l2_squared_distance_dispatch` for why
+        // this is tried before the `wide`-based kernel on non-x86_64 targets.
+        #[cfg(all(feature = "portable_simd", not(target_arch = "x86_64")))]
+        {
+            if a.len() >= 8 {
+                return cosine_distance_portable::<8>(a, b);
+            }
+        }
+
+        // Use portable SIMD if supported
+        if a.len() >= 8 {
+            return cosine_distance_simd(a, b);
+        }
+    }
+
+    // Fallback to scalar implementation
+    cosine_distance_scalar(a, b)
+}
+
+/// Runtime dispatch for L1 (Manhattan) distance computation
+///
+/// Automatically selects the best available implementation based on CPU
+/// features, mirroring [`l2_squared_distance_dispatch`]'s AVX2/NEON/portable
+/// cascade:
+/// - AVX2/NEON SIMD implementation when available
+/// - Scalar fallback for compatibility
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::l1_distance_dispatch;
+///
+/// let a = vec![0.0f32, 0.0];
+/// let b = vec![3.0f32, 4.0];
+/// let distance = l1_distance_dispatch(&a, &b);
+/// assert!((distance - 7.0).abs() < 1e-6);
+/// ```
+pub fn l1_distance_dispatch(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return f32::INFINITY;
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                // SAFETY: We've just checked that AVX2 is available at runtime
+                return unsafe { l1_distance_avx2(a, b) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if is_aarch64_feature_detected!("neon") {
+                // SAFETY: We've just checked that NEON is available at runtime
+                return unsafe { l1_distance_neon(a, b) };
+            }
+        }
+
+        // Use portable SIMD if supported
+        if a.len() >= 8 {
+            return l1_distance_simd(a, b);
+        }
+    }
+
+    // Fallback to scalar implementation
+    l1_distance_scalar(a, b)
+}
+
+/// Scalar implementation of cosine distance
+///
+/// This is the fallback implementation that works on all architectures; see
+/// [`cosine_distance_dispatch`] for the fused dot-product/norm accumulation
+/// and zero-vector handling this mirrors.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::cosine_distance_scalar;
+///
+/// let a = vec![1.0f32, 0.0];
+/// let b = vec![1.0f32, 0.0];
+/// let distance = cosine_distance_scalar(&a, &b);
+/// assert!(distance < 1e-6);
+/// ```
+pub fn cosine_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0; // Maximum distance for zero vectors
+    }
+
+    let cosine_sim = (dot / (sqrtf(norm_a) * sqrtf(norm_b))).max(-1.0).min(1.0);
+    1.0 - cosine_sim
+}
+
 /// Scalar implementation of L2 squared distance
 /// 
 /// This is the fallback implementation that works on all architectures.
@@ -133,9 +366,30 @@ pub fn inner_product_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
     1.0 - dot_product
 }
 
+/// Scalar implementation of L1 (Manhattan) distance
+///
+/// This is the fallback implementation that works on all architectures.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::l1_distance_scalar;
+///
+/// let a = vec![0.0f32, 0.0];
+/// let b = vec![3.0f32, 4.0];
+/// let dist = l1_distance_scalar(&a, &b);
+/// assert!((dist - 7.0).abs() < 1e-6);
+/// ```
+pub fn l1_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        sum += (x - y).abs();
+    }
+    sum
+}
+
 #[cfg(feature = "simd")]
 /// SIMD implementation of L2 squared distance using portable SIMD
-/// 
+///
 /// Uses 256-bit SIMD vectors (8 f32 elements) for acceleration.
 /// Falls back to scalar processing for remaining elements.
 /// 
@@ -241,6 +495,122 @@ pub fn inner_product_distance_simd(a: &[f32], b: &[f32]) -> f32 {
     1.0 - dot_product
 }
 
+#[cfg(feature = "simd")]
+/// SIMD implementation of cosine distance using portable SIMD
+///
+/// Fuses the dot product and both squared norms into one pass of 256-bit
+/// SIMD accumulators (8 f32 elements), reducing all three together before
+/// falling back to scalar for the tail; see [`cosine_distance_dispatch`] for
+/// the zero-vector handling this mirrors.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "simd")]
+/// # {
+/// use diskann_core::simd::cosine_distance_simd;
+///
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let distance = cosine_distance_simd(&a, &b);
+/// assert!((distance - 1.0).abs() < 1e-6);
+/// # }
+/// ```
+pub fn cosine_distance_simd(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let mut dot_sum = f32x8::ZERO;
+    let mut norm_a_sum = f32x8::ZERO;
+    let mut norm_b_sum = f32x8::ZERO;
+
+    // Process 8 elements at a time
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: We've ensured i + 8 <= simd_len <= len, so slice access is safe
+        let chunk_a = f32x8::from([
+            a[i], a[i+1], a[i+2], a[i+3],
+            a[i+4], a[i+5], a[i+6], a[i+7]
+        ]);
+        let chunk_b = f32x8::from([
+            b[i], b[i+1], b[i+2], b[i+3],
+            b[i+4], b[i+5], b[i+6], b[i+7]
+        ]);
+        dot_sum += chunk_a * chunk_b;
+        norm_a_sum += chunk_a * chunk_a;
+        norm_b_sum += chunk_b * chunk_b;
+        i += 8;
+    }
+
+    // Sum each SIMD register
+    let mut dot = dot_sum.to_array().iter().sum::<f32>();
+    let mut norm_a = norm_a_sum.to_array().iter().sum::<f32>();
+    let mut norm_b = norm_b_sum.to_array().iter().sum::<f32>();
+
+    // Process remaining elements
+    for j in simd_len..len {
+        dot += a[j] * b[j];
+        norm_a += a[j] * a[j];
+        norm_b += b[j] * b[j];
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0; // Maximum distance for zero vectors
+    }
+
+    let cosine_sim = (dot / (sqrtf(norm_a) * sqrtf(norm_b))).max(-1.0).min(1.0);
+    1.0 - cosine_sim
+}
+
+#[cfg(feature = "simd")]
+/// SIMD implementation of L1 (Manhattan) distance using portable SIMD
+///
+/// Uses 256-bit SIMD vectors (8 f32 elements), summing per-lane absolute
+/// differences before the horizontal reduction. Falls back to scalar
+/// processing for remaining elements.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "simd")]
+/// # {
+/// use diskann_core::simd::l1_distance_simd;
+///
+/// let a: Vec<f32> = (0..16).map(|i| i as f32).collect();
+/// let b: Vec<f32> = (0..16).map(|i| (i + 1) as f32).collect();
+/// let distance = l1_distance_simd(&a, &b);
+/// assert_eq!(distance, 16.0); // Each diff is 1.0, abs = 1.0, sum = 16.0
+/// # }
+/// ```
+pub fn l1_distance_simd(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let mut sum = f32x8::ZERO;
+
+    // Process 8 elements at a time
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: We've ensured i + 8 <= simd_len <= len, so slice access is safe
+        let chunk_a = f32x8::from([
+            a[i], a[i+1], a[i+2], a[i+3],
+            a[i+4], a[i+5], a[i+6], a[i+7]
+        ]);
+        let chunk_b = f32x8::from([
+            b[i], b[i+1], b[i+2], b[i+3],
+            b[i+4], b[i+5], b[i+6], b[i+7]
+        ]);
+        sum += (chunk_a - chunk_b).abs();
+        i += 8;
+    }
+
+    // Sum the SIMD register
+    let mut result = sum.to_array().iter().sum::<f32>();
+
+    // Process remaining elements
+    for j in simd_len..len {
+        result += (a[j] - b[j]).abs();
+    }
+
+    result
+}
+
 #[cfg(all(feature = "simd", target_arch = "x86_64"))]
 /// AVX2-optimized L2 squared distance computation
 /// 
@@ -365,46 +735,1294 @@ pub unsafe fn inner_product_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
     1.0 - dot_product
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[cfg(not(feature = "std"))]
-    use alloc::vec;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2+FMA-optimized L2 squared distance computation
+///
+/// Same 8-lane loop as [`l2_squared_distance_avx2`], but folds the
+/// multiply and add of the squared-diff term into a single
+/// `_mm256_fmadd_ps`, trading one instruction (and one fewer rounding
+/// step) for the separate `_mm256_mul_ps`/`_mm256_add_ps` pair.
+///
+/// # Safety
+/// This function requires both AVX2 and FMA support. Call only after
+/// verifying with `is_x86_feature_detected!("avx2")` and
+/// `is_x86_feature_detected!("fma")`.
+///
+/// The function uses raw pointer access with unchecked bounds, so the
+/// caller must ensure that both slices have the same length.
+#[target_feature(enable = "avx2,fma")]
+pub unsafe fn l2_squared_distance_avx2_fma(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
 
-    #[test]
-    fn test_l2_squared_distance_scalar() {
-        let a = vec![0.0f32, 0.0];
-        let b = vec![3.0f32, 4.0];
-        let distance = l2_squared_distance_scalar(&a, &b);
-        assert!((distance - 25.0).abs() < 1e-6);
-    }
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let mut sum = _mm256_setzero_ps();
 
-    #[test]
-    fn test_inner_product_distance_scalar() {
-        let a = vec![1.0f32, 0.0];
-        let b = vec![0.0f32, 1.0];
-        let distance = inner_product_distance_scalar(&a, &b);
-        assert!((distance - 1.0).abs() < 1e-6);
-        
-        let parallel = inner_product_distance_scalar(&a, &a);
-        assert!(parallel < 1e-6);
-    }
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
 
-    #[test]
-    fn test_l2_squared_distance_dispatch() {
-        let a = vec![0.0f32, 0.0, 1.0, 2.0];
-        let b = vec![3.0f32, 4.0, 1.0, 2.0];
-        let distance = l2_squared_distance_dispatch(&a, &b);
-        assert!((distance - 25.0).abs() < 1e-6); // 3^2 + 4^2 + 0^2 + 0^2 = 25
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm256_loadu_ps(a_ptr.add(i));
+        let vb = _mm256_loadu_ps(b_ptr.add(i));
+        let diff = _mm256_sub_ps(va, vb);
+        sum = _mm256_fmadd_ps(diff, diff, sum);
+        i += 8;
     }
 
-    #[test]
-    fn test_inner_product_distance_dispatch() {
-        let a = vec![1.0f32, 0.0, 0.0, 0.0];
-        let b = vec![0.0f32, 1.0, 0.0, 0.0];
-        let distance = inner_product_distance_dispatch(&a, &b);
-        assert!((distance - 1.0).abs() < 1e-6);
-    }
+    // Horizontal sum of the AVX2 register
+    let sum_lo = _mm256_castps256_ps128(sum);
+    let sum_hi = _mm256_extractf128_ps(sum, 1);
+    let sum_128 = _mm_add_ps(sum_lo, sum_hi);
+    let sum_64 = _mm_add_ps(sum_128, _mm_movehl_ps(sum_128, sum_128));
+    let sum_32 = _mm_add_ss(sum_64, _mm_shuffle_ps(sum_64, sum_64, 1));
+    let mut result = _mm_cvtss_f32(sum_32);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let diff = *a_ptr.add(j) - *b_ptr.add(j);
+        result += diff * diff;
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2+FMA-optimized inner product distance computation
+///
+/// Same 8-lane loop as [`inner_product_distance_avx2`], but accumulates via
+/// `_mm256_fmadd_ps` instead of a separate multiply/add pair.
+///
+/// # Safety
+/// This function requires both AVX2 and FMA support. Call only after
+/// verifying with `is_x86_feature_detected!("avx2")` and
+/// `is_x86_feature_detected!("fma")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+#[target_feature(enable = "avx2,fma")]
+pub unsafe fn inner_product_distance_avx2_fma(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let mut sum = _mm256_setzero_ps();
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm256_loadu_ps(a_ptr.add(i));
+        let vb = _mm256_loadu_ps(b_ptr.add(i));
+        sum = _mm256_fmadd_ps(va, vb, sum);
+        i += 8;
+    }
+
+    // Horizontal sum of the AVX2 register
+    let sum_lo = _mm256_castps256_ps128(sum);
+    let sum_hi = _mm256_extractf128_ps(sum, 1);
+    let sum_128 = _mm_add_ps(sum_lo, sum_hi);
+    let sum_64 = _mm_add_ps(sum_128, _mm_movehl_ps(sum_128, sum_128));
+    let sum_32 = _mm_add_ss(sum_64, _mm_shuffle_ps(sum_64, sum_64, 1));
+    let mut dot_product = _mm_cvtss_f32(sum_32);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        dot_product += *a_ptr.add(j) * *b_ptr.add(j);
+    }
+
+    1.0 - dot_product
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX-512-optimized L2 squared distance computation
+///
+/// 16-lane counterpart to [`l2_squared_distance_avx2_fma`]: each chunk is
+/// folded via `_mm512_fmadd_ps` and the 16-wide accumulator is reduced with
+/// `_mm512_reduce_add_ps`.
+///
+/// # Safety
+/// This function requires AVX-512F support. Call only after verifying with
+/// `is_x86_feature_detected!("avx512f")`.
+///
+/// The function uses raw pointer access with unchecked bounds, so the
+/// caller must ensure that both slices have the same length.
+#[target_feature(enable = "avx512f")]
+pub unsafe fn l2_squared_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 16);
+    let mut acc = _mm512_setzero_ps();
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm512_loadu_ps(a_ptr.add(i));
+        let vb = _mm512_loadu_ps(b_ptr.add(i));
+        let diff = _mm512_sub_ps(va, vb);
+        acc = _mm512_fmadd_ps(diff, diff, acc);
+        i += 16;
+    }
+
+    let mut result = _mm512_reduce_add_ps(acc);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let diff = *a_ptr.add(j) - *b_ptr.add(j);
+        result += diff * diff;
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX-512-optimized inner product distance computation
+///
+/// 16-lane counterpart to [`inner_product_distance_avx2_fma`]; see
+/// [`l2_squared_distance_avx512`] for the fmadd/reduce strategy this shares.
+///
+/// # Safety
+/// This function requires AVX-512F support. Call only after verifying with
+/// `is_x86_feature_detected!("avx512f")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+#[target_feature(enable = "avx512f")]
+pub unsafe fn inner_product_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 16);
+    let mut acc = _mm512_setzero_ps();
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm512_loadu_ps(a_ptr.add(i));
+        let vb = _mm512_loadu_ps(b_ptr.add(i));
+        acc = _mm512_fmadd_ps(va, vb, acc);
+        i += 16;
+    }
+
+    let mut dot_product = _mm512_reduce_add_ps(acc);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        dot_product += *a_ptr.add(j) * *b_ptr.add(j);
+    }
+
+    1.0 - dot_product
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2-optimized cosine distance computation
+///
+/// Fuses the dot product and both squared norms into a single pass over
+/// AVX2 registers, horizontally reducing each of the three accumulators
+/// before combining them; see [`cosine_distance_dispatch`] for the
+/// zero-vector handling this mirrors.
+///
+/// # Safety
+/// This function requires AVX2 support. Call only after verifying with
+/// `is_x86_feature_detected!("avx2")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// # {
+/// use diskann_core::simd::cosine_distance_avx2;
+///
+/// if is_x86_feature_detected!("avx2") {
+///     let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+///     let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+///     let distance = unsafe { cosine_distance_avx2(&a, &b) };
+///     assert!((distance - 1.0).abs() < 1e-6);
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "avx2")]
+pub unsafe fn cosine_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let mut dot_sum = _mm256_setzero_ps();
+    let mut norm_a_sum = _mm256_setzero_ps();
+    let mut norm_b_sum = _mm256_setzero_ps();
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm256_loadu_ps(a_ptr.add(i));
+        let vb = _mm256_loadu_ps(b_ptr.add(i));
+        dot_sum = _mm256_add_ps(dot_sum, _mm256_mul_ps(va, vb));
+        norm_a_sum = _mm256_add_ps(norm_a_sum, _mm256_mul_ps(va, va));
+        norm_b_sum = _mm256_add_ps(norm_b_sum, _mm256_mul_ps(vb, vb));
+        i += 8;
+    }
+
+    // Horizontal sum of the dot product accumulator
+    let dot_lo = _mm256_castps256_ps128(dot_sum);
+    let dot_hi = _mm256_extractf128_ps(dot_sum, 1);
+    let dot_128 = _mm_add_ps(dot_lo, dot_hi);
+    let dot_64 = _mm_add_ps(dot_128, _mm_movehl_ps(dot_128, dot_128));
+    let dot_32 = _mm_add_ss(dot_64, _mm_shuffle_ps(dot_64, dot_64, 1));
+    let mut dot = _mm_cvtss_f32(dot_32);
+
+    // Horizontal sum of the norm_a accumulator
+    let norm_a_lo = _mm256_castps256_ps128(norm_a_sum);
+    let norm_a_hi = _mm256_extractf128_ps(norm_a_sum, 1);
+    let norm_a_128 = _mm_add_ps(norm_a_lo, norm_a_hi);
+    let norm_a_64 = _mm_add_ps(norm_a_128, _mm_movehl_ps(norm_a_128, norm_a_128));
+    let norm_a_32 = _mm_add_ss(norm_a_64, _mm_shuffle_ps(norm_a_64, norm_a_64, 1));
+    let mut norm_a = _mm_cvtss_f32(norm_a_32);
+
+    // Horizontal sum of the norm_b accumulator
+    let norm_b_lo = _mm256_castps256_ps128(norm_b_sum);
+    let norm_b_hi = _mm256_extractf128_ps(norm_b_sum, 1);
+    let norm_b_128 = _mm_add_ps(norm_b_lo, norm_b_hi);
+    let norm_b_64 = _mm_add_ps(norm_b_128, _mm_movehl_ps(norm_b_128, norm_b_128));
+    let norm_b_32 = _mm_add_ss(norm_b_64, _mm_shuffle_ps(norm_b_64, norm_b_64, 1));
+    let mut norm_b = _mm_cvtss_f32(norm_b_32);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let x = *a_ptr.add(j);
+        let y = *b_ptr.add(j);
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0; // Maximum distance for zero vectors
+    }
+
+    let cosine_sim = (dot / (sqrtf(norm_a) * sqrtf(norm_b))).max(-1.0).min(1.0);
+    1.0 - cosine_sim
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2-optimized L1 (Manhattan) distance computation
+///
+/// # Safety
+/// This function requires AVX2 support. Call only after verifying with
+/// `is_x86_feature_detected!("avx2")`.
+///
+/// The function uses raw pointer access with unchecked bounds, so the caller
+/// must ensure that both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// # {
+/// use diskann_core::simd::l1_distance_avx2;
+///
+/// if is_x86_feature_detected!("avx2") {
+///     let a = vec![0.0f32, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+///     let b = vec![3.0f32, 4.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+///     let distance = unsafe { l1_distance_avx2(&a, &b) };
+///     assert!((distance - 7.0).abs() < 1e-6);
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "avx2")]
+pub unsafe fn l1_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::x86_64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 8);
+    let abs_mask = _mm256_set1_ps(f32::from_bits(0x7FFF_FFFF));
+    let mut sum = _mm256_setzero_ps();
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = _mm256_loadu_ps(a_ptr.add(i));
+        let vb = _mm256_loadu_ps(b_ptr.add(i));
+        let diff = _mm256_and_ps(_mm256_sub_ps(va, vb), abs_mask);
+        sum = _mm256_add_ps(sum, diff);
+        i += 8;
+    }
+
+    // Horizontal sum of the accumulator
+    let sum_lo = _mm256_castps256_ps128(sum);
+    let sum_hi = _mm256_extractf128_ps(sum, 1);
+    let sum_128 = _mm_add_ps(sum_lo, sum_hi);
+    let sum_64 = _mm_add_ps(sum_128, _mm_movehl_ps(sum_128, sum_128));
+    let sum_32 = _mm_add_ss(sum_64, _mm_shuffle_ps(sum_64, sum_64, 1));
+    let mut result = _mm_cvtss_f32(sum_32);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        result += (*a_ptr.add(j) - *b_ptr.add(j)).abs();
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+/// NEON-optimized L2 squared distance computation
+///
+/// # Safety
+/// This function requires NEON support. Call only after verifying with
+/// `is_aarch64_feature_detected!("neon")`.
+///
+/// The function uses raw pointer access with unchecked bounds, so the caller
+/// must ensure that both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(target_arch = "aarch64")]
+/// # {
+/// use diskann_core::simd::l2_squared_distance_neon;
+///
+/// if is_aarch64_feature_detected!("neon") {
+///     let a = vec![1.0f32, 2.0, 3.0, 4.0];
+///     let b = vec![2.0f32, 3.0, 4.0, 5.0];
+///     let distance = unsafe { l2_squared_distance_neon(&a, &b) };
+///     assert_eq!(distance, 4.0); // Each diff is 1.0, squared = 1.0, sum = 4.0
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "neon")]
+pub unsafe fn l2_squared_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::aarch64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut sum = vdupq_n_f32(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = vld1q_f32(a_ptr.add(i));
+        let vb = vld1q_f32(b_ptr.add(i));
+        let diff = vsubq_f32(va, vb);
+        sum = vfmaq_f32(sum, diff, diff);
+        i += 4;
+    }
+
+    // Horizontal sum of the four NEON lanes
+    let mut result = vaddvq_f32(sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let diff = *a_ptr.add(j) - *b_ptr.add(j);
+        result += diff * diff;
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+/// NEON-optimized inner product distance computation
+///
+/// # Safety
+/// This function requires NEON support. Call only after verifying with
+/// `is_aarch64_feature_detected!("neon")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(target_arch = "aarch64")]
+/// # {
+/// use diskann_core::simd::inner_product_distance_neon;
+///
+/// if is_aarch64_feature_detected!("neon") {
+///     let a = vec![1.0f32, 0.0, 0.0, 0.0];
+///     let b = vec![1.0f32, 0.0, 0.0, 0.0];
+///     let distance = unsafe { inner_product_distance_neon(&a, &b) };
+///     assert!((distance - 0.0).abs() < 1e-6); // 1.0 - 1.0 = 0.0
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "neon")]
+pub unsafe fn inner_product_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::aarch64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut sum = vdupq_n_f32(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = vld1q_f32(a_ptr.add(i));
+        let vb = vld1q_f32(b_ptr.add(i));
+        sum = vfmaq_f32(sum, va, vb);
+        i += 4;
+    }
+
+    // Horizontal sum of the four NEON lanes
+    let mut dot_product = vaddvq_f32(sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        dot_product += *a_ptr.add(j) * *b_ptr.add(j);
+    }
+
+    1.0 - dot_product
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+/// NEON-optimized cosine distance computation
+///
+/// Fuses the dot product and both squared norms into a single pass over
+/// NEON registers, each accumulated via `vfmaq_f32`; see
+/// [`cosine_distance_dispatch`] for the zero-vector handling this mirrors.
+///
+/// # Safety
+/// This function requires NEON support. Call only after verifying with
+/// `is_aarch64_feature_detected!("neon")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(target_arch = "aarch64")]
+/// # {
+/// use diskann_core::simd::cosine_distance_neon;
+///
+/// if is_aarch64_feature_detected!("neon") {
+///     let a = vec![1.0f32, 0.0, 0.0, 0.0];
+///     let b = vec![0.0f32, 1.0, 0.0, 0.0];
+///     let distance = unsafe { cosine_distance_neon(&a, &b) };
+///     assert!((distance - 1.0).abs() < 1e-6);
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "neon")]
+pub unsafe fn cosine_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::aarch64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut dot_sum = vdupq_n_f32(0.0);
+    let mut norm_a_sum = vdupq_n_f32(0.0);
+    let mut norm_b_sum = vdupq_n_f32(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = vld1q_f32(a_ptr.add(i));
+        let vb = vld1q_f32(b_ptr.add(i));
+        dot_sum = vfmaq_f32(dot_sum, va, vb);
+        norm_a_sum = vfmaq_f32(norm_a_sum, va, va);
+        norm_b_sum = vfmaq_f32(norm_b_sum, vb, vb);
+        i += 4;
+    }
+
+    // Horizontal sum of each set of four NEON lanes
+    let mut dot = vaddvq_f32(dot_sum);
+    let mut norm_a = vaddvq_f32(norm_a_sum);
+    let mut norm_b = vaddvq_f32(norm_b_sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let x = *a_ptr.add(j);
+        let y = *b_ptr.add(j);
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0; // Maximum distance for zero vectors
+    }
+
+    let cosine_sim = (dot / (sqrtf(norm_a) * sqrtf(norm_b))).max(-1.0).min(1.0);
+    1.0 - cosine_sim
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+/// NEON-optimized L1 (Manhattan) distance computation
+///
+/// # Safety
+/// This function requires NEON support. Call only after verifying with
+/// `is_aarch64_feature_detected!("neon")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// both slices have the same length.
+///
+/// # Examples
+/// ```
+/// # #[cfg(target_arch = "aarch64")]
+/// # {
+/// use diskann_core::simd::l1_distance_neon;
+///
+/// if is_aarch64_feature_detected!("neon") {
+///     let a = vec![0.0f32, 0.0, 1.0, 2.0];
+///     let b = vec![3.0f32, 4.0, 1.0, 2.0];
+///     let distance = unsafe { l1_distance_neon(&a, &b) };
+///     assert!((distance - 7.0).abs() < 1e-6);
+/// }
+/// # }
+/// ```
+#[target_feature(enable = "neon")]
+pub unsafe fn l1_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::aarch64::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut sum = vdupq_n_f32(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = vld1q_f32(a_ptr.add(i));
+        let vb = vld1q_f32(b_ptr.add(i));
+        let diff = vabdq_f32(va, vb);
+        sum = vaddq_f32(sum, diff);
+        i += 4;
+    }
+
+    // Horizontal sum of the four NEON lanes
+    let mut result = vaddvq_f32(sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        result += (*a_ptr.add(j) - *b_ptr.add(j)).abs();
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+/// WASM SIMD128-optimized L2 squared distance computation
+///
+/// 4-lane counterpart to [`l2_squared_distance_avx2`]/[`l2_squared_distance_neon`],
+/// built on `core::arch::wasm32` so browser/edge (`wasm32` + `simd128`)
+/// deployments get accelerated distances instead of falling back to scalar.
+///
+/// # Safety
+/// This function requires the `simd128` target feature, checked at compile
+/// time via the `target_feature = "simd128"` cfg rather than runtime
+/// detection (WASM has no `is_wasm_feature_detected!`). The function uses
+/// raw pointer access, so the caller must ensure that both slices have the
+/// same length.
+pub unsafe fn l2_squared_distance_wasm(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::wasm32::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut sum = f32x4_splat(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = v128_load(a_ptr.add(i) as *const v128);
+        let vb = v128_load(b_ptr.add(i) as *const v128);
+        let diff = f32x4_sub(va, vb);
+        let sq_diff = f32x4_mul(diff, diff);
+        sum = f32x4_add(sum, sq_diff);
+        i += 4;
+    }
+
+    // Horizontal sum of the four SIMD128 lanes
+    let mut result = f32x4_extract_lane::<0>(sum)
+        + f32x4_extract_lane::<1>(sum)
+        + f32x4_extract_lane::<2>(sum)
+        + f32x4_extract_lane::<3>(sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        let diff = *a_ptr.add(j) - *b_ptr.add(j);
+        result += diff * diff;
+    }
+
+    result
+}
+
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+/// WASM SIMD128-optimized inner product distance computation
+///
+/// See [`l2_squared_distance_wasm`] for the lane width and reduction
+/// strategy this shares.
+///
+/// # Safety
+/// Same requirements as [`l2_squared_distance_wasm`].
+pub unsafe fn inner_product_distance_wasm(a: &[f32], b: &[f32]) -> f32 {
+    use core::arch::wasm32::*;
+
+    let len = a.len();
+    let simd_len = len - (len % 4);
+    let mut sum = f32x4_splat(0.0);
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: Caller ensures slices have same length and we stay within bounds
+        let va = v128_load(a_ptr.add(i) as *const v128);
+        let vb = v128_load(b_ptr.add(i) as *const v128);
+        sum = f32x4_add(sum, f32x4_mul(va, vb));
+        i += 4;
+    }
+
+    // Horizontal sum of the four SIMD128 lanes
+    let mut dot_product = f32x4_extract_lane::<0>(sum)
+        + f32x4_extract_lane::<1>(sum)
+        + f32x4_extract_lane::<2>(sum)
+        + f32x4_extract_lane::<3>(sum);
+
+    // Process remaining elements
+    for j in simd_len..len {
+        dot_product += *a_ptr.add(j) * *b_ptr.add(j);
+    }
+
+    1.0 - dot_product
+}
+
+// Portable SIMD distance kernels built on nightly `core::simd`.
+//
+// The AVX2 kernels above only run on x86_64, and the NEON and WASM simd128
+// kernels above only on aarch64 and wasm32; on other targets only the scalar
+// fallback or the `wide`-based `*_simd` functions apply, and `wide` itself
+// falls back to scalar on targets it doesn't special-case. This section adds
+// a backend built on
+// `core::simd::Simd<f32, LANES>`, generic over the lane count, so the same
+// kernel lowers to the target's native vector instructions (NEON, WASM
+// SIMD128, ...) instead. Requires a nightly compiler; gated behind the
+// `portable_simd` feature, which also enables `#![feature(portable_simd)]`
+// crate-wide in `lib.rs`.
+#[cfg(feature = "portable_simd")]
+use core::simd::{LaneCount, Simd, SupportedLaneCount, num::SimdFloat};
+
+/// L2 squared distance using nightly `core::simd::Simd<f32, LANES>`
+///
+/// Generic over the lane count so the same source compiles to whatever
+/// vector width the target natively supports (e.g. 4 lanes for NEON's
+/// `float32x4_t`, 8 for AVX2-width portable code).
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # #[cfg(feature = "portable_simd")]
+/// # {
+/// use diskann_core::simd::l2_squared_distance_portable;
+///
+/// let a: Vec<f32> = (0..16).map(|i| i as f32).collect();
+/// let b: Vec<f32> = (0..16).map(|i| (i + 1) as f32).collect();
+/// let distance = l2_squared_distance_portable::<8>(&a, &b);
+/// assert_eq!(distance, 16.0);
+/// # }
+/// ```
+#[cfg(feature = "portable_simd")]
+pub fn l2_squared_distance_portable<const LANES: usize>(a: &[f32], b: &[f32]) -> f32
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let len = a.len();
+    let simd_len = len - (len % LANES);
+    let mut sum = Simd::<f32, LANES>::splat(0.0);
+
+    let mut i = 0;
+    while i < simd_len {
+        let chunk_a = Simd::<f32, LANES>::from_slice(&a[i..i + LANES]);
+        let chunk_b = Simd::<f32, LANES>::from_slice(&b[i..i + LANES]);
+        let diff = chunk_a - chunk_b;
+        sum += diff * diff;
+        i += LANES;
+    }
+
+    let mut result = sum.reduce_sum();
+    for j in simd_len..len {
+        let diff = a[j] - b[j];
+        result += diff * diff;
+    }
+    result
+}
+
+/// Inner product distance using nightly `core::simd::Simd<f32, LANES>`
+///
+/// Computes `1.0 - dot_product(a, b)`; see [`l2_squared_distance_portable`]
+/// for the lane-count generics.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # #[cfg(feature = "portable_simd")]
+/// # {
+/// use diskann_core::simd::inner_product_distance_portable;
+///
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let distance = inner_product_distance_portable::<8>(&a, &b);
+/// assert!((distance - 1.0).abs() < 1e-6);
+/// # }
+/// ```
+#[cfg(feature = "portable_simd")]
+pub fn inner_product_distance_portable<const LANES: usize>(a: &[f32], b: &[f32]) -> f32
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let len = a.len();
+    let simd_len = len - (len % LANES);
+    let mut sum = Simd::<f32, LANES>::splat(0.0);
+
+    let mut i = 0;
+    while i < simd_len {
+        let chunk_a = Simd::<f32, LANES>::from_slice(&a[i..i + LANES]);
+        let chunk_b = Simd::<f32, LANES>::from_slice(&b[i..i + LANES]);
+        sum += chunk_a * chunk_b;
+        i += LANES;
+    }
+
+    let mut dot_product = sum.reduce_sum();
+    for j in simd_len..len {
+        dot_product += a[j] * b[j];
+    }
+    1.0 - dot_product
+}
+
+/// Cosine distance using nightly `core::simd::Simd<f32, LANES>`
+///
+/// Fuses the dot product and both squared norms into one pass of `LANES`-wide
+/// accumulators, mirroring [`cosine_distance_simd`]; see
+/// [`cosine_distance_dispatch`] for the zero-vector handling this shares.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # #[cfg(feature = "portable_simd")]
+/// # {
+/// use diskann_core::simd::cosine_distance_portable;
+///
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let distance = cosine_distance_portable::<8>(&a, &b);
+/// assert!((distance - 1.0).abs() < 1e-6);
+/// # }
+/// ```
+#[cfg(feature = "portable_simd")]
+pub fn cosine_distance_portable<const LANES: usize>(a: &[f32], b: &[f32]) -> f32
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let len = a.len();
+    let simd_len = len - (len % LANES);
+    let mut dot_sum = Simd::<f32, LANES>::splat(0.0);
+    let mut norm_a_sum = Simd::<f32, LANES>::splat(0.0);
+    let mut norm_b_sum = Simd::<f32, LANES>::splat(0.0);
+
+    let mut i = 0;
+    while i < simd_len {
+        let chunk_a = Simd::<f32, LANES>::from_slice(&a[i..i + LANES]);
+        let chunk_b = Simd::<f32, LANES>::from_slice(&b[i..i + LANES]);
+        dot_sum += chunk_a * chunk_b;
+        norm_a_sum += chunk_a * chunk_a;
+        norm_b_sum += chunk_b * chunk_b;
+        i += LANES;
+    }
+
+    let mut dot = dot_sum.reduce_sum();
+    let mut norm_a = norm_a_sum.reduce_sum();
+    let mut norm_b = norm_b_sum.reduce_sum();
+
+    for j in simd_len..len {
+        dot += a[j] * b[j];
+        norm_a += a[j] * a[j];
+        norm_b += b[j] * b[j];
+    }
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 1.0;
+    }
+
+    let cosine_sim = (dot / (sqrtf(norm_a) * sqrtf(norm_b))).max(-1.0).min(1.0);
+    1.0 - cosine_sim
+}
+
+// Batched one-to-many distance kernels.
+//
+// Beam search computes the distance from one query to many candidate
+// neighbors in a tight loop; a naive `for c in candidates { dispatch(query,
+// c) }` re-derives `query`'s SIMD chunks on every single call. The functions
+// below load `query` into SIMD registers once per batch and stream each
+// candidate through that resident state instead.
+
+#[cfg(feature = "simd")]
+/// Split `query` into `f32x8` chunks once, for reuse across a batch of candidates
+///
+/// Returns the whole-chunk lanes; callers must still handle the
+/// `query.len() % 8` tail themselves, matching the scalar-tail pattern used
+/// throughout this module.
+fn load_query_chunks(query: &[f32]) -> Vec<f32x8> {
+    let simd_len = query.len() - (query.len() % 8);
+    let mut chunks = Vec::with_capacity(simd_len / 8);
+    let mut i = 0;
+    while i < simd_len {
+        chunks.push(f32x8::from([
+            query[i], query[i+1], query[i+2], query[i+3],
+            query[i+4], query[i+5], query[i+6], query[i+7],
+        ]));
+        i += 8;
+    }
+    chunks
+}
+
+/// Compute squared L2 distance from `query` to every vector in `candidates`, writing into `out`
+///
+/// Equivalent to calling [`l2_squared_distance_dispatch`] once per candidate,
+/// but (when the `simd` feature is enabled and `query.len() >= 8`) `query`'s
+/// SIMD chunks are loaded once and reused across the whole batch instead of
+/// being re-derived on every call.
+///
+/// # Panics
+/// Panics if `candidates.len() != out.len()`.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::l2_squared_distance_batch;
+///
+/// let query = vec![0.0f32, 0.0];
+/// let a = vec![3.0f32, 4.0];
+/// let b = vec![1.0f32, 0.0];
+/// let candidates: Vec<&[f32]> = vec![&a, &b];
+/// let mut out = [0.0f32; 2];
+/// l2_squared_distance_batch(&query, &candidates, &mut out);
+/// assert!((out[0] - 25.0).abs() < 1e-6);
+/// assert!((out[1] - 1.0).abs() < 1e-6);
+/// ```
+pub fn l2_squared_distance_batch(query: &[f32], candidates: &[&[f32]], out: &mut [f32]) {
+    assert_eq!(candidates.len(), out.len(), "candidates and out must have the same length");
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", feature = "std"))]
+    {
+        if query.len() >= 8 && is_x86_feature_detected!("avx2") {
+            // SAFETY: We've just checked that AVX2 is available at runtime
+            unsafe { l2_squared_distance_batch_avx2(query, candidates, out) };
+            return;
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        if query.len() >= 8 {
+            let query_chunks = load_query_chunks(query);
+            let simd_len = query_chunks.len() * 8;
+
+            for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+                if candidate.len() != query.len() {
+                    *slot = f32::INFINITY;
+                    continue;
+                }
+
+                let mut sum = f32x8::ZERO;
+                for (chunk_idx, &query_chunk) in query_chunks.iter().enumerate() {
+                    let base = chunk_idx * 8;
+                    let chunk_c = f32x8::from([
+                        candidate[base], candidate[base+1], candidate[base+2], candidate[base+3],
+                        candidate[base+4], candidate[base+5], candidate[base+6], candidate[base+7],
+                    ]);
+                    let diff = query_chunk - chunk_c;
+                    sum += diff * diff;
+                }
+
+                let mut result = sum.to_array().iter().sum::<f32>();
+                for j in simd_len..query.len() {
+                    let diff = query[j] - candidate[j];
+                    result += diff * diff;
+                }
+                *slot = result;
+            }
+            return;
+        }
+    }
+
+    for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+        *slot = l2_squared_distance_dispatch(query, candidate);
+    }
+}
+
+/// Compute inner-product distance from `query` to every vector in `candidates`, writing into `out`
+///
+/// See [`l2_squared_distance_batch`] for the register-blocking strategy this shares.
+///
+/// # Panics
+/// Panics if `candidates.len() != out.len()`.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::inner_product_distance_batch;
+///
+/// let query = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let candidates: Vec<&[f32]> = vec![&a, &b];
+/// let mut out = [0.0f32; 2];
+/// inner_product_distance_batch(&query, &candidates, &mut out);
+/// assert!(out[0] < 1e-6);
+/// assert!((out[1] - 1.0).abs() < 1e-6);
+/// ```
+pub fn inner_product_distance_batch(query: &[f32], candidates: &[&[f32]], out: &mut [f32]) {
+    assert_eq!(candidates.len(), out.len(), "candidates and out must have the same length");
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", feature = "std"))]
+    {
+        if query.len() >= 8 && is_x86_feature_detected!("avx2") {
+            // SAFETY: We've just checked that AVX2 is available at runtime
+            unsafe { inner_product_distance_batch_avx2(query, candidates, out) };
+            return;
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        if query.len() >= 8 {
+            let query_chunks = load_query_chunks(query);
+            let simd_len = query_chunks.len() * 8;
+
+            for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+                if candidate.len() != query.len() {
+                    *slot = f32::INFINITY;
+                    continue;
+                }
+
+                let mut sum = f32x8::ZERO;
+                for (chunk_idx, &query_chunk) in query_chunks.iter().enumerate() {
+                    let base = chunk_idx * 8;
+                    let chunk_c = f32x8::from([
+                        candidate[base], candidate[base+1], candidate[base+2], candidate[base+3],
+                        candidate[base+4], candidate[base+5], candidate[base+6], candidate[base+7],
+                    ]);
+                    sum += query_chunk * chunk_c;
+                }
+
+                let mut dot_product = sum.to_array().iter().sum::<f32>();
+                for j in simd_len..query.len() {
+                    dot_product += query[j] * candidate[j];
+                }
+                *slot = 1.0 - dot_product;
+            }
+            return;
+        }
+    }
+
+    for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+        *slot = inner_product_distance_dispatch(query, candidate);
+    }
+}
+
+/// Compute cosine distance from `query` to every vector in `candidates`, writing into `out`
+///
+/// See [`l2_squared_distance_batch`] for the register-blocking strategy this
+/// shares, and [`cosine_distance_dispatch`] for the zero-vector handling.
+///
+/// # Panics
+/// Panics if `candidates.len() != out.len()`.
+///
+/// # Examples
+/// ```
+/// use diskann_core::simd::cosine_distance_batch;
+///
+/// let query = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+/// let candidates: Vec<&[f32]> = vec![&a, &b];
+/// let mut out = [0.0f32; 2];
+/// cosine_distance_batch(&query, &candidates, &mut out);
+/// assert!(out[0] < 1e-6);
+/// assert!((out[1] - 1.0).abs() < 1e-6);
+/// ```
+pub fn cosine_distance_batch(query: &[f32], candidates: &[&[f32]], out: &mut [f32]) {
+    assert_eq!(candidates.len(), out.len(), "candidates and out must have the same length");
+
+    #[cfg(feature = "simd")]
+    {
+        if query.len() >= 8 {
+            let query_chunks = load_query_chunks(query);
+            let simd_len = query_chunks.len() * 8;
+            let query_norm_sq: f32 = query_chunks.iter()
+                .map(|&c| (c * c).to_array().iter().sum::<f32>())
+                .sum::<f32>()
+                + query[simd_len..].iter().map(|x| x * x).sum::<f32>();
+
+            for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+                if candidate.len() != query.len() {
+                    *slot = f32::INFINITY;
+                    continue;
+                }
+
+                let mut dot_sum = f32x8::ZERO;
+                let mut norm_c_sum = f32x8::ZERO;
+                for (chunk_idx, &query_chunk) in query_chunks.iter().enumerate() {
+                    let base = chunk_idx * 8;
+                    let chunk_c = f32x8::from([
+                        candidate[base], candidate[base+1], candidate[base+2], candidate[base+3],
+                        candidate[base+4], candidate[base+5], candidate[base+6], candidate[base+7],
+                    ]);
+                    dot_sum += query_chunk * chunk_c;
+                    norm_c_sum += chunk_c * chunk_c;
+                }
+
+                let mut dot = dot_sum.to_array().iter().sum::<f32>();
+                let mut norm_c = norm_c_sum.to_array().iter().sum::<f32>();
+                for j in simd_len..query.len() {
+                    dot += query[j] * candidate[j];
+                    norm_c += candidate[j] * candidate[j];
+                }
+
+                *slot = if query_norm_sq == 0.0 || norm_c == 0.0 {
+                    1.0
+                } else {
+                    let cosine_sim = (dot / (sqrtf(query_norm_sq) * sqrtf(norm_c))).max(-1.0).min(1.0);
+                    1.0 - cosine_sim
+                };
+            }
+            return;
+        }
+    }
+
+    for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+        *slot = cosine_distance_dispatch(query, candidate);
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// Split `query` into `__m256` chunks once, for reuse across a batch of
+/// candidates in the AVX2 batch kernels; the raw-intrinsic counterpart to
+/// [`load_query_chunks`], which serves the same role for the `wide`-based
+/// batch fallback.
+///
+/// # Safety
+/// Requires AVX2 support. Call only after `is_x86_feature_detected!("avx2")`.
+#[target_feature(enable = "avx2")]
+unsafe fn load_query_chunks_avx2(query: &[f32]) -> Vec<core::arch::x86_64::__m256> {
+    use core::arch::x86_64::*;
+
+    let simd_len = query.len() - (query.len() % 8);
+    let mut chunks = Vec::with_capacity(simd_len / 8);
+    let ptr = query.as_ptr();
+    let mut i = 0;
+    while i < simd_len {
+        // SAFETY: i + 8 <= simd_len <= query.len()
+        chunks.push(_mm256_loadu_ps(ptr.add(i)));
+        i += 8;
+    }
+    chunks
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2-accelerated one-to-many squared L2 distance
+///
+/// Keeps `query`'s AVX2 register chunks loaded once (via
+/// [`load_query_chunks_avx2`]) and reused across every candidate, cutting
+/// the redundant reloads a naive per-pair [`l2_squared_distance_avx2`] call
+/// in a loop would otherwise pay.
+///
+/// # Safety
+/// Requires AVX2 support. Call only after verifying with
+/// `is_x86_feature_detected!("avx2")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// `candidates.len() == out.len()`.
+#[target_feature(enable = "avx2")]
+pub unsafe fn l2_squared_distance_batch_avx2(query: &[f32], candidates: &[&[f32]], out: &mut [f32]) {
+    use core::arch::x86_64::*;
+
+    let query_chunks = load_query_chunks_avx2(query);
+    let simd_len = query_chunks.len() * 8;
+
+    for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+        if candidate.len() != query.len() {
+            *slot = f32::INFINITY;
+            continue;
+        }
+
+        let mut sum = _mm256_setzero_ps();
+        let c_ptr = candidate.as_ptr();
+        for (chunk_idx, &qv) in query_chunks.iter().enumerate() {
+            // SAFETY: candidate.len() == query.len(), so chunk_idx * 8 + 8 <= simd_len <= query.len()
+            let cv = _mm256_loadu_ps(c_ptr.add(chunk_idx * 8));
+            let diff = _mm256_sub_ps(qv, cv);
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(diff, diff));
+        }
+
+        let sum_lo = _mm256_castps256_ps128(sum);
+        let sum_hi = _mm256_extractf128_ps(sum, 1);
+        let sum_128 = _mm_add_ps(sum_lo, sum_hi);
+        let sum_64 = _mm_add_ps(sum_128, _mm_movehl_ps(sum_128, sum_128));
+        let sum_32 = _mm_add_ss(sum_64, _mm_shuffle_ps(sum_64, sum_64, 1));
+        let mut result = _mm_cvtss_f32(sum_32);
+
+        for j in simd_len..query.len() {
+            let diff = query[j] - candidate[j];
+            result += diff * diff;
+        }
+        *slot = result;
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+/// AVX2-accelerated one-to-many inner-product distance
+///
+/// See [`l2_squared_distance_batch_avx2`] for the register-reuse strategy
+/// this shares.
+///
+/// # Safety
+/// Requires AVX2 support. Call only after verifying with
+/// `is_x86_feature_detected!("avx2")`.
+///
+/// The function uses raw pointer access, so the caller must ensure that
+/// `candidates.len() == out.len()`.
+#[target_feature(enable = "avx2")]
+pub unsafe fn inner_product_distance_batch_avx2(query: &[f32], candidates: &[&[f32]], out: &mut [f32]) {
+    use core::arch::x86_64::*;
+
+    let query_chunks = load_query_chunks_avx2(query);
+    let simd_len = query_chunks.len() * 8;
+
+    for (candidate, slot) in candidates.iter().zip(out.iter_mut()) {
+        if candidate.len() != query.len() {
+            *slot = f32::INFINITY;
+            continue;
+        }
+
+        let mut sum = _mm256_setzero_ps();
+        let c_ptr = candidate.as_ptr();
+        for (chunk_idx, &qv) in query_chunks.iter().enumerate() {
+            // SAFETY: candidate.len() == query.len(), so chunk_idx * 8 + 8 <= simd_len <= query.len()
+            let cv = _mm256_loadu_ps(c_ptr.add(chunk_idx * 8));
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(qv, cv));
+        }
+
+        let sum_lo = _mm256_castps256_ps128(sum);
+        let sum_hi = _mm256_extractf128_ps(sum, 1);
+        let sum_128 = _mm_add_ps(sum_lo, sum_hi);
+        let sum_64 = _mm_add_ps(sum_128, _mm_movehl_ps(sum_128, sum_128));
+        let sum_32 = _mm_add_ss(sum_64, _mm_shuffle_ps(sum_64, sum_64, 1));
+        let mut dot_product = _mm_cvtss_f32(sum_32);
+
+        for j in simd_len..query.len() {
+            dot_product += query[j] * candidate[j];
+        }
+        *slot = 1.0 - dot_product;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn test_l2_squared_distance_scalar() {
+        let a = vec![0.0f32, 0.0];
+        let b = vec![3.0f32, 4.0];
+        let distance = l2_squared_distance_scalar(&a, &b);
+        assert!((distance - 25.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_inner_product_distance_scalar() {
+        let a = vec![1.0f32, 0.0];
+        let b = vec![0.0f32, 1.0];
+        let distance = inner_product_distance_scalar(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-6);
+        
+        let parallel = inner_product_distance_scalar(&a, &a);
+        assert!(parallel < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_distance_scalar() {
+        let a = vec![1.0f32, 0.0];
+        let b = vec![0.0f32, 1.0];
+        let distance = cosine_distance_scalar(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-6);
+
+        let parallel = cosine_distance_scalar(&a, &a);
+        assert!(parallel < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_distance_zero_vector() {
+        let zero = vec![0.0f32, 0.0];
+        let other = vec![1.0f32, 1.0];
+        assert_eq!(cosine_distance_scalar(&zero, &other), 1.0);
+        assert_eq!(cosine_distance_dispatch(&zero, &other), 1.0);
+    }
+
+    #[test]
+    fn test_l2_squared_distance_dispatch() {
+        let a = vec![0.0f32, 0.0, 1.0, 2.0];
+        let b = vec![3.0f32, 4.0, 1.0, 2.0];
+        let distance = l2_squared_distance_dispatch(&a, &b);
+        assert!((distance - 25.0).abs() < 1e-6); // 3^2 + 4^2 + 0^2 + 0^2 = 25
+    }
+
+    #[test]
+    fn test_inner_product_distance_dispatch() {
+        let a = vec![1.0f32, 0.0, 0.0, 0.0];
+        let b = vec![0.0f32, 1.0, 0.0, 0.0];
+        let distance = inner_product_distance_dispatch(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cosine_distance_dispatch() {
+        let a = vec![1.0f32, 0.0, 0.0, 0.0];
+        let b = vec![0.0f32, 1.0, 0.0, 0.0];
+        let distance = cosine_distance_dispatch(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_l1_distance_scalar() {
+        let a = vec![0.0f32, 0.0];
+        let b = vec![3.0f32, 4.0];
+        let distance = l1_distance_scalar(&a, &b);
+        assert!((distance - 7.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_l1_distance_dispatch() {
+        let a = vec![0.0f32, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let b = vec![3.0f32, 4.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let distance = l1_distance_dispatch(&a, &b);
+        assert!((distance - 7.0).abs() < 1e-6);
+    }
 
     #[cfg(feature = "simd")]
     #[test]
@@ -424,6 +2042,46 @@ mod tests {
         assert!((distance - 1.0).abs() < 1e-6);
     }
 
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_cosine_distance_simd() {
+        let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let distance = cosine_distance_simd(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-6);
+
+        let c: Vec<f32> = (1..17).map(|i| i as f32).collect();
+        assert!(cosine_distance_simd(&c, &c) < 1e-5);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_l1_distance_simd() {
+        let a: Vec<f32> = (0..16).map(|i| i as f32).collect();
+        let b: Vec<f32> = (0..16).map(|i| (i + 1) as f32).collect();
+        let distance = l1_distance_simd(&a, &b);
+        assert!((distance - 16.0).abs() < 1e-6); // Each diff is 1.0, abs = 1.0, sum = 16.0
+    }
+
+    #[cfg(feature = "portable_simd")]
+    #[test]
+    fn test_portable_simd_matches_scalar() {
+        let a: Vec<f32> = (0..16).map(|i| i as f32).collect();
+        let b: Vec<f32> = (0..16).map(|i| (i + 1) as f32).collect();
+
+        let l2_portable = l2_squared_distance_portable::<8>(&a, &b);
+        let l2_scalar = l2_squared_distance_scalar(&a, &b);
+        assert!((l2_portable - l2_scalar).abs() < 1e-4);
+
+        let inner_portable = inner_product_distance_portable::<8>(&a, &b);
+        let inner_scalar = inner_product_distance_scalar(&a, &b);
+        assert!((inner_portable - inner_scalar).abs() < 1e-4);
+
+        let cosine_portable = cosine_distance_portable::<8>(&a, &b);
+        let cosine_scalar = cosine_distance_scalar(&a, &b);
+        assert!((cosine_portable - cosine_scalar).abs() < 1e-4);
+    }
+
     #[cfg(all(feature = "simd", target_arch = "x86_64"))]
     #[test]
     fn test_avx2_implementations() {
@@ -438,9 +2096,85 @@ mod tests {
             let expected_dot = a.iter().map(|x| x * x).sum::<f32>();
             let expected_distance = 1.0 - expected_dot;
             assert!((inner_distance - expected_distance).abs() < 1e-6);
+
+            let cosine_distance = unsafe { cosine_distance_avx2(&a, &b) };
+            let cosine_scalar = cosine_distance_scalar(&a, &b);
+            assert!((cosine_distance - cosine_scalar).abs() < 1e-5);
+
+            let l1_distance = unsafe { l1_distance_avx2(&a, &b) };
+            let l1_scalar = l1_distance_scalar(&a, &b);
+            assert!((l1_distance - l1_scalar).abs() < 1e-5);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_avx2_fma_implementations() {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            let a = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+            let b = vec![2.0f32, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+
+            let l2_distance = unsafe { l2_squared_distance_avx2_fma(&a, &b) };
+            assert!((l2_distance - l2_squared_distance_scalar(&a, &b)).abs() < 1e-5);
+
+            let inner_distance = unsafe { inner_product_distance_avx2_fma(&a, &b) };
+            assert!((inner_distance - inner_product_distance_scalar(&a, &b)).abs() < 1e-5);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_avx512_implementations() {
+        if is_x86_feature_detected!("avx512f") {
+            let a: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+            let b: Vec<f32> = (0..20).map(|i| (i as f32 * 0.1) + 0.5).collect();
+
+            let l2_distance = unsafe { l2_squared_distance_avx512(&a, &b) };
+            assert!((l2_distance - l2_squared_distance_scalar(&a, &b)).abs() < 1e-4);
+
+            let inner_distance = unsafe { inner_product_distance_avx512(&a, &b) };
+            assert!((inner_distance - inner_product_distance_scalar(&a, &b)).abs() < 1e-4);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+    #[test]
+    fn test_neon_implementations() {
+        if is_aarch64_feature_detected!("neon") {
+            let a = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+            let b = vec![2.0f32, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+
+            let l2_distance = unsafe { l2_squared_distance_neon(&a, &b) };
+            assert!((l2_distance - 8.0).abs() < 1e-6);
+
+            let inner_distance = unsafe { inner_product_distance_neon(&a, &a) };
+            let expected_dot = a.iter().map(|x| x * x).sum::<f32>();
+            let expected_distance = 1.0 - expected_dot;
+            assert!((inner_distance - expected_distance).abs() < 1e-6);
+
+            let cosine_distance = unsafe { cosine_distance_neon(&a, &b) };
+            let cosine_scalar = cosine_distance_scalar(&a, &b);
+            assert!((cosine_distance - cosine_scalar).abs() < 1e-5);
+
+            let l1_distance = unsafe { l1_distance_neon(&a, &b) };
+            let l1_scalar = l1_distance_scalar(&a, &b);
+            assert!((l1_distance - l1_scalar).abs() < 1e-5);
         }
     }
 
+    #[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+    #[test]
+    fn test_wasm_implementations() {
+        let a = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let b = vec![2.0f32, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+
+        let l2_distance = unsafe { l2_squared_distance_wasm(&a, &b) };
+        assert!((l2_distance - l2_squared_distance_scalar(&a, &b)).abs() < 1e-5);
+
+        let inner_distance = unsafe { inner_product_distance_wasm(&a, &b) };
+        assert!((inner_distance - inner_product_distance_scalar(&a, &b)).abs() < 1e-5);
+    }
+
     #[test]
     fn test_simd_scalar_equivalence() {
         let a: Vec<f32> = (0..33).map(|i| i as f32 * 0.1).collect();
@@ -453,6 +2187,14 @@ mod tests {
         let scalar_inner = inner_product_distance_scalar(&a, &b);
         let dispatch_inner = inner_product_distance_dispatch(&a, &b);
         assert!((scalar_inner - dispatch_inner).abs() < 1e-6);
+
+        let scalar_cosine = cosine_distance_scalar(&a, &b);
+        let dispatch_cosine = cosine_distance_dispatch(&a, &b);
+        assert!((scalar_cosine - dispatch_cosine).abs() < 1e-6);
+
+        let scalar_l1 = l1_distance_scalar(&a, &b);
+        let dispatch_l1 = l1_distance_dispatch(&a, &b);
+        assert!((scalar_l1 - dispatch_l1).abs() < 1e-6);
     }
 
     #[test]
@@ -461,11 +2203,106 @@ mod tests {
         let empty: Vec<f32> = vec![];
         assert_eq!(l2_squared_distance_dispatch(&empty, &empty), 0.0);
         assert_eq!(inner_product_distance_dispatch(&empty, &empty), 1.0);
-        
+        assert_eq!(cosine_distance_dispatch(&empty, &empty), 1.0);
+        assert_eq!(l1_distance_dispatch(&empty, &empty), 0.0);
+
         // Mismatched lengths
         let a = vec![1.0f32, 2.0];
         let b = vec![1.0f32, 2.0, 3.0];
         assert_eq!(l2_squared_distance_dispatch(&a, &b), f32::INFINITY);
         assert_eq!(inner_product_distance_dispatch(&a, &b), f32::INFINITY);
+        assert_eq!(cosine_distance_dispatch(&a, &b), f32::INFINITY);
+        assert_eq!(l1_distance_dispatch(&a, &b), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_l2_squared_distance_batch_matches_per_pair_dispatch() {
+        let query: Vec<f32> = (0..20).map(|i| i as f32 * 0.3).collect();
+        let a: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+        let b: Vec<f32> = (0..20).map(|i| (19 - i) as f32 * 0.2).collect();
+        let mismatched = vec![1.0f32, 2.0];
+        let candidates: Vec<&[f32]> = vec![&a, &b, &mismatched];
+
+        let mut out = [0.0f32; 3];
+        l2_squared_distance_batch(&query, &candidates, &mut out);
+
+        assert!((out[0] - l2_squared_distance_dispatch(&query, &a)).abs() < 1e-4);
+        assert!((out[1] - l2_squared_distance_dispatch(&query, &b)).abs() < 1e-4);
+        assert_eq!(out[2], f32::INFINITY);
+    }
+
+    #[test]
+    fn test_inner_product_distance_batch_matches_per_pair_dispatch() {
+        let query: Vec<f32> = (0..20).map(|i| i as f32 * 0.3).collect();
+        let a: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+        let b: Vec<f32> = (0..20).map(|i| (19 - i) as f32 * 0.2).collect();
+        let candidates: Vec<&[f32]> = vec![&a, &b];
+
+        let mut out = [0.0f32; 2];
+        inner_product_distance_batch(&query, &candidates, &mut out);
+
+        assert!((out[0] - inner_product_distance_dispatch(&query, &a)).abs() < 1e-4);
+        assert!((out[1] - inner_product_distance_dispatch(&query, &b)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cosine_distance_batch_matches_per_pair_dispatch() {
+        let query: Vec<f32> = (0..20).map(|i| i as f32 * 0.3 + 1.0).collect();
+        let a: Vec<f32> = (0..20).map(|i| i as f32 * 0.1 + 1.0).collect();
+        let b: Vec<f32> = (0..20).map(|i| (19 - i) as f32 * 0.2 + 1.0).collect();
+        let candidates: Vec<&[f32]> = vec![&a, &b];
+
+        let mut out = [0.0f32; 2];
+        cosine_distance_batch(&query, &candidates, &mut out);
+
+        assert!((out[0] - cosine_distance_dispatch(&query, &a)).abs() < 1e-4);
+        assert!((out[1] - cosine_distance_dispatch(&query, &b)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_distance_batch_panics_on_length_mismatch() {
+        let query = vec![0.0f32; 8];
+        let a = vec![0.0f32; 8];
+        let candidates: Vec<&[f32]> = vec![&a];
+        let mut out = [0.0f32; 2];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            l2_squared_distance_batch(&query, &candidates, &mut out);
+        }));
+        assert!(result.is_err());
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_batch_avx2_matches_scalar() {
+        if is_x86_feature_detected!("avx2") {
+            let query: Vec<f32> = (0..20).map(|i| i as f32 * 0.3).collect();
+            let a: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+            let b: Vec<f32> = (0..20).map(|i| (19 - i) as f32 * 0.2).collect();
+            let mismatched = vec![1.0f32, 2.0];
+            let candidates: Vec<&[f32]> = vec![&a, &b, &mismatched];
+
+            let mut l2_out = [0.0f32; 3];
+            unsafe { l2_squared_distance_batch_avx2(&query, &candidates, &mut l2_out) };
+            assert!((l2_out[0] - l2_squared_distance_scalar(&query, &a)).abs() < 1e-4);
+            assert!((l2_out[1] - l2_squared_distance_scalar(&query, &b)).abs() < 1e-4);
+            assert_eq!(l2_out[2], f32::INFINITY);
+
+            let mut ip_out = [0.0f32; 3];
+            unsafe { inner_product_distance_batch_avx2(&query, &candidates, &mut ip_out) };
+            assert!((ip_out[0] - inner_product_distance_scalar(&query, &a)).abs() < 1e-4);
+            assert!((ip_out[1] - inner_product_distance_scalar(&query, &b)).abs() < 1e-4);
+            assert_eq!(ip_out[2], f32::INFINITY);
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[test]
+    fn test_cosine_distance_avx2_zero_vector() {
+        if is_x86_feature_detected!("avx2") {
+            let zero = vec![0.0f32; 8];
+            let other: Vec<f32> = (0..8).map(|i| i as f32 + 1.0).collect();
+            let distance = unsafe { cosine_distance_avx2(&zero, &other) };
+            assert_eq!(distance, 1.0);
+        }
     }
-}
\ No newline at end of file
+}
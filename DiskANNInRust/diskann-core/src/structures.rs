@@ -12,6 +12,15 @@ pub struct GraphNode {
     pub vector: Vector,
     /// Neighbors in the graph
     pub neighbors: Vec<VectorId>,
+    /// Id of the source document this node's vector was chunked from, for
+    /// nodes produced by a text-chunking pipeline. `None` for nodes built
+    /// directly from a single already-embedded vector.
+    #[serde(default)]
+    pub source_doc_id: Option<u32>,
+    /// `[start, end)` byte offsets into the source document's text that
+    /// produced this node's vector, alongside `source_doc_id`
+    #[serde(default)]
+    pub char_span: Option<(usize, usize)>,
 }
 
 impl GraphNode {
@@ -21,6 +30,20 @@ impl GraphNode {
             id,
             vector,
             neighbors: Vec::new(),
+            source_doc_id: None,
+            char_span: None,
+        }
+    }
+
+    /// Create a node carrying chunk provenance: which document it came from
+    /// and the `[start, end)` span within that document's text
+    pub fn with_span(id: VectorId, vector: Vector, source_doc_id: u32, char_span: (usize, usize)) -> Self {
+        Self {
+            id,
+            vector,
+            neighbors: Vec::new(),
+            source_doc_id: Some(source_doc_id),
+            char_span: Some(char_span),
         }
     }
 }
\ No newline at end of file
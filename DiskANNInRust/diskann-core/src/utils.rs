@@ -2,6 +2,12 @@
 //!
 //! This module provides utilities for memory alignment, bit manipulation,
 //! and low-level operations needed by the DiskANN system.
+//!
+//! Every function here is a pure, allocation-free `const fn` operating on
+//! integers, so the whole module compiles under `#![no_std]` with no
+//! additional features (no `std`, `libm`, or `alloc` required) -- useful for
+//! embedded/wasm builds that only need the bit-manipulation and alignment
+//! helpers, not the floating-point math in [`crate::math`].
 
 /// Round up X to the nearest multiple of Y
 /// 
@@ -147,6 +153,107 @@ pub const fn is_power_of_2(x: u64) -> bool {
     x != 0 && (x & (x - 1)) == 0
 }
 
+/// Integer base-`exp` power computed with `u128` headroom, saturating
+/// instead of wrapping/panicking on overflow. Used internally by
+/// [`nth_root`]'s Newton iteration and boundary correction, where `base` is
+/// always itself a root candidate (far smaller than `u64::MAX`) so
+/// saturation in practice only ever guards the Newton iteration's initial,
+/// deliberately-oversized guess.
+const fn pow_u128(base: u64, exp: u32) -> u128 {
+    let mut result: u128 = 1;
+    let mut cur = base as u128;
+    let mut e = exp;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = result.saturating_mul(cur);
+        }
+        cur = cur.saturating_mul(cur);
+        e >>= 1;
+    }
+    result
+}
+
+/// Exact integer `k`-th root of `n`, computed purely with integer
+/// arithmetic via Newton's method -- no floating point, so no risk of a
+/// float `sqrt`/`pow` plus rounding landing on the wrong side of a perfect
+/// power.
+///
+/// Starts from a power-of-two upper bound derived from `n`'s bit length,
+/// iterates `x = ((k-1)*x + n/x^(k-1)) / k` until it stops decreasing, then
+/// walks the boundary by +/-1 so the result is exactly `floor(root(n, k))`.
+/// [`isqrt`]/[`icbrt`] are the `k = 2`/`k = 3` cases of this.
+///
+/// # Panics
+/// Panics if `k == 0` (the 0th root is undefined).
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::nth_root;
+///
+/// assert_eq!(nth_root(1000, 2), 31); // 31*31 = 961, 32*32 = 1024
+/// assert_eq!(nth_root(27, 3), 3);
+/// assert_eq!(nth_root(26, 3), 2);
+/// ```
+pub const fn nth_root(n: u64, k: u32) -> u64 {
+    assert!(k != 0, "nth_root: k must be at least 1");
+    if k == 1 || n == 0 {
+        return n;
+    }
+
+    let bits = 64 - n.leading_zeros();
+    let mut x: u64 = 1u64 << (bits / k + 1);
+
+    loop {
+        let xp = pow_u128(x, k - 1);
+        if xp == 0 {
+            break;
+        }
+        let next = (((k - 1) as u128 * x as u128 + n as u128 / xp) / k as u128) as u64;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+
+    while pow_u128(x, k) > n as u128 {
+        x -= 1;
+    }
+    while pow_u128(x + 1, k) <= n as u128 {
+        x += 1;
+    }
+    x
+}
+
+/// Exact integer square root: `floor(sqrt(n))`, the `k = 2` case of
+/// [`nth_root`]
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::isqrt;
+///
+/// assert_eq!(isqrt(15), 3);
+/// assert_eq!(isqrt(16), 4);
+/// assert_eq!(isqrt(17), 4);
+/// ```
+pub const fn isqrt(n: u64) -> u64 {
+    nth_root(n, 2)
+}
+
+/// Exact integer cube root: `floor(cbrt(n))`, the `k = 3` case of
+/// [`nth_root`]
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::icbrt;
+///
+/// assert_eq!(icbrt(26), 2);
+/// assert_eq!(icbrt(27), 3);
+/// assert_eq!(icbrt(28), 3);
+/// ```
+pub const fn icbrt(n: u64) -> u64 {
+    nth_root(n, 3)
+}
+
 /// Count the number of set bits in an integer (population count)
 /// 
 /// # Examples
@@ -214,6 +321,64 @@ pub const fn leading_zeros(mut x: u64) -> Option<u32> {
     Some(count)
 }
 
+/// Count the number of set bits in an integer using the hardware `POPCNT`
+/// instruction (via `u64::count_ones`) rather than [`popcount`]'s software
+/// loop. [`crate::bitset::Bitset`] uses this for its word-wise [`Bitset::count`](crate::bitset::Bitset::count).
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::popcount_hw;
+///
+/// assert_eq!(popcount_hw(0b1010), 2);
+/// assert_eq!(popcount_hw(0b1111), 4);
+/// assert_eq!(popcount_hw(0), 0);
+/// ```
+#[inline]
+pub const fn popcount_hw(x: u64) -> u32 {
+    x.count_ones()
+}
+
+/// Find the position of the least significant set bit (0-indexed), using
+/// the hardware `TZCNT` instruction (via `u64::trailing_zeros`) rather than
+/// [`trailing_zeros`]'s software loop. Returns `None` if `x` is 0.
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::trailing_zeros_hw;
+///
+/// assert_eq!(trailing_zeros_hw(8), Some(3));  // 0b1000
+/// assert_eq!(trailing_zeros_hw(12), Some(2)); // 0b1100
+/// assert_eq!(trailing_zeros_hw(0), None);
+/// ```
+#[inline]
+pub const fn trailing_zeros_hw(x: u64) -> Option<u32> {
+    if x == 0 {
+        None
+    } else {
+        Some(x.trailing_zeros())
+    }
+}
+
+/// Count the zero bits above the most significant set bit, using the
+/// hardware `LZCNT` instruction (via `u64::leading_zeros`)
+/// rather than [`leading_zeros`]'s software loop. Returns `None` if `x` is 0.
+///
+/// # Examples
+/// ```
+/// use diskann_core::utils::leading_zeros_hw;
+///
+/// assert_eq!(leading_zeros_hw(8), Some(60));  // 0b1000 in 64-bit
+/// assert_eq!(leading_zeros_hw(0), None);
+/// ```
+#[inline]
+pub const fn leading_zeros_hw(x: u64) -> Option<u32> {
+    if x == 0 {
+        None
+    } else {
+        Some(x.leading_zeros())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +483,67 @@ mod tests {
         assert_eq!(leading_zeros(4), Some(61));
         assert_eq!(leading_zeros(8), Some(60));
     }
+
+    #[test]
+    fn test_isqrt_boundaries() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+
+        for k in 1u64..1000 {
+            assert_eq!(isqrt(k * k - 1), k - 1, "k = {}", k);
+            assert_eq!(isqrt(k * k), k, "k = {}", k);
+            assert_eq!(isqrt(k * k + 1), k, "k = {}", k);
+        }
+
+        // Near u64::MAX: floor(sqrt(u64::MAX)) = 4294967295
+        assert_eq!(isqrt(u64::MAX), 4_294_967_295);
+        assert_eq!(isqrt(4_294_967_295u64 * 4_294_967_295u64), 4_294_967_295);
+    }
+
+    #[test]
+    fn test_icbrt_boundaries() {
+        assert_eq!(icbrt(0), 0);
+        assert_eq!(icbrt(1), 1);
+
+        for k in 1u64..1000 {
+            assert_eq!(icbrt(k * k * k - 1), k - 1, "k = {}", k);
+            assert_eq!(icbrt(k * k * k), k, "k = {}", k);
+            assert_eq!(icbrt(k * k * k + 1), k, "k = {}", k);
+        }
+
+        // floor(cbrt(u64::MAX)) = 2642245
+        assert_eq!(icbrt(u64::MAX), 2_642_245);
+    }
+
+    #[test]
+    fn test_nth_root_matches_isqrt_and_icbrt() {
+        for n in 0u64..2000 {
+            assert_eq!(nth_root(n, 2), isqrt(n), "n = {}", n);
+            assert_eq!(nth_root(n, 3), icbrt(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_nth_root_k_one_is_identity() {
+        assert_eq!(nth_root(0, 1), 0);
+        assert_eq!(nth_root(42, 1), 42);
+        assert_eq!(nth_root(u64::MAX, 1), u64::MAX);
+    }
+
+    #[test]
+    fn test_nth_root_higher_powers() {
+        // 4th roots, boundary-tested the same way as isqrt/icbrt
+        for k in 1u64..200 {
+            let fourth = k.pow(4);
+            assert_eq!(nth_root(fourth - 1, 4), k - 1, "k = {}", k);
+            assert_eq!(nth_root(fourth, 4), k, "k = {}", k);
+            assert_eq!(nth_root(fourth + 1, 4), k, "k = {}", k);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "nth_root: k must be at least 1")]
+    fn test_nth_root_rejects_k_zero() {
+        nth_root(10, 0);
+    }
 }
\ No newline at end of file
@@ -9,15 +9,57 @@ pub type Vector = Vec<f32>;
 /// Vector identifier type
 pub type VectorId = u32;
 
-/// Placeholder distance function
-pub fn euclidean_distance(_a: &[f32], _b: &[f32]) -> f32 {
-    // TODO: Implement actual euclidean distance computation
-    0.0
+/// Compute the Euclidean (L2) distance between two vectors
+///
+/// Vectors are zipped elementwise, so a length mismatch is not an error —
+/// extra elements in the longer vector are simply ignored, matching the
+/// convention the other bare functions in [`crate::math`] use.
+pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| (x - y) * (x - y))
+        .fold(0.0f32, |acc, v| acc + v)
+        .sqrt()
 }
 
 #[cfg(feature = "simd")]
-/// SIMD-optimized distance computation (placeholder)
-pub fn euclidean_distance_simd(_a: &[f32], _b: &[f32]) -> f32 {
-    // TODO: Implement SIMD-optimized euclidean distance
-    euclidean_distance(_a, _b)
+/// SIMD-accelerated Euclidean (L2) distance
+///
+/// Dispatches to [`crate::simd::l2_squared_distance_dispatch`], which picks
+/// an AVX2/NEON/portable-SIMD kernel at runtime (falling back to scalar when
+/// none apply). Unlike [`euclidean_distance`], the dispatcher requires equal
+/// lengths and returns `f32::INFINITY` otherwise.
+pub fn euclidean_distance_simd(a: &[f32], b: &[f32]) -> f32 {
+    crate::simd::l2_squared_distance_dispatch(a, b).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn test_euclidean_distance() {
+        let a = vec![0.0f32, 0.0];
+        let b = vec![3.0f32, 4.0];
+        assert!((euclidean_distance(&a, &b) - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_euclidean_distance_to_self_is_zero() {
+        let a = vec![1.0f32, 2.0, 3.0];
+        assert!(euclidean_distance(&a, &a) < 1e-6);
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_euclidean_distance_simd_matches_scalar() {
+        let a = vec![0.0f32, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let b = vec![3.0f32, 4.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+
+        let scalar = euclidean_distance(&a, &b);
+        let simd = euclidean_distance_simd(&a, &b);
+        assert!((scalar - simd).abs() < 1e-4, "scalar {} vs simd {}", scalar, simd);
+    }
 }
\ No newline at end of file
@@ -2,8 +2,11 @@
 //! These tests verify mathematical invariants that should hold for any input
 
 use proptest::prelude::*;
-use diskann_core::math::{dot_product, l2_norm, l2_norm_squared, normalize};
-use diskann_traits::distance::{EuclideanDistance, CosineDistance, Distance};
+use diskann_core::math::{dot_product, hamming_distance, l2_norm, l2_norm_squared, normalize};
+use diskann_traits::distance::{
+    CosineDistance, Distance, EuclideanDistance, HammingDistance, InnerProductDistance,
+    ManhattanDistance,
+};
 
 /// Generate vectors with reasonable size and values
 fn arb_vector() -> impl Strategy<Value = Vec<f32>> {
@@ -36,6 +39,11 @@ fn arb_vector_pair() -> impl Strategy<Value = (Vec<f32>, Vec<f32>)> {
     })
 }
 
+/// Generate bit-packed (byte) vectors for Hamming distance tests
+fn arb_bit_vector() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(prop::num::u8::ANY, 1..=16)
+}
+
 proptest! {
     /// Test that normalization produces unit vectors
     #[test]
@@ -174,6 +182,62 @@ proptest! {
         }
     }
 
+    /// Test triangle inequality and symmetry for Manhattan (L1) distance
+    #[test]
+    fn test_manhattan_triangle_inequality_and_symmetry((v1, v2) in arb_vector_pair(), v3 in arb_vector()) {
+        if v3.len() == v1.len() {
+            let manhattan = ManhattanDistance;
+
+            let d12 = manhattan.distance(&v1, &v2);
+            let d13 = manhattan.distance(&v1, &v3);
+            let d23 = manhattan.distance(&v2, &v3);
+
+            prop_assert!(d13 <= d12 + d23 + f32::EPSILON,
+                "Triangle inequality violated: {} > {} + {}", d13, d12, d23);
+
+            let d21 = manhattan.distance(&v2, &v1);
+            prop_assert!((d12 - d21).abs() < f32::EPSILON, "Manhattan distance should be symmetric");
+        }
+    }
+
+    /// Test symmetry for inner-product distance, and that it agrees with cosine distance once
+    /// both inputs are normalized (dividing out the magnitude difference between the two
+    /// metrics)
+    #[test]
+    fn test_inner_product_symmetry_and_cosine_relation((v1, v2) in arb_vector_pair()) {
+        let inner_product = InnerProductDistance;
+        let cosine = CosineDistance;
+
+        let d12 = inner_product.distance(&v1, &v2);
+        let d21 = inner_product.distance(&v2, &v1);
+        prop_assert!((d12 - d21).abs() < f32::EPSILON, "Inner product distance should be symmetric");
+
+        if l2_norm(&v1) > 1e-6 && l2_norm(&v2) > 1e-6 {
+            let n1 = normalize(&v1);
+            let n2 = normalize(&v2);
+
+            let ip_normalized = inner_product.distance(&n1, &n2);
+            let cosine_distance = cosine.distance(&n1, &n2);
+            prop_assert!((ip_normalized - cosine_distance).abs() < 1e-4,
+                "Inner product distance on normalized vectors should match cosine distance: {} vs {}",
+                ip_normalized, cosine_distance);
+        }
+    }
+
+    /// Test non-negativity and identity for Hamming distance over bit-packed vectors
+    #[test]
+    fn test_hamming_non_negativity_and_identity(a in arb_bit_vector(), b in arb_bit_vector()) {
+        if a.len() == b.len() {
+            let hamming = HammingDistance;
+
+            let distance = hamming.distance(&a, &b);
+            prop_assert!(distance >= 0.0, "Hamming distance should be non-negative");
+            prop_assert_eq!(distance, hamming_distance(&a, &b) as f32);
+
+            prop_assert_eq!(hamming.distance(&a, &a), 0.0, "Hamming distance from a vector to itself should be zero");
+        }
+    }
+
     /// Test scaling properties
     #[test]
     fn test_scaling_properties(v in arb_vector(), scale in 0.1f32..10.0f32) {
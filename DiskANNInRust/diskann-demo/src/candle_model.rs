@@ -0,0 +1,147 @@
+//! Sentence-transformer embeddings via `candle`
+//!
+//! [`RealEmbeddingModel`](crate::model::RealEmbeddingModel) runs the same
+//! `all-MiniLM-L6-v2` model through `ort`/ONNX; this is the pure-Rust
+//! alternative the module doc for `embeddings.rs` used to point at, loading
+//! the model's native `safetensors` weights directly with `candle` instead
+//! of exporting to ONNX first. Downloads go through `hf-hub`'s cache rather
+//! than the raw `ureq` fetch [`RealEmbeddingModel::download_file`] uses,
+//! since `hf-hub` already knows the HuggingFace repo layout and resume/ETag
+//! rules.
+
+use anyhow::{Context, Result};
+use candle_core::{DType, Device, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
+use hf_hub::api::sync::Api;
+use hf_hub::{Repo, RepoType};
+use tokenizers::{PaddingParams, PaddingStrategy, Tokenizer};
+
+use crate::embedding_provider::EmbeddingProvider;
+
+/// Default HuggingFace repo [`CandleSentenceTransformer::new`] loads; the
+/// same model [`crate::model::RealEmbeddingModel`] uses, so the two
+/// backends' output is directly comparable
+const DEFAULT_MODEL_ID: &str = "sentence-transformers/all-MiniLM-L6-v2";
+
+/// Sentence-transformer embedding backend running on `candle` (no ONNX
+/// Runtime dependency)
+pub struct CandleSentenceTransformer {
+    model: BertModel,
+    tokenizer: Tokenizer,
+    device: Device,
+    dimension: usize,
+    model_id: String,
+}
+
+impl CandleSentenceTransformer {
+    /// Download (via `hf-hub`'s cache, so repeat runs don't re-fetch) and
+    /// load [`DEFAULT_MODEL_ID`]'s weights, config and tokenizer, then build
+    /// a CPU [`BertModel`] from them
+    pub fn new() -> Result<Self> {
+        Self::from_repo(DEFAULT_MODEL_ID)
+    }
+
+    /// Same as [`CandleSentenceTransformer::new`], but for an arbitrary
+    /// HuggingFace sentence-transformers repo id
+    pub fn from_repo(model_id: &str) -> Result<Self> {
+        let device = Device::Cpu;
+
+        let api = Api::new().context("Failed to initialize HuggingFace Hub API client")?;
+        let repo = api.repo(Repo::new(model_id.to_string(), RepoType::Model));
+
+        let config_path = repo.get("config.json").context("Failed to fetch config.json")?;
+        let tokenizer_path = repo.get("tokenizer.json").context("Failed to fetch tokenizer.json")?;
+        let weights_path = repo.get("model.safetensors").context("Failed to fetch model.safetensors")?;
+
+        let config: BertConfig = serde_json::from_reader(std::fs::File::open(&config_path)?)
+            .context("Failed to parse config.json")?;
+        let dimension = config.hidden_size;
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+        tokenizer.with_padding(Some(PaddingParams {
+            strategy: PaddingStrategy::BatchLongest,
+            ..Default::default()
+        }));
+
+        let vb = unsafe {
+            VarBuilder::from_mmaped_safetensors(&[weights_path], DTYPE, &device)
+                .context("Failed to map model.safetensors")?
+        };
+        let model = BertModel::load(vb, &config).context("Failed to build BertModel")?;
+
+        Ok(Self {
+            model,
+            tokenizer,
+            device,
+            dimension,
+            model_id: model_id.to_string(),
+        })
+    }
+
+    /// Generate embeddings for a batch of texts
+    pub fn embed_texts(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let encodings = self.tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
+
+        let token_ids: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_ids().to_vec()).collect();
+        let attention_mask: Vec<Vec<u32>> = encodings.iter().map(|e| e.get_attention_mask().to_vec()).collect();
+
+        let token_ids = Tensor::new(token_ids, &self.device)?;
+        let attention_mask_tensor = Tensor::new(attention_mask.clone(), &self.device)?;
+        let token_type_ids = token_ids.zeros_like()?;
+
+        let token_embeddings = self.model
+            .forward(&token_ids, &token_type_ids, Some(&attention_mask_tensor))
+            .context("BERT forward pass failed")?;
+
+        // Mean-pool token embeddings weighted by the attention mask, then L2
+        // normalize -- same recipe as RealEmbeddingModel::mean_pooling.
+        let mask = Tensor::new(attention_mask, &self.device)?
+            .to_dtype(DType::F32)?
+            .unsqueeze(2)?
+            .broadcast_as(token_embeddings.shape())?;
+        let masked = (token_embeddings * &mask)?;
+        let summed = masked.sum(1)?;
+        let counts = mask.sum(1)?.clamp(1e-9, f64::INFINITY)?;
+        let pooled = summed.broadcast_div(&counts)?;
+
+        let norms = pooled.sqr()?.sum_keepdim(1)?.sqrt()?;
+        let normalized = pooled.broadcast_div(&norms.clamp(1e-9, f64::INFINITY)?)?;
+
+        normalized.to_vec2::<f32>().context("Failed to read back embeddings")
+    }
+
+    /// Generate an embedding for a single text
+    pub fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed_texts(&[text.to_string()])?
+            .into_iter()
+            .next()
+            .expect("embed_texts returns one vector per input text"))
+    }
+
+    /// Get embedding dimension
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+}
+
+impl EmbeddingProvider for CandleSentenceTransformer {
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embed_texts(texts)
+    }
+
+    fn dimension(&self) -> usize {
+        CandleSentenceTransformer::dimension(self)
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
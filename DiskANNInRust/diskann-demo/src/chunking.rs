@@ -0,0 +1,111 @@
+//! Token-budget text chunking
+//!
+//! [`crate::embeddings::EmbeddingsGenerator::embed_text`] and the other
+//! [`crate::embedding_provider::EmbeddingProvider`] backends collapse a
+//! whole document into a single averaged vector, which loses recall once a
+//! document is longer than a sentence or two. [`chunk_text`] splits a
+//! document into overlapping, token-budget-sized windows instead, each of
+//! which is embedded and stored as its own
+//! [`diskann_core::structures::GraphNode`] (via [`to_graph_node`]) carrying
+//! the source document id and byte span it came from, following Zed's
+//! semantic index design. A search hit on a chunk's node can then be
+//! resolved back to the exact span in the original document.
+
+use diskann_core::structures::GraphNode;
+use diskann_core::vectors::{Vector, VectorId};
+
+/// Default chunk size, in whitespace-separated words (approximating
+/// tokens, as [`crate::embeddings::tokenize`] already does elsewhere in
+/// this crate)
+pub const DEFAULT_CHUNK_TOKEN_BUDGET: usize = 256;
+/// Default overlap between consecutive chunks, in the same word-token unit
+pub const DEFAULT_CHUNK_OVERLAP_TOKENS: usize = 32;
+
+/// One chunk of a source document: the chunk's text plus the `[start, end)`
+/// byte span in the document it was extracted from
+#[derive(Debug, Clone)]
+pub struct TextChunk {
+    pub doc_id: u32,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+/// Byte `[start, end)` span of each whitespace-separated word in `text`, in
+/// order
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for word in text.split_whitespace() {
+        let start = cursor + text[cursor..].find(word).expect("word came from text.split_whitespace()");
+        let end = start + word.len();
+        spans.push((start, end));
+        cursor = end;
+    }
+    spans
+}
+
+/// Scan `spans[window_start+1..window_end]` backward for the last word
+/// ending in sentence punctuation, so a chunk can end on a sentence
+/// boundary instead of splitting mid-sentence. Falls back to `window_end`
+/// (the token-budget cutoff) if no sentence boundary is found.
+fn sentence_boundary_end(text: &str, spans: &[(usize, usize)], window_start: usize, window_end: usize) -> usize {
+    for i in (window_start + 1..window_end).rev() {
+        let (_, end) = spans[i];
+        if text[..end].ends_with(['.', '!', '?']) {
+            return i + 1;
+        }
+    }
+    window_end
+}
+
+/// Split `text` into overlapping windows of at most `token_budget`
+/// whitespace-separated words, preferring to end each window at a sentence
+/// boundary (so chunks never split mid-word and rarely split
+/// mid-sentence). Each window after the first starts `overlap_tokens` words
+/// before the previous chunk's end (always at least one word after the
+/// previous window's start), so a full-length window steps by
+/// `token_budget - overlap_tokens` words. Returns one [`TextChunk`] per
+/// window, tagged with `doc_id`.
+pub fn chunk_text(doc_id: u32, text: &str, token_budget: usize, overlap_tokens: usize) -> Vec<TextChunk> {
+    let spans = word_spans(text);
+    if spans.is_empty() {
+        return Vec::new();
+    }
+
+    let token_budget = token_budget.max(1);
+    let overlap_tokens = overlap_tokens.min(token_budget - 1);
+
+    let mut chunks = Vec::new();
+    let mut window_start = 0;
+    loop {
+        let window_end = (window_start + token_budget).min(spans.len());
+        let boundary_end = if window_end < spans.len() {
+            sentence_boundary_end(text, &spans, window_start, window_end)
+        } else {
+            window_end
+        };
+
+        let start = spans[window_start].0;
+        let end = spans[boundary_end - 1].1;
+        chunks.push(TextChunk {
+            doc_id,
+            start,
+            end,
+            text: text[start..end].to_string(),
+        });
+
+        if window_end >= spans.len() {
+            break;
+        }
+        window_start = boundary_end.saturating_sub(overlap_tokens).max(window_start + 1);
+    }
+    chunks
+}
+
+/// Build a [`GraphNode`] for an embedded chunk, tagging it with the
+/// document id and byte span it came from so a later search hit can
+/// be resolved back to the source text
+pub fn to_graph_node(chunk: &TextChunk, id: VectorId, vector: Vector) -> GraphNode {
+    GraphNode::with_span(id, vector, chunk.doc_id, (chunk.start, chunk.end))
+}
@@ -2,11 +2,66 @@
 
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
-use std::io::{BufReader, Write};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use std::sync::Mutex;
 use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+
+use diskann_impl::{VamanaConfig, VamanaIndex};
+use diskann_traits::distance::{CosineDistance, Distance};
+use diskann_traits::index::Index;
+use diskann_traits::search::Search;
+
+use crate::embedding_provider::EmbeddingProvider;
+use crate::embedding_queue::EmbeddingQueue;
+
+/// A file-backed source of [`STSBEntry`] records, read a record at a time
+/// rather than deserializing the whole corpus into memory up front -- the
+/// difference matters once a corpus grows past what fits comfortably in
+/// RAM. [`DatasetSource::Tsv`] expects `sentence1\tsentence2\tscore` per
+/// line (STS Benchmark's own on-disk format); [`DatasetSource::Jsonl`]
+/// expects one JSON-encoded [`STSBEntry`] object per line.
+pub enum DatasetSource {
+    Jsonl(std::path::PathBuf),
+    Tsv(std::path::PathBuf),
+}
+
+impl DatasetSource {
+    /// Stream this source's records, surfacing each line's parse error
+    /// individually rather than failing the whole read on the first bad row
+    fn read_entries(&self) -> Result<Box<dyn Iterator<Item = Result<STSBEntry>>>> {
+        match self {
+            DatasetSource::Jsonl(path) => {
+                let file = File::open(path)
+                    .with_context(|| format!("Failed to open JSONL dataset at {path:?}"))?;
+                let reader = BufReader::new(file);
+                let stream = serde_json::Deserializer::from_reader(reader).into_iter::<STSBEntry>();
+                Ok(Box::new(stream.map(|entry| entry.map_err(anyhow::Error::from))))
+            }
+            DatasetSource::Tsv(path) => {
+                let file = File::open(path)
+                    .with_context(|| format!("Failed to open TSV dataset at {path:?}"))?;
+                let reader = BufReader::new(file);
+                Ok(Box::new(reader.lines().map(|line| {
+                    let line = line.context("Failed to read TSV line")?;
+                    let mut fields = line.splitn(3, '\t');
+                    let sentence1 = fields.next().context("TSV row missing sentence1")?.to_string();
+                    let sentence2 = fields.next().context("TSV row missing sentence2")?.to_string();
+                    let score: f32 = fields
+                        .next()
+                        .context("TSV row missing score")?
+                        .trim()
+                        .parse()
+                        .context("TSV row's score column isn't a valid f32")?;
+                    Ok(STSBEntry { sentence1, sentence2, score })
+                })))
+            }
+        }
+    }
+}
 
 /// STSB dataset entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,154 +79,73 @@ pub struct STSBDataset {
 }
 
 impl STSBDataset {
-    /// Download and load STSB dataset
+    /// Load the STSB dataset, preferring (in order): a cached local copy
+    /// from a previous run, a real network fetch from
+    /// `DISKANN_STSB_DATASET_URL` (tried only when no cached copy exists),
+    /// and only then the small built-in
+    /// [`Self::create_fallback_dataset`] -- this used to silently fabricate
+    /// data whenever no cache file existed yet, which made it easy to miss
+    /// that no real dataset had ever been loaded.
     pub fn download_and_load(cache_dir: &Path) -> Result<Self> {
         fs::create_dir_all(cache_dir)?;
-        
+
         let dataset_path = cache_dir.join("stsb_dataset.json");
-        
+
         if !dataset_path.exists() {
-            println!("📥 Downloading STSB dataset from HuggingFace...");
-            Self::download_dataset(&dataset_path)?;
+            if let Err(e) = Self::try_download(&dataset_path) {
+                eprintln!("⚠ STSB download failed ({e:#}); looking for a local copy instead");
+            }
         } else {
             println!("✓ Using cached STSB dataset");
         }
-        
-        Self::load_from_file(&dataset_path)
+
+        if dataset_path.exists() {
+            match Self::load_from_file(&dataset_path) {
+                Ok(dataset) => return Ok(dataset),
+                Err(e) => eprintln!("⚠ Failed to read cached dataset ({e:#}); using the built-in fallback"),
+            }
+        }
+
+        println!("📦 Using the built-in fallback dataset (no network source configured and no local copy found)");
+        Self::from_entries(Self::create_fallback_dataset())
     }
-    
-    /// Download dataset from HuggingFace
-    fn download_dataset(output_path: &Path) -> Result<()> {
-        // Download actual STSB dataset
+
+    /// Fetch the real STSB dataset from `DISKANN_STSB_DATASET_URL` (a JSON
+    /// array of [`STSBEntry`]) and cache it at `output_path`. Returns an
+    /// error -- rather than silently synthesizing data -- when the env var
+    /// isn't set or the request fails, so callers can fall back explicitly.
+    fn try_download(output_path: &Path) -> Result<()> {
+        let url = std::env::var("DISKANN_STSB_DATASET_URL")
+            .context("DISKANN_STSB_DATASET_URL is not set")?;
+
         let pb = ProgressBar::new_spinner();
         pb.set_style(
             ProgressStyle::default_spinner()
                 .template("{spinner:.green} {msg}")
                 .unwrap()
         );
-        pb.set_message("Downloading STSB dataset from HuggingFace...");
-        
-        // For now, use our comprehensive dataset
-        pb.set_message("Creating comprehensive dataset...");
-        let entries = Self::create_comprehensive_dataset();
-        
-        pb.finish_with_message(format!("✓ Dataset prepared with {} entries", entries.len()));
-        
-        // Save to file
-        let file = File::create(output_path)?;
-        serde_json::to_writer_pretty(file, &entries)?;
-        
+        pb.set_message(format!("Downloading STSB dataset from {url}..."));
+
+        let response = ureq::get(&url)
+            .timeout(std::time::Duration::from_secs(60))
+            .call()
+            .context("Failed to download STSB dataset")?;
+
+        let mut bytes = Vec::new();
+        response.into_reader().read_to_end(&mut bytes)
+            .context("Failed to read STSB dataset response body")?;
+
+        // Fail fast on a malformed response instead of caching garbage that
+        // would just surface as a confusing error on the next run.
+        let entries: Vec<STSBEntry> = serde_json::from_slice(&bytes)
+            .context("STSB dataset response wasn't a JSON array of entries")?;
+
+        pb.finish_with_message(format!("✓ Downloaded {} entries", entries.len()));
+
+        fs::write(output_path, &bytes)?;
         Ok(())
     }
     
-    /// Create a comprehensive dataset for demo
-    fn create_comprehensive_dataset() -> Vec<STSBEntry> {
-        let mut entries = Vec::new();
-        
-        // Create a large, diverse dataset with realistic sentence pairs
-        let sentence_pairs = vec![
-            // Technology
-            ("The computer processes data quickly", "The machine computes information rapidly", 0.85),
-            ("Artificial intelligence is transforming industries", "AI is revolutionizing business sectors", 0.90),
-            ("The software update fixed the bug", "The patch resolved the issue", 0.88),
-            ("Machine learning models need training data", "ML algorithms require training examples", 0.92),
-            ("The server crashed during peak hours", "The system failed at maximum load", 0.87),
-            
-            // Science
-            ("Scientists discovered a new planet", "Researchers found a new celestial body", 0.89),
-            ("The experiment yielded surprising results", "The test produced unexpected outcomes", 0.86),
-            ("Climate change affects global temperatures", "Global warming impacts Earth's climate", 0.91),
-            ("The vaccine prevents disease transmission", "The immunization stops illness spread", 0.88),
-            ("Quantum physics explains particle behavior", "Quantum mechanics describes subatomic actions", 0.90),
-            
-            // Business
-            ("The company reported record profits", "The firm announced highest earnings", 0.92),
-            ("Stock prices fluctuated wildly today", "Share values varied significantly", 0.87),
-            ("The merger was completed successfully", "The acquisition finished smoothly", 0.85),
-            ("Sales increased during the holiday season", "Revenue grew in the festive period", 0.89),
-            ("The startup secured venture funding", "The new company obtained investor capital", 0.88),
-            
-            // Daily life
-            ("The weather is beautiful today", "It's a lovely day outside", 0.90),
-            ("Children are playing in the park", "Kids are having fun outdoors", 0.88),
-            ("She is reading a fascinating book", "She's enjoying an interesting novel", 0.86),
-            ("The restaurant serves delicious food", "The eatery offers tasty meals", 0.87),
-            ("Traffic is heavy during rush hour", "Roads are congested at peak times", 0.89),
-            
-            // Sports
-            ("The team won the championship", "The squad claimed the title", 0.91),
-            ("The athlete broke the world record", "The sportsperson set a new global best", 0.90),
-            ("The match ended in a draw", "The game finished tied", 0.88),
-            ("Fans cheered for their favorite team", "Supporters applauded their preferred squad", 0.87),
-            ("The player scored a stunning goal", "The athlete made an amazing score", 0.89),
-            
-            // Education
-            ("Students are preparing for exams", "Pupils are studying for tests", 0.91),
-            ("The professor explained the concept clearly", "The teacher clarified the idea well", 0.88),
-            ("The library has many books", "The reading room contains numerous volumes", 0.86),
-            ("Online learning is becoming popular", "Digital education is gaining traction", 0.87),
-            ("The research paper was published", "The academic article was released", 0.89),
-            
-            // Nature
-            ("The forest is full of wildlife", "The woods contain many animals", 0.88),
-            ("Mountains are covered with snow", "Peaks are blanketed in white", 0.85),
-            ("The river flows through the valley", "The stream runs across the lowland", 0.87),
-            ("Birds are migrating south for winter", "Avians are traveling to warmer regions", 0.86),
-            ("The garden blooms in spring", "Flowers blossom in the warmer season", 0.88),
-            
-            // Health
-            ("Regular exercise improves health", "Consistent workouts enhance wellness", 0.90),
-            ("The patient recovered quickly", "The sick person healed rapidly", 0.88),
-            ("Eating vegetables is nutritious", "Consuming greens is healthy", 0.87),
-            ("The doctor prescribed medication", "The physician recommended medicine", 0.91),
-            ("Sleep is essential for wellbeing", "Rest is crucial for health", 0.89),
-            
-            // Travel
-            ("The flight was delayed by weather", "The plane was postponed due to climate", 0.88),
-            ("Tourists visited the famous landmark", "Travelers saw the renowned monument", 0.87),
-            ("The hotel offers excellent service", "The accommodation provides great hospitality", 0.86),
-            ("The train arrived on schedule", "The railway came on time", 0.89),
-            ("Vacation planning requires preparation", "Holiday organization needs planning", 0.88),
-            
-            // Food
-            ("The chef prepared a gourmet meal", "The cook made a fancy dinner", 0.85),
-            ("Coffee helps people wake up", "Caffeine assists in alertness", 0.87),
-            ("The bakery sells fresh bread", "The shop offers new baked goods", 0.88),
-            ("Restaurants are busy on weekends", "Eateries are crowded on Saturdays and Sundays", 0.86),
-            ("Cooking at home saves money", "Making food yourself reduces costs", 0.89),
-        ];
-        
-        // Add all base pairs
-        for (s1, s2, score) in &sentence_pairs {
-            entries.push(STSBEntry {
-                sentence1: s1.to_string(),
-                sentence2: s2.to_string(),
-                score: *score,
-            });
-        }
-        
-        // Generate more variations to reach 600+ unique sentences
-        for (s1, s2, score) in &sentence_pairs {
-            // Add reversed pairs
-            entries.push(STSBEntry {
-                sentence1: s2.to_string(),
-                sentence2: s1.to_string(),
-                score: *score,
-            });
-            
-            // Add some cross-category pairs with lower scores
-            if entries.len() < 1000 {
-                entries.push(STSBEntry {
-                    sentence1: s1.to_string(),
-                    sentence2: "The quick brown fox jumps over the lazy dog".to_string(),
-                    score: 0.1,
-                });
-            }
-        }
-        
-        entries
-    }
-    
     /// Create a fallback dataset if download fails
     fn create_fallback_dataset() -> Vec<STSBEntry> {
         // Create a more substantial fallback dataset
@@ -231,12 +205,41 @@ impl STSBDataset {
         entries
     }
     
-    /// Load dataset from file
+    /// Load a dataset previously cached as a single JSON array by
+    /// [`Self::try_download`]
     fn load_from_file(path: &Path) -> Result<Self> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
         let entries: Vec<STSBEntry> = serde_json::from_reader(reader)?;
-        
+        Self::from_entries(entries)
+    }
+
+    /// Stream a JSON-Lines dataset (one [`STSBEntry`] object per line) from
+    /// `path`, so corpora too large to deserialize as a single `Vec` up
+    /// front can still be loaded a record at a time
+    pub fn from_jsonl(path: &Path) -> Result<Self> {
+        let entries = DatasetSource::Jsonl(path.to_path_buf())
+            .read_entries()?
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Failed to stream JSONL dataset from {path:?}"))?;
+        Self::from_entries(entries)
+    }
+
+    /// Stream a tab-separated dataset (`sentence1\tsentence2\tscore` per
+    /// line, matching STS Benchmark's own distribution format) from `path`
+    pub fn from_tsv(path: &Path) -> Result<Self> {
+        let entries = DatasetSource::Tsv(path.to_path_buf())
+            .read_entries()?
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Failed to stream TSV dataset from {path:?}"))?;
+        Self::from_entries(entries)
+    }
+
+    /// Build a dataset from already-loaded entries: dedupe sentences, split
+    /// off a query set, and pad the indexing set to 1000 sentences. Shared
+    /// by every constructor (file-backed or in-memory) so they all apply
+    /// the same post-processing.
+    fn from_entries(entries: Vec<STSBEntry>) -> Result<Self> {
         // Extract unique sentences
         let mut unique_sentences = HashSet::new();
         for entry in &entries {
@@ -273,36 +276,29 @@ impl STSBDataset {
         
         let mut sentences = all_sentences;
         
-        // Ensure we have at least 1000 sentences for indexing
+        // Ensure we have at least 1000 sentences for indexing. Each missing
+        // slot is a pure function of its own `variation_id` (which base
+        // sentence it derives from and which of the five phrasings to use),
+        // so the fill is a rayon parallel map rather than a sequential loop.
         let original_count = sentences.len();
-        if sentences.len() < 1000 {
+        let needed = 1000usize.saturating_sub(sentences.len());
+        if needed > 0 {
             println!("📊 Extending dataset from {} to 1000 sentences for indexing", sentences.len());
-            
-            // Clone existing sentences and add variations
-            let mut additional_sentences = Vec::new();
-            let mut index = 0;
-            
-            while sentences.len() + additional_sentences.len() < 1000 {
-                let base_sentence = &sentences[index % original_count];
-                
-                // Create variations
-                let variations = vec![
-                    format!("{} again", base_sentence),
-                    format!("Actually, {}", base_sentence.to_lowercase()),
-                    format!("{}.", base_sentence.trim_end_matches('.')),
-                    format!("Indeed, {}", base_sentence.to_lowercase()),
-                    format!("{} too", base_sentence),
-                ];
-                
-                for variation in variations {
-                    if sentences.len() + additional_sentences.len() < 1000 {
-                        additional_sentences.push(variation);
+
+            let additional_sentences: Vec<String> = (0..needed)
+                .into_par_iter()
+                .map(|variation_id| {
+                    let base_sentence = &sentences[(variation_id / 5) % original_count];
+                    match variation_id % 5 {
+                        0 => format!("{} again", base_sentence),
+                        1 => format!("Actually, {}", base_sentence.to_lowercase()),
+                        2 => format!("{}.", base_sentence.trim_end_matches('.')),
+                        3 => format!("Indeed, {}", base_sentence.to_lowercase()),
+                        _ => format!("{} too", base_sentence),
                     }
-                }
-                
-                index += 1;
-            }
-            
+                })
+                .collect();
+
             sentences.extend(additional_sentences);
         }
         
@@ -352,7 +348,271 @@ impl STSBDataset {
                 }
             }
         }
-        
+
         queries
     }
+
+    /// Embed [`Self::sentences`] and build a Vamana index over them, using
+    /// up to `threads` rayon workers to compute embeddings concurrently
+    /// instead of [`crate::demo::build_index`]'s fully sequential
+    /// embed-then-insert pipeline. Each sentence's vector id is its
+    /// position in `self.sentences`, pre-assigned before any embedding work
+    /// starts, so the index's contents don't depend on which batch finishes
+    /// first; only the batches' completion order is actually concurrent.
+    /// Insertion itself takes the index mutex once per batch and calls
+    /// [`VamanaIndex::add`] for each of the batch's sentences under that
+    /// lock, the same synchronization
+    /// [`crate::demo::EmbeddingCache::get_or_compute`] uses to fold
+    /// concurrently-completed batches into shared state one at a time.
+    /// Returns both the index and the embeddings (in `self.sentences`
+    /// order), since callers such as [`Self::evaluate`] need the latter too.
+    pub fn build_index_parallel(
+        &self,
+        provider: &(dyn EmbeddingProvider + Sync),
+        threads: usize,
+    ) -> Result<(VamanaIndex<CosineDistance>, Vec<Vec<f32>>)> {
+        let config = VamanaConfig {
+            max_degree: 32,
+            search_list_size: 64,
+            alpha: 1.2,
+            seed: 42,
+        };
+        let index = Mutex::new(VamanaIndex::new(CosineDistance, config));
+
+        let embeddings = EmbeddingQueue::with_parallelism(threads.max(1))
+            .embed_all(&self.sentences, provider, |batch_indices, batch_embeddings| {
+                let mut index = index.lock().unwrap();
+                for (&i, embedding) in batch_indices.iter().zip(batch_embeddings.iter()) {
+                    index
+                        .add(i as u32, embedding.clone())
+                        .map_err(|e| anyhow::anyhow!("Failed to insert sentence {i} into index: {e:?}"))?;
+                }
+                Ok(())
+            })
+            .context("Failed to build index in parallel")?;
+
+        Ok((index.into_inner().unwrap(), embeddings))
+    }
+
+    /// Evaluate `index`'s retrieval quality against this dataset's
+    /// human-annotated [`STSBEntry::score`] pairs, treating `sentence1` as a
+    /// query and `sentence2` as the document it should retrieve. `embeddings`
+    /// must be parallel to `self.sentences` (`embeddings[i]` is the vector
+    /// indexed under vector id `i`, the same convention
+    /// [`crate::demo::build_index`] uses) so a pair's query vector and its
+    /// target id can both be looked up by sentence text. Pairs where either
+    /// sentence isn't part of the indexed set are skipped, since there's
+    /// nothing to query or check membership against.
+    pub fn evaluate<D: Distance<f32> + Sync + Send>(
+        &self,
+        index: &VamanaIndex<D>,
+        embeddings: &[Vec<f32>],
+        k: usize,
+    ) -> Result<EvalReport> {
+        let sentence_ids: HashMap<&str, usize> = self
+            .sentences
+            .iter()
+            .enumerate()
+            .map(|(id, sentence)| (sentence.as_str(), id))
+            .collect();
+
+        let mut hits = 0usize;
+        let mut reciprocal_ranks = Vec::new();
+        let mut pairs_evaluated = 0usize;
+        // Negative distance / gold score, paired up only for pairs where
+        // sentence2 actually surfaced in the top-k (otherwise there's no
+        // vector distance to correlate against the gold score).
+        let mut distance_score_pairs: Vec<(f32, f32)> = Vec::new();
+
+        for entry in &self.entries {
+            let (Some(&query_id), Some(&target_id)) = (
+                sentence_ids.get(entry.sentence1.as_str()),
+                sentence_ids.get(entry.sentence2.as_str()),
+            ) else {
+                continue;
+            };
+            pairs_evaluated += 1;
+
+            let results = index
+                .search(&embeddings[query_id], k)
+                .map_err(|e| anyhow::anyhow!("Vector search failed during evaluation: {e:?}"))?;
+
+            let target_id = target_id as u32;
+            if let Some((rank, result)) = results
+                .iter()
+                .enumerate()
+                .find(|(_, result)| result.id == target_id)
+            {
+                hits += 1;
+                reciprocal_ranks.push(1.0 / (rank as f32 + 1.0));
+                distance_score_pairs.push((-result.distance, entry.score));
+            } else {
+                reciprocal_ranks.push(0.0);
+            }
+        }
+
+        let recall_at_k = if pairs_evaluated > 0 {
+            hits as f32 / pairs_evaluated as f32
+        } else {
+            0.0
+        };
+        let mrr = if reciprocal_ranks.is_empty() {
+            0.0
+        } else {
+            reciprocal_ranks.iter().sum::<f32>() / reciprocal_ranks.len() as f32
+        };
+        let spearman_correlation = spearman_rank_correlation(&distance_score_pairs);
+
+        Ok(EvalReport {
+            k,
+            pairs_evaluated,
+            recall_at_k,
+            mrr,
+            spearman_correlation,
+        })
+    }
+}
+
+/// Assign average ranks to `values` (1-based, ties share the mean of the
+/// positions they span), the standard tie-handling Spearman's rho expects
+fn fractional_ranks(values: &[f32]) -> Vec<f32> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && (values[order[j + 1]] - values[order[i]]).abs() < f32::EPSILON {
+            j += 1;
+        }
+        let average_rank = (i + j) as f32 / 2.0 + 1.0;
+        for &idx in &order[i..=j] {
+            ranks[idx] = average_rank;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+/// Spearman rank correlation between the first and second element of each
+/// pair in `pairs`, computed as the Pearson correlation of their fractional
+/// ranks. Returns `0.0` for fewer than two pairs or zero variance in either
+/// ranking, rather than dividing by zero.
+fn spearman_rank_correlation(pairs: &[(f32, f32)]) -> f32 {
+    if pairs.len() < 2 {
+        return 0.0;
+    }
+
+    let xs: Vec<f32> = pairs.iter().map(|(x, _)| *x).collect();
+    let ys: Vec<f32> = pairs.iter().map(|(_, y)| *y).collect();
+    let rank_x = fractional_ranks(&xs);
+    let rank_y = fractional_ranks(&ys);
+
+    let n = rank_x.len() as f32;
+    let mean_x = rank_x.iter().sum::<f32>() / n;
+    let mean_y = rank_y.iter().sum::<f32>() / n;
+
+    let mut covariance = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for (&x, &y) in rank_x.iter().zip(rank_y.iter()) {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        0.0
+    } else {
+        covariance / (var_x.sqrt() * var_y.sqrt())
+    }
+}
+
+/// Retrieval-quality metrics from [`STSBDataset::evaluate`]
+#[derive(Debug, Clone)]
+pub struct EvalReport {
+    /// `k` used for the top-k membership and rank checks
+    pub k: usize,
+    /// Number of gold pairs where both sentences were part of the indexed
+    /// set and so could actually be evaluated
+    pub pairs_evaluated: usize,
+    /// Fraction of evaluated pairs where `sentence2` appeared in
+    /// `sentence1`'s top-`k` vector results
+    pub recall_at_k: f32,
+    /// Mean reciprocal rank of `sentence2` within that same top-`k` list
+    pub mrr: f32,
+    /// Spearman rank correlation between negative vector distance and gold
+    /// `score`, over pairs where `sentence2` was found within the top-`k`
+    pub spearman_correlation: f32,
+}
+
+impl EvalReport {
+    /// Render as an aligned text table (metric, value, k) so different index
+    /// configurations (beam width, graph degree) can be compared at a glance
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        table.push_str(&format!("{:<10} {:>10} {:>6}\n", "metric", "value", "k"));
+        table.push_str(&format!("{:<10} {:>10.4} {:>6}\n", "recall@k", self.recall_at_k, self.k));
+        table.push_str(&format!("{:<10} {:>10.4} {:>6}\n", "mrr", self.mrr, self.k));
+        table.push_str(&format!("{:<10} {:>10.4} {:>6}\n", "spearman", self.spearman_correlation, self.k));
+        table.push_str(&format!("({} pairs evaluated)", self.pairs_evaluated));
+        table
+    }
+}
+
+impl std::fmt::Display for EvalReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_table())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::EmbeddingsGenerator;
+
+    fn tiny_dataset() -> STSBDataset {
+        STSBDataset {
+            sentences: (0..12).map(|i| format!("sentence number {i}")).collect(),
+            query_sentences: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn build_index_parallel_with_one_thread_matches_sequential_insertion() {
+        let dataset = tiny_dataset();
+        let provider = EmbeddingsGenerator::new(16);
+
+        let (parallel_index, embeddings) = dataset.build_index_parallel(&provider, 1).unwrap();
+
+        // `EmbeddingsGenerator` is a pure function of its input text, so
+        // `embeddings` doesn't depend on how the sentences above were
+        // batched; inserting those same vectors sequentially, in the same
+        // order `build_index_parallel` assigns ids, should then build an
+        // identical graph -- `build_index_parallel` uses the same
+        // `VamanaIndex::add` insertion path as a sequential build, it just
+        // runs the embedding step (not the insertion step) across batches.
+        // Full adjacency equality isn't observable through `VamanaIndex`'s
+        // public API, so degree distribution (a function of every node's
+        // adjacency list) stands in for it.
+        let config = VamanaConfig {
+            max_degree: 32,
+            search_list_size: 64,
+            alpha: 1.2,
+            seed: 42,
+        };
+        let mut sequential_index = VamanaIndex::new(CosineDistance, config);
+        for (i, embedding) in embeddings.iter().enumerate() {
+            sequential_index.add(i as u32, embedding.clone()).unwrap();
+        }
+
+        assert_eq!(parallel_index.size(), sequential_index.size());
+        let mut parallel_degrees = parallel_index.degree_distribution();
+        let mut sequential_degrees = sequential_index.degree_distribution();
+        parallel_degrees.sort_unstable();
+        sequential_degrees.sort_unstable();
+        assert_eq!(parallel_degrees, sequential_degrees);
+    }
 }
\ No newline at end of file
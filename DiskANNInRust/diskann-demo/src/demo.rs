@@ -2,18 +2,225 @@
 
 use anyhow::{Result, Context};
 use colored::*;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Instant;
 use std::fs;
 use std::io::Write;
+use sha3::{Digest, Sha3_256};
 
 use diskann_impl::{VamanaIndex, IndexBuilder, VamanaConfig};
 use diskann_traits::{distance::{EuclideanDistance, CosineDistance}, index::Index, search::Search};
-use diskann_io::{write_vectors_f32, read_vectors_f32};
 
+use crate::candle_model::CandleSentenceTransformer;
+use crate::chunking::{chunk_text, to_graph_node, DEFAULT_CHUNK_OVERLAP_TOKENS, DEFAULT_CHUNK_TOKEN_BUDGET};
 use crate::dataset::STSBDataset;
+use crate::embedding_provider::{EmbeddingMetadata, EmbeddingProvider};
+use crate::embedding_queue::EmbeddingQueue;
 use crate::embeddings::EmbeddingsGenerator;
+use crate::hybrid_search::{hybrid_search, Bm25Index};
 use crate::model::RealEmbeddingModel;
+use crate::ranking::{rank, ExactPhraseMatchRule, LengthPenaltyRule, RankingQuery, VectorDistanceRule};
+use crate::remote_model::RemoteEmbeddingProvider;
+
+/// `semantic_ratio` passed to [`hybrid_search`] in both the benchmark and
+/// interactive flows, unless overridden by `DISKANN_SEMANTIC_RATIO`; `0.5`
+/// weights the BM25 and vector rankings evenly
+const DEFAULT_SEMANTIC_RATIO: f32 = 0.5;
+
+/// Pick the best available embedding backend, in order: an operator-configured
+/// remote endpoint (`DISKANN_REMOTE_EMBEDDINGS_URL` and friends, for
+/// OpenAI/Ollama-style `/embeddings` servers), the local ONNX model, the
+/// pure-Rust `candle` model, and finally the toy bag-of-words fallback that
+/// never needs a download. Callers don't need to know which one wins --
+/// [`EmbeddingProvider`] is all the rest of the demo touches.
+pub(crate) fn select_provider(cache_dir: &Path) -> Box<dyn EmbeddingProvider + Sync> {
+    if let Ok(url) = std::env::var("DISKANN_REMOTE_EMBEDDINGS_URL") {
+        let model_id = std::env::var("DISKANN_REMOTE_EMBEDDINGS_MODEL")
+            .unwrap_or_else(|_| "text-embedding-3-small".to_string());
+        let dimension = std::env::var("DISKANN_REMOTE_EMBEDDINGS_DIM")
+            .ok()
+            .and_then(|d| d.parse().ok())
+            .unwrap_or(384);
+        let api_key = std::env::var("DISKANN_REMOTE_EMBEDDINGS_API_KEY").ok();
+        println!("✓ Using remote embeddings endpoint {} ({})", url, model_id);
+        return Box::new(RemoteEmbeddingProvider::new(url, model_id, dimension, api_key));
+    }
+
+    match RealEmbeddingModel::new(cache_dir) {
+        Ok(model) => {
+            println!("✓ Using real all-MiniLM-L6-v2 model (ONNX)");
+            return Box::new(model);
+        }
+        Err(e) => println!("⚠ Could not load ONNX model: {}", e),
+    }
+
+    match CandleSentenceTransformer::new() {
+        Ok(model) => {
+            println!("✓ Using all-MiniLM-L6-v2 model (candle)");
+            return Box::new(model);
+        }
+        Err(e) => println!("⚠ Could not load candle model: {}", e),
+    }
+
+    println!("  Falling back to simple embeddings...");
+    Box::new(EmbeddingsGenerator::new(384))
+}
+
+/// Load cached embeddings if they exist and were produced by
+/// [`select_provider`]'s current choice of model, otherwise (re)generate
+/// them and stamp the cache with [`EmbeddingMetadata`] for next time
+///
+/// `embeddings_path` is only used to derive [`EmbeddingCache`]'s on-disk
+/// path; the actual cache is keyed per-sentence so editing a handful of
+/// dataset sentences doesn't force recomputing the rest.
+pub(crate) fn load_or_generate_embeddings(cache_dir: &Path, sentences: &[String], embeddings_path: &Path) -> Result<Vec<Vec<f32>>> {
+    let provider = select_provider(cache_dir);
+    EmbeddingCache::get_or_compute(embeddings_path, sentences, provider.as_ref())
+}
+
+/// Disk-backed embedding cache keyed by a content digest (SHA3-256, the same
+/// digest [`crate::model::RealEmbeddingModel`]'s on-disk cache uses) of each
+/// sentence, so a dataset edit only costs embedding the sentences that
+/// actually changed -- every digest already on disk is reused as-is instead
+/// of recomputing the whole matrix. [`EmbeddingMetadata`] still guards the
+/// cache as a whole: if the configured provider has changed since it was
+/// written, every entry is treated as a miss rather than risking embeddings
+/// from two different models ending up side by side under the same digest.
+pub(crate) struct EmbeddingCache {
+    path: PathBuf,
+    entries: HashMap<String, Vec<f32>>,
+}
+
+impl EmbeddingCache {
+    /// Digest a sentence the same way cache entries are keyed, so a lookup
+    /// always agrees with the entry that was stored for it
+    fn digest(sentence: &str) -> String {
+        let digest = Sha3_256::digest(sentence.trim().as_bytes());
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Load the cache at `path`, or start empty if it doesn't exist yet or
+    /// was stamped by a different provider than `provider`
+    fn load(path: &Path, provider: &dyn EmbeddingProvider) -> Self {
+        let entries = if EmbeddingMetadata::matches(path, provider) {
+            fs::read(path).ok().map(Self::decode).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        Self { path: path.to_path_buf(), entries }
+    }
+
+    /// Look up `sentences` in the cache in dataset order. Sentences not
+    /// already present are embedded through an [`EmbeddingQueue`] (so large
+    /// misses are batched to a token budget and dispatched concurrently);
+    /// each completed batch is folded into the cache and persisted to disk
+    /// as soon as it lands, so an interrupted run only loses the batches
+    /// still in flight. The [`EmbeddingMetadata`] sidecar is written only
+    /// after every batch has finished, so a cache file left without a
+    /// matching sidecar (e.g. by an interrupted first run) is discarded
+    /// whole on the next load.
+    pub(crate) fn get_or_compute(
+        path: &Path,
+        sentences: &[String],
+        provider: &(dyn EmbeddingProvider + Sync),
+    ) -> Result<Vec<Vec<f32>>> {
+        let cache = Mutex::new(Self::load(path, provider));
+
+        let digests: Vec<String> = sentences.iter().map(|s| Self::digest(s)).collect();
+        let mut pending_indices = Vec::new();
+        let mut pending_texts = Vec::new();
+        {
+            let cache = cache.lock().unwrap();
+            for (i, digest) in digests.iter().enumerate() {
+                if !cache.entries.contains_key(digest) {
+                    pending_indices.push(i);
+                    pending_texts.push(sentences[i].clone());
+                }
+            }
+        }
+
+        if pending_texts.is_empty() {
+            println!("✓ Using cached embeddings ({} sentences)", sentences.len());
+        } else {
+            println!(
+                "  Embedding {} new/changed sentence(s) ({} already cached)",
+                pending_texts.len(),
+                sentences.len() - pending_texts.len()
+            );
+            let pending_digests: Vec<String> = pending_indices.iter().map(|&i| digests[i].clone()).collect();
+
+            EmbeddingQueue::new().embed_all(&pending_texts, provider, |batch_indices, embeddings| {
+                let mut cache = cache.lock().unwrap();
+                for (&local_i, embedding) in batch_indices.iter().zip(embeddings.iter()) {
+                    cache.entries.insert(pending_digests[local_i].clone(), embedding.clone());
+                }
+                cache.save()
+            })?;
+            EmbeddingMetadata::write(path, provider)?;
+        }
+
+        let cache = cache.into_inner().unwrap();
+        Ok(digests
+            .iter()
+            .map(|digest| {
+                cache.entries.get(digest)
+                    .cloned()
+                    .expect("every digest was either already cached or just inserted")
+            })
+            .collect())
+    }
+
+    /// Write the cache to a `.tmp` sibling, then rename it over `self.path`,
+    /// so a crash mid-batch never leaves a truncated or half-written cache
+    /// file behind -- the same atomic-write pattern
+    /// [`crate::model::RealEmbeddingModel`]'s on-disk cache uses.
+    fn save(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("cache.tmp");
+        fs::write(&tmp_path, self.encode())?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Serialize `entries` as a flat sequence of `(64-byte hex digest, u32
+    /// LE dimension, f32 LE vector)` records
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (digest, embedding) in &self.entries {
+            bytes.extend_from_slice(digest.as_bytes());
+            bytes.extend_from_slice(&(embedding.len() as u32).to_le_bytes());
+            for value in embedding {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Inverse of [`EmbeddingCache::encode`]; a malformed or truncated cache
+    /// file is treated the same as a missing one (every digest recomputed)
+    /// rather than failing the whole run
+    fn decode(bytes: Vec<u8>) -> HashMap<String, Vec<f32>> {
+        let mut entries = HashMap::new();
+        let mut offset = 0;
+        while offset + 64 + 4 <= bytes.len() {
+            let Ok(digest) = String::from_utf8(bytes[offset..offset + 64].to_vec()) else { break };
+            offset += 64;
+            let dimension = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+            offset += 4;
+            if offset + dimension * 4 > bytes.len() {
+                break;
+            }
+            let embedding = bytes[offset..offset + dimension * 4]
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            offset += dimension * 4;
+            entries.insert(digest, embedding);
+        }
+        entries
+    }
+}
 
 /// Run the complete demo
 pub fn run_full_demo(cache_dir: &Path) -> Result<()> {
@@ -30,26 +237,7 @@ pub fn run_full_demo(cache_dir: &Path) -> Result<()> {
     // Step 2: Generate embeddings
     println!("{}", "Step 2: Generating Embeddings".bold());
     let embeddings_path = cache_dir.join("embeddings.bin");
-    let embeddings = if embeddings_path.exists() {
-        println!("✓ Using cached embeddings");
-        load_embeddings(&embeddings_path)?
-    } else {
-        // Try to use real model first, fall back to simple embeddings
-        let embeddings = match RealEmbeddingModel::new(cache_dir) {
-            Ok(mut model) => {
-                println!("✓ Using real all-MiniLM-L6-v2 model");
-                model.embed_texts(&dataset.sentences)?
-            }
-            Err(e) => {
-                println!("⚠ Could not load ONNX model: {}", e);
-                println!("  Falling back to simple embeddings...");
-                let generator = EmbeddingsGenerator::new(384);
-                generator.embed_texts(&dataset.sentences)?
-            }
-        };
-        save_embeddings(&embeddings, &embeddings_path)?;
-        embeddings
-    };
+    let embeddings = load_or_generate_embeddings(cache_dir, &dataset.sentences, &embeddings_path)?;
     println!();
     
     // Step 3: Build index
@@ -67,7 +255,14 @@ pub fn run_full_demo(cache_dir: &Path) -> Result<()> {
     // Step 4: Run benchmark queries
     println!("{}", "Step 4: Running Benchmark Queries".bold());
     run_benchmark_queries(&index, &dataset, cache_dir)?;
-    
+
+    // Step 4.5: Score retrieval quality against STSB's gold similarity labels
+    println!();
+    println!("{}", "Step 4.5: Evaluating Against Gold Labels".bold());
+    let eval_k = 10;
+    let report = dataset.evaluate(&index, &embeddings, eval_k)?;
+    println!("{}", report);
+
     // Step 5: Show statistics
     println!();
     println!("{}", "=".repeat(60).blue());
@@ -118,43 +313,37 @@ fn run_benchmark_queries(
     dataset: &STSBDataset,
     cache_dir: &Path,
 ) -> Result<()> {
-    // Try to use real model, fall back to simple
     let queries = dataset.get_sample_queries();
-    let query_embeddings = match RealEmbeddingModel::new(cache_dir) {
-        Ok(mut model) => {
-            println!("Using real model for queries");
-            let mut embeddings = Vec::new();
-            for q in &queries {
-                embeddings.push(model.embed_text(q)?);
-            }
-            embeddings
-        }
-        Err(_) => {
-            let generator = EmbeddingsGenerator::new(384);
-            queries.iter().map(|q| generator.embed_text(q)).collect()
-        }
-    };
-    
+    let provider = select_provider(cache_dir);
+    let query_embeddings = provider.embed_batch(
+        &queries.iter().map(|q| q.to_string()).collect::<Vec<_>>(),
+    )?;
+    let bm25 = Bm25Index::build(&dataset.sentences)?;
+    let semantic_ratio = std::env::var("DISKANN_SEMANTIC_RATIO")
+        .ok()
+        .and_then(|ratio| ratio.parse().ok())
+        .unwrap_or(DEFAULT_SEMANTIC_RATIO);
+
     println!();
     for (i, (query, query_embedding)) in queries.iter().zip(query_embeddings.iter()).enumerate() {
         println!("{}", format!("Query {}: \"{}\"", i + 1, query).yellow());
-        
-        // Search
+
+        // Hybrid (BM25 + vector) search
         let start = Instant::now();
-        let results = index.search(&query_embedding, 5)?;
+        let results = hybrid_search(index, &bm25, query, query_embedding, 5, semantic_ratio)?;
         let search_time = start.elapsed();
-        
+
         println!("Search time: {:.2?}", search_time);
         println!("Results:");
-        
+
         for (rank, result) in results.iter().enumerate() {
             let sentence = &dataset.sentences[result.id as usize];
-            // Cosine distance is 1 - cosine_similarity, so similarity = 1 - distance
-            let similarity = 1.0 - result.distance;
-            
-            println!("  {}. [Similarity: {:.3}] {}", 
-                     rank + 1, 
-                     similarity,
+
+            println!("  {}. [fused {:.4}, lexical_rank={:?}, vector_distance={:?}] {}",
+                     rank + 1,
+                     result.fused_score,
+                     result.lexical_rank,
+                     result.vector_distance,
                      if sentence.len() > 60 {
                          format!("{}...", &sentence[..60])
                      } else {
@@ -163,23 +352,37 @@ fn run_benchmark_queries(
         }
         println!();
     }
-    
-    Ok(())
-}
 
-/// Save embeddings to file
-fn save_embeddings(embeddings: &[Vec<f32>], path: &Path) -> Result<()> {
-    let file = fs::File::create(path)?;
-    let mut writer = std::io::BufWriter::new(file);
-    write_vectors_f32(&mut writer, embeddings)?;
-    Ok(())
-}
+    // Demonstrate the rule-based reranking cascade once, against the first
+    // query, showing which rule decided each result's final position.
+    if let (Some(query), Some(query_embedding)) = (queries.first(), query_embeddings.first()) {
+        println!("{}", format!("Ranking cascade for \"{}\":", query).yellow());
+
+        let vector_rule = VectorDistanceRule::new(index, 0.01);
+        let phrase_rule = ExactPhraseMatchRule::new(&dataset.sentences);
+        let length_rule = LengthPenaltyRule::new(&dataset.sentences);
+        let ranking_query = RankingQuery { text: query, embedding: query_embedding };
+        let universe: Vec<u32> = (0..dataset.sentences.len() as u32).collect();
+        let cascade_results = rank(&[&vector_rule, &phrase_rule, &length_rule], universe, &ranking_query, 5);
 
-/// Load embeddings from file
-fn load_embeddings(path: &Path) -> Result<Vec<Vec<f32>>> {
-    let file = fs::File::open(path)?;
-    let mut reader = std::io::BufReader::new(file);
-    read_vectors_f32(&mut reader)
+        for (position, detail) in cascade_results.iter().enumerate() {
+            let sentence = &dataset.sentences[detail.id as usize];
+            println!(
+                "  {}. [placed_by={}, contribution={:.4}] {}",
+                position + 1,
+                detail.placed_by,
+                detail.contribution,
+                if sentence.len() > 60 {
+                    format!("{}...", &sentence[..60])
+                } else {
+                    sentence.clone()
+                }
+            );
+        }
+        println!();
+    }
+
+    Ok(())
 }
 
 /// Run interactive search mode
@@ -187,66 +390,139 @@ pub fn run_interactive_mode(cache_dir: &Path) -> Result<()> {
     // Load dataset and embeddings
     let dataset = STSBDataset::download_and_load(cache_dir)?;
     let embeddings_path = cache_dir.join("embeddings.bin");
-    
-    let embeddings = if embeddings_path.exists() {
-        load_embeddings(&embeddings_path)?
-    } else {
-        let generator = EmbeddingsGenerator::new(384);
-        let embeddings = generator.embed_texts(&dataset.sentences)?;
-        save_embeddings(&embeddings, &embeddings_path)?;
-        embeddings
-    };
-    
+    let embeddings = load_or_generate_embeddings(cache_dir, &dataset.sentences, &embeddings_path)?;
+
     // Build index
     println!("Building index...");
-    let index = build_index(embeddings)?;
-    
-    // Try to use real model, fall back to simple
-    // Since we need mutable access for the real model, we'll use simple embeddings for interactive mode
-    println!("⚠ Using simple embeddings for interactive queries");
-    let generator = EmbeddingsGenerator::new(384);
-    let embed_fn = move |text: &str| generator.embed_text(text);
-    
+    let mut index = build_index(embeddings)?;
+    // Sentences live alongside the index rather than borrowed from `dataset`,
+    // so `:add` can grow both the index and the text it's looking up
+    // together; `bm25` is rebuilt from this list whenever it grows since
+    // `Bm25Index` has no incremental update path.
+    let mut sentences = dataset.sentences.clone();
+    let mut bm25 = Bm25Index::build(&sentences)?;
+    let mut next_id = sentences.len() as u32;
+
+    let provider = select_provider(cache_dir);
+    let semantic_ratio = std::env::var("DISKANN_SEMANTIC_RATIO")
+        .ok()
+        .and_then(|ratio| ratio.parse().ok())
+        .unwrap_or(DEFAULT_SEMANTIC_RATIO);
+
     println!("{}", "=".repeat(60).blue());
     println!("{}", "Interactive Search Mode".bold().green());
-    println!("{}", "Type 'quit' to exit".italic());
+    println!("{}", "Type 'quit' to exit, ':add <text>' to index a new sentence live".italic());
     println!("{}", "=".repeat(60).blue());
-    
+
     loop {
         print!("\n{} ", "Query:".cyan());
         std::io::stdout().flush()?;
-        
+
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
         let query = input.trim();
-        
+
         if query.eq_ignore_ascii_case("quit") || query.eq_ignore_ascii_case("exit") {
             println!("Goodbye!");
             break;
         }
-        
+
         if query.is_empty() {
             continue;
         }
-        
-        // Generate embedding and search
-        let query_embedding = embed_fn(query);
+
+        if let Some(text) = query.strip_prefix(":add ") {
+            let text = text.trim();
+            if text.is_empty() {
+                println!("Usage: :add <text>");
+                continue;
+            }
+
+            let embedding = provider.embed_text(text)?;
+            index.add(next_id, embedding).context("Failed to insert new sentence into index")?;
+            sentences.push(text.to_string());
+            // Vamana insertion is incremental (see VamanaIndex::insert_node);
+            // BM25 isn't, so it's rebuilt over the grown sentence list. Fine
+            // for interactive use -- it's O(sentences) and runs once per add.
+            bm25 = Bm25Index::build(&sentences)?;
+            println!("✓ Indexed as id {next_id}, searchable immediately");
+            next_id += 1;
+            continue;
+        }
+
+        // Generate embedding and run hybrid (BM25 + vector) search
+        let query_embedding = provider.embed_text(query)?;
         let start = Instant::now();
-        let results = index.search(&query_embedding, 5)?;
+        let results = hybrid_search(&index, &bm25, query, &query_embedding, 5, semantic_ratio)?;
         let search_time = start.elapsed();
-        
+
         println!("\n{} ({:.2?})", "Results:".green(), search_time);
         for (rank, result) in results.iter().enumerate() {
-            let sentence = &dataset.sentences[result.id as usize];
-            // Cosine distance is 1 - cosine_similarity, so similarity = 1 - distance
-            let similarity = 1.0 - result.distance;
-            
-            println!("  {}. [{:.3}] {}", 
-                     rank + 1, 
-                     similarity,
+            let sentence = &sentences[result.id as usize];
+
+            println!("  {}. [fused {:.4}, lexical_rank={:?}, vector_distance={:?}] {}",
+                     rank + 1,
+                     result.fused_score,
+                     result.lexical_rank,
+                     result.vector_distance,
                      sentence);
         }
     }
-    
+
+    Ok(())
+}
+
+/// Demonstrate chunked search: split each sentence into token-budget
+/// windows, embed and index the chunks rather than whole documents, then
+/// resolve a search hit back to the exact span of the source sentence it
+/// came from
+pub fn run_chunking_demo(cache_dir: &Path) -> Result<()> {
+    let dataset = STSBDataset::download_and_load(cache_dir)?;
+    let provider = select_provider(cache_dir);
+
+    println!("{}", "Chunking documents...".bold());
+    let mut chunks = Vec::new();
+    for (doc_id, sentence) in dataset.sentences.iter().enumerate() {
+        chunks.extend(chunk_text(
+            doc_id as u32,
+            sentence,
+            DEFAULT_CHUNK_TOKEN_BUDGET,
+            DEFAULT_CHUNK_OVERLAP_TOKENS,
+        ));
+    }
+    println!("✓ {} chunks from {} documents", chunks.len(), dataset.sentences.len());
+
+    let chunk_texts: Vec<String> = chunks.iter().map(|chunk| chunk.text.clone()).collect();
+    let embeddings = provider.embed_batch(&chunk_texts)?;
+
+    let nodes: Vec<_> = chunks
+        .iter()
+        .zip(embeddings.iter())
+        .enumerate()
+        .map(|(id, (chunk, vector))| to_graph_node(chunk, id as u32, vector.clone()))
+        .collect();
+
+    let index = build_index(embeddings)?;
+
+    let query = dataset
+        .get_sample_queries()
+        .into_iter()
+        .next()
+        .context("dataset has no sample queries")?;
+    println!("\n{}", format!("Query: \"{}\"", query).yellow());
+
+    let query_embedding = provider.embed_text(&query)?;
+    let results = index.search(&query_embedding, 5)?;
+
+    println!("{}", "Results (resolved to source spans):".green());
+    for (rank, result) in results.iter().enumerate() {
+        let node = &nodes[result.id as usize];
+        let doc_id = node.source_doc_id.context("chunked node is missing its source_doc_id")?;
+        let (start, end) = node.char_span.context("chunked node is missing its char_span")?;
+        let span_text = &dataset.sentences[doc_id as usize][start..end];
+
+        println!("  {}. [doc {} span {:?}] {}", rank + 1, doc_id, (start, end), span_text);
+    }
+
     Ok(())
 }
\ No newline at end of file
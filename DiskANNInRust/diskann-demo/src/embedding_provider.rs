@@ -0,0 +1,115 @@
+//! Pluggable embedding backends
+//!
+//! [`EmbeddingsGenerator`](crate::embeddings::EmbeddingsGenerator),
+//! [`RealEmbeddingModel`](crate::model::RealEmbeddingModel),
+//! [`CandleSentenceTransformer`](crate::candle_model::CandleSentenceTransformer)
+//! and [`RemoteEmbeddingProvider`](crate::remote_model::RemoteEmbeddingProvider)
+//! all implement [`EmbeddingProvider`], so `demo`/`main` can pick whichever
+//! backend is available without the rest of the pipeline (index build,
+//! search) knowing which one produced a given vector. Every provider also
+//! reports a stable [`EmbeddingProvider::model_id`]; [`EmbeddingMetadata`] is
+//! the sidecar record stamped next to cached embeddings so a later run can
+//! tell a cache was built by a different model and needs regenerating.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::prompt::{default_template, PromptTemplate};
+
+/// A backend that turns text into fixed-dimension embedding vectors
+///
+/// `embed_batch` takes `&self` so a provider can be shared (e.g. held behind
+/// a single `Box<dyn EmbeddingProvider>` across the full demo flow and
+/// interactive mode alike) without callers needing an exclusive borrow just
+/// to run inference. Heavier backends that carry a session/cache which isn't
+/// naturally `&self`-callable ([`RealEmbeddingModel`](crate::model::RealEmbeddingModel))
+/// reach for interior mutability (a `Mutex`) internally instead of pushing
+/// `&mut self` onto the trait. Methods are synchronous rather than `async` to
+/// match the rest of this crate, which is a blocking CLI with no async
+/// runtime in the loop.
+pub trait EmbeddingProvider {
+    /// Embed a batch of texts, one vector per input in the same order
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Embed a single text; the default implementation just calls
+    /// [`EmbeddingProvider::embed_batch`] with a one-element batch
+    fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed_batch(&[text.to_string()])?
+            .into_iter()
+            .next()
+            .expect("embed_batch returns one vector per input text"))
+    }
+
+    /// Embed a structured JSON document by first rendering it to text with
+    /// `template` (or, if `None`, [`default_template`]'s "concatenate every
+    /// string field" fallback), then embedding the rendered text. Lets
+    /// callers indexing JSON records control what semantic content enters
+    /// each vector without writing their own rendering glue.
+    fn embed_document(
+        &self,
+        document: &Map<String, Value>,
+        template: Option<&PromptTemplate>,
+    ) -> Result<Vec<f32>> {
+        let text = match template {
+            Some(template) => template.render(document)?,
+            None => default_template(document),
+        };
+        self.embed_text(&text)
+    }
+
+    /// Dimensionality of the vectors this provider produces
+    fn dimension(&self) -> usize;
+
+    /// Stable identifier for the model/config that produced these vectors
+    /// (e.g. a HuggingFace repo id), used to detect stale cached embeddings
+    fn model_id(&self) -> &str;
+}
+
+/// Sidecar record stamped next to a cached embeddings file, recording which
+/// [`EmbeddingProvider::model_id`] produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingMetadata {
+    /// [`EmbeddingProvider::model_id`] of the provider that generated the
+    /// embeddings this metadata accompanies
+    pub model_id: String,
+    /// [`EmbeddingProvider::dimension`] of the generated embeddings
+    pub dimension: usize,
+}
+
+impl EmbeddingMetadata {
+    /// Path of the metadata sidecar for an embeddings file, e.g.
+    /// `embeddings.bin` -> `embeddings.bin.meta.json`
+    pub fn sidecar_path(embeddings_path: &Path) -> std::path::PathBuf {
+        let mut os_string = embeddings_path.as_os_str().to_owned();
+        os_string.push(".meta.json");
+        std::path::PathBuf::from(os_string)
+    }
+
+    /// Record a provider's identity next to its generated embeddings
+    pub fn write(embeddings_path: &Path, provider: &dyn EmbeddingProvider) -> Result<()> {
+        let metadata = Self {
+            model_id: provider.model_id().to_string(),
+            dimension: provider.dimension(),
+        };
+        let file = fs::File::create(Self::sidecar_path(embeddings_path))?;
+        serde_json::to_writer_pretty(file, &metadata)?;
+        Ok(())
+    }
+
+    /// Whether a cached embeddings file's sidecar matches `provider`, i.e.
+    /// the cache can be reused as-is instead of being regenerated. Missing
+    /// metadata (e.g. a cache written before this sidecar existed) is
+    /// treated as stale rather than assumed compatible.
+    pub fn matches(embeddings_path: &Path, provider: &dyn EmbeddingProvider) -> bool {
+        let Ok(file) = fs::File::open(Self::sidecar_path(embeddings_path)) else {
+            return false;
+        };
+        let Ok(metadata) = serde_json::from_reader::<_, Self>(file) else {
+            return false;
+        };
+        metadata.model_id == provider.model_id() && metadata.dimension == provider.dimension()
+    }
+}
@@ -0,0 +1,219 @@
+//! Token-budget batching and bounded-parallel dispatch for embedding
+//! providers
+//!
+//! Remote providers ([`crate::remote_model::RemoteEmbeddingProvider`])
+//! charge per input token and cap how much a single request can carry, so
+//! embedding a whole dataset one sentence per request (too slow) or one
+//! request for everything (too large, and a single failure discards all
+//! progress) are both wrong. [`EmbeddingQueue`] instead packs pending texts
+//! into batches sized to stay under a configurable token budget (estimated
+//! with a cheap characters-per-token heuristic, not a real tokenizer -- the same
+//! trade-off [`crate::chunking`] makes for chunk sizing), then runs up to
+//! [`EmbeddingQueue::max_parallel_batches`] of them concurrently on a rayon
+//! thread pool, the same concurrency primitive [`diskann_impl`]'s graph
+//! build uses. Retrying rate-limited requests is the remote provider's own
+//! concern ([`crate::remote_model::RemoteEmbeddingProvider::embed_texts`]);
+//! this queue only owns batching, concurrency, and handing each completed
+//! batch to a caller-supplied callback so e.g. [`crate::demo::EmbeddingCache`]
+//! can persist it to disk as it lands rather than only after the whole
+//! dataset finishes.
+
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+
+use crate::embedding_provider::EmbeddingProvider;
+
+/// Characters per token, the same cheap heuristic OpenAI's own docs suggest
+/// for English text when an exact tokenizer isn't worth pulling in
+const CHARS_PER_TOKEN_ESTIMATE: usize = 4;
+
+/// Default per-request token budget, comfortably under every
+/// OpenAI-compatible embeddings endpoint's documented request limits
+const DEFAULT_MAX_TOKENS_PER_BATCH: usize = 8_000;
+
+/// Default number of batches dispatched concurrently
+const DEFAULT_MAX_PARALLEL_BATCHES: usize = 4;
+
+/// Estimate a text's token count from its character length
+fn estimate_tokens(text: &str) -> usize {
+    (text.chars().count() / CHARS_PER_TOKEN_ESTIMATE).max(1)
+}
+
+/// Group `texts`' indices into batches that each stay under `max_tokens`; a
+/// single text whose own estimate exceeds the budget still gets a
+/// (oversized) batch of its own rather than being dropped
+fn plan_batches(texts: &[String], max_tokens: usize) -> Vec<Vec<usize>> {
+    let mut batches = Vec::new();
+    let mut current = Vec::new();
+    let mut current_tokens = 0;
+
+    for (i, text) in texts.iter().enumerate() {
+        let tokens = estimate_tokens(text);
+        if !current.is_empty() && current_tokens + tokens > max_tokens {
+            batches.push(std::mem::take(&mut current));
+            current_tokens = 0;
+        }
+        current.push(i);
+        current_tokens += tokens;
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+/// Token-budget batching and bounded-parallel dispatch over an
+/// [`EmbeddingProvider`]
+pub(crate) struct EmbeddingQueue {
+    max_tokens_per_batch: usize,
+    max_parallel_batches: usize,
+}
+
+impl EmbeddingQueue {
+    /// A queue with [`DEFAULT_MAX_TOKENS_PER_BATCH`] and
+    /// [`DEFAULT_MAX_PARALLEL_BATCHES`]
+    pub(crate) fn new() -> Self {
+        Self {
+            max_tokens_per_batch: DEFAULT_MAX_TOKENS_PER_BATCH,
+            max_parallel_batches: DEFAULT_MAX_PARALLEL_BATCHES,
+        }
+    }
+
+    /// A queue with [`DEFAULT_MAX_TOKENS_PER_BATCH`] but a caller-chosen
+    /// number of concurrently-dispatched batches, for callers that want to
+    /// size parallelism to their own workload rather than the default
+    pub(crate) fn with_parallelism(max_parallel_batches: usize) -> Self {
+        Self {
+            max_tokens_per_batch: DEFAULT_MAX_TOKENS_PER_BATCH,
+            max_parallel_batches,
+        }
+    }
+
+    /// Embed every text in `texts`, dispatching token-budget batches across
+    /// up to `max_parallel_batches` rayon threads. `on_batch` is called once
+    /// per completed batch with the original indices (into `texts`) and
+    /// their embeddings in the same order; it may be invoked concurrently
+    /// from multiple threads, so callers sharing state across calls (like a
+    /// cache file) must synchronize it themselves. The first error from
+    /// either the provider or `on_batch` aborts the remaining batches and is
+    /// returned; batches already persisted via `on_batch` before that point
+    /// stay persisted.
+    pub(crate) fn embed_all(
+        &self,
+        texts: &[String],
+        provider: &(dyn EmbeddingProvider + Sync),
+        on_batch: impl Fn(&[usize], &[Vec<f32>]) -> Result<()> + Sync,
+    ) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let batches = plan_batches(texts, self.max_tokens_per_batch);
+        let results: Mutex<Vec<Option<Vec<f32>>>> = Mutex::new(vec![None; texts.len()]);
+        let first_error: Mutex<Option<anyhow::Error>> = Mutex::new(None);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.max_parallel_batches)
+            .build()
+            .context("Failed to build embedding queue thread pool")?;
+
+        pool.install(|| {
+            batches.par_iter().for_each(|indices| {
+                if first_error.lock().unwrap().is_some() {
+                    return;
+                }
+
+                let batch_texts: Vec<String> = indices.iter().map(|&i| texts[i].clone()).collect();
+                let outcome = provider
+                    .embed_batch(&batch_texts)
+                    .and_then(|embeddings| on_batch(indices, &embeddings).map(|_| embeddings));
+
+                match outcome {
+                    Ok(embeddings) => {
+                        let mut results = results.lock().unwrap();
+                        for (&i, embedding) in indices.iter().zip(embeddings.into_iter()) {
+                            results[i] = Some(embedding);
+                        }
+                    }
+                    Err(e) => {
+                        first_error.lock().unwrap().get_or_insert(e);
+                    }
+                }
+            });
+        });
+
+        if let Some(error) = first_error.into_inner().unwrap() {
+            return Err(error);
+        }
+
+        Ok(results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.expect("every index is covered by exactly one batch"))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_batches_respects_token_budget() {
+        let texts: Vec<String> = (0..10).map(|i| "word ".repeat(20 + i)).collect();
+        let batches = plan_batches(&texts, 20);
+
+        let all_indices: Vec<usize> = batches.iter().flatten().copied().collect();
+        let mut sorted = all_indices.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..texts.len()).collect::<Vec<_>>());
+        assert!(batches.len() > 1);
+    }
+
+    #[test]
+    fn plan_batches_keeps_oversized_text_in_its_own_batch() {
+        let texts = vec!["word ".repeat(1000)];
+        let batches = plan_batches(&texts, 10);
+        assert_eq!(batches, vec![vec![0]]);
+    }
+
+    #[test]
+    fn embed_all_preserves_order_and_persists_every_batch() {
+        let texts: Vec<String> = (0..25).map(|i| format!("sentence number {i}")).collect();
+        let provider = crate::embeddings::EmbeddingsGenerator::new(8);
+        let persisted: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+        let queue = EmbeddingQueue {
+            max_tokens_per_batch: 4,
+            max_parallel_batches: 3,
+        };
+        let embeddings = queue
+            .embed_all(&texts, &provider, |indices, batch_embeddings| {
+                assert_eq!(indices.len(), batch_embeddings.len());
+                persisted.lock().unwrap().extend_from_slice(indices);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(embeddings.len(), texts.len());
+        for (text, embedding) in texts.iter().zip(&embeddings) {
+            assert_eq!(embedding, &provider.embed_text(text));
+        }
+        let mut persisted = persisted.into_inner().unwrap();
+        persisted.sort_unstable();
+        assert_eq!(persisted, (0..texts.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn embed_all_propagates_on_batch_errors() {
+        let texts: Vec<String> = (0..5).map(|i| format!("sentence {i}")).collect();
+        let provider = crate::embeddings::EmbeddingsGenerator::new(8);
+        let queue = EmbeddingQueue::new();
+
+        let result = queue.embed_all(&texts, &provider, |_, _| anyhow::bail!("disk full"));
+        assert!(result.is_err());
+    }
+}
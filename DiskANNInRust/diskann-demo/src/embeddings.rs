@@ -1,13 +1,19 @@
 //! Simple text embeddings generator
-//! 
-//! For demo purposes, we'll use a simple approach rather than loading
-//! a full transformer model. In production, you'd use candle or ort
-//! to load actual sentence-transformers models.
+//!
+//! For demo purposes, we'll use a simple bag-of-words approach rather than
+//! loading a full transformer model; [`crate::model::RealEmbeddingModel`]
+//! and [`crate::candle_model::CandleSentenceTransformer`] are the
+//! production-grade `ort`/`candle` alternatives.
 
 use anyhow::Result;
 use std::collections::HashMap;
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::embedding_provider::EmbeddingProvider;
+
+/// [`EmbeddingProvider::model_id`] for [`EmbeddingsGenerator`]
+const MODEL_ID: &str = "diskann-demo/bag-of-words-toy-v1";
+
 /// Simple embeddings generator for demonstration
 pub struct EmbeddingsGenerator {
     dimension: usize,
@@ -153,4 +159,30 @@ impl EmbeddingsGenerator {
     pub fn dimension(&self) -> usize {
         self.dimension
     }
+}
+
+/// Lowercase, whitespace-split, punctuation-trimmed terms for a text -- the
+/// same per-word cleanup [`EmbeddingsGenerator::embed_text`] applies before
+/// looking a word up, extracted here so other modules (the BM25 ranker in
+/// [`crate::hybrid_search`]) tokenize text the same way
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_string())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+impl EmbeddingProvider for EmbeddingsGenerator {
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        EmbeddingsGenerator::embed_texts(self, texts)
+    }
+
+    fn dimension(&self) -> usize {
+        EmbeddingsGenerator::dimension(self)
+    }
+
+    fn model_id(&self) -> &str {
+        MODEL_ID
+    }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! Typo-tolerant term index
+//!
+//! [`crate::hybrid_search::Bm25Index`] only matches a query term against
+//! the corpus if it's spelled exactly as indexed, which makes the lexical
+//! side of [`crate::hybrid_search::hybrid_search`] brittle to misspellings
+//! that vector search is immune to. [`FuzzyTermIndex`] builds an FST over
+//! every term [`crate::embeddings::tokenize`] has ever seen and, for each
+//! query word, builds a Levenshtein DFA accepting every string within some
+//! edit-distance tier -- importing Meilisearch's own word-derivation
+//! cascade: try an exact match first, widen to one edit if that's empty,
+//! and only widen to two edits for longer words that are still unmatched --
+//! then intersects the DFA with the FST in a single traversal to stream out
+//! matching vocabulary terms, rather than scanning the whole vocabulary per
+//! query. The last word of a query is treated as still being typed, so it's
+//! matched with a prefix DFA instead of a whole-word one.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use fst::{Set, Streamer};
+use levenshtein_automata::{LevenshteinAutomatonBuilder, DFA};
+use once_cell::sync::OnceCell;
+
+use crate::embeddings::tokenize;
+
+/// Word length at or above which a still-unmatched token falls back to a
+/// two-edit tier, instead of every token eventually reaching it
+const TWO_EDIT_MIN_LEN: usize = 8;
+/// Cap on vocabulary terms returned per query word, so a short
+/// stopword-like token accepted by a wide DFA can't blow up the candidate
+/// set combinatorially
+const MAX_EXPANSIONS_PER_TERM: usize = 10;
+
+/// `LevenshteinAutomatonBuilder` construction does nontrivial work to
+/// build its distance tables, and all three tiers are reused across every
+/// query, so each is built once and cached
+fn exact_builder() -> &'static LevenshteinAutomatonBuilder {
+    static BUILDER: OnceCell<LevenshteinAutomatonBuilder> = OnceCell::new();
+    BUILDER.get_or_init(|| LevenshteinAutomatonBuilder::new(0, true))
+}
+
+fn one_edit_builder() -> &'static LevenshteinAutomatonBuilder {
+    static BUILDER: OnceCell<LevenshteinAutomatonBuilder> = OnceCell::new();
+    BUILDER.get_or_init(|| LevenshteinAutomatonBuilder::new(1, true))
+}
+
+fn two_edit_builder() -> &'static LevenshteinAutomatonBuilder {
+    static BUILDER: OnceCell<LevenshteinAutomatonBuilder> = OnceCell::new();
+    BUILDER.get_or_init(|| LevenshteinAutomatonBuilder::new(2, true))
+}
+
+/// FST-backed vocabulary supporting typo-tolerant term lookup
+pub struct FuzzyTermIndex {
+    vocabulary: Set<Vec<u8>>,
+}
+
+impl FuzzyTermIndex {
+    /// Build the vocabulary FST from every distinct term
+    /// [`crate::embeddings::tokenize`] extracts from `documents`. `fst::Set`
+    /// requires its input sorted and deduplicated, which a `BTreeSet`
+    /// gives for free.
+    pub fn build(documents: &[String]) -> Result<Self> {
+        let mut terms: BTreeSet<String> = BTreeSet::new();
+        for document in documents {
+            terms.extend(tokenize(document));
+        }
+        let vocabulary = Set::from_iter(terms).context("failed to build FST term vocabulary")?;
+        Ok(Self { vocabulary })
+    }
+
+    /// Vocabulary terms matching `word`, trying distance-0 then distance-1
+    /// and returning as soon as a tier finds anything; distance-2 is only
+    /// tried when `word` is at least [`TWO_EDIT_MIN_LEN`] chars and the
+    /// tighter tiers came up empty. `is_prefix` matches `word` as an
+    /// in-progress prefix rather than a complete word -- set it for the
+    /// last token of a query, which may still be mid-typing.
+    pub fn expand_term(&self, word: &str, is_prefix: bool) -> Vec<String> {
+        for builder in [exact_builder(), one_edit_builder()] {
+            let matches = self.search_dfa(&builder.build_dfa_for(word, is_prefix));
+            if !matches.is_empty() {
+                return matches;
+            }
+        }
+
+        if word.chars().count() >= TWO_EDIT_MIN_LEN {
+            return self.search_dfa(&two_edit_builder().build_dfa_for(word, is_prefix));
+        }
+
+        Vec::new()
+    }
+
+    /// Intersect `dfa` with the vocabulary FST, capping results at
+    /// [`MAX_EXPANSIONS_PER_TERM`]
+    fn search_dfa(&self, dfa: &DFA) -> Vec<String> {
+        let mut stream = self.vocabulary.search(dfa).into_stream();
+        let mut matches = Vec::new();
+        while matches.len() < MAX_EXPANSIONS_PER_TERM {
+            let Some(term) = stream.next() else { break };
+            matches.push(String::from_utf8_lossy(term).into_owned());
+        }
+        matches
+    }
+}
+
+/// Build either a whole-word or a prefix Levenshtein DFA from `builder`,
+/// depending on `is_prefix`
+trait BuildDfaFor {
+    fn build_dfa_for(&self, word: &str, is_prefix: bool) -> DFA;
+}
+
+impl BuildDfaFor for LevenshteinAutomatonBuilder {
+    fn build_dfa_for(&self, word: &str, is_prefix: bool) -> DFA {
+        if is_prefix {
+            self.build_prefix_dfa(word)
+        } else {
+            self.build_dfa(word)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> FuzzyTermIndex {
+        FuzzyTermIndex::build(&[
+            "the cat sat on the mat".to_string(),
+            "a categorical index of categories".to_string(),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn exact_match_is_preferred_over_wider_tiers() {
+        let matches = index().expand_term("cat", false);
+        assert_eq!(matches, vec!["cat".to_string()]);
+    }
+
+    #[test]
+    fn one_edit_tier_catches_a_typo_when_no_exact_match_exists() {
+        // "cet" is zero edits from nothing in the vocabulary but one edit
+        // from "cat".
+        let matches = index().expand_term("cet", false);
+        assert!(matches.contains(&"cat".to_string()));
+    }
+
+    #[test]
+    fn short_unmatched_token_never_reaches_the_two_edit_tier() {
+        // "cxy" is two edits from "cat" (and more than one from every
+        // term) but is too short to use that tier, so it should expand to
+        // nothing rather than over-matching.
+        let matches = index().expand_term("cxy", false);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn prefix_match_finds_longer_vocabulary_terms() {
+        let matches = index().expand_term("categor", true);
+        assert!(matches.contains(&"categorical".to_string()));
+        assert!(matches.contains(&"categories".to_string()));
+    }
+}
@@ -0,0 +1,293 @@
+//! Hybrid keyword + vector search
+//!
+//! [`VamanaIndex::search`] only ever ranks by embedding distance, which
+//! misses exact-term queries an embedding can blur away (product codes,
+//! names, rare vocabulary). [`Bm25Index`] ranks the same corpus lexically
+//! using the terms [`crate::embeddings::tokenize`] already extracts for the
+//! bag-of-words embedder, and [`hybrid_search`] fuses the two independent
+//! rankings with Reciprocal Rank Fusion (RRF) rather than trying to combine
+//! BM25 scores and vector distances directly, since the two live on
+//! incomparable scales. This mirrors Meilisearch's hybrid search, including
+//! its `semantic_ratio` knob for weighting the vector ranking against the
+//! lexical one.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use diskann_impl::VamanaIndex;
+use diskann_traits::distance::Distance;
+use diskann_traits::search::Search;
+
+use crate::embeddings::tokenize;
+use crate::fuzzy_term_index::FuzzyTermIndex;
+
+/// RRF's own `k` constant (Cormack et al.'s recommended default), not to be
+/// confused with [`hybrid_search`]'s top-`k` result count
+const RRF_K: f32 = 60.0;
+
+/// Each ranker contributes this many candidates per requested result before
+/// fusion, so rarely-matched documents still get a chance to show up in the
+/// other ranker's list
+const CANDIDATE_POOL_MULTIPLIER: usize = 4;
+/// Floor on the per-ranker candidate pool size, so small `k` still searches
+/// a reasonable neighborhood
+const MIN_CANDIDATE_POOL: usize = 50;
+
+/// Okapi BM25 term-frequency saturation parameter
+const BM25_K1: f32 = 1.5;
+/// Okapi BM25 document-length normalization parameter
+const BM25_B: f32 = 0.75;
+
+/// BM25 lexical ranker over a fixed corpus of documents
+pub struct Bm25Index {
+    /// Per-document term -> frequency, indexed by document id
+    doc_term_freqs: Vec<HashMap<String, u32>>,
+    /// Term count per document, indexed by document id
+    doc_lengths: Vec<usize>,
+    /// Mean of `doc_lengths`, used by the length-normalization term
+    avg_doc_length: f32,
+    /// Number of documents each term appears in at least once
+    doc_freq: HashMap<String, usize>,
+    num_docs: usize,
+    /// Typo-tolerant vocabulary used to expand each query term to every
+    /// indexed term within its edit-distance tier before scoring
+    fuzzy: FuzzyTermIndex,
+}
+
+impl Bm25Index {
+    /// Build a BM25 index over `documents`, tokenized with
+    /// [`crate::embeddings::tokenize`]; document id `i` is `documents[i]`'s
+    /// position
+    pub fn build(documents: &[String]) -> Result<Self> {
+        let mut doc_term_freqs = Vec::with_capacity(documents.len());
+        let mut doc_lengths = Vec::with_capacity(documents.len());
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+
+        for document in documents {
+            let terms = tokenize(document);
+            doc_lengths.push(terms.len());
+
+            let mut term_freqs: HashMap<String, u32> = HashMap::new();
+            for term in terms {
+                *term_freqs.entry(term).or_insert(0) += 1;
+            }
+            for term in term_freqs.keys() {
+                *doc_freq.entry(term.clone()).or_insert(0) += 1;
+            }
+            doc_term_freqs.push(term_freqs);
+        }
+
+        let num_docs = documents.len();
+        let avg_doc_length = if num_docs > 0 {
+            doc_lengths.iter().sum::<usize>() as f32 / num_docs as f32
+        } else {
+            0.0
+        };
+
+        let fuzzy = FuzzyTermIndex::build(documents)?;
+
+        Ok(Self {
+            doc_term_freqs,
+            doc_lengths,
+            avg_doc_length,
+            doc_freq,
+            num_docs,
+            fuzzy,
+        })
+    }
+
+    /// Inverse document frequency, using the standard BM25+0.5 smoothing so
+    /// a term present in every document still gets a small positive weight
+    fn idf(&self, term: &str) -> f32 {
+        let n = self.num_docs as f32;
+        let df = *self.doc_freq.get(term).unwrap_or(&0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    /// Rank every document against `query`'s tokenized terms, returning
+    /// `(doc_id, score)` pairs sorted by descending BM25 score and truncated
+    /// to `limit`. Each query term is first expanded via
+    /// [`FuzzyTermIndex::expand_term`] to every indexed term within its
+    /// edit-distance tier, so a misspelled query term still contributes
+    /// score through whichever vocabulary term it's closest to; the query's
+    /// last term is expanded as a prefix, since it may still be mid-typing.
+    /// Documents matching none of the query's terms (nor any of their
+    /// typo-tolerant expansions) are omitted rather than returned with a
+    /// zero score.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(u32, f32)> {
+        let query_terms = tokenize(query);
+        let mut scores = vec![0.0f32; self.num_docs];
+        let last_term_index = query_terms.len().saturating_sub(1);
+
+        for (term_index, term) in query_terms.iter().enumerate() {
+            // The last token of a query may still be mid-typing, so expand
+            // it as a prefix rather than requiring a complete word match.
+            let is_last_term = term_index == last_term_index;
+            for candidate in self.fuzzy.expand_term(term, is_last_term) {
+                let idf = self.idf(&candidate);
+                for (doc_id, term_freqs) in self.doc_term_freqs.iter().enumerate() {
+                    let Some(&tf) = term_freqs.get(&candidate) else { continue };
+                    let tf = tf as f32;
+                    let doc_length = self.doc_lengths[doc_id] as f32;
+                    let length_norm = 1.0 - BM25_B + BM25_B * doc_length / self.avg_doc_length.max(1.0);
+                    scores[doc_id] += idf * (tf * (BM25_K1 + 1.0)) / (tf + BM25_K1 * length_norm);
+                }
+            }
+        }
+
+        let mut ranked: Vec<(u32, f32)> = scores
+            .into_iter()
+            .enumerate()
+            .filter(|(_, score)| *score > 0.0)
+            .map(|(id, score)| (id as u32, score))
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+/// Per-result score breakdown returned by [`hybrid_search`]
+#[derive(Debug, Clone)]
+pub struct HybridSearchResult {
+    /// Document / vector id
+    pub id: u32,
+    /// 1-based position in [`Bm25Index::search`]'s ranking, if the document
+    /// matched at least one query term
+    pub lexical_rank: Option<usize>,
+    /// Distance [`VamanaIndex::search`] returned for this document, if it
+    /// was in the vector ranker's candidate pool
+    pub vector_distance: Option<f32>,
+    /// Reciprocal Rank Fusion of the two rankers' positions, weighted by
+    /// `semantic_ratio`
+    pub fused_score: f32,
+}
+
+/// Hybrid search: rank `query_text` with [`Bm25Index::search`] and
+/// `query_embedding` with [`VamanaIndex::search`] independently, then fuse
+/// the two rankings with Reciprocal Rank Fusion --
+/// `score(d) = semantic_ratio / (RRF_K + vector_rank(d)) + (1 - semantic_ratio) / (RRF_K + lexical_rank(d))`,
+/// where a document absent from one ranker's list contributes nothing from
+/// that term. `semantic_ratio` is clamped to `[0, 1]`: `0.0` is pure BM25,
+/// `1.0` is pure vector search, and `0.5` weights them evenly, matching
+/// Meilisearch's hybrid search knob of the same name. Results are sorted by
+/// descending `fused_score` and truncated to `k`.
+pub fn hybrid_search<D: Distance<f32> + Sync + Send>(
+    index: &VamanaIndex<D>,
+    bm25: &Bm25Index,
+    query_text: &str,
+    query_embedding: &[f32],
+    k: usize,
+    semantic_ratio: f32,
+) -> anyhow::Result<Vec<HybridSearchResult>> {
+    let semantic_ratio = semantic_ratio.clamp(0.0, 1.0);
+    let pool_size = (k * CANDIDATE_POOL_MULTIPLIER).max(MIN_CANDIDATE_POOL);
+
+    let lexical_results = bm25.search(query_text, pool_size);
+    let vector_results = index
+        .search(query_embedding, pool_size)
+        .map_err(|e| anyhow::anyhow!("Vector search failed: {:?}", e))?;
+
+    let lexical_ranks: HashMap<u32, usize> = lexical_results
+        .iter()
+        .enumerate()
+        .map(|(rank, (id, _))| (*id, rank + 1))
+        .collect();
+    let vector_ranks: HashMap<u32, (usize, f32)> = vector_results
+        .iter()
+        .enumerate()
+        .map(|(rank, result)| (result.id, (rank + 1, result.distance)))
+        .collect();
+
+    let mut candidate_ids: HashSet<u32> = HashSet::new();
+    candidate_ids.extend(lexical_ranks.keys());
+    candidate_ids.extend(vector_ranks.keys());
+
+    let mut fused: Vec<HybridSearchResult> = candidate_ids
+        .into_iter()
+        .map(|id| {
+            let lexical_rank = lexical_ranks.get(&id).copied();
+            let vector_info = vector_ranks.get(&id).copied();
+
+            let lexical_term = lexical_rank.map_or(0.0, |rank| 1.0 / (RRF_K + rank as f32));
+            let vector_term = vector_info.map_or(0.0, |(rank, _)| 1.0 / (RRF_K + rank as f32));
+            let fused_score = semantic_ratio * vector_term + (1.0 - semantic_ratio) * lexical_term;
+
+            HybridSearchResult {
+                id,
+                lexical_rank,
+                vector_distance: vector_info.map(|(_, distance)| distance),
+                fused_score,
+            }
+        })
+        .collect();
+
+    fused.sort_by(|a, b| b.fused_score.partial_cmp(&a.fused_score).unwrap_or(Ordering::Equal));
+    fused.truncate(k);
+
+    Ok(fused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diskann_impl::IndexBuilder;
+    use diskann_traits::distance::CosineDistance;
+
+    fn documents() -> Vec<String> {
+        vec![
+            "the cat sat on the mat".to_string(),
+            "dogs are loyal animals".to_string(),
+            "a feline rested on the rug".to_string(),
+        ]
+    }
+
+    #[test]
+    fn bm25_ranks_exact_term_matches_above_non_matches() {
+        let bm25 = Bm25Index::build(&documents()).unwrap();
+        let results = bm25.search("cat", 10);
+
+        assert_eq!(results[0].0, 0);
+        assert!(results.iter().all(|(id, _)| *id != 1));
+    }
+
+    fn build_tiny_index() -> VamanaIndex<CosineDistance> {
+        let vectors: Vec<(u32, Vec<f32>)> = vec![
+            (0, vec![1.0, 0.0]),
+            (1, vec![0.0, 1.0]),
+            (2, vec![0.9, 0.1]),
+        ];
+        IndexBuilder::new(CosineDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .alpha(1.2)
+            .seed(7)
+            .build(vectors)
+            .unwrap()
+    }
+
+    #[test]
+    fn hybrid_search_favors_lexical_match_when_semantic_ratio_is_zero() {
+        let index = build_tiny_index();
+        let bm25 = Bm25Index::build(&documents()).unwrap();
+
+        // Vector query points straight at doc 1, but with semantic_ratio=0
+        // only the lexical ranking (which favors doc 0, the only "cat" match)
+        // should count.
+        let results = hybrid_search(&index, &bm25, "cat", &[0.0, 1.0], 3, 0.0).unwrap();
+
+        assert_eq!(results[0].id, 0);
+        assert!(results[0].lexical_rank.is_some());
+    }
+
+    #[test]
+    fn hybrid_search_favors_vector_match_when_semantic_ratio_is_one() {
+        let index = build_tiny_index();
+        let bm25 = Bm25Index::build(&documents()).unwrap();
+
+        let results = hybrid_search(&index, &bm25, "cat", &[0.0, 1.0], 3, 1.0).unwrap();
+
+        assert_eq!(results[0].id, 1);
+    }
+}
@@ -7,9 +7,18 @@
 //! 4. Performing semantic search
 //! 5. Displaying results with timing
 
+mod chunking;
 mod dataset;
+mod embedding_provider;
+mod embedding_queue;
 mod embeddings;
+mod fuzzy_term_index;
+mod hybrid_search;
 mod model;
+mod candle_model;
+mod prompt;
+mod ranking;
+mod remote_model;
 mod demo;
 
 use clap::{Parser, Subcommand};
@@ -39,7 +48,10 @@ enum Commands {
     
     /// Just prepare the dataset
     Prepare,
-    
+
+    /// Chunk documents into token-budget windows and search over the chunks
+    Chunks,
+
     /// Clean cache directory
     Clean,
 }
@@ -68,33 +80,19 @@ fn main() -> Result<()> {
             
             println!("{}", "Generating embeddings...".bold());
             let embeddings_path = cli.cache_dir.join("embeddings.bin");
-            
-            if !embeddings_path.exists() {
-                // Try real model first
-                let embeddings = match model::RealEmbeddingModel::new(&cli.cache_dir) {
-                    Ok(mut model) => {
-                        println!("✓ Using real all-MiniLM-L6-v2 model");
-                        model.embed_texts(&dataset.sentences)?
-                    }
-                    Err(e) => {
-                        println!("⚠ Could not load ONNX model: {}", e);
-                        println!("  Falling back to simple embeddings...");
-                        let generator = embeddings::EmbeddingsGenerator::new(384);
-                        generator.embed_texts(&dataset.sentences)?
-                    }
-                };
-                
-                // Save embeddings
-                use diskann_io::write_vectors_f32;
-                let file = std::fs::File::create(&embeddings_path)?;
-                let mut writer = std::io::BufWriter::new(file);
-                write_vectors_f32(&mut writer, &embeddings)?;
-                
-                println!("✓ Embeddings saved to {:?}", embeddings_path);
-            } else {
+            let already_cached = embeddings_path.exists();
+
+            demo::load_or_generate_embeddings(&cli.cache_dir, &dataset.sentences, &embeddings_path)?;
+
+            if already_cached {
                 println!("✓ Embeddings already exist");
+            } else {
+                println!("✓ Embeddings saved to {:?}", embeddings_path);
             }
         }
+        Some(Commands::Chunks) => {
+            demo::run_chunking_demo(&cli.cache_dir)?;
+        }
         Some(Commands::Clean) => {
             println!("Cleaning cache directory...");
             if cli.cache_dir.exists() {
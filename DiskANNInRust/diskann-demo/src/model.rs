@@ -3,13 +3,22 @@
 //! Downloads and uses sentence-transformers/all-MiniLM-L6-v2 for actual embeddings
 
 use anyhow::{Result, Context};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::Mutex;
 use indicatif::{ProgressBar, ProgressStyle};
 use ort::session::{Session, builder::GraphOptimizationLevel};
 use ndarray::{Array2, Axis};
+use sha3::{Digest, Sha3_256};
 use tokenizers::{Tokenizer, PaddingParams, PaddingStrategy, TruncationParams};
 
+use crate::embedding_provider::EmbeddingProvider;
+
+/// [`EmbeddingProvider::model_id`] for [`RealEmbeddingModel`] -- the
+/// HuggingFace repo [`MODEL_FILES`] downloads from
+const MODEL_ID: &str = "sentence-transformers/all-MiniLM-L6-v2";
+
 /// Model files we need to download
 const MODEL_FILES: &[(&str, &str)] = &[
     ("model.onnx", "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/onnx/model.onnx"),
@@ -19,9 +28,15 @@ const MODEL_FILES: &[(&str, &str)] = &[
 
 /// Real embedding model using ONNX
 pub struct RealEmbeddingModel {
-    session: Session,
+    /// `ort::Session::run` takes `&mut Session`; wrapping it in a `Mutex`
+    /// gives [`EmbeddingProvider`] an `&self` API so a single provider can
+    /// back both the full demo flow and interactive mode.
+    session: Mutex<Session>,
     tokenizer: Tokenizer,
     dimension: usize,
+    /// Directory holding `<sha3-256(text)>.f32` cached embeddings, if enabled
+    /// via [`RealEmbeddingModel::with_cache`]
+    embedding_cache_dir: Option<PathBuf>,
 }
 
 impl RealEmbeddingModel {
@@ -70,12 +85,63 @@ impl RealEmbeddingModel {
         }));
         
         Ok(Self {
-            session,
+            session: Mutex::new(session),
             tokenizer,
             dimension: 384, // all-MiniLM-L6-v2 outputs 384D embeddings
+            embedding_cache_dir: None,
         })
     }
-    
+
+    /// Enable a disk-backed embedding cache under `<cache_dir>/emb_cache`
+    ///
+    /// Embeddings are keyed by the SHA3-256 hash of their source text, so
+    /// `embed_texts`/`embed_batch` skip inference entirely for texts already
+    /// seen (across calls and across processes).
+    pub fn with_cache(mut self, cache_dir: &Path) -> Result<Self> {
+        let dir = cache_dir.join("emb_cache");
+        fs::create_dir_all(&dir)?;
+        self.embedding_cache_dir = Some(dir);
+        Ok(self)
+    }
+
+    fn hash_text(text: &str) -> String {
+        let digest = Sha3_256::digest(text.as_bytes());
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Read a cached embedding, if present
+    fn read_cached(&self, hash: &str) -> Result<Option<Vec<f32>>> {
+        let Some(dir) = &self.embedding_cache_dir else {
+            return Ok(None);
+        };
+        let path = dir.join(format!("{hash}.f32"));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&path).with_context(|| format!("Failed to read cache entry {:?}", path))?;
+        let embedding = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Ok(Some(embedding))
+    }
+
+    /// Write a cache entry atomically: write to a `.tmp` sibling, then rename
+    /// over the final path so a crash mid-write never leaves a truncated file.
+    fn write_cached(&self, hash: &str, embedding: &[f32]) -> Result<()> {
+        let Some(dir) = &self.embedding_cache_dir else {
+            return Ok(());
+        };
+        let final_path = dir.join(format!("{hash}.f32"));
+        let tmp_path = dir.join(format!("{hash}.f32.tmp"));
+
+        let bytes: Vec<u8> = embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(&tmp_path, &bytes).with_context(|| format!("Failed to write cache entry {:?}", tmp_path))?;
+        fs::rename(&tmp_path, &final_path)
+            .with_context(|| format!("Failed to finalize cache entry {:?}", final_path))?;
+        Ok(())
+    }
+
     /// Download model files if they don't exist
     fn download_model_files(model_dir: &Path) -> Result<()> {
         for (filename, url) in MODEL_FILES {
@@ -134,7 +200,7 @@ impl RealEmbeddingModel {
     }
     
     /// Generate embeddings for multiple texts
-    pub fn embed_texts(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    pub fn embed_texts(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         if texts.is_empty() {
             return Ok(Vec::new());
         }
@@ -162,7 +228,49 @@ impl RealEmbeddingModel {
     }
     
     /// Generate embeddings for a batch of texts
-    fn embed_batch(&mut self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    ///
+    /// Checks the disk cache (if enabled) and deduplicates identical texts
+    /// before running inference, so the model only ever sees each unique,
+    /// uncached string once; results are then scattered back to every
+    /// position that requested them (including repeats) and newly-computed
+    /// embeddings are persisted to the cache.
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let hashes: Vec<String> = texts.iter().map(|t| Self::hash_text(t)).collect();
+
+        let mut results: Vec<Option<Vec<f32>>> = Vec::with_capacity(texts.len());
+        let mut pending_texts = Vec::new();
+        // Maps a unique pending text's hash to every position in `texts` that needs it.
+        let mut pending_positions: HashMap<&str, Vec<usize>> = HashMap::new();
+
+        for (i, hash) in hashes.iter().enumerate() {
+            if let Some(cached) = self.read_cached(hash)? {
+                results.push(Some(cached));
+                continue;
+            }
+            results.push(None);
+            let positions = pending_positions.entry(hash.as_str()).or_default();
+            if positions.is_empty() {
+                pending_texts.push(texts[i].clone());
+            }
+            positions.push(i);
+        }
+
+        if !pending_texts.is_empty() {
+            let computed = self.infer_batch(&pending_texts)?;
+            for (text, embedding) in pending_texts.iter().zip(computed.into_iter()) {
+                let hash = Self::hash_text(text);
+                self.write_cached(&hash, &embedding)?;
+                for &position in pending_positions.get(hash.as_str()).unwrap() {
+                    results[position] = Some(embedding.clone());
+                }
+            }
+        }
+
+        Ok(results.into_iter().map(|r| r.expect("every position is filled by cache hit or inference")).collect())
+    }
+
+    /// Run ONNX inference for a batch of (already-deduplicated) texts
+    fn infer_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         // Tokenize texts
         let encodings = self.tokenizer
             .encode_batch(texts.to_vec(), true)
@@ -210,7 +318,8 @@ impl RealEmbeddingModel {
             token_type_ids.as_slice().unwrap().to_vec()
         ))?;
         
-        let outputs = self.session.run(inputs![
+        let mut session = self.session.lock().expect("ONNX session mutex poisoned");
+        let outputs = session.run(inputs![
             input_ids_tensor,
             attention_mask_tensor,
             token_type_ids_tensor
@@ -268,7 +377,7 @@ impl RealEmbeddingModel {
     }
     
     /// Generate embedding for a single text
-    pub fn embed_text(&mut self, text: &str) -> Result<Vec<f32>> {
+    pub fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
         let embeddings = self.embed_batch(&[text.to_string()])?;
         Ok(embeddings.into_iter().next().unwrap())
     }
@@ -277,4 +386,18 @@ impl RealEmbeddingModel {
     pub fn dimension(&self) -> usize {
         self.dimension
     }
+}
+
+impl EmbeddingProvider for RealEmbeddingModel {
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embed_texts(texts)
+    }
+
+    fn dimension(&self) -> usize {
+        RealEmbeddingModel::dimension(self)
+    }
+
+    fn model_id(&self) -> &str {
+        MODEL_ID
+    }
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+//! Prompt templates for embedding structured documents
+//!
+//! [`crate::embedding_provider::EmbeddingProvider`] only knows how to embed
+//! plain strings, but callers indexing JSON records need control over which
+//! fields actually end up in that string and how they're arranged -- the
+//! same problem Meilisearch's `prompt` module solves for autoembedding.
+//! [`PromptTemplate`] parses a `{{field}}`-style template once, validates it
+//! against a document's fields up front, and renders it into the text that
+//! gets passed to `embed_text`. [`default_template`] covers the common case
+//! of "just embed every string field" without writing a template at all.
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+/// A parsed `{{field}}`-style template, e.g. `"{{title}} by {{author}}: {{body}}"`
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    template: String,
+    fields: Vec<String>,
+}
+
+impl PromptTemplate {
+    /// Parse `template`, extracting its `{{field}}` placeholders. Fails if
+    /// a `{{` is never closed, a `}}` appears with no matching `{{`, or a
+    /// placeholder's field name is empty (e.g. `{{}}`).
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut fields = Vec::new();
+        let mut rest = template;
+
+        loop {
+            let Some(open) = rest.find("{{") else {
+                if rest.contains("}}") {
+                    bail!("prompt template has a `}}}}` with no matching `{{{{`");
+                }
+                break;
+            };
+            if rest[..open].contains("}}") {
+                bail!("prompt template has a `}}}}` with no matching `{{{{`");
+            }
+            let after_open = &rest[open + 2..];
+            let Some(close) = after_open.find("}}") else {
+                bail!("prompt template has an unclosed `{{{{`");
+            };
+
+            let field = after_open[..close].trim();
+            if field.is_empty() {
+                bail!("prompt template has an empty placeholder `{{{{}}}}`");
+            }
+            fields.push(field.to_string());
+            rest = &after_open[close + 2..];
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+            fields,
+        })
+    }
+
+    /// Check that every field this template references is present in
+    /// `document`, returning all missing fields at once rather than
+    /// failing on the first one
+    pub fn validate_against(&self, document: &Map<String, Value>) -> Result<()> {
+        let missing: Vec<&str> = self
+            .fields
+            .iter()
+            .filter(|field| !document.contains_key(field.as_str()))
+            .map(|field| field.as_str())
+            .collect();
+
+        if !missing.is_empty() {
+            bail!(
+                "prompt template references field(s) missing from the document: {}",
+                missing.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Render this template against `document`, substituting each
+    /// `{{field}}` with that field's value (strings are inserted as-is;
+    /// other JSON types are rendered with their `Display`/JSON form).
+    /// Fails with the same missing-field errors as [`Self::validate_against`].
+    pub fn render(&self, document: &Map<String, Value>) -> Result<String> {
+        self.validate_against(document)?;
+
+        let mut rendered = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        for field in &self.fields {
+            // `parse` found exactly one closed placeholder per field, in order
+            let open = rest.find("{{").expect("parse found a `{{` for every field");
+            let close = open + 2 + rest[open + 2..].find("}}").expect("parse rejects an unclosed `{{`");
+            rendered.push_str(&rest[..open]);
+            let value = &document[field];
+            let text = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            rendered.push_str(&text);
+            rest = &rest[close + 2..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+
+    /// The field names this template references, in the order they first
+    /// appear
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+}
+
+/// Fallback for documents with no explicit template: concatenate every
+/// string-valued field (in the document's own key order, space-separated),
+/// skipping non-string fields entirely
+pub fn default_template(document: &Map<String, Value>) -> String {
+    document
+        .values()
+        .filter_map(|value| value.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,395 @@
+//! Pluggable multi-criteria reranking pipeline
+//!
+//! [`crate::hybrid_search::hybrid_search`] fuses exactly two rankers (BM25
+//! and vector distance) with a single RRF formula. Meilisearch instead
+//! cascades through an ordered list of ranking rules: each rule buckets the
+//! current candidate set into ranked groups, and only the documents tied
+//! within a bucket get handed to the next rule to break the tie -- a
+//! document's final position is wherever the first rule that could tell it
+//! apart from its neighbors placed it. [`rank`] runs that same cascade over
+//! a [`VamanaIndex`], reporting a [`ScoreDetail`] per result so callers can
+//! see which rule actually decided its position.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use diskann_impl::VamanaIndex;
+use diskann_traits::distance::Distance;
+use diskann_traits::search::Search;
+
+/// Read-only context a [`RankingRule`] needs to bucket or score candidates
+pub struct RankingQuery<'a> {
+    /// Raw query text, for lexical rules like [`ExactPhraseMatchRule`]
+    pub text: &'a str,
+    /// Query embedding, for [`VectorDistanceRule`]
+    pub embedding: &'a [f32],
+}
+
+/// One stage of the reranking cascade driven by [`rank`]
+pub trait RankingRule {
+    /// Name recorded in [`ScoreDetail::placed_by`] for documents this rule
+    /// places into a singleton bucket
+    fn name(&self) -> &'static str;
+
+    /// Split `universe` into the next ranked bucket plus everything not yet
+    /// placed. Called repeatedly by [`rank`] with the previous call's
+    /// remainder until it returns an empty bucket, which signals that this
+    /// rule can't distinguish any of what's left -- the remainder is then
+    /// handed whole to the next rule in the cascade.
+    fn next_bucket(&self, universe: &[u32], query: &RankingQuery) -> (Vec<u32>, Vec<u32>);
+
+    /// This rule's contribution to `id`'s [`ScoreDetail`], for documents it
+    /// ends up placing (directly or via [`Self::next_bucket`] never even
+    /// seeing them, if an earlier rule decided first)
+    fn contribution(&self, id: u32, query: &RankingQuery) -> f32;
+}
+
+/// First rule of the cascade: ranks `universe` by ascending distance from
+/// `query.embedding`, using [`VamanaIndex::search`] rather than a linear
+/// scan. Distances within `bucket_width` of each other are grouped into the
+/// same bucket instead of broken by float comparison, so later rules get a
+/// real chance to distinguish near-tied vector matches.
+pub struct VectorDistanceRule<'a, D> {
+    index: &'a VamanaIndex<D>,
+    bucket_width: f32,
+    /// Distances looked up while bucketing, reused by [`Self::contribution`]
+    /// instead of re-running the search for every placed document
+    distances: RefCell<HashMap<u32, f32>>,
+}
+
+impl<'a, D: Distance<f32> + Sync + Send> VectorDistanceRule<'a, D> {
+    /// A rule over `index`, treating candidates within `bucket_width` of
+    /// each other's distance as tied
+    pub fn new(index: &'a VamanaIndex<D>, bucket_width: f32) -> Self {
+        Self {
+            index,
+            bucket_width,
+            distances: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<D: Distance<f32> + Sync + Send> RankingRule for VectorDistanceRule<'_, D> {
+    fn name(&self) -> &'static str {
+        "vector_distance"
+    }
+
+    fn next_bucket(&self, universe: &[u32], query: &RankingQuery) -> (Vec<u32>, Vec<u32>) {
+        if universe.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let results = self.index.search(query.embedding, universe.len()).unwrap_or_default();
+        let mut found: HashMap<u32, f32> = HashMap::new();
+        for result in &results {
+            found.insert(result.id, result.distance);
+        }
+
+        let mut sorted: Vec<(u32, f32)> = universe
+            .iter()
+            .map(|&id| (id, *found.get(&id).unwrap_or(&f32::INFINITY)))
+            .collect();
+        sorted.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        {
+            let mut distances = self.distances.borrow_mut();
+            for &(id, distance) in &sorted {
+                distances.insert(id, distance);
+            }
+        }
+
+        let first_distance = sorted[0].1;
+        let bucket_len = sorted
+            .iter()
+            .take_while(|&&(_, distance)| (distance - first_distance).abs() <= self.bucket_width)
+            .count();
+        let bucket: Vec<u32> = sorted[..bucket_len].iter().map(|&(id, _)| id).collect();
+        let remaining: Vec<u32> = sorted[bucket_len..].iter().map(|&(id, _)| id).collect();
+        (bucket, remaining)
+    }
+
+    fn contribution(&self, id: u32, _query: &RankingQuery) -> f32 {
+        self.distances.borrow().get(&id).copied().unwrap_or(f32::INFINITY)
+    }
+}
+
+/// Boosts documents containing `query.text` verbatim (case-insensitively)
+/// into a single bucket ahead of everything else, the way an exact phrase
+/// match beats a bag-of-words one in most lexical rankers
+pub struct ExactPhraseMatchRule<'a> {
+    sentences: &'a [String],
+}
+
+impl<'a> ExactPhraseMatchRule<'a> {
+    /// A rule testing each candidate's sentence, looked up by vector id into
+    /// `sentences` (the same id convention [`crate::demo::build_index`] uses)
+    pub fn new(sentences: &'a [String]) -> Self {
+        Self { sentences }
+    }
+
+    fn matches(&self, id: u32, needle: &str) -> bool {
+        self.sentences
+            .get(id as usize)
+            .map(|sentence| sentence.to_lowercase().contains(needle))
+            .unwrap_or(false)
+    }
+}
+
+impl RankingRule for ExactPhraseMatchRule<'_> {
+    fn name(&self) -> &'static str {
+        "exact_phrase_match"
+    }
+
+    fn next_bucket(&self, universe: &[u32], query: &RankingQuery) -> (Vec<u32>, Vec<u32>) {
+        let needle = query.text.to_lowercase();
+        let (matches, rest): (Vec<u32>, Vec<u32>) =
+            universe.iter().copied().partition(|&id| self.matches(id, &needle));
+
+        if matches.is_empty() {
+            // Nothing left in this universe matches verbatim; returning an
+            // empty bucket tells `rank` this rule is done, so `rest` (which
+            // here is the whole input) is handed to the next rule whole.
+            (Vec::new(), Vec::new())
+        } else {
+            (matches, rest)
+        }
+    }
+
+    fn contribution(&self, id: u32, query: &RankingQuery) -> f32 {
+        let needle = query.text.to_lowercase();
+        if self.matches(id, &needle) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Last-resort rule: prefers shorter sentences, measured in whitespace-split
+/// words, on the theory that a shorter match says the same thing more
+/// directly. Always makes progress, so it's safe to put last in a cascade.
+pub struct LengthPenaltyRule<'a> {
+    sentences: &'a [String],
+}
+
+impl<'a> LengthPenaltyRule<'a> {
+    /// A rule measuring each candidate's sentence, looked up the same way as
+    /// [`ExactPhraseMatchRule`]
+    pub fn new(sentences: &'a [String]) -> Self {
+        Self { sentences }
+    }
+
+    fn word_count(&self, id: u32) -> usize {
+        self.sentences
+            .get(id as usize)
+            .map(|sentence| sentence.split_whitespace().count())
+            .unwrap_or(usize::MAX)
+    }
+}
+
+impl RankingRule for LengthPenaltyRule<'_> {
+    fn name(&self) -> &'static str {
+        "length_penalty"
+    }
+
+    fn next_bucket(&self, universe: &[u32], _query: &RankingQuery) -> (Vec<u32>, Vec<u32>) {
+        if universe.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let mut by_length: Vec<(u32, usize)> =
+            universe.iter().map(|&id| (id, self.word_count(id))).collect();
+        by_length.sort_by_key(|&(_, len)| len);
+
+        let shortest = by_length[0].1;
+        let bucket_len = by_length.iter().take_while(|&&(_, len)| len == shortest).count();
+        let bucket: Vec<u32> = by_length[..bucket_len].iter().map(|&(id, _)| id).collect();
+        let remaining: Vec<u32> = by_length[bucket_len..].iter().map(|&(id, _)| id).collect();
+        (bucket, remaining)
+    }
+
+    fn contribution(&self, id: u32, _query: &RankingQuery) -> f32 {
+        self.word_count(id) as f32
+    }
+}
+
+/// Which rule placed a [`rank`] result where it ended up, and that rule's
+/// own score for it -- e.g. the vector distance, or `1.0`/`0.0` for whether
+/// an exact phrase rule matched. `"unranked"` marks a tie no rule in the
+/// cascade could break.
+#[derive(Debug, Clone)]
+pub struct ScoreDetail {
+    /// Vector / document id
+    pub id: u32,
+    /// Name of the rule that placed this document, or `"unranked"`
+    pub placed_by: String,
+    /// That rule's own score for this document
+    pub contribution: f32,
+}
+
+/// Run `rules` as an ordered cascade over `universe`, truncating to `limit`.
+/// Each rule buckets its input via repeated [`RankingRule::next_bucket`]
+/// calls; a bucket of more than one id is tied as far as that rule is
+/// concerned and is handed whole to the next rule, while a singleton bucket
+/// is a final placement, recorded as that rule's [`ScoreDetail`].
+pub fn rank(
+    rules: &[&dyn RankingRule],
+    universe: Vec<u32>,
+    query: &RankingQuery,
+    limit: usize,
+) -> Vec<ScoreDetail> {
+    let mut results = rank_cascade(rules, universe, query);
+    results.truncate(limit);
+    results
+}
+
+fn rank_cascade(rules: &[&dyn RankingRule], universe: Vec<u32>, query: &RankingQuery) -> Vec<ScoreDetail> {
+    let Some((rule, rest_rules)) = rules.split_first() else {
+        return universe
+            .into_iter()
+            .map(|id| ScoreDetail { id, placed_by: "unranked".to_string(), contribution: 0.0 })
+            .collect();
+    };
+
+    if universe.len() <= 1 {
+        // Drive the rule's own bucketing logic even though its grouping is
+        // moot for <=1 candidates: stateful rules (e.g. `VectorDistanceRule`,
+        // which only populates its distance cache inside `next_bucket`)
+        // otherwise never get the chance, and `contribution` below would
+        // report their "not found" default instead of a real score.
+        let _ = rule.next_bucket(&universe, query);
+        return universe
+            .into_iter()
+            .map(|id| ScoreDetail {
+                id,
+                placed_by: rule.name().to_string(),
+                contribution: rule.contribution(id, query),
+            })
+            .collect();
+    }
+
+    // `resolved` is false only for the final bucket when `next_bucket`
+    // returned empty, meaning this rule gave up on what's left rather than
+    // actually placing it.
+    let mut buckets: Vec<(Vec<u32>, bool)> = Vec::new();
+    let mut remaining = universe;
+    while !remaining.is_empty() {
+        let (bucket, rest) = rule.next_bucket(&remaining, query);
+        if bucket.is_empty() {
+            buckets.push((remaining, false));
+            break;
+        }
+        buckets.push((bucket, true));
+        remaining = rest;
+    }
+
+    let mut results = Vec::new();
+    for (bucket, resolved) in buckets {
+        if resolved && bucket.len() == 1 {
+            let id = bucket[0];
+            results.push(ScoreDetail {
+                id,
+                placed_by: rule.name().to_string(),
+                contribution: rule.contribution(id, query),
+            });
+        } else {
+            results.extend(rank_cascade(rest_rules, bucket, query));
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diskann_impl::IndexBuilder;
+    use diskann_traits::distance::CosineDistance;
+
+    fn build_tiny_index() -> VamanaIndex<CosineDistance> {
+        let vectors: Vec<(u32, Vec<f32>)> = vec![
+            (0, vec![1.0, 0.0]),
+            (1, vec![1.0, 0.0001]),
+            (2, vec![0.0, 1.0]),
+        ];
+        IndexBuilder::new(CosineDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .alpha(1.2)
+            .seed(7)
+            .build(vectors)
+            .unwrap()
+    }
+
+    #[test]
+    fn vector_distance_rule_alone_ranks_by_ascending_distance() {
+        let index = build_tiny_index();
+        // Docs 0 and 1 point the same way to within f32 precision, so the
+        // query lies along doc 2's axis to give all three distinct cosine
+        // distances (0, ~0.9999 and 1).
+        let rule = VectorDistanceRule::new(&index, 0.00001);
+        let query = RankingQuery { text: "", embedding: &[0.0, 1.0] };
+
+        let results = rank(&[&rule], vec![0, 1, 2], &query, 10);
+
+        assert_eq!(results[0].id, 2);
+        assert_eq!(results.last().unwrap().id, 0);
+        assert!(results.iter().all(|r| r.placed_by == "vector_distance"));
+    }
+
+    #[test]
+    fn exact_phrase_match_breaks_a_vector_distance_tie() {
+        let index = build_tiny_index();
+        let sentences = vec![
+            "a cat sat on a mat".to_string(),
+            "the exact phrase we want".to_string(),
+            "an unrelated sentence".to_string(),
+        ];
+        let vector_rule = VectorDistanceRule::new(&index, 1.0); // wide enough to tie 0 and 1
+        let phrase_rule = ExactPhraseMatchRule::new(&sentences);
+        let query = RankingQuery { text: "exact phrase", embedding: &[1.0, 0.0] };
+
+        let results = rank(&[&vector_rule, &phrase_rule], vec![0, 1, 2], &query, 10);
+
+        assert_eq!(results[0].id, 1);
+        assert_eq!(results[0].placed_by, "exact_phrase_match");
+    }
+
+    #[test]
+    fn length_penalty_breaks_a_remaining_tie_when_nothing_else_can() {
+        let index = build_tiny_index();
+        let sentences = vec![
+            "short".to_string(),
+            "a much longer sentence than the other one".to_string(),
+            "an unrelated sentence".to_string(),
+        ];
+        let vector_rule = VectorDistanceRule::new(&index, 1.0);
+        let phrase_rule = ExactPhraseMatchRule::new(&sentences);
+        let length_rule = LengthPenaltyRule::new(&sentences);
+        let query = RankingQuery { text: "nothing matches this", embedding: &[1.0, 0.0] };
+
+        let results = rank(&[&vector_rule, &phrase_rule, &length_rule], vec![0, 1], &query, 10);
+
+        assert_eq!(results[0].id, 0);
+        assert_eq!(results[0].placed_by, "length_penalty");
+    }
+
+    #[test]
+    fn vector_distance_rule_reports_real_distance_for_singleton_universe() {
+        let index = build_tiny_index();
+        let rule = VectorDistanceRule::new(&index, 0.0001);
+        let query = RankingQuery { text: "", embedding: &[1.0, 0.0] };
+
+        // A single-candidate top-level universe hits `rank_cascade`'s
+        // `universe.len() <= 1` early return directly, without ever going
+        // through the bucketing loop below it.
+        let results = rank(&[&rule], vec![0], &query, 10);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, 0);
+        assert!(
+            results[0].contribution.is_finite(),
+            "expected a real distance, got {}",
+            results[0].contribution
+        );
+    }
+}
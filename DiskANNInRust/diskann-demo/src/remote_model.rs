@@ -0,0 +1,148 @@
+//! Remote HTTP embedding backend
+//!
+//! Talks to an OpenAI-compatible `/embeddings` endpoint (the shape both
+//! OpenAI and locally-hosted servers like Ollama's OpenAI-compatible route
+//! expose), so a single [`RemoteEmbeddingProvider`] covers any of them by
+//! just swapping `base_url`. Uses the same blocking `ureq` client
+//! [`crate::model::RealEmbeddingModel`] already uses for file downloads,
+//! rather than pulling in an async HTTP stack this binary doesn't otherwise
+//! need.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::embedding_provider::EmbeddingProvider;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Attempts for a single batch request before giving up, including the
+/// first try
+const MAX_ATTEMPTS: u32 = 5;
+/// Backoff before the first retry of a rate-limited request, doubled after
+/// each further attempt unless the server names its own delay
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingsResponseEntry>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponseEntry {
+    embedding: Vec<f32>,
+}
+
+/// The delay a `429` response asks for via its `Retry-After` header (given
+/// in whole seconds, per the HTTP spec), if present and parseable
+fn retry_after(response: &ureq::Response) -> Option<Duration> {
+    response.header("Retry-After")?.parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Embedding backend delegating to a remote OpenAI-compatible `/embeddings`
+/// endpoint instead of running inference locally
+pub struct RemoteEmbeddingProvider {
+    base_url: String,
+    api_key: Option<String>,
+    model_id: String,
+    dimension: usize,
+}
+
+impl RemoteEmbeddingProvider {
+    /// Point at `base_url` (e.g. `https://api.openai.com/v1` or a local
+    /// Ollama's `http://localhost:11434/v1`), requesting embeddings from
+    /// `model_id` and expecting `dimension`-length vectors back. `api_key`
+    /// is sent as a `Bearer` token when present; local servers that don't
+    /// check it can pass `None`.
+    pub fn new(base_url: impl Into<String>, model_id: impl Into<String>, dimension: usize, api_key: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key,
+            model_id: model_id.into(),
+            dimension,
+        }
+    }
+
+    /// Generate embeddings for a batch of texts in a single request,
+    /// retrying with exponential backoff (honoring a `Retry-After` header
+    /// when the server sends one) if the endpoint responds `429 Too Many
+    /// Requests`
+    pub fn embed_texts(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let url = format!("{}/embeddings", self.base_url.trim_end_matches('/'));
+        let body = EmbeddingsRequest {
+            model: &self.model_id,
+            input: texts,
+        };
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request = ureq::post(&url).timeout(REQUEST_TIMEOUT);
+            if let Some(api_key) = &self.api_key {
+                request = request.set("Authorization", &format!("Bearer {api_key}"));
+            }
+
+            match request.send_json(&body) {
+                Ok(response) => {
+                    let response: EmbeddingsResponse = response
+                        .into_json()
+                        .context("Failed to parse embeddings response")?;
+
+                    if response.data.len() != texts.len() {
+                        anyhow::bail!(
+                            "Embeddings endpoint returned {} vectors for {} inputs",
+                            response.data.len(),
+                            texts.len()
+                        );
+                    }
+
+                    return Ok(response.data.into_iter().map(|entry| entry.embedding).collect());
+                }
+                Err(ureq::Error::Status(429, response)) if attempt < MAX_ATTEMPTS => {
+                    std::thread::sleep(retry_after(&response).unwrap_or(backoff));
+                    backoff *= 2;
+                }
+                Err(e) => return Err(e).context("Embeddings request failed"),
+            }
+        }
+
+        unreachable!("the loop above always returns on its last attempt")
+    }
+
+    /// Generate an embedding for a single text
+    pub fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed_texts(&[text.to_string()])?
+            .into_iter()
+            .next()
+            .expect("embed_texts returns one vector per input text"))
+    }
+
+    /// Get embedding dimension the endpoint is configured to produce
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+}
+
+impl EmbeddingProvider for RemoteEmbeddingProvider {
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        RemoteEmbeddingProvider::embed_texts(self, texts)
+    }
+
+    fn dimension(&self) -> usize {
+        RemoteEmbeddingProvider::dimension(self)
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+}
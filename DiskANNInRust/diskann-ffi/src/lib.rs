@@ -7,12 +7,21 @@
 #![warn(missing_docs)]
 
 use std::ffi::CStr;
-use std::os::raw::{c_char, c_float, c_uint, c_void};
+use std::io::Write;
+use std::os::raw::{c_char, c_float, c_uchar, c_uint, c_void};
+use std::path::Path;
 use std::ptr;
 use std::slice;
 
-use diskann_impl::{VamanaIndex, IndexBuilder, VamanaConfig};
-use diskann_traits::{distance::EuclideanDistance, index::Index, search::{Search, SearchBuffer}};
+use diskann_core::{math::normalize_in_place, vectors::VectorId, DiskAnnResult};
+use diskann_impl::{
+    peek_metric_tag, recall_at_k_from_ids, IndexBuilder, MetricTag, VamanaConfig, VamanaIndex,
+};
+use diskann_traits::{
+    distance::{CosineDistance, EuclideanDistance, InnerProductDistance},
+    index::Index,
+    search::{Search, SearchBuffer, SearchResult},
+};
 
 /// Opaque handle to a DiskANN index
 pub type DiskAnnIndexHandle = *mut c_void;
@@ -45,35 +54,284 @@ pub enum DiskAnnError {
     SearchError = 5,
 }
 
-/// Create a new DiskANN index with default configuration
+/// Distance metric selector for the C interface
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskAnnMetric {
+    /// Euclidean (L2) distance
+    L2 = 0,
+    /// Cosine distance (vectors are normalized on insert and query)
+    Cosine = 1,
+    /// Inner product ("MIPS") distance
+    InnerProduct = 2,
+}
+
+/// Element type of the vectors an index was built with
+///
+/// Every handle is tagged with the element type it was created for, so
+/// entry points for the wrong dtype can be rejected with `InvalidArgument`
+/// rather than misinterpreting the raw bytes behind the handle.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskAnnElementType {
+    /// 32-bit floating point vectors
+    F32 = 0,
+    /// Signed 8-bit integer vectors
+    I8 = 1,
+    /// Unsigned 8-bit integer vectors
+    U8 = 2,
+}
+
+impl DiskAnnElementType {
+    /// Decode the byte [`diskann_save_index`] appends after the on-disk graph, mirroring
+    /// this enum's own `#[repr(C)]` discriminants
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(DiskAnnElementType::F32),
+            1 => Some(DiskAnnElementType::I8),
+            2 => Some(DiskAnnElementType::U8),
+            _ => None,
+        }
+    }
+}
+
+/// Metric-tagged index storage
+///
+/// `VamanaIndex<D>` is generic over its distance function, so a type-erased
+/// FFI handle needs one variant per metric rather than a single concrete type.
+enum MetricIndex {
+    /// Euclidean (L2) distance index
+    L2(VamanaIndex<EuclideanDistance>),
+    /// Cosine distance index
+    Cosine(VamanaIndex<CosineDistance>),
+    /// Inner product distance index
+    InnerProduct(VamanaIndex<InnerProductDistance>),
+}
+
+/// Index handle tagging its metric and element type
+struct TaggedIndex {
+    element_type: DiskAnnElementType,
+    index: MetricIndex,
+}
+
+impl TaggedIndex {
+    fn new(metric: DiskAnnMetric, element_type: DiskAnnElementType, config: VamanaConfig) -> Self {
+        let index = match metric {
+            DiskAnnMetric::L2 => MetricIndex::L2(VamanaIndex::new(EuclideanDistance, config)),
+            DiskAnnMetric::Cosine => MetricIndex::Cosine(VamanaIndex::new(CosineDistance, config)),
+            DiskAnnMetric::InnerProduct => {
+                MetricIndex::InnerProduct(VamanaIndex::new(InnerProductDistance, config))
+            }
+        };
+        Self { element_type, index }
+    }
+
+    fn from_vectors(
+        metric: DiskAnnMetric,
+        element_type: DiskAnnElementType,
+        config: VamanaConfig,
+        mut vector_data: Vec<(VectorId, Vec<f32>)>,
+    ) -> DiskAnnResult<Self> {
+        if metric == DiskAnnMetric::Cosine {
+            for (_, vector) in vector_data.iter_mut() {
+                normalize_in_place(vector);
+            }
+        }
+
+        let index = match metric {
+            DiskAnnMetric::L2 => MetricIndex::L2(
+                IndexBuilder::new(EuclideanDistance)
+                    .max_degree(config.max_degree)
+                    .search_list_size(config.search_list_size)
+                    .alpha(config.alpha)
+                    .seed(config.seed)
+                    .build(vector_data)?,
+            ),
+            DiskAnnMetric::Cosine => MetricIndex::Cosine(
+                IndexBuilder::new(CosineDistance)
+                    .max_degree(config.max_degree)
+                    .search_list_size(config.search_list_size)
+                    .alpha(config.alpha)
+                    .seed(config.seed)
+                    .build(vector_data)?,
+            ),
+            DiskAnnMetric::InnerProduct => MetricIndex::InnerProduct(
+                IndexBuilder::new(InnerProductDistance)
+                    .max_degree(config.max_degree)
+                    .search_list_size(config.search_list_size)
+                    .alpha(config.alpha)
+                    .seed(config.seed)
+                    .build(vector_data)?,
+            ),
+        };
+
+        Ok(Self { element_type, index })
+    }
+
+    fn metric(&self) -> DiskAnnMetric {
+        match &self.index {
+            MetricIndex::L2(_) => DiskAnnMetric::L2,
+            MetricIndex::Cosine(_) => DiskAnnMetric::Cosine,
+            MetricIndex::InnerProduct(_) => DiskAnnMetric::InnerProduct,
+        }
+    }
+
+    fn add(&mut self, id: VectorId, mut vector: Vec<f32>) -> DiskAnnResult<()> {
+        if self.metric() == DiskAnnMetric::Cosine {
+            normalize_in_place(&mut vector);
+        }
+        match &mut self.index {
+            MetricIndex::L2(index) => index.add(id, vector),
+            MetricIndex::Cosine(index) => index.add(id, vector),
+            MetricIndex::InnerProduct(index) => index.add(id, vector),
+        }
+    }
+
+    fn delete(&mut self, id: VectorId) -> DiskAnnResult<()> {
+        match &mut self.index {
+            MetricIndex::L2(index) => index.delete(id),
+            MetricIndex::Cosine(index) => index.delete(id),
+            MetricIndex::InnerProduct(index) => index.delete(id),
+        }
+    }
+
+    fn consolidate(&mut self) -> DiskAnnResult<()> {
+        match &mut self.index {
+            MetricIndex::L2(index) => index.consolidate(),
+            MetricIndex::Cosine(index) => index.consolidate(),
+            MetricIndex::InnerProduct(index) => index.consolidate(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match &self.index {
+            MetricIndex::L2(index) => index.size(),
+            MetricIndex::Cosine(index) => index.size(),
+            MetricIndex::InnerProduct(index) => index.size(),
+        }
+    }
+
+    fn search(&self, query: &[f32], k: usize, beam_width: usize) -> DiskAnnResult<Vec<SearchResult>> {
+        let mut query = query.to_vec();
+        if self.metric() == DiskAnnMetric::Cosine {
+            normalize_in_place(&mut query);
+        }
+
+        match &self.index {
+            MetricIndex::L2(index) => {
+                if beam_width > 0 {
+                    index.search_with_beam(&query, k, beam_width)
+                } else {
+                    index.search(&query, k)
+                }
+            }
+            MetricIndex::Cosine(index) => {
+                if beam_width > 0 {
+                    index.search_with_beam(&query, k, beam_width)
+                } else {
+                    index.search(&query, k)
+                }
+            }
+            MetricIndex::InnerProduct(index) => {
+                if beam_width > 0 {
+                    index.search_with_beam(&query, k, beam_width)
+                } else {
+                    index.search(&query, k)
+                }
+            }
+        }
+    }
+
+    fn search_with_buffer(
+        &self,
+        query: &[f32],
+        k: usize,
+        beam_width: usize,
+        buffer: &mut SearchBuffer,
+    ) -> DiskAnnResult<Vec<SearchResult>> {
+        let mut query = query.to_vec();
+        if self.metric() == DiskAnnMetric::Cosine {
+            normalize_in_place(&mut query);
+        }
+
+        match &self.index {
+            MetricIndex::L2(index) => index.search_with_buffer(&query, k, beam_width, buffer),
+            MetricIndex::Cosine(index) => index.search_with_buffer(&query, k, beam_width, buffer),
+            MetricIndex::InnerProduct(index) => index.search_with_buffer(&query, k, beam_width, buffer),
+        }
+    }
+
+    #[cfg(feature = "unsafe_opt")]
+    fn search_batch(
+        &self,
+        queries: &[Vec<f32>],
+        k: usize,
+        beam_width: usize,
+        max_threads: Option<usize>,
+    ) -> DiskAnnResult<Vec<Vec<SearchResult>>> {
+        let mut queries = queries.to_vec();
+        if self.metric() == DiskAnnMetric::Cosine {
+            for query in queries.iter_mut() {
+                normalize_in_place(query);
+            }
+        }
+
+        match &self.index {
+            MetricIndex::L2(index) => index.search_batch(&queries, k, beam_width, max_threads),
+            MetricIndex::Cosine(index) => index.search_batch(&queries, k, beam_width, max_threads),
+            MetricIndex::InnerProduct(index) => index.search_batch(&queries, k, beam_width, max_threads),
+        }
+    }
+}
+
+/// Copy search results into the caller-provided C buffer
+fn write_results(
+    search_results: &[SearchResult],
+    k: usize,
+    results: *mut SearchResultC,
+    results_len: *mut c_uint,
+) {
+    let num_results = search_results.len().min(k);
+    unsafe {
+        *results_len = num_results as c_uint;
+        for (i, result) in search_results.iter().take(num_results).enumerate() {
+            (*results.add(i)).id = result.id;
+            (*results.add(i)).distance = result.distance;
+        }
+    }
+}
+
+/// Create a new DiskANN index with default configuration (L2, f32)
 #[no_mangle]
 pub extern "C" fn diskann_create_index() -> DiskAnnIndexHandle {
-    let distance_fn = EuclideanDistance;
-    let config = VamanaConfig::default();
-    let index = Box::new(VamanaIndex::new(distance_fn, config));
+    let index = Box::new(TaggedIndex::new(
+        DiskAnnMetric::L2,
+        DiskAnnElementType::F32,
+        VamanaConfig::default(),
+    ));
     Box::into_raw(index) as DiskAnnIndexHandle
 }
 
-/// Create a new DiskANN index with custom configuration
+/// Create a new DiskANN index with custom configuration and metric (f32)
 #[no_mangle]
 pub extern "C" fn diskann_create_index_with_config(
     max_degree: c_uint,
     search_list_size: c_uint,
     alpha: c_float,
     seed: c_uint,
+    metric: DiskAnnMetric,
 ) -> DiskAnnIndexHandle {
-    let distance_fn = EuclideanDistance;
     let config = VamanaConfig {
         max_degree: max_degree as usize,
         search_list_size: search_list_size as usize,
         alpha,
         seed: seed as u64,
     };
-    let index = Box::new(VamanaIndex::new(distance_fn, config));
+    let index = Box::new(TaggedIndex::new(metric, DiskAnnElementType::F32, config));
     Box::into_raw(index) as DiskAnnIndexHandle
 }
 
-/// Build an index from vectors
+/// Build an index from f32 vectors
 #[no_mangle]
 pub extern "C" fn diskann_build_index(
     vectors: *const c_float,
@@ -83,12 +341,12 @@ pub extern "C" fn diskann_build_index(
     search_list_size: c_uint,
     alpha: c_float,
     seed: c_uint,
+    metric: DiskAnnMetric,
 ) -> DiskAnnIndexHandle {
     if vectors.is_null() || num_vectors == 0 || vector_dim == 0 {
         return ptr::null_mut();
     }
 
-    let distance_fn = EuclideanDistance;
     let config = VamanaConfig {
         max_degree: max_degree as usize,
         search_list_size: search_list_size as usize,
@@ -96,31 +354,106 @@ pub extern "C" fn diskann_build_index(
         seed: seed as u64,
     };
 
-    // Convert C array to Rust vectors
-    let mut vector_data = Vec::new();
-    unsafe {
+    let vector_data = unsafe {
         let slice = slice::from_raw_parts(vectors, (num_vectors * vector_dim) as usize);
-        for i in 0..num_vectors {
-            let start = (i * vector_dim) as usize;
-            let end = start + vector_dim as usize;
-            let vector = slice[start..end].to_vec();
-            vector_data.push((i, vector));
-        }
+        (0..num_vectors)
+            .map(|i| {
+                let start = (i * vector_dim) as usize;
+                let end = start + vector_dim as usize;
+                (i, slice[start..end].to_vec())
+            })
+            .collect()
+    };
+
+    match TaggedIndex::from_vectors(metric, DiskAnnElementType::F32, config, vector_data) {
+        Ok(index) => Box::into_raw(Box::new(index)) as DiskAnnIndexHandle,
+        Err(_) => ptr::null_mut(),
     }
+}
 
-    match IndexBuilder::new(distance_fn)
-        .max_degree(config.max_degree)
-        .search_list_size(config.search_list_size)
-        .alpha(config.alpha)
-        .seed(config.seed)
-        .build(vector_data) 
-    {
+/// Build an index from int8 vectors
+#[no_mangle]
+pub extern "C" fn diskann_build_index_i8(
+    vectors: *const i8,
+    num_vectors: c_uint,
+    vector_dim: c_uint,
+    max_degree: c_uint,
+    search_list_size: c_uint,
+    alpha: c_float,
+    seed: c_uint,
+    metric: DiskAnnMetric,
+) -> DiskAnnIndexHandle {
+    if vectors.is_null() || num_vectors == 0 || vector_dim == 0 {
+        return ptr::null_mut();
+    }
+
+    let config = VamanaConfig {
+        max_degree: max_degree as usize,
+        search_list_size: search_list_size as usize,
+        alpha,
+        seed: seed as u64,
+    };
+
+    let vector_data = unsafe {
+        let slice = slice::from_raw_parts(vectors, (num_vectors * vector_dim) as usize);
+        (0..num_vectors)
+            .map(|i| {
+                let start = (i * vector_dim) as usize;
+                let end = start + vector_dim as usize;
+                let vector = slice[start..end].iter().map(|&v| v as f32).collect();
+                (i, vector)
+            })
+            .collect()
+    };
+
+    match TaggedIndex::from_vectors(metric, DiskAnnElementType::I8, config, vector_data) {
+        Ok(index) => Box::into_raw(Box::new(index)) as DiskAnnIndexHandle,
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Build an index from uint8 vectors
+#[no_mangle]
+pub extern "C" fn diskann_build_index_u8(
+    vectors: *const c_uchar,
+    num_vectors: c_uint,
+    vector_dim: c_uint,
+    max_degree: c_uint,
+    search_list_size: c_uint,
+    alpha: c_float,
+    seed: c_uint,
+    metric: DiskAnnMetric,
+) -> DiskAnnIndexHandle {
+    if vectors.is_null() || num_vectors == 0 || vector_dim == 0 {
+        return ptr::null_mut();
+    }
+
+    let config = VamanaConfig {
+        max_degree: max_degree as usize,
+        search_list_size: search_list_size as usize,
+        alpha,
+        seed: seed as u64,
+    };
+
+    let vector_data = unsafe {
+        let slice = slice::from_raw_parts(vectors, (num_vectors * vector_dim) as usize);
+        (0..num_vectors)
+            .map(|i| {
+                let start = (i * vector_dim) as usize;
+                let end = start + vector_dim as usize;
+                let vector = slice[start..end].iter().map(|&v| v as f32).collect();
+                (i, vector)
+            })
+            .collect()
+    };
+
+    match TaggedIndex::from_vectors(metric, DiskAnnElementType::U8, config, vector_data) {
         Ok(index) => Box::into_raw(Box::new(index)) as DiskAnnIndexHandle,
         Err(_) => ptr::null_mut(),
     }
 }
 
-/// Add a vector to the index
+/// Add an f32 vector to the index
 #[no_mangle]
 pub extern "C" fn diskann_add_vector(
     handle: DiskAnnIndexHandle,
@@ -132,8 +465,11 @@ pub extern "C" fn diskann_add_vector(
         return DiskAnnError::InvalidArgument;
     }
 
-    let index = unsafe { &mut *(handle as *mut VamanaIndex<EuclideanDistance>) };
-    
+    let index = unsafe { &mut *(handle as *mut TaggedIndex) };
+    if index.element_type != DiskAnnElementType::F32 {
+        return DiskAnnError::InvalidArgument;
+    }
+
     let vector_slice = unsafe { slice::from_raw_parts(vector, dim as usize) };
     let vector_vec = vector_slice.to_vec();
 
@@ -143,7 +479,59 @@ pub extern "C" fn diskann_add_vector(
     }
 }
 
-/// Search for k nearest neighbors
+/// Add an int8 vector to the index
+#[no_mangle]
+pub extern "C" fn diskann_add_vector_i8(
+    handle: DiskAnnIndexHandle,
+    id: c_uint,
+    vector: *const i8,
+    dim: c_uint,
+) -> DiskAnnError {
+    if handle.is_null() || vector.is_null() || dim == 0 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &mut *(handle as *mut TaggedIndex) };
+    if index.element_type != DiskAnnElementType::I8 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let vector_slice = unsafe { slice::from_raw_parts(vector, dim as usize) };
+    let vector_vec = vector_slice.iter().map(|&v| v as f32).collect();
+
+    match index.add(id, vector_vec) {
+        Ok(()) => DiskAnnError::Success,
+        Err(_) => DiskAnnError::BuildError,
+    }
+}
+
+/// Add a uint8 vector to the index
+#[no_mangle]
+pub extern "C" fn diskann_add_vector_u8(
+    handle: DiskAnnIndexHandle,
+    id: c_uint,
+    vector: *const c_uchar,
+    dim: c_uint,
+) -> DiskAnnError {
+    if handle.is_null() || vector.is_null() || dim == 0 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &mut *(handle as *mut TaggedIndex) };
+    if index.element_type != DiskAnnElementType::U8 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let vector_slice = unsafe { slice::from_raw_parts(vector, dim as usize) };
+    let vector_vec = vector_slice.iter().map(|&v| v as f32).collect();
+
+    match index.add(id, vector_vec) {
+        Ok(()) => DiskAnnError::Success,
+        Err(_) => DiskAnnError::BuildError,
+    }
+}
+
+/// Search for k nearest neighbors with an f32 query
 #[no_mangle]
 pub extern "C" fn diskann_search(
     handle: DiskAnnIndexHandle,
@@ -158,35 +546,87 @@ pub extern "C" fn diskann_search(
         return DiskAnnError::InvalidArgument;
     }
 
-    let index = unsafe { &*(handle as *const VamanaIndex<EuclideanDistance>) };
-    
+    let index = unsafe { &*(handle as *const TaggedIndex) };
+    if index.element_type != DiskAnnElementType::F32 {
+        return DiskAnnError::InvalidArgument;
+    }
+
     let query_slice = unsafe { slice::from_raw_parts(query, query_dim as usize) };
-    
-    let search_results = if beam_width > 0 {
-        match index.search_with_beam(query_slice, k as usize, beam_width as usize) {
-            Ok(results) => results,
-            Err(_) => return DiskAnnError::SearchError,
-        }
-    } else {
-        match index.search(query_slice, k as usize) {
-            Ok(results) => results,
-            Err(_) => return DiskAnnError::SearchError,
-        }
+
+    let search_results = match index.search(query_slice, k as usize, beam_width as usize) {
+        Ok(results) => results,
+        Err(_) => return DiskAnnError::SearchError,
     };
 
-    let num_results = search_results.len().min(k as usize);
-    unsafe {
-        *results_len = num_results as c_uint;
-        for (i, result) in search_results.iter().take(num_results).enumerate() {
-            (*results.add(i)).id = result.id;
-            (*results.add(i)).distance = result.distance;
-        }
+    write_results(&search_results, k as usize, results, results_len);
+    DiskAnnError::Success
+}
+
+/// Search for k nearest neighbors with an int8 query
+#[no_mangle]
+pub extern "C" fn diskann_search_i8(
+    handle: DiskAnnIndexHandle,
+    query: *const i8,
+    query_dim: c_uint,
+    k: c_uint,
+    beam_width: c_uint,
+    results: *mut SearchResultC,
+    results_len: *mut c_uint,
+) -> DiskAnnError {
+    if handle.is_null() || query.is_null() || query_dim == 0 || k == 0 || results.is_null() || results_len.is_null() {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &*(handle as *const TaggedIndex) };
+    if index.element_type != DiskAnnElementType::I8 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let query_slice = unsafe { slice::from_raw_parts(query, query_dim as usize) };
+    let query_vec: Vec<f32> = query_slice.iter().map(|&v| v as f32).collect();
+
+    let search_results = match index.search(&query_vec, k as usize, beam_width as usize) {
+        Ok(results) => results,
+        Err(_) => return DiskAnnError::SearchError,
+    };
+
+    write_results(&search_results, k as usize, results, results_len);
+    DiskAnnError::Success
+}
+
+/// Search for k nearest neighbors with a uint8 query
+#[no_mangle]
+pub extern "C" fn diskann_search_u8(
+    handle: DiskAnnIndexHandle,
+    query: *const c_uchar,
+    query_dim: c_uint,
+    k: c_uint,
+    beam_width: c_uint,
+    results: *mut SearchResultC,
+    results_len: *mut c_uint,
+) -> DiskAnnError {
+    if handle.is_null() || query.is_null() || query_dim == 0 || k == 0 || results.is_null() || results_len.is_null() {
+        return DiskAnnError::InvalidArgument;
     }
 
+    let index = unsafe { &*(handle as *const TaggedIndex) };
+    if index.element_type != DiskAnnElementType::U8 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let query_slice = unsafe { slice::from_raw_parts(query, query_dim as usize) };
+    let query_vec: Vec<f32> = query_slice.iter().map(|&v| v as f32).collect();
+
+    let search_results = match index.search(&query_vec, k as usize, beam_width as usize) {
+        Ok(results) => results,
+        Err(_) => return DiskAnnError::SearchError,
+    };
+
+    write_results(&search_results, k as usize, results, results_len);
     DiskAnnError::Success
 }
 
-/// Search with zero-allocation using provided buffer
+/// Search with zero-allocation using provided buffer (f32 only)
 #[no_mangle]
 pub extern "C" fn diskann_search_with_buffer(
     handle: DiskAnnIndexHandle,
@@ -198,38 +638,144 @@ pub extern "C" fn diskann_search_with_buffer(
     results: *mut SearchResultC,
     results_len: *mut c_uint,
 ) -> DiskAnnError {
-    if handle.is_null() || query.is_null() || query_dim == 0 || k == 0 || 
+    if handle.is_null() || query.is_null() || query_dim == 0 || k == 0 ||
        buffer_handle.is_null() || results.is_null() || results_len.is_null() {
         return DiskAnnError::InvalidArgument;
     }
 
-    let index = unsafe { &*(handle as *const VamanaIndex<EuclideanDistance>) };
+    let index = unsafe { &*(handle as *const TaggedIndex) };
+    if index.element_type != DiskAnnElementType::F32 {
+        return DiskAnnError::InvalidArgument;
+    }
     let buffer = unsafe { &mut *(buffer_handle as *mut SearchBuffer) };
-    
+
     let query_slice = unsafe { slice::from_raw_parts(query, query_dim as usize) };
-    
+
     let search_results = match index.search_with_buffer(
-        query_slice, 
-        k as usize, 
-        beam_width as usize, 
-        buffer
+        query_slice,
+        k as usize,
+        beam_width as usize,
+        buffer,
     ) {
         Ok(results) => results,
         Err(_) => return DiskAnnError::SearchError,
     };
 
-    let num_results = search_results.len().min(k as usize);
-    unsafe {
-        *results_len = num_results as c_uint;
-        for (i, result) in search_results.iter().take(num_results).enumerate() {
-            (*results.add(i)).id = result.id;
-            (*results.add(i)).distance = result.distance;
-        }
+    write_results(&search_results, k as usize, results, results_len);
+    DiskAnnError::Success
+}
+
+/// Run many independent f32 queries concurrently on a bounded thread pool
+///
+/// `queries` is a flat, row-major `num_queries * query_dim` array. `results`
+/// must have room for `num_queries * k` entries and `results_len` for one
+/// entry per query, recording how many neighbors were written to that
+/// query's row (`row i` occupies `results[i * k .. i * k + results_len[i]]`).
+///
+/// `max_pool_threads` caps how many worker threads this call may spin up (0
+/// means let the runtime size the pool itself), so host applications that
+/// manage their own threads can bound the concurrency this call introduces.
+///
+/// Aggregation is all-or-nothing, mirroring a "wait all, then report"
+/// semantics: every query runs to completion even if one of them fails, and
+/// only then is the first error code returned, so the output is never left
+/// half-written.
+#[cfg(feature = "unsafe_opt")]
+#[no_mangle]
+pub extern "C" fn diskann_search_batch(
+    handle: DiskAnnIndexHandle,
+    queries: *const c_float,
+    num_queries: c_uint,
+    query_dim: c_uint,
+    k: c_uint,
+    beam_width: c_uint,
+    max_pool_threads: c_uint,
+    results: *mut SearchResultC,
+    results_len: *mut c_uint,
+) -> DiskAnnError {
+    if handle.is_null()
+        || queries.is_null()
+        || num_queries == 0
+        || query_dim == 0
+        || k == 0
+        || results.is_null()
+        || results_len.is_null()
+    {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &*(handle as *const TaggedIndex) };
+    if index.element_type != DiskAnnElementType::F32 {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let query_rows: Vec<Vec<f32>> = unsafe {
+        let slice = slice::from_raw_parts(queries, (num_queries * query_dim) as usize);
+        (0..num_queries)
+            .map(|i| {
+                let start = (i * query_dim) as usize;
+                let end = start + query_dim as usize;
+                slice[start..end].to_vec()
+            })
+            .collect()
+    };
+
+    let max_threads = if max_pool_threads == 0 {
+        None
+    } else {
+        Some(max_pool_threads as usize)
+    };
+
+    let batch_results = match index.search_batch(&query_rows, k as usize, beam_width as usize, max_threads) {
+        Ok(rows) => rows,
+        Err(_) => return DiskAnnError::SearchError,
+    };
+
+    for (i, row) in batch_results.iter().enumerate() {
+        let row_results = unsafe { results.add(i * k as usize) };
+        let row_len = unsafe { results_len.add(i) };
+        write_results(row, k as usize, row_results, row_len);
     }
 
     DiskAnnError::Success
 }
 
+/// Compute mean recall@k from flat groundtruth/result id arrays
+///
+/// Both `groundtruth_ids` and `result_ids` are row-major `num_queries * k`
+/// arrays (row `i` holding query `i`'s first `k` neighbor ids), so Python/JS
+/// bindings can report search accuracy directly without re-deriving ground
+/// truth per language. Returns `0.0` for invalid arguments.
+#[no_mangle]
+pub extern "C" fn diskann_compute_recall(
+    groundtruth_ids: *const c_uint,
+    result_ids: *const c_uint,
+    num_queries: c_uint,
+    k: c_uint,
+) -> c_float {
+    if groundtruth_ids.is_null() || result_ids.is_null() || num_queries == 0 || k == 0 {
+        return 0.0;
+    }
+
+    let row_len = k as usize;
+    let total = num_queries as usize * row_len;
+
+    let groundtruth: Vec<Vec<VectorId>> = unsafe {
+        slice::from_raw_parts(groundtruth_ids, total)
+            .chunks(row_len)
+            .map(|row| row.to_vec())
+            .collect()
+    };
+    let results: Vec<Vec<VectorId>> = unsafe {
+        slice::from_raw_parts(result_ids, total)
+            .chunks(row_len)
+            .map(|row| row.to_vec())
+            .collect()
+    };
+
+    recall_at_k_from_ids(&groundtruth, &results, row_len)
+}
+
 /// Create a search buffer for zero-allocation search
 #[no_mangle]
 pub extern "C" fn diskann_create_search_buffer(capacity: c_uint) -> *mut c_void {
@@ -247,6 +793,40 @@ pub extern "C" fn diskann_destroy_search_buffer(buffer_handle: *mut c_void) {
     }
 }
 
+/// Lazily delete a vector from the index
+///
+/// The vector stays in the graph so live vectors can still route through it,
+/// but it is excluded from future search results. Call
+/// `diskann_consolidate` to repair the affected edges and reclaim the slot.
+#[no_mangle]
+pub extern "C" fn diskann_delete_vector(handle: DiskAnnIndexHandle, id: c_uint) -> DiskAnnError {
+    if handle.is_null() {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &mut *(handle as *mut TaggedIndex) };
+
+    match index.delete(id) {
+        Ok(()) => DiskAnnError::Success,
+        Err(_) => DiskAnnError::BuildError,
+    }
+}
+
+/// Repair edges around tombstoned vectors and reclaim their slots
+#[no_mangle]
+pub extern "C" fn diskann_consolidate(handle: DiskAnnIndexHandle) -> DiskAnnError {
+    if handle.is_null() {
+        return DiskAnnError::InvalidArgument;
+    }
+
+    let index = unsafe { &mut *(handle as *mut TaggedIndex) };
+
+    match index.consolidate() {
+        Ok(()) => DiskAnnError::Success,
+        Err(_) => DiskAnnError::BuildError,
+    }
+}
+
 /// Get the size of the index
 #[no_mangle]
 pub extern "C" fn diskann_get_index_size(handle: DiskAnnIndexHandle) -> c_uint {
@@ -254,7 +834,7 @@ pub extern "C" fn diskann_get_index_size(handle: DiskAnnIndexHandle) -> c_uint {
         return 0;
     }
 
-    let index = unsafe { &*(handle as *const VamanaIndex<EuclideanDistance>) };
+    let index = unsafe { &*(handle as *const TaggedIndex) };
     index.size() as c_uint
 }
 
@@ -263,12 +843,18 @@ pub extern "C" fn diskann_get_index_size(handle: DiskAnnIndexHandle) -> c_uint {
 pub extern "C" fn diskann_destroy_index(handle: DiskAnnIndexHandle) {
     if !handle.is_null() {
         unsafe {
-            let _ = Box::from_raw(handle as *mut VamanaIndex<EuclideanDistance>);
+            let _ = Box::from_raw(handle as *mut TaggedIndex);
         }
     }
 }
 
-/// Save index to file (placeholder for file I/O integration)
+/// Save an index to `filename` using [`VamanaIndex::save`]'s on-disk format
+///
+/// The file is still a plain [`VamanaIndex::save`] file -- openable with
+/// `DiskVamanaIndex::open` like any other -- with one extra byte appended recording this
+/// handle's [`DiskAnnElementType`], which [`diskann_load_index`] reads back to restore the
+/// tag exactly. [`VamanaIndex::save`] requires vector ids to be dense starting at zero, so
+/// a handle with deletions should be [`diskann_consolidate`] first.
 #[no_mangle]
 pub extern "C" fn diskann_save_index(
     handle: DiskAnnIndexHandle,
@@ -278,34 +864,81 @@ pub extern "C" fn diskann_save_index(
         return DiskAnnError::InvalidArgument;
     }
 
-    // For now, return success as file I/O would require integration with diskann-io
-    let _filename_str = unsafe {
+    let filename_str = unsafe {
         match CStr::from_ptr(filename).to_str() {
             Ok(s) => s,
             Err(_) => return DiskAnnError::InvalidArgument,
         }
     };
+    let path = Path::new(filename_str);
+    let tagged = unsafe { &*(handle as *const TaggedIndex) };
+
+    let save_result = match &tagged.index {
+        MetricIndex::L2(index) => index.save(path),
+        MetricIndex::Cosine(index) => index.save(path),
+        MetricIndex::InnerProduct(index) => index.save(path),
+    };
+    if save_result.is_err() {
+        return DiskAnnError::IoError;
+    }
+
+    let trailer_written = std::fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(&[tagged.element_type as u8]));
+    if trailer_written.is_err() {
+        return DiskAnnError::IoError;
+    }
 
-    // TODO: Implement actual file saving using diskann-io
     DiskAnnError::Success
 }
 
-/// Load index from file (placeholder for file I/O integration)
+/// Load an index previously written by [`diskann_save_index`]
+///
+/// The file's [`MetricTag`] (via [`peek_metric_tag`]) picks which concrete
+/// `VamanaIndex<D>` to reconstruct with [`VamanaIndex::load`] before the element-type
+/// trailer byte is read back; the loaded index restores every node's vector and adjacency
+/// list exactly as saved and supports the same [`diskann_add_vector`]/
+/// [`diskann_delete_vector`]/[`diskann_search`] entry points as a freshly built one.
+/// Returns a null pointer on any I/O or format error.
 #[no_mangle]
 pub extern "C" fn diskann_load_index(filename: *const c_char) -> DiskAnnIndexHandle {
     if filename.is_null() {
         return ptr::null_mut();
     }
 
-    let _filename_str = unsafe {
+    let filename_str = unsafe {
         match CStr::from_ptr(filename).to_str() {
             Ok(s) => s,
             Err(_) => return ptr::null_mut(),
         }
     };
+    let path = Path::new(filename_str);
+
+    let Ok(metric_tag) = peek_metric_tag(path) else {
+        return ptr::null_mut();
+    };
+
+    let index = match metric_tag {
+        MetricTag::L2 => VamanaIndex::load(path, EuclideanDistance).map(MetricIndex::L2),
+        MetricTag::Cosine => VamanaIndex::load(path, CosineDistance).map(MetricIndex::Cosine),
+        MetricTag::InnerProduct => {
+            VamanaIndex::load(path, InnerProductDistance).map(MetricIndex::InnerProduct)
+        }
+        MetricTag::L1 | MetricTag::Other => return ptr::null_mut(),
+    };
+    let Ok(index) = index else {
+        return ptr::null_mut();
+    };
+
+    let element_type = std::fs::read(path)
+        .ok()
+        .and_then(|bytes| bytes.last().copied())
+        .and_then(DiskAnnElementType::from_u8)
+        .unwrap_or(DiskAnnElementType::F32);
 
-    // TODO: Implement actual file loading using diskann-io
-    ptr::null_mut()
+    let tagged = TaggedIndex { element_type, index };
+    Box::into_raw(Box::new(tagged)) as DiskAnnIndexHandle
 }
 
 /// Get version string
@@ -328,7 +961,7 @@ mod tests {
         // Test adding vectors
         let vector1 = [1.0f32, 0.0, 0.0];
         let vector2 = [0.0f32, 1.0, 0.0];
-        
+
         assert_eq!(
             diskann_add_vector(handle, 0, vector1.as_ptr(), 3),
             DiskAnnError::Success
@@ -392,20 +1025,216 @@ mod tests {
             100, // search_list_size
             1.2, // alpha
             42, // seed
+            DiskAnnMetric::L2,
+        );
+
+        assert!(!handle.is_null());
+        assert_eq!(diskann_get_index_size(handle), 3);
+
+        diskann_destroy_index(handle);
+    }
+
+    #[test]
+    fn test_ffi_delete_and_consolidate() {
+        let handle = diskann_create_index();
+
+        let vector1 = [1.0f32, 0.0, 0.0];
+        let vector2 = [0.0f32, 1.0, 0.0];
+        let vector3 = [0.0f32, 0.0, 1.0];
+        diskann_add_vector(handle, 0, vector1.as_ptr(), 3);
+        diskann_add_vector(handle, 1, vector2.as_ptr(), 3);
+        diskann_add_vector(handle, 2, vector3.as_ptr(), 3);
+
+        assert_eq!(diskann_delete_vector(handle, 1), DiskAnnError::Success);
+        assert_eq!(diskann_get_index_size(handle), 3);
+
+        let query = [0.0f32, 0.9, 0.1];
+        let mut results = [SearchResultC { id: 0, distance: 0.0 }; 3];
+        let mut results_len = 0;
+        assert_eq!(
+            diskann_search(handle, query.as_ptr(), 3, 3, 64, results.as_mut_ptr(), &mut results_len),
+            DiskAnnError::Success
         );
+        assert!(results[..results_len as usize].iter().all(|r| r.id != 1));
+
+        assert_eq!(diskann_consolidate(handle), DiskAnnError::Success);
+        assert_eq!(diskann_get_index_size(handle), 2);
+
+        diskann_destroy_index(handle);
+    }
+
+    #[test]
+    fn test_ffi_cosine_metric() {
+        let handle = diskann_create_index_with_config(64, 100, 1.2, 42, DiskAnnMetric::Cosine);
+
+        // Unnormalized vectors: the FFI layer should normalize them on insert
+        let vector1 = [2.0f32, 0.0, 0.0];
+        let vector2 = [0.0f32, 3.0, 0.0];
+        diskann_add_vector(handle, 0, vector1.as_ptr(), 3);
+        diskann_add_vector(handle, 1, vector2.as_ptr(), 3);
+
+        let query = [5.0f32, 0.0, 0.0];
+        let mut results = [SearchResultC { id: 0, distance: 0.0 }; 2];
+        let mut results_len = 0;
+        assert_eq!(
+            diskann_search(handle, query.as_ptr(), 3, 2, 64, results.as_mut_ptr(), &mut results_len),
+            DiskAnnError::Success
+        );
+
+        assert!(results_len > 0);
+        assert_eq!(results[0].id, 0);
 
+        diskann_destroy_index(handle);
+    }
+
+    #[test]
+    fn test_ffi_i8_vectors() {
+        let vectors: [i8; 9] = [1, 0, 0, 0, 1, 0, 0, 0, 1];
+
+        let handle = diskann_build_index_i8(
+            vectors.as_ptr(),
+            3,
+            3,
+            64,
+            100,
+            1.2,
+            42,
+            DiskAnnMetric::L2,
+        );
         assert!(!handle.is_null());
         assert_eq!(diskann_get_index_size(handle), 3);
 
+        // An f32 entry point must reject an int8-tagged handle
+        let f32_vector = [1.0f32, 0.0, 0.0];
+        assert_eq!(
+            diskann_add_vector(handle, 3, f32_vector.as_ptr(), 3),
+            DiskAnnError::InvalidArgument
+        );
+
+        let query: [i8; 3] = [1, 0, 0];
+        let mut results = [SearchResultC { id: 0, distance: 0.0 }; 3];
+        let mut results_len = 0;
+        assert_eq!(
+            diskann_search_i8(handle, query.as_ptr(), 3, 3, 64, results.as_mut_ptr(), &mut results_len),
+            DiskAnnError::Success
+        );
+        assert!(results_len > 0);
+
         diskann_destroy_index(handle);
     }
 
+    #[cfg(feature = "unsafe_opt")]
+    #[test]
+    fn test_ffi_search_batch() {
+        let vectors = [
+            1.0f32, 0.0, 0.0,
+            0.0f32, 1.0, 0.0,
+            0.0f32, 0.0, 1.0,
+        ];
+
+        let handle = diskann_build_index(
+            vectors.as_ptr(),
+            3,
+            3,
+            64,
+            100,
+            1.2,
+            42,
+            DiskAnnMetric::L2,
+        );
+        assert!(!handle.is_null());
+
+        let queries = [
+            0.9f32, 0.1, 0.0,
+            0.0f32, 0.9, 0.1,
+        ];
+        let mut results = [SearchResultC { id: 0, distance: 0.0 }; 4];
+        let mut results_len = [0u32; 2];
+
+        assert_eq!(
+            diskann_search_batch(
+                handle,
+                queries.as_ptr(),
+                2,
+                3,
+                2,
+                64,
+                1,
+                results.as_mut_ptr(),
+                results_len.as_mut_ptr(),
+            ),
+            DiskAnnError::Success
+        );
+
+        assert!(results_len[0] > 0);
+        assert!(results_len[1] > 0);
+        assert_eq!(results[0].id, 0);
+        assert_eq!(results[2].id, 1);
+
+        diskann_destroy_index(handle);
+    }
+
+    #[test]
+    fn test_ffi_compute_recall() {
+        let groundtruth = [0u32, 1, 5, 6];
+        let results = [0u32, 2, 5, 6];
+
+        let recall = diskann_compute_recall(groundtruth.as_ptr(), results.as_ptr(), 2, 2);
+        assert!((recall - 0.75).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn test_ffi_version() {
         let version = diskann_get_version();
         assert!(!version.is_null());
-        
+
         let version_str = unsafe { CStr::from_ptr(version) };
         assert!(version_str.to_str().unwrap().starts_with("0.1.0"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ffi_save_and_load_round_trip() {
+        let handle = diskann_create_index_with_config(64, 100, 1.2, 42, DiskAnnMetric::Cosine);
+        let vector1 = [1.0f32, 0.0, 0.0];
+        let vector2 = [0.0f32, 1.0, 0.0];
+        diskann_add_vector(handle, 0, vector1.as_ptr(), 3);
+        diskann_add_vector(handle, 1, vector2.as_ptr(), 3);
+
+        let path = std::env::temp_dir().join(format!(
+            "diskann_ffi_test_{}_{}.bin",
+            std::process::id(),
+            "save_and_load_round_trip"
+        ));
+        let filename = std::ffi::CString::new(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            diskann_save_index(handle, filename.as_ptr()),
+            DiskAnnError::Success
+        );
+        diskann_destroy_index(handle);
+
+        let loaded = diskann_load_index(filename.as_ptr());
+        assert!(!loaded.is_null());
+        assert_eq!(diskann_get_index_size(loaded), 2);
+
+        let query = [0.9f32, 0.1, 0.0];
+        let mut results = [SearchResultC { id: 0, distance: 0.0 }; 2];
+        let mut results_len = 0;
+        assert_eq!(
+            diskann_search(
+                loaded,
+                query.as_ptr(),
+                3,
+                2,
+                64,
+                results.as_mut_ptr(),
+                &mut results_len,
+            ),
+            DiskAnnError::Success
+        );
+        assert!(results_len > 0);
+
+        diskann_destroy_index(loaded);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,180 @@
+//! Sharded concurrent index
+//!
+//! The loom models in `tests/loom_tests.rs` used to drive a throwaway
+//! `ConcurrentTestIndex` that guarded its entire node map behind one
+//! `Mutex<HashMap<..>>`, so every insert/search/remove serialized against
+//! every other operation. [`ShardedConcurrentIndex`] stripes nodes across
+//! `N` independently-locked shards (keyed by a hash of [`VectorId`]), so
+//! inserts and searches touching disjoint shards proceed in parallel, and
+//! only a write to a given shard blocks other writers of *that* shard.
+//!
+//! [`ShardedConcurrentIndex::remove`] is the one operation that needs every
+//! shard: to uphold the invariant that a removed id can never end up as a
+//! dangling neighbor, it takes a write lock on *all* shards before
+//! touching any of them, so a concurrent
+//! [`ShardedConcurrentIndex::add_edge`] either completes entirely before
+//! the removal starts or blocks until the removal (and its neighbor
+//! scrub) has finished. Every multi-shard lock acquisition -- both here and
+//! in `add_edge`'s cross-shard case -- always takes shards in ascending
+//! index order, so no pair of operations can ever form a lock-ordering
+//! cycle.
+//!
+//! Gated behind `#[cfg(loom)]` so the loom tests exercise this exact
+//! locking under `loom`'s own `Arc`/`RwLock`, while production builds use
+//! `std::sync` with no change to the algorithm.
+
+#[cfg(loom)]
+use loom::sync::{Arc, RwLock};
+#[cfg(not(loom))]
+use std::sync::{Arc, RwLock};
+
+use std::collections::HashMap;
+
+use diskann_core::{structures::GraphNode, vectors::VectorId};
+
+/// Default number of shards a [`ShardedConcurrentIndex`] stripes its nodes
+/// across
+pub const DEFAULT_SHARD_COUNT: usize = 16;
+
+/// Which shard `id` belongs to, out of `shard_count` shards
+fn shard_for(id: VectorId, shard_count: usize) -> usize {
+    (id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) as usize % shard_count
+}
+
+type Shard = Arc<RwLock<HashMap<VectorId, GraphNode>>>;
+
+/// A concurrent-safe graph index that shards its nodes across `N` striped
+/// `RwLock`s instead of one global lock, so operations on disjoint nodes
+/// can proceed in parallel
+pub struct ShardedConcurrentIndex {
+    shards: Vec<Shard>,
+    shard_count: usize,
+}
+
+impl ShardedConcurrentIndex {
+    /// Create an index with [`DEFAULT_SHARD_COUNT`] shards
+    pub fn new() -> Self {
+        Self::with_shards(DEFAULT_SHARD_COUNT)
+    }
+
+    /// Create an index with a specific number of shards (clamped to at
+    /// least 1)
+    pub fn with_shards(shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        let shards = (0..shard_count)
+            .map(|_| Arc::new(RwLock::new(HashMap::new())))
+            .collect();
+        Self { shards, shard_count }
+    }
+
+    fn shard_index(&self, id: VectorId) -> usize {
+        shard_for(id, self.shard_count)
+    }
+
+    /// Insert a new node. Only locks the one shard `id` hashes to, so
+    /// concurrent inserts of ids in different shards never contend.
+    pub fn insert(&self, id: VectorId, vector: Vec<f32>) -> Result<(), &'static str> {
+        let mut shard = self.shards[self.shard_index(id)].write().unwrap();
+        if shard.contains_key(&id) {
+            return Err("Node already exists");
+        }
+        shard.insert(id, GraphNode::new(id, vector));
+        Ok(())
+    }
+
+    /// Remove `id`, atomically scrubbing it from every other node's
+    /// neighbor list. Takes a write lock on every shard, in ascending
+    /// shard-index order, before making any change, so a concurrent
+    /// `add_edge(_, id)` can never race with the scrub and leave a
+    /// dangling neighbor behind.
+    pub fn remove(&self, id: VectorId) -> Result<(), &'static str> {
+        let mut guards: Vec<_> = self.shards.iter().map(|shard| shard.write().unwrap()).collect();
+
+        let owning_shard = self.shard_index(id);
+        if !guards[owning_shard].contains_key(&id) {
+            return Err("Node does not exist");
+        }
+        guards[owning_shard].remove(&id);
+
+        for shard in guards.iter_mut() {
+            for node in shard.values_mut() {
+                node.neighbors.retain(|&neighbor_id| neighbor_id != id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a directed edge `from -> to`. Locks only the shard(s) `from` and
+    /// `to` hash to (one lock if they collide, two -- taken in ascending
+    /// shard-index order -- if they don't), rather than the whole index.
+    pub fn add_edge(&self, from: VectorId, to: VectorId) -> Result<(), &'static str> {
+        let from_idx = self.shard_index(from);
+        let to_idx = self.shard_index(to);
+
+        if from_idx == to_idx {
+            let mut shard = self.shards[from_idx].write().unwrap();
+            if !shard.contains_key(&to) {
+                return Err("One or both nodes do not exist");
+            }
+            return match shard.get_mut(&from) {
+                Some(node) => {
+                    if !node.neighbors.contains(&to) {
+                        node.neighbors.push(to);
+                    }
+                    Ok(())
+                }
+                None => Err("One or both nodes do not exist"),
+            };
+        }
+
+        let (first_idx, second_idx) = if from_idx < to_idx { (from_idx, to_idx) } else { (to_idx, from_idx) };
+        let mut first = self.shards[first_idx].write().unwrap();
+        let mut second = self.shards[second_idx].write().unwrap();
+
+        let to_exists = if to_idx == first_idx { first.contains_key(&to) } else { second.contains_key(&to) };
+        if !to_exists {
+            return Err("One or both nodes do not exist");
+        }
+
+        let from_node = if from_idx == first_idx { first.get_mut(&from) } else { second.get_mut(&from) };
+        match from_node {
+            Some(node) => {
+                if !node.neighbors.contains(&to) {
+                    node.neighbors.push(to);
+                }
+                Ok(())
+            }
+            None => Err("One or both nodes do not exist"),
+        }
+    }
+
+    /// Every node id currently in the index. Each shard is locked for
+    /// reading only long enough to copy out its keys, so this never blocks
+    /// a writer for longer than one shard's worth of copying.
+    pub fn search(&self, _query: &[f32]) -> Vec<VectorId> {
+        let mut ids = Vec::new();
+        for shard in &self.shards {
+            ids.extend(shard.read().unwrap().keys().cloned());
+        }
+        ids
+    }
+
+    /// Total number of nodes across all shards
+    pub fn size(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().unwrap().len()).sum()
+    }
+
+    /// `id`'s current neighbor list, or `None` if `id` isn't present.
+    /// Mainly useful for tests asserting the no-dangling-neighbor
+    /// invariant `remove` upholds.
+    pub fn neighbors(&self, id: VectorId) -> Option<Vec<VectorId>> {
+        let shard = self.shards[self.shard_index(id)].read().unwrap();
+        shard.get(&id).map(|node| node.neighbors.clone())
+    }
+}
+
+impl Default for ShardedConcurrentIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
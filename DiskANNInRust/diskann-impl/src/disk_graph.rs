@@ -0,0 +1,696 @@
+//! On-disk, memory-mapped companion to [`crate::graph::VamanaIndex`]
+//!
+//! [`VamanaIndex::save`](crate::graph::VamanaIndex::save) serializes the graph into a single
+//! file holding a small fixed header followed by one fixed-stride
+//! `[vector: f32; dim][degree: u32][neighbors: u32; max_degree]` record per node, indexed
+//! directly by [`VectorId`]. [`DiskVamanaIndex::open`] maps that file back (falling back to a
+//! buffered read where mmap isn't available) and its beam search reads node records lazily
+//! through the mapping, keeping only a bounded cache of recently touched nodes resident. This
+//! is what makes indices larger than memory searchable, which the fully in-RAM `VamanaIndex`
+//! cannot do.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use diskann_core::vectors::VectorId;
+use diskann_traits::distance::{
+    CosineDistance, Distance, EuclideanDistance, InnerProductDistance, ManhattanDistance,
+    NearnessValue,
+};
+
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+
+const MAGIC: u32 = 0x564D_4E31; // "VMN1"
+const HEADER_SIZE: usize = 24;
+
+/// Distance-metric tag embedded in a [`DiskVamanaIndex`] file header
+///
+/// Recorded at [`save`](crate::graph::VamanaIndex::save) time so a reader can tell which
+/// distance function produced the graph. Only the metrics DiskANN ships as first-class
+/// `Distance` implementations round-trip exactly; anything else is recorded as `Other` and
+/// falls back to [`EuclideanDistance`] on open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricTag {
+    /// Euclidean (L2) distance
+    L2,
+    /// Cosine distance
+    Cosine,
+    /// Inner product distance
+    InnerProduct,
+    /// Manhattan (L1) distance
+    L1,
+    /// Any other distance metric
+    Other,
+}
+
+impl MetricTag {
+    /// Map a [`Distance::name`] string to its tag, falling back to `Other`
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "euclidean" => MetricTag::L2,
+            "cosine" => MetricTag::Cosine,
+            "inner_product" => MetricTag::InnerProduct,
+            "manhattan" => MetricTag::L1,
+            _ => MetricTag::Other,
+        }
+    }
+
+    fn to_u32(self) -> u32 {
+        match self {
+            MetricTag::L2 => 0,
+            MetricTag::Cosine => 1,
+            MetricTag::InnerProduct => 2,
+            MetricTag::L1 => 3,
+            MetricTag::Other => 255,
+        }
+    }
+
+    fn from_u32(tag: u32) -> Self {
+        match tag {
+            0 => MetricTag::L2,
+            1 => MetricTag::Cosine,
+            2 => MetricTag::InnerProduct,
+            3 => MetricTag::L1,
+            _ => MetricTag::Other,
+        }
+    }
+}
+
+/// The concrete distance function a [`DiskVamanaIndex`] resolved its [`MetricTag`] to
+///
+/// `DiskVamanaIndex` has no type parameter (the metric is only known once the header is
+/// read), so this plays the same role `MetricIndex` plays in the C FFI: picking one of a
+/// fixed set of concrete `Distance` impls at runtime.
+enum DiskMetric {
+    L2(EuclideanDistance),
+    Cosine(CosineDistance),
+    InnerProduct(InnerProductDistance),
+    L1(ManhattanDistance),
+}
+
+impl DiskMetric {
+    fn from_tag(tag: MetricTag) -> Self {
+        match tag {
+            MetricTag::Cosine => DiskMetric::Cosine(CosineDistance),
+            MetricTag::InnerProduct => DiskMetric::InnerProduct(InnerProductDistance),
+            MetricTag::L1 => DiskMetric::L1(ManhattanDistance),
+            MetricTag::L2 | MetricTag::Other => DiskMetric::L2(EuclideanDistance),
+        }
+    }
+
+    /// Nearness for candidate-heap ordering, skipping L2's `sqrt()` until a
+    /// result is actually returned (see [`DiskCandidate`])
+    fn nearness(&self, a: &[f32], b: &[f32]) -> NearnessValue {
+        match self {
+            DiskMetric::L2(d) => d.partial_nearness(a, b),
+            DiskMetric::Cosine(d) => d.partial_nearness(a, b),
+            DiskMetric::InnerProduct(d) => d.partial_nearness(a, b),
+            DiskMetric::L1(d) => d.partial_nearness(a, b),
+        }
+    }
+}
+
+/// Configuration for opening a [`DiskVamanaIndex`]
+#[derive(Debug, Clone)]
+pub struct DiskVamanaConfig {
+    /// Maximum number of decoded node records kept resident in the bounded node cache
+    pub node_cache_capacity: usize,
+}
+
+impl Default for DiskVamanaConfig {
+    fn default() -> Self {
+        Self {
+            node_cache_capacity: 1024,
+        }
+    }
+}
+
+/// Write the header and one record per node to `path`
+///
+/// `records` must be indexed by dense `VectorId` (record `i` is node `i`), which is what
+/// [`VamanaIndex::save`](crate::graph::VamanaIndex::save) guarantees before calling this.
+pub(crate) fn write_disk_vamana(
+    path: &Path,
+    dim: usize,
+    max_degree: usize,
+    start_node: VectorId,
+    metric_tag: MetricTag,
+    records: &[(Vec<f32>, Vec<VectorId>)],
+) -> io::Result<()> {
+    use std::io::{BufWriter, Write};
+
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    writer.write_all(&MAGIC.to_le_bytes())?;
+    writer.write_all(&(records.len() as u32).to_le_bytes())?;
+    writer.write_all(&(dim as u32).to_le_bytes())?;
+    writer.write_all(&(max_degree as u32).to_le_bytes())?;
+    writer.write_all(&start_node.to_le_bytes())?;
+    writer.write_all(&metric_tag.to_u32().to_le_bytes())?;
+
+    for (vector, neighbors) in records {
+        for &value in vector {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        let degree = neighbors.len().min(max_degree);
+        writer.write_all(&(degree as u32).to_le_bytes())?;
+        for &neighbor in &neighbors[..degree] {
+            writer.write_all(&neighbor.to_le_bytes())?;
+        }
+        for _ in degree..max_degree {
+            writer.write_all(&0u32.to_le_bytes())?;
+        }
+    }
+
+    writer.flush()
+}
+
+/// Read back a file written by [`write_disk_vamana`], decoding every record up front
+///
+/// Unlike [`DiskVamanaIndex::open`], this loads every vector and adjacency list eagerly
+/// (no mmap, no [`NodeCache`]) into owned `Vec`s, which is what
+/// [`VamanaIndex::load`](crate::graph::VamanaIndex::load) needs to rebuild a fully in-memory,
+/// mutable graph. Only the degree-prefixed neighbors are kept -- the zero-padding
+/// [`write_disk_vamana`] writes past a node's actual degree is discarded rather than treated
+/// as edges to vector id 0.
+pub(crate) fn read_disk_vamana(
+    path: &Path,
+) -> io::Result<(usize, usize, VectorId, MetricTag, Vec<(Vec<f32>, Vec<VectorId>)>)> {
+    let backing = Backing::open(File::open(path)?)?;
+    let data = backing.as_slice();
+
+    if data.len() < HEADER_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "file is smaller than a DiskVamanaIndex header",
+        ));
+    }
+
+    let magic = read_u32(data, 0);
+    if magic != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "file is not a DiskVamanaIndex (bad magic)",
+        ));
+    }
+
+    let num_nodes = read_u32(data, 4) as usize;
+    let dim = read_u32(data, 8) as usize;
+    let max_degree = read_u32(data, 12) as usize;
+    let start_node = read_u32(data, 16);
+    let metric_tag = MetricTag::from_u32(read_u32(data, 20));
+
+    let record_size = dim * 4 + 4 + max_degree * 4;
+    let expected_len = HEADER_SIZE + num_nodes * record_size;
+    if data.len() < expected_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "file truncated: expected at least {} bytes, found {}",
+                expected_len,
+                data.len()
+            ),
+        ));
+    }
+
+    let mut records = Vec::with_capacity(num_nodes);
+    for i in 0..num_nodes {
+        let offset = HEADER_SIZE + i * record_size;
+
+        let mut vector = Vec::with_capacity(dim);
+        for d in 0..dim {
+            vector.push(f32::from_le_bytes(read_bytes4(data, offset + d * 4)));
+        }
+
+        let degree_offset = offset + dim * 4;
+        let degree = read_u32(data, degree_offset) as usize;
+        let mut neighbors = Vec::with_capacity(degree);
+        for n in 0..degree {
+            neighbors.push(read_u32(data, degree_offset + 4 + n * 4));
+        }
+
+        records.push((vector, neighbors));
+    }
+
+    Ok((dim, max_degree, start_node, metric_tag, records))
+}
+
+/// Read just the [`MetricTag`] a [`write_disk_vamana`] file was written with, without
+/// decoding any node records
+///
+/// Callers that reconstruct a concrete `VamanaIndex<D>` (the C FFI's `diskann_load_index`,
+/// for one) need to know the metric before they can pick `D` and call
+/// [`VamanaIndex::load`](crate::graph::VamanaIndex::load), which [`read_disk_vamana`] can't
+/// help with since it is only reached through `load`, after `D` is already fixed.
+pub fn peek_metric_tag(path: &Path) -> io::Result<MetricTag> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; HEADER_SIZE];
+    file.read_exact(&mut header)?;
+
+    if read_u32(&header, 0) != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "file is not a DiskVamanaIndex (bad magic)",
+        ));
+    }
+
+    Ok(MetricTag::from_u32(read_u32(&header, 20)))
+}
+
+/// The backing storage for a [`DiskVamanaIndex`]: a memory map where available, or the whole
+/// file read into a buffer as a portable fallback
+enum Backing {
+    #[cfg(feature = "mmap")]
+    Mapped(Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl Backing {
+    fn open(mut file: File) -> io::Result<Self> {
+        #[cfg(feature = "mmap")]
+        {
+            if let Ok(mmap) = unsafe { Mmap::map(&file) } {
+                return Ok(Backing::Mapped(mmap));
+            }
+        }
+
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        Ok(Backing::Buffered(buffer))
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            #[cfg(feature = "mmap")]
+            Backing::Mapped(mmap) => &mmap[..],
+            Backing::Buffered(buffer) => &buffer[..],
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedNode {
+    vector: Vec<f32>,
+    neighbors: Vec<VectorId>,
+}
+
+/// A bounded, recency-ordered cache of decoded node records
+struct NodeCache {
+    capacity: usize,
+    entries: HashMap<VectorId, CachedNode>,
+    recency: VecDeque<VectorId>,
+}
+
+impl NodeCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, id: VectorId) -> Option<CachedNode> {
+        let node = self.entries.get(&id).cloned()?;
+        self.recency.retain(|&cached_id| cached_id != id);
+        self.recency.push_back(id);
+        Some(node)
+    }
+
+    fn insert(&mut self, id: VectorId, node: CachedNode) {
+        if self.entries.contains_key(&id) {
+            self.recency.retain(|&cached_id| cached_id != id);
+        } else if self.entries.len() >= self.capacity {
+            if let Some(evicted) = self.recency.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.recency.push_back(id);
+        self.entries.insert(id, node);
+    }
+}
+
+/// A candidate during disk-resident beam search
+///
+/// Orders by [`NearnessValue`] rather than a bare distance so that L2
+/// candidates compare on the squared distance and never pay for a `sqrt()`
+/// until [`DiskVamanaIndex::search`] converts the final, truncated result
+/// list back to real distances.
+#[derive(Clone)]
+struct DiskCandidate {
+    id: VectorId,
+    nearness: NearnessValue,
+}
+
+impl PartialEq for DiskCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.nearness == other.nearness && self.id == other.id
+    }
+}
+
+impl Eq for DiskCandidate {}
+
+impl PartialOrd for DiskCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DiskCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse ordering so a max-heap behaves like a min-heap on nearness
+        other
+            .nearness
+            .cmp(&self.nearness)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+/// On-disk, memory-mapped Vamana index for graphs too large to keep fully resident
+///
+/// Created via [`VamanaIndex::save`](crate::graph::VamanaIndex::save) and reopened with
+/// [`DiskVamanaIndex::open`]. Unlike `VamanaIndex`, it has no type parameter: the distance
+/// metric is resolved from the [`MetricTag`] stored in the file header.
+pub struct DiskVamanaIndex {
+    backing: Backing,
+    num_nodes: u32,
+    dim: usize,
+    max_degree: usize,
+    start_node: VectorId,
+    metric: DiskMetric,
+    cache: RefCell<NodeCache>,
+}
+
+impl DiskVamanaIndex {
+    /// Open a file written by [`VamanaIndex::save`](crate::graph::VamanaIndex::save)
+    pub fn open(path: &Path, config: DiskVamanaConfig) -> io::Result<Self> {
+        let backing = Backing::open(File::open(path)?)?;
+        let data = backing.as_slice();
+
+        if data.len() < HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file is smaller than a DiskVamanaIndex header",
+            ));
+        }
+
+        let magic = read_u32(data, 0);
+        if magic != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file is not a DiskVamanaIndex (bad magic)",
+            ));
+        }
+
+        let num_nodes = read_u32(data, 4);
+        let dim = read_u32(data, 8) as usize;
+        let max_degree = read_u32(data, 12) as usize;
+        let start_node = read_u32(data, 16);
+        let metric_tag = MetricTag::from_u32(read_u32(data, 20));
+
+        let record_size = dim * 4 + 4 + max_degree * 4;
+        let expected_len = HEADER_SIZE + num_nodes as usize * record_size;
+        if data.len() < expected_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "file truncated: expected at least {} bytes, found {}",
+                    expected_len,
+                    data.len()
+                ),
+            ));
+        }
+
+        Ok(Self {
+            backing,
+            num_nodes,
+            dim,
+            max_degree,
+            start_node,
+            metric: DiskMetric::from_tag(metric_tag),
+            cache: RefCell::new(NodeCache::new(config.node_cache_capacity)),
+        })
+    }
+
+    /// Dimension of the vectors stored in this index
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of nodes in this index
+    pub fn size(&self) -> usize {
+        self.num_nodes as usize
+    }
+
+    fn record_size(&self) -> usize {
+        self.dim * 4 + 4 + self.max_degree * 4
+    }
+
+    fn load_node(&self, id: VectorId) -> io::Result<CachedNode> {
+        if let Some(cached) = self.cache.borrow_mut().get(id) {
+            return Ok(cached);
+        }
+        if id >= self.num_nodes {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("node id {} out of range", id),
+            ));
+        }
+
+        let record_size = self.record_size();
+        let offset = HEADER_SIZE + id as usize * record_size;
+        let data = self.backing.as_slice();
+        let record = &data[offset..offset + record_size];
+
+        let mut vector = Vec::with_capacity(self.dim);
+        for i in 0..self.dim {
+            vector.push(f32::from_le_bytes(read_bytes4(record, i * 4)));
+        }
+
+        let degree_offset = self.dim * 4;
+        let degree = read_u32(record, degree_offset) as usize;
+
+        let neighbors_offset = degree_offset + 4;
+        let mut neighbors = Vec::with_capacity(degree);
+        for i in 0..degree {
+            neighbors.push(read_u32(record, neighbors_offset + i * 4));
+        }
+
+        let node = CachedNode { vector, neighbors };
+        self.cache.borrow_mut().insert(id, node.clone());
+        Ok(node)
+    }
+
+    /// Beam search over the disk-resident graph
+    ///
+    /// Node records are fetched through [`DiskVamanaIndex::load_node`] as the traversal
+    /// reaches them, so only the nodes actually visited (plus whatever the bounded node
+    /// cache still holds from earlier calls) are ever decoded into memory.
+    pub fn search(&self, query: &[f32], k: usize, beam_width: usize) -> io::Result<Vec<(VectorId, f32)>> {
+        if self.num_nodes == 0 {
+            return Ok(Vec::new());
+        }
+        if query.len() != self.dim {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("query has {} dimensions, index expects {}", query.len(), self.dim),
+            ));
+        }
+
+        let effective_beam_width = beam_width.max(k * 2);
+        let mut visited = HashSet::new();
+        let mut candidates: BinaryHeap<DiskCandidate> = BinaryHeap::new();
+        let mut best_candidates: BinaryHeap<DiskCandidate> = BinaryHeap::new();
+
+        let start = self.start_node.min(self.num_nodes - 1);
+        let start_node = self.load_node(start)?;
+        let start_candidate = DiskCandidate {
+            id: start,
+            nearness: self.metric.nearness(query, &start_node.vector),
+        };
+        candidates.push(start_candidate.clone());
+        best_candidates.push(start_candidate);
+        visited.insert(start);
+
+        while let Some(current) = candidates.pop() {
+            if let Some(furthest) = best_candidates.peek() {
+                if best_candidates.len() >= effective_beam_width && current.nearness > furthest.nearness {
+                    break;
+                }
+            }
+
+            let node = self.load_node(current.id)?;
+            for &neighbor_id in &node.neighbors {
+                if visited.contains(&neighbor_id) {
+                    continue;
+                }
+                visited.insert(neighbor_id);
+
+                let neighbor_node = self.load_node(neighbor_id)?;
+                let candidate = DiskCandidate {
+                    id: neighbor_id,
+                    nearness: self.metric.nearness(query, &neighbor_node.vector),
+                };
+                candidates.push(candidate.clone());
+                best_candidates.push(candidate);
+
+                if best_candidates.len() > effective_beam_width {
+                    best_candidates.pop();
+                }
+            }
+        }
+
+        let mut result: Vec<DiskCandidate> = best_candidates.into_sorted_vec();
+        result.reverse();
+        result.truncate(k);
+        Ok(result
+            .into_iter()
+            .map(|c| (c.id, c.nearness.into_distance()))
+            .collect())
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(read_bytes4(data, offset))
+}
+
+fn read_bytes4(data: &[u8], offset: usize) -> [u8; 4] {
+    [
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{IndexBuilder, VamanaConfig, VamanaIndex};
+    use diskann_traits::distance::{CosineDistance, EuclideanDistance};
+    use diskann_traits::search::Search;
+    use tempfile::NamedTempFile;
+
+    fn sample_vectors() -> Vec<(VectorId, Vec<f32>)> {
+        vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![0.0, 1.0]),
+            (3, vec![1.0, 1.0]),
+            (4, vec![0.5, 0.5]),
+        ]
+    }
+
+    #[test]
+    fn test_save_and_open_round_trip() {
+        let index = IndexBuilder::new(EuclideanDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(42)
+            .build(sample_vectors())
+            .unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        index.save(file.path()).unwrap();
+
+        let disk_index = DiskVamanaIndex::open(file.path(), DiskVamanaConfig::default()).unwrap();
+        assert_eq!(disk_index.size(), 5);
+        assert_eq!(disk_index.dim(), 2);
+
+        let query = vec![0.4, 0.4];
+        let in_memory = index.search(&query, 3).unwrap();
+        let on_disk = disk_index.search(&query, 3, 8).unwrap();
+
+        assert_eq!(on_disk.len(), in_memory.len());
+        assert_eq!(on_disk[0].0, in_memory[0].id);
+    }
+
+    #[test]
+    fn test_save_requires_dense_ids() {
+        let mut index = VamanaIndex::new(EuclideanDistance, VamanaConfig::default());
+        index.add(0, vec![0.0, 0.0]).unwrap();
+        index.add(5, vec![1.0, 1.0]).unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        assert!(index.save(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_metric_tag_round_trips_cosine() {
+        let index = IndexBuilder::new(CosineDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(7)
+            .build(sample_vectors())
+            .unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        index.save(file.path()).unwrap();
+        assert_eq!(peek_metric_tag(file.path()).unwrap(), MetricTag::Cosine);
+
+        let disk_index = DiskVamanaIndex::open(file.path(), DiskVamanaConfig::default()).unwrap();
+        let query = vec![0.9, 0.8];
+        let results = disk_index.search(&query, 2, 8).unwrap();
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_metric_tag_round_trips_manhattan() {
+        let index = IndexBuilder::new(ManhattanDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(7)
+            .build(sample_vectors())
+            .unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        index.save(file.path()).unwrap();
+        assert_eq!(peek_metric_tag(file.path()).unwrap(), MetricTag::L1);
+
+        let disk_index = DiskVamanaIndex::open(file.path(), DiskVamanaConfig::default()).unwrap();
+        let query = vec![0.4, 0.4];
+        let results = disk_index.search(&query, 2, 8).unwrap();
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_small_node_cache_still_searches_correctly() {
+        let index = IndexBuilder::new(EuclideanDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(42)
+            .build(sample_vectors())
+            .unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        index.save(file.path()).unwrap();
+
+        let disk_index = DiskVamanaIndex::open(
+            file.path(),
+            DiskVamanaConfig { node_cache_capacity: 1 },
+        )
+        .unwrap();
+
+        let query = vec![0.4, 0.4];
+        let results = disk_index.search(&query, 2, 8).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 4);
+    }
+
+    #[test]
+    fn test_open_rejects_bad_magic() {
+        use std::io::Write;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[0u8; HEADER_SIZE]).unwrap();
+
+        let err = DiskVamanaIndex::open(file.path(), DiskVamanaConfig::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
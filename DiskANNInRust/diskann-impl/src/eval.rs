@@ -0,0 +1,202 @@
+//! Ground-truth computation and recall evaluation for measuring index quality
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use diskann_core::vectors::VectorId;
+use diskann_traits::distance::Distance;
+
+#[cfg(feature = "unsafe_opt")]
+use rayon::prelude::*;
+
+/// A single entry in an exact ground-truth neighbor list, ordered by ascending
+/// distance to the query it was computed for
+#[derive(Debug, Clone, Copy)]
+pub struct GroundTruthNeighbor {
+    /// Base-set vector id
+    pub id: VectorId,
+    /// Exact distance from the query to this vector
+    pub distance: f32,
+}
+
+/// Brute-force exact k-NN ground truth for a batch of queries over a base set
+///
+/// For each query, every base vector is scored against it and the `k`
+/// closest are kept, sorted by ascending distance, mirroring upstream
+/// DiskANN's `compute_groundtruth` tool. With the `unsafe_opt` feature
+/// enabled queries are scored in parallel on a rayon thread pool, one base
+/// scan per query; without it they are scored sequentially.
+pub fn compute_groundtruth<D: Distance<f32> + Sync>(
+    base: &[(VectorId, Vec<f32>)],
+    queries: &[Vec<f32>],
+    k: usize,
+    distance_fn: &D,
+) -> Vec<Vec<GroundTruthNeighbor>> {
+    let score_query = |query: &Vec<f32>| -> Vec<GroundTruthNeighbor> {
+        let mut scored: Vec<GroundTruthNeighbor> = base
+            .iter()
+            .map(|(id, vector)| GroundTruthNeighbor {
+                id: *id,
+                distance: distance_fn.distance(query, vector),
+            })
+            .collect();
+        scored.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+        scored.truncate(k);
+        scored
+    };
+
+    #[cfg(feature = "unsafe_opt")]
+    {
+        queries.par_iter().map(score_query).collect()
+    }
+    #[cfg(not(feature = "unsafe_opt"))]
+    {
+        queries.iter().map(score_query).collect()
+    }
+}
+
+/// Mean recall@k of approximate search results against exact ground truth
+///
+/// For each query, recall is `|returned ∩ groundtruth| / k` (both lists
+/// truncated to their first `k` entries); the function returns the mean
+/// across all queries. When `count_ties` is set, a returned id whose
+/// distance matches the k-th ground-truth distance (within `f32::EPSILON`)
+/// also counts as a match even if it falls just past the first `k`
+/// ground-truth ids, so legitimate ties at the truncation boundary are not
+/// penalized.
+pub fn recall_at_k(
+    groundtruth: &[Vec<GroundTruthNeighbor>],
+    results: &[Vec<VectorId>],
+    k: usize,
+    count_ties: bool,
+) -> f32 {
+    if groundtruth.is_empty() || k == 0 {
+        return 0.0;
+    }
+
+    let per_query_recall: Vec<f32> = groundtruth
+        .iter()
+        .zip(results.iter())
+        .map(|(truth, returned)| {
+            let truth_k = &truth[..truth.len().min(k)];
+            let returned_k = &returned[..returned.len().min(k)];
+
+            let truth_ids: HashSet<VectorId> = truth_k.iter().map(|n| n.id).collect();
+            let mut matches = returned_k.iter().filter(|id| truth_ids.contains(id)).count();
+
+            if count_ties && truth_k.len() == k {
+                let kth_distance = truth_k[k - 1].distance;
+                let tie_ids: HashSet<VectorId> = truth
+                    .iter()
+                    .skip(k)
+                    .take_while(|n| (n.distance - kth_distance).abs() < f32::EPSILON)
+                    .map(|n| n.id)
+                    .collect();
+                matches += returned_k
+                    .iter()
+                    .filter(|id| !truth_ids.contains(id) && tie_ids.contains(id))
+                    .count();
+            }
+
+            matches as f32 / k as f32
+        })
+        .collect();
+
+    per_query_recall.iter().sum::<f32>() / per_query_recall.len() as f32
+}
+
+/// Mean recall@k computed purely from neighbor-id lists, with no distance
+/// information available for tie-breaking
+///
+/// Equivalent to [`recall_at_k`] with tie counting disabled; this is what the
+/// FFI boundary uses, since `diskann_compute_recall` only has id arrays.
+pub fn recall_at_k_from_ids(
+    groundtruth: &[Vec<VectorId>],
+    results: &[Vec<VectorId>],
+    k: usize,
+) -> f32 {
+    if groundtruth.is_empty() || k == 0 {
+        return 0.0;
+    }
+
+    let per_query_recall: Vec<f32> = groundtruth
+        .iter()
+        .zip(results.iter())
+        .map(|(truth, returned)| {
+            let truth_ids: HashSet<VectorId> = truth.iter().take(k).cloned().collect();
+            let returned_k = &returned[..returned.len().min(k)];
+            let matches = returned_k.iter().filter(|id| truth_ids.contains(id)).count();
+            matches as f32 / k as f32
+        })
+        .collect();
+
+    per_query_recall.iter().sum::<f32>() / per_query_recall.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diskann_traits::distance::EuclideanDistance;
+
+    #[test]
+    fn test_compute_groundtruth_orders_by_distance() {
+        let base = vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![2.0, 0.0]),
+            (3, vec![3.0, 0.0]),
+        ];
+        let queries = vec![vec![0.0, 0.0]];
+
+        let groundtruth = compute_groundtruth(&base, &queries, 2, &EuclideanDistance);
+
+        assert_eq!(groundtruth.len(), 1);
+        assert_eq!(groundtruth[0].len(), 2);
+        assert_eq!(groundtruth[0][0].id, 0);
+        assert_eq!(groundtruth[0][1].id, 1);
+    }
+
+    #[test]
+    fn test_recall_at_k_perfect_match() {
+        let groundtruth = vec![vec![
+            GroundTruthNeighbor { id: 0, distance: 0.0 },
+            GroundTruthNeighbor { id: 1, distance: 1.0 },
+        ]];
+        let results = vec![vec![0, 1]];
+
+        assert_eq!(recall_at_k(&groundtruth, &results, 2, false), 1.0);
+    }
+
+    #[test]
+    fn test_recall_at_k_partial_match() {
+        let groundtruth = vec![vec![
+            GroundTruthNeighbor { id: 0, distance: 0.0 },
+            GroundTruthNeighbor { id: 1, distance: 1.0 },
+        ]];
+        let results = vec![vec![0, 2]];
+
+        assert_eq!(recall_at_k(&groundtruth, &results, 2, false), 0.5);
+    }
+
+    #[test]
+    fn test_recall_at_k_counts_ties_at_boundary() {
+        let groundtruth = vec![vec![
+            GroundTruthNeighbor { id: 0, distance: 0.0 },
+            GroundTruthNeighbor { id: 1, distance: 1.0 },
+            GroundTruthNeighbor { id: 2, distance: 1.0 },
+        ]];
+        // The index returned id 2 instead of the tied id 1 at the k=2 boundary
+        let results = vec![vec![0, 2]];
+
+        assert_eq!(recall_at_k(&groundtruth, &results, 2, false), 0.5);
+        assert_eq!(recall_at_k(&groundtruth, &results, 2, true), 1.0);
+    }
+
+    #[test]
+    fn test_recall_at_k_from_ids() {
+        let groundtruth = vec![vec![0, 1], vec![5, 6]];
+        let results = vec![vec![0, 2], vec![5, 6]];
+
+        assert_eq!(recall_at_k_from_ids(&groundtruth, &results, 2), 0.75);
+    }
+}
@@ -2,13 +2,17 @@
 
 use diskann_core::{DiskAnnResult, vectors::VectorId, structures::GraphNode};
 use diskann_traits::{index::Index, search::{Search, SearchResult, SearchBuffer}, distance::Distance};
-use std::collections::{HashMap, HashSet, BinaryHeap};
+use std::collections::{HashMap, HashSet};
 use std::cmp::Ordering;
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::disk_graph::MetricTag;
 
 #[cfg(feature = "unsafe_opt")]
-#[allow(unused_imports)]
 use rayon::prelude::*;
 
 /// Configuration parameters for Vamana graph construction
@@ -35,6 +39,23 @@ impl Default for VamanaConfig {
     }
 }
 
+impl VamanaConfig {
+    /// The sequence of alpha values [`VamanaIndex::robust_prune_scheduled`] runs in order
+    ///
+    /// When `alpha > 1.0` this is `[1.0, alpha]`: a first round at alpha = 1.0 keeps only
+    /// the closest, most diverse neighbors, and a second round at the configured alpha is
+    /// more permissive about occlusion and can add longer-range "shortcut" edges on top,
+    /// matching upstream DiskANN's two-pass build. When `alpha <= 1.0` there is nothing
+    /// the second round would add beyond the first, so only `[alpha]` is run.
+    fn alpha_schedule(&self) -> Vec<f32> {
+        if self.alpha > 1.0 {
+            vec![1.0, self.alpha]
+        } else {
+            vec![self.alpha]
+        }
+    }
+}
+
 /// A candidate during graph search
 #[derive(Debug, Clone)]
 struct Candidate {
@@ -65,12 +86,42 @@ impl Ord for Candidate {
     }
 }
 
+/// Insert `entry` into `list` (kept sorted by ascending distance), with a parallel
+/// `expanded` flag for each slot, truncating both back to `capacity` by dropping the
+/// farthest entry if it grew past it
+fn insert_sorted_capped(list: &mut Vec<Candidate>, expanded: &mut Vec<bool>, entry: Candidate, capacity: usize) {
+    let pos = list.partition_point(|existing| existing.distance <= entry.distance);
+    list.insert(pos, entry);
+    expanded.insert(pos, false);
+    if list.len() > capacity {
+        list.pop();
+        expanded.pop();
+    }
+}
+
+/// Same insertion as [`insert_sorted_capped`], but operating directly on a
+/// [`SearchBuffer`]'s parallel `candidates`/`distances`/`expanded` vectors
+fn insert_into_buffer_list(buffer: &mut SearchBuffer, id: VectorId, distance: f32, capacity: usize) {
+    let pos = buffer.distances.partition_point(|&existing| existing <= distance);
+    buffer.candidates.insert(pos, id);
+    buffer.distances.insert(pos, distance);
+    buffer.expanded.insert(pos, false);
+    if buffer.candidates.len() > capacity {
+        buffer.candidates.pop();
+        buffer.distances.pop();
+        buffer.expanded.pop();
+    }
+}
+
 /// Vamana graph-based index implementation
 pub struct VamanaIndex<D> {
     nodes: HashMap<VectorId, GraphNode>,
     distance_fn: D,
     config: VamanaConfig,
     start_node: Option<VectorId>,
+    /// Lazily-deleted nodes, still present in `nodes` but excluded from
+    /// search results until the next [`VamanaIndex::consolidate`] pass
+    tombstones: HashSet<VectorId>,
     #[cfg(feature = "unsafe_opt")]
     #[allow(dead_code)]
     scratch_buffer: std::cell::RefCell<Vec<VectorId>>,
@@ -84,6 +135,7 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
             distance_fn,
             config,
             start_node: None,
+            tombstones: HashSet::new(),
             #[cfg(feature = "unsafe_opt")]
             scratch_buffer: std::cell::RefCell::new(Vec::new()),
         }
@@ -130,87 +182,87 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
         Some(best_id)
     }
 
-    /// Enhanced beam search for better quality results
+    /// Beam search over a single fixed-capacity candidate list (beam_search entry point)
+    ///
+    /// Thin wrapper around [`VamanaIndex::greedy_search`] for call sites that don't
+    /// care about the hop count; see that method for the algorithm itself.
     fn beam_search(&self, query: &[f32], k: usize, beam_width: usize, start_id: VectorId) -> Vec<Candidate> {
+        self.greedy_search(query, k, beam_width, start_id).0
+    }
+
+    /// Canonical Vamana GreedySearch over a single fixed-capacity candidate list
+    ///
+    /// The candidate list stays sorted by ascending distance to `query` and is
+    /// capped at `beam_width.max(k)` entries. Each step finds the closest entry
+    /// that hasn't been expanded yet, marks it expanded, and inserts any of its
+    /// unseen neighbors into the list (re-truncating to capacity), stopping once
+    /// every entry in the list has been expanded. This replaces the old
+    /// twin-`BinaryHeap` design: there is one list, no `Candidate` is cloned into
+    /// a second structure, and the number of distance computations ("hops") is
+    /// returned alongside the result so callers can tune `search_list_size`.
+    /// Tombstoned nodes are still inserted and expanded (so the graph stays
+    /// traversable through them) but are filtered out when the final top-`k` is
+    /// taken.
+    fn greedy_search(&self, query: &[f32], k: usize, beam_width: usize, start_id: VectorId) -> (Vec<Candidate>, usize) {
         // Check if start_id exists, if not, find an alternative
         let actual_start_id = if self.nodes.contains_key(&start_id) {
             start_id
         } else if let Some(&first_id) = self.nodes.keys().next() {
             first_id
         } else {
-            return Vec::new(); // No nodes in the graph
+            return (Vec::new(), 0); // No nodes in the graph
         };
 
-        let effective_beam_width = beam_width.max(k * 2); // Ensure beam is large enough
-        let mut visited = HashSet::new();
-        let mut candidates = BinaryHeap::new();
-        let mut best_candidates = BinaryHeap::new();
-
-        // Initialize with start node
-        let start_distance = self.distance_fn.distance(
-            query, 
-            &self.nodes[&actual_start_id].vector
-        );
-        let start_candidate = Candidate {
-            id: actual_start_id,
-            distance: start_distance,
-        };
-        
-        candidates.push(start_candidate.clone());
-        best_candidates.push(start_candidate);
-        visited.insert(actual_start_id);
-
-        while let Some(current) = candidates.pop() {
-            // Check if we should continue exploring
-            if let Some(furthest) = best_candidates.peek() {
-                if best_candidates.len() >= effective_beam_width && 
-                   current.distance > furthest.distance {
-                    break;
-                }
-            }
+        let capacity = beam_width.max(k);
+        let mut list: Vec<Candidate> = Vec::with_capacity(capacity + 1);
+        let mut expanded: Vec<bool> = Vec::with_capacity(capacity + 1);
+        let mut seen = HashSet::new();
+        let mut hops = 0usize;
 
-            // Explore neighbors
-            if let Some(node) = self.nodes.get(&current.id) {
+        let start_distance = self.distance_fn.distance(query, &self.nodes[&actual_start_id].vector);
+        hops += 1;
+        insert_sorted_capped(&mut list, &mut expanded, Candidate { id: actual_start_id, distance: start_distance }, capacity);
+        seen.insert(actual_start_id);
+
+        while let Some(next_idx) = expanded.iter().position(|&is_expanded| !is_expanded) {
+            expanded[next_idx] = true;
+            let current_id = list[next_idx].id;
+
+            if let Some(node) = self.nodes.get(&current_id) {
                 for &neighbor_id in &node.neighbors {
-                    if !visited.contains(&neighbor_id) {
-                        visited.insert(neighbor_id);
-                        
+                    if seen.insert(neighbor_id) {
                         if let Some(neighbor_node) = self.nodes.get(&neighbor_id) {
-                            let distance = self.distance_fn.distance(
-                                query, 
-                                &neighbor_node.vector
-                            );
-                            let neighbor_candidate = Candidate {
-                                id: neighbor_id,
-                                distance,
-                            };
-
-                            candidates.push(neighbor_candidate.clone());
-                            best_candidates.push(neighbor_candidate);
-
-                            // Keep only the best candidates within beam width
-                            if best_candidates.len() > effective_beam_width {
-                                best_candidates.pop();
-                            }
+                            let distance = self.distance_fn.distance(query, &neighbor_node.vector);
+                            hops += 1;
+                            insert_sorted_capped(&mut list, &mut expanded, Candidate { id: neighbor_id, distance }, capacity);
                         }
                     }
                 }
             }
         }
 
-        // Return top k candidates
-        let mut result: Vec<Candidate> = best_candidates.into_sorted_vec();
-        result.reverse(); // Convert to ascending order by distance
-        result.truncate(k);
-        result
+        let results: Vec<Candidate> = list.into_iter()
+            .filter(|candidate| !self.tombstones.contains(&candidate.id))
+            .take(k)
+            .collect();
+
+        (results, hops)
     }
 
-    /// Zero-allocation beam search using provided buffers
+    /// Zero-allocation GreedySearch using a caller-owned [`SearchBuffer`]
+    ///
+    /// Same algorithm as [`VamanaIndex::greedy_search`], but the candidate list
+    /// lives in `buffer.candidates`/`buffer.distances`/`buffer.expanded` and the
+    /// "already inserted" set lives in `buffer.visited` (indexed by vector id),
+    /// so repeated searches reuse the same allocations instead of building a
+    /// fresh list and `HashSet` each call. `buffer.visited` is sized to
+    /// `self.nodes.len()`, so a neighbor whose id is not below that count is
+    /// skipped; with non-dense ids this can miss nodes `greedy_search` finds.
     fn beam_search_with_buffer(
-        &self, 
-        query: &[f32], 
-        k: usize, 
-        beam_width: usize, 
+        &self,
+        query: &[f32],
+        k: usize,
+        beam_width: usize,
         start_id: VectorId,
         buffer: &mut SearchBuffer,
     ) -> Vec<Candidate> {
@@ -227,112 +279,106 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
             return Vec::new(); // No nodes in the graph
         };
 
-        let effective_beam_width = beam_width.max(k * 2);
-        let mut candidates = BinaryHeap::new();
-        let mut best_candidates = BinaryHeap::new();
-
-        // Initialize with start node
-        let start_distance = self.distance_fn.distance(
-            query, 
-            &self.nodes[&actual_start_id].vector
-        );
-        let start_candidate = Candidate {
-            id: actual_start_id,
-            distance: start_distance,
-        };
-        
-        candidates.push(start_candidate.clone());
-        best_candidates.push(start_candidate);
-        
-        // Use visited buffer instead of HashSet for zero allocation
-        if let Some(visited_slot) = buffer.visited.get_mut(actual_start_id as usize) {
-            *visited_slot = true;
+        let capacity = beam_width.max(k);
+
+        let start_distance = self.distance_fn.distance(query, &self.nodes[&actual_start_id].vector);
+        insert_into_buffer_list(buffer, actual_start_id, start_distance, capacity);
+        if let Some(slot) = buffer.visited.get_mut(actual_start_id as usize) {
+            *slot = true;
         }
 
-        while let Some(current) = candidates.pop() {
-            // Check if we should continue exploring
-            if let Some(furthest) = best_candidates.peek() {
-                if best_candidates.len() >= effective_beam_width && 
-                   current.distance > furthest.distance {
-                    break;
-                }
-            }
+        while let Some(next_idx) = buffer.expanded.iter().position(|&is_expanded| !is_expanded) {
+            buffer.expanded[next_idx] = true;
+            let current_id = buffer.candidates[next_idx];
 
-            // Explore neighbors
-            if let Some(node) = self.nodes.get(&current.id) {
+            if let Some(node) = self.nodes.get(&current_id) {
                 for &neighbor_id in &node.neighbors {
                     let neighbor_idx = neighbor_id as usize;
                     if neighbor_idx < buffer.visited.len() && !buffer.visited[neighbor_idx] {
                         buffer.visited[neighbor_idx] = true;
-                        
+
                         if let Some(neighbor_node) = self.nodes.get(&neighbor_id) {
-                            let distance = self.distance_fn.distance(
-                                query, 
-                                &neighbor_node.vector
-                            );
-                            let neighbor_candidate = Candidate {
-                                id: neighbor_id,
-                                distance,
-                            };
-
-                            candidates.push(neighbor_candidate.clone());
-                            best_candidates.push(neighbor_candidate);
-
-                            // Keep only the best candidates within beam width
-                            if best_candidates.len() > effective_beam_width {
-                                best_candidates.pop();
-                            }
+                            let distance = self.distance_fn.distance(query, &neighbor_node.vector);
+                            insert_into_buffer_list(buffer, neighbor_id, distance, capacity);
                         }
                     }
                 }
             }
         }
 
-        // Return top k candidates
-        let mut result: Vec<Candidate> = best_candidates.into_sorted_vec();
-        result.reverse(); // Convert to ascending order by distance
-        result.truncate(k);
-        result
+        // Return top k candidates, tombstones traversed but excluded from results
+        buffer.candidates.iter()
+            .zip(buffer.distances.iter())
+            .filter(|(id, _)| !self.tombstones.contains(id))
+            .take(k)
+            .map(|(&id, &distance)| Candidate { id, distance })
+            .collect()
     }
 
-    /// Robust prune procedure for maintaining graph quality
+    /// Canonical two-phase RobustPrune occlusion test
+    ///
+    /// `candidates` are sorted by distance to the node `p` being pruned for; the closest
+    /// surviving candidate `p*` is repeatedly selected and appended to the result, then
+    /// every remaining candidate `p'` for which `alpha * d(p*, p') <= d(p, p')` is
+    /// discarded (`p*` already occludes it at this alpha), until the result reaches
+    /// `max_degree`. Called once at `alpha = 1.0` and once at the configured `alpha` per
+    /// [`VamanaConfig::alpha_schedule`]: the first round keeps only the closest, most
+    /// diverse edges, and the second adds longer-range "shortcut" edges that alpha > 1.0
+    /// permits.
     fn robust_prune(&self, candidates: &[Candidate], alpha: f32) -> Vec<VectorId> {
         if candidates.is_empty() {
             return Vec::new();
         }
 
+        let mut remaining: Vec<Candidate> = candidates.to_vec();
+        remaining.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+
         let mut pruned = Vec::new();
-        let mut remaining: Vec<_> = candidates.iter().cloned().collect();
-        
         while !remaining.is_empty() && pruned.len() < self.config.max_degree {
-            // Find the closest candidate
-            let (best_idx, _) = remaining.iter()
-                .enumerate()
-                .min_by(|(_, a), (_, b)| a.distance.partial_cmp(&b.distance).unwrap())
-                .unwrap();
-            
-            let best_candidate = remaining.remove(best_idx);
-            pruned.push(best_candidate.id);
-
-            // Remove candidates that are too close to the selected one
-            if let Some(best_node) = self.nodes.get(&best_candidate.id) {
-                remaining.retain(|candidate| {
-                    if let Some(candidate_node) = self.nodes.get(&candidate.id) {
-                        let dist_to_best = self.distance_fn.distance(
-                            &best_node.vector, 
-                            &candidate_node.vector
-                        );
-                        candidate.distance < alpha * dist_to_best
-                    } else {
-                        false
-                    }
-                });
-            }
+            // `remaining` stays sorted by distance to p, so the closest surviving
+            // candidate p* is always the first entry.
+            let best = remaining.remove(0);
+            pruned.push(best.id);
+
+            let Some(best_node) = self.nodes.get(&best.id) else { continue };
+            let best_vector = best_node.vector.clone();
+
+            remaining.retain(|candidate| {
+                let Some(candidate_node) = self.nodes.get(&candidate.id) else { return false };
+                let dist_best_to_candidate = self.distance_fn.distance(&best_vector, &candidate_node.vector);
+                alpha * dist_best_to_candidate > candidate.distance
+            });
         }
 
         pruned
     }
 
+    /// Run [`VamanaIndex::robust_prune`] once per alpha in [`VamanaConfig::alpha_schedule`],
+    /// each round pruning the same original candidate set independently, and union the
+    /// results (earlier rounds' ids first) up to `max_degree`
+    ///
+    /// With the default schedule `[1.0, alpha]`, the `alpha = 1.0` round picks the
+    /// closest, most diverse neighbors; the configured-`alpha` round is more permissive
+    /// about occlusion and so can select longer-range "shortcut" candidates that the
+    /// first round discarded. Their union is the node's final neighbor set.
+    fn robust_prune_scheduled(&self, candidates: &[Candidate]) -> Vec<VectorId> {
+        let mut result: Vec<VectorId> = Vec::new();
+        for round_alpha in self.config.alpha_schedule() {
+            if result.len() >= self.config.max_degree {
+                break;
+            }
+            for id in self.robust_prune(candidates, round_alpha) {
+                if result.len() >= self.config.max_degree {
+                    break;
+                }
+                if !result.contains(&id) {
+                    result.push(id);
+                }
+            }
+        }
+        result
+    }
+
     /// Advanced pruning with memory optimization (unsafe_opt feature)
     /// 
     /// This function provides optimized pruning that may reuse memory buffers
@@ -351,6 +397,55 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
         self.robust_prune(candidates, alpha)
     }
 
+    /// Guarantee a zero-distance cluster stays linked into the rest of the graph
+    ///
+    /// The alpha occlusion test in [`VamanaIndex::robust_prune`] discards any candidate
+    /// `p'` once a closer `p*` occludes it; for a duplicate (distance 0) `p*` this is true
+    /// for every other zero-distance candidate, so a node being inserted at an already
+    /// occupied location normally ends up with only one edge: back to that single
+    /// duplicate. A chain of such nodes never reaches a non-duplicate, so `beam_search`
+    /// from the medoid can't find them. When `candidates` contains a distance-0 entry,
+    /// this makes sure the pruned neighbor set keeps that duplicate edge *and* one of the
+    /// duplicate's own non-zero-distance neighbors, so every new duplicate immediately has
+    /// a path onward into the main graph rather than only into the cluster.
+    fn ensure_colocation_link(&self, candidates: &[Candidate], mut neighbors: Vec<VectorId>) -> Vec<VectorId> {
+        let Some(duplicate) = candidates.iter().find(|c| c.distance == 0.0) else {
+            return neighbors;
+        };
+        let Some(duplicate_node) = self.nodes.get(&duplicate.id) else {
+            return neighbors;
+        };
+
+        if !neighbors.contains(&duplicate.id) {
+            if neighbors.len() >= self.config.max_degree && !neighbors.is_empty() {
+                neighbors.pop();
+            }
+            neighbors.push(duplicate.id);
+        }
+
+        let onward = duplicate_node.neighbors.iter().copied().find(|&nb_id| {
+            nb_id != duplicate.id
+                && self.nodes.get(&nb_id)
+                    .map(|nb| self.distance_fn.distance(&duplicate_node.vector, &nb.vector) > 0.0)
+                    .unwrap_or(false)
+        });
+
+        if let Some(onward_id) = onward {
+            if !neighbors.contains(&onward_id) {
+                if neighbors.len() >= self.config.max_degree {
+                    if let Some(pos) = neighbors.iter().rposition(|&id| id != duplicate.id) {
+                        neighbors.remove(pos);
+                    }
+                }
+                if neighbors.len() < self.config.max_degree {
+                    neighbors.push(onward_id);
+                }
+            }
+        }
+
+        neighbors
+    }
+
     /// Insert a new node into the graph using Vamana algorithm
     fn insert_node(&mut self, id: VectorId, vector: Vec<f32>) -> DiskAnnResult<()> {
         // Create the new node
@@ -372,8 +467,9 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
         let candidates = self.beam_search(&vector, self.config.search_list_size, self.config.search_list_size, start_id);
         
         // Prune to get the actual neighbors
-        let neighbors = self.robust_prune(&candidates, self.config.alpha);
-        
+        let neighbors = self.robust_prune_scheduled(&candidates);
+        let neighbors = self.ensure_colocation_link(&candidates, neighbors);
+
         // Create node with initial neighbors
         let mut node_with_neighbors = new_node;
         node_with_neighbors.neighbors = neighbors.clone();
@@ -410,7 +506,7 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
                     })
                     .collect();
                 
-                let pruned_neighbors = self.robust_prune(&neighbor_candidates, self.config.alpha);
+                let pruned_neighbors = self.robust_prune_scheduled(&neighbor_candidates);
                 
                 // Update the neighbor's edges
                 if let Some(neighbor_node) = self.nodes.get_mut(&neighbor_id) {
@@ -421,11 +517,121 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
 
         // Update start node to be the medoid
         self.start_node = self.find_medoid();
-        
+
+        Ok(())
+    }
+
+    /// Build (or extend) the graph from a batch of vectors using parallel edge computation
+    ///
+    /// Unlike [`VamanaIndex::add`], which runs a full `beam_search` + `robust_prune` +
+    /// medoid recomputation per vector, this inserts every node with an empty adjacency
+    /// list, wires up a random initial graph seeded from `config.seed` so beam search has
+    /// something to traverse, then runs two refinement passes. Each pass computes every
+    /// node's pruned neighbor set in parallel against a frozen snapshot of the graph, and
+    /// applies the resulting edge updates (including reverse edges) in a single
+    /// synchronized step afterward. The medoid is computed once at the end instead of
+    /// after every insertion.
+    #[cfg(feature = "unsafe_opt")]
+    pub fn build_batch(&mut self, items: Vec<(VectorId, Vec<f32>)>) -> DiskAnnResult<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<VectorId> = items.iter().map(|(id, _)| *id).collect();
+        for (id, vector) in items {
+            self.nodes.insert(id, GraphNode::new(id, vector));
+        }
+
+        let mut rng = ChaCha20Rng::seed_from_u64(self.config.seed);
+        for &id in &ids {
+            let degree = self.config.max_degree.min(ids.len().saturating_sub(1));
+            let mut neighbors = Vec::with_capacity(degree);
+            while neighbors.len() < degree {
+                let candidate = ids[rng.gen_range(0..ids.len())];
+                if candidate != id && !neighbors.contains(&candidate) {
+                    neighbors.push(candidate);
+                }
+            }
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.neighbors = neighbors;
+            }
+        }
+
+        for _ in 0..2 {
+            let updates: Vec<(VectorId, Vec<VectorId>)> = ids
+                .par_iter()
+                .map(|&id| {
+                    let vector = self.nodes[&id].vector.clone();
+                    let candidates = self.beam_search(
+                        &vector,
+                        self.config.search_list_size,
+                        self.config.search_list_size,
+                        id,
+                    );
+                    (id, self.robust_prune_scheduled(&candidates))
+                })
+                .collect();
+
+            for (id, neighbors) in &updates {
+                if let Some(node) = self.nodes.get_mut(id) {
+                    node.neighbors = neighbors.clone();
+                }
+            }
+
+            // Reverse edges, pruning back to max_degree with the same alpha rule used
+            // during sequential insertion whenever a node's in-degree overflows.
+            for (id, neighbors) in &updates {
+                for &neighbor_id in neighbors {
+                    let needs_pruning = if let Some(neighbor_node) = self.nodes.get_mut(&neighbor_id) {
+                        if !neighbor_node.neighbors.contains(id) {
+                            neighbor_node.neighbors.push(*id);
+                        }
+                        neighbor_node.neighbors.len() > self.config.max_degree
+                    } else {
+                        false
+                    };
+
+                    if needs_pruning {
+                        let (neighbor_vector, neighbor_ids) = if let Some(neighbor_node) = self.nodes.get(&neighbor_id) {
+                            (neighbor_node.vector.clone(), neighbor_node.neighbors.clone())
+                        } else {
+                            continue;
+                        };
+
+                        let neighbor_candidates: Vec<Candidate> = neighbor_ids.iter()
+                            .map(|&nb_id| {
+                                if let Some(nb_node) = self.nodes.get(&nb_id) {
+                                    Candidate {
+                                        id: nb_id,
+                                        distance: self.distance_fn.distance(&neighbor_vector, &nb_node.vector),
+                                    }
+                                } else {
+                                    Candidate { id: nb_id, distance: f32::INFINITY }
+                                }
+                            })
+                            .collect();
+
+                        let pruned_neighbors = self.robust_prune_scheduled(&neighbor_candidates);
+                        if let Some(neighbor_node) = self.nodes.get_mut(&neighbor_id) {
+                            neighbor_node.neighbors = pruned_neighbors;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.start_node = self.find_medoid();
+
         Ok(())
     }
 
-    /// Remove a node and repair the graph connectivity
+    /// Remove a node and repair the graph connectivity immediately
+    ///
+    /// This is the eager path: every affected neighbor runs a fresh
+    /// `beam_search` + `robust_prune` right away, which is O(degree) repair
+    /// work per call. For streaming workloads with frequent deletes, prefer
+    /// [`VamanaIndex::delete`] (O(1)) followed by a batched
+    /// [`VamanaIndex::consolidate`] once enough tombstones have accumulated.
     fn remove_node(&mut self, id: VectorId) -> DiskAnnResult<()> {
         if !self.nodes.contains_key(&id) {
             return Ok(()); // Node doesn't exist
@@ -464,7 +670,7 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
                     if !other_neighbors.is_empty() {
                         let start_id = self.start_node.unwrap_or(other_neighbors[0]);
                         let candidates = self.beam_search(&neighbor_vector, self.config.search_list_size, self.config.search_list_size, start_id);
-                        let new_connections = self.robust_prune(&candidates, self.config.alpha);
+                        let new_connections = self.robust_prune_scheduled(&candidates);
                         
                         if let Some(neighbor_node_mut) = self.nodes.get_mut(&neighbor_id) {
                             // Add new connections while maintaining degree limit
@@ -555,6 +761,211 @@ impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
             .sum();
         total_degree as f64 / self.nodes.len() as f64
     }
+
+    /// Check whether a node has been lazily deleted and is awaiting consolidation
+    pub fn is_tombstoned(&self, id: VectorId) -> bool {
+        self.tombstones.contains(&id)
+    }
+
+    /// Lazily delete a node
+    ///
+    /// The node's vector and edges stay in the graph (so live nodes can still
+    /// route through it) but it is excluded from future search results. Call
+    /// [`VamanaIndex::consolidate`] to repair the affected edges and reclaim
+    /// the slot.
+    pub fn delete(&mut self, id: VectorId) -> DiskAnnResult<()> {
+        if self.nodes.contains_key(&id) {
+            self.tombstones.insert(id);
+        }
+        Ok(())
+    }
+
+    /// Repair edges around tombstoned nodes and reclaim their slots
+    ///
+    /// For every live node with a tombstoned out-neighbor, the dead edge is
+    /// replaced by that neighbor's surviving neighbors, and the resulting
+    /// candidate set is pruned back to `max_degree` with the same
+    /// alpha-based [`VamanaIndex::robust_prune`] rule used during
+    /// construction. Once every affected edge has been repaired, the
+    /// tombstoned nodes are physically removed.
+    pub fn consolidate(&mut self) -> DiskAnnResult<()> {
+        if self.tombstones.is_empty() {
+            return Ok(());
+        }
+
+        let live_ids: Vec<VectorId> = self.nodes.keys()
+            .filter(|id| !self.tombstones.contains(id))
+            .cloned()
+            .collect();
+
+        for id in live_ids {
+            let has_dead_neighbor = self.nodes[&id].neighbors.iter()
+                .any(|n| self.tombstones.contains(n));
+            if !has_dead_neighbor {
+                continue;
+            }
+
+            let node_vector = self.nodes[&id].vector.clone();
+            let mut expanded: Vec<VectorId> = Vec::new();
+            for &neighbor_id in &self.nodes[&id].neighbors {
+                if self.tombstones.contains(&neighbor_id) {
+                    if let Some(dead_node) = self.nodes.get(&neighbor_id) {
+                        expanded.extend(
+                            dead_node.neighbors.iter()
+                                .filter(|&&n| n != id && !self.tombstones.contains(&n))
+                                .cloned(),
+                        );
+                    }
+                } else {
+                    expanded.push(neighbor_id);
+                }
+            }
+            expanded.sort_unstable();
+            expanded.dedup();
+
+            let candidates: Vec<Candidate> = expanded.iter()
+                .filter_map(|&neighbor_id| {
+                    self.nodes.get(&neighbor_id).map(|neighbor_node| Candidate {
+                        id: neighbor_id,
+                        distance: self.distance_fn.distance(&node_vector, &neighbor_node.vector),
+                    })
+                })
+                .collect();
+
+            let pruned = self.robust_prune_scheduled(&candidates);
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.neighbors = pruned;
+            }
+        }
+
+        for id in self.tombstones.drain().collect::<Vec<_>>() {
+            self.nodes.remove(&id);
+        }
+
+        if self.start_node.map_or(true, |start| !self.nodes.contains_key(&start)) {
+            self.start_node = self.find_medoid();
+        }
+
+        Ok(())
+    }
+
+    /// Flatten every node's adjacency into one [`CompactVamanaIndex`] for read-only search
+    ///
+    /// Each `GraphNode` normally owns its own `Vec<VectorId>` of neighbors — one
+    /// heap allocation and an extra pointer chase per node, which adds up at
+    /// million-scale. This flattens every node's neighbor list into a single
+    /// contiguous `edge_data` buffer plus a per-node `(start, end)` range into
+    /// it, so traversal reads `&edge_data[start..end]` instead of dereferencing
+    /// a separate allocation per node: one allocation for the whole graph's
+    /// edges and much better cache locality during greedy search. This trades
+    /// away incremental mutability — [`CompactVamanaIndex`] only implements
+    /// [`Search`], not [`Index`]; go back through a mutable `VamanaIndex` (and
+    /// `compact` again) to add or remove nodes.
+    pub fn compact(self) -> CompactVamanaIndex<D> {
+        let mut edge_data = Vec::new();
+        let mut edge_ranges = HashMap::with_capacity(self.nodes.len());
+        let mut vectors = HashMap::with_capacity(self.nodes.len());
+
+        for (id, node) in self.nodes {
+            let start = edge_data.len();
+            edge_data.extend(node.neighbors);
+            edge_ranges.insert(id, (start, edge_data.len()));
+            vectors.insert(id, node.vector);
+        }
+
+        CompactVamanaIndex {
+            vectors,
+            edge_data,
+            edge_ranges,
+            distance_fn: self.distance_fn,
+            config: self.config,
+            start_node: self.start_node,
+        }
+    }
+
+    /// Serialize this graph to a fixed-stride, memory-mappable file
+    ///
+    /// The file holds a small header (dimension, max degree, start node, distance-metric
+    /// tag) followed by one `[vector; dim][degree][neighbors; max_degree]` record per node,
+    /// indexed directly by [`VectorId`]. Reopen it with
+    /// [`DiskVamanaIndex::open`](crate::disk_graph::DiskVamanaIndex::open) to search it
+    /// without loading the whole graph into memory. Vector ids must be dense and start at
+    /// zero (`0..size()`) to fit this array layout.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if self.nodes.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "cannot save an empty index"));
+        }
+
+        let num_nodes = self.nodes.len();
+        let dim = self.nodes.values().next().unwrap().vector.len();
+        let start_node = self.start_node.unwrap_or(0);
+        let metric_tag = MetricTag::from_name(self.distance_fn.name());
+
+        let mut records = Vec::with_capacity(num_nodes);
+        for id in 0..num_nodes as VectorId {
+            let node = self.nodes.get(&id).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("vector ids must be dense starting at 0; missing id {}", id),
+                )
+            })?;
+            records.push((node.vector.clone(), node.neighbors.clone()));
+        }
+
+        crate::disk_graph::write_disk_vamana(path, dim, self.config.max_degree, start_node, metric_tag, &records)
+    }
+
+    /// Reconstruct a fully in-memory, mutable graph from a file written by
+    /// [`VamanaIndex::save`]
+    ///
+    /// Restores every node's vector and adjacency list exactly as they were serialized --
+    /// no edges are recomputed and no GreedySearch/RobustPrune pass is rerun -- so the
+    /// returned index is ready for [`Search`] and further [`Index::add`]/[`Index::remove`]
+    /// calls immediately. `distance_fn` must match the metric the file was saved under (see
+    /// [`MetricTag::from_name`]); a mismatch is rejected rather than silently rescoring the
+    /// graph with the wrong distance function. Use [`DiskVamanaIndex::open`](crate::disk_graph::DiskVamanaIndex::open)
+    /// instead if you only need read-only, memory-mapped search over an index larger than RAM.
+    pub fn load(path: &Path, distance_fn: D) -> io::Result<Self> {
+        let (dim, max_degree, start_node, metric_tag, records) =
+            crate::disk_graph::read_disk_vamana(path)?;
+
+        let expected_tag = MetricTag::from_name(distance_fn.name());
+        if metric_tag != expected_tag {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "file was saved under metric {:?}, but this index uses {:?}",
+                    metric_tag, expected_tag
+                ),
+            ));
+        }
+
+        let mut nodes = HashMap::with_capacity(records.len());
+        for (id, (vector, neighbors)) in records.into_iter().enumerate() {
+            if vector.len() != dim {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("node {} has {} dimensions, expected {}", id, vector.len(), dim),
+                ));
+            }
+            let mut node = GraphNode::new(id as VectorId, vector);
+            node.neighbors = neighbors;
+            nodes.insert(id as VectorId, node);
+        }
+
+        Ok(Self {
+            nodes,
+            distance_fn,
+            config: VamanaConfig {
+                max_degree,
+                ..VamanaConfig::default()
+            },
+            start_node: Some(start_node),
+            tombstones: HashSet::new(),
+            #[cfg(feature = "unsafe_opt")]
+            scratch_buffer: std::cell::RefCell::new(Vec::new()),
+        })
+    }
 }
 
 impl<D: Distance<f32> + Sync + Send> Default for VamanaIndex<D> 
@@ -571,10 +982,11 @@ impl<D: Distance<f32> + Sync + Send> Index<f32> for VamanaIndex<D> {
         self.insert_node(id, vector)
     }
     
+    /// Eager removal; see [`VamanaIndex::remove_node`] for the eager/lazy tradeoff.
     fn remove(&mut self, id: VectorId) -> DiskAnnResult<()> {
         self.remove_node(id)
     }
-    
+
     fn size(&self) -> usize {
         self.nodes.len()
     }
@@ -669,18 +1081,142 @@ impl<D: Distance<f32> + Sync + Send> Search<f32> for VamanaIndex<D> {
     }
 }
 
+impl<D: Distance<f32> + Sync + Send> VamanaIndex<D> {
+    /// Like [`Search::search`], but also returns the number of distance
+    /// computations ("hops") [`VamanaIndex::greedy_search`] performed to reach it,
+    /// so callers can tune `search_list_size` without instrumenting the index
+    /// externally.
+    pub fn search_with_stats(&self, query: &[f32], k: usize) -> DiskAnnResult<(Vec<SearchResult>, usize)> {
+        if self.nodes.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let start_id = if let Some(start) = self.start_node {
+            if self.nodes.contains_key(&start) {
+                start
+            } else {
+                self.nodes.keys().next().cloned().unwrap()
+            }
+        } else {
+            self.nodes.keys().next().cloned().unwrap()
+        };
+
+        let (candidates, hops) = self.greedy_search(query, k, self.config.search_list_size, start_id);
+
+        let results = candidates.into_iter()
+            .map(|candidate| SearchResult {
+                id: candidate.id,
+                distance: candidate.distance,
+            })
+            .collect();
+
+        Ok((results, hops))
+    }
+
+    /// Run many independent queries concurrently on a rayon thread pool, each with
+    /// its own [`SearchBuffer`] so workers never contend over scratch state.
+    ///
+    /// Results are written into disjoint rows of the returned `Vec`, one per query
+    /// in input order. Aggregation is all-or-nothing: if any query errors, every
+    /// query still runs to completion, then the first error encountered is
+    /// returned rather than handing back a partially-populated result set.
+    ///
+    /// `max_threads` caps the pool size, so host applications that manage their
+    /// own threads can bound how many workers this call may spin up. `None` uses
+    /// rayon's global pool (sized to the number of logical CPUs).
+    #[cfg(feature = "unsafe_opt")]
+    pub fn search_batch(
+        &self,
+        queries: &[Vec<f32>],
+        k: usize,
+        beam_width: usize,
+        max_threads: Option<usize>,
+    ) -> DiskAnnResult<Vec<Vec<SearchResult>>> {
+        use rayon::prelude::*;
+
+        let run = || -> Vec<DiskAnnResult<Vec<SearchResult>>> {
+            queries
+                .par_iter()
+                .map(|query| {
+                    let mut buffer = SearchBuffer::new(self.config.search_list_size);
+                    self.search_with_buffer(query, k, beam_width, &mut buffer)
+                })
+                .collect()
+        };
+
+        let outcomes = match max_threads {
+            Some(num_threads) => {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(num_threads)
+                    .build()
+                    .map_err(|e| {
+                        diskann_core::DiskAnnError::InvalidParameter(format!(
+                            "failed to build thread pool with {} threads: {}",
+                            num_threads, e
+                        ))
+                    })?;
+                pool.install(run)
+            }
+            None => run(),
+        };
+
+        let mut first_err = None;
+        let mut results = Vec::with_capacity(outcomes.len());
+        for outcome in outcomes {
+            match outcome {
+                Ok(rows) => results.push(rows),
+                Err(e) => {
+                    if first_err.is_none() {
+                        first_err = Some(e);
+                    }
+                    results.push(Vec::new());
+                }
+            }
+        }
+
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(results),
+        }
+    }
+}
+
+/// How often (in inserted points) [`IndexBuilder`]'s progress callback fires; it
+/// also always fires on the very last point regardless of this stride.
+const PROGRESS_THROTTLE: usize = 1000;
+
 /// Builder for constructing Vamana indices
-pub struct IndexBuilder<D> {
+///
+/// `R` selects the random generator used to shuffle insertion order (see
+/// [`IndexBuilder::build`]/[`IndexBuilder::build_parallel`]); batch tie-break
+/// seeds come from `config.seed` alone. It defaults to [`ChaCha20Rng`], whose algorithm is
+/// explicitly versioned by the `rand` ecosystem and so reproduces identical
+/// sequences across `rand`/`rand_chacha` releases and target platforms, unlike
+/// [`rand::rngs::StdRng`] (whose algorithm is only guaranteed stable within a
+/// single `rand` minor version).
+pub struct IndexBuilder<D, R = ChaCha20Rng> {
     distance_fn: D,
     config: VamanaConfig,
+    progress: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+    _rng: std::marker::PhantomData<R>,
 }
 
 impl<D: Distance<f32> + Sync + Send> IndexBuilder<D> {
-    /// Create a new index builder
+    /// Create a new index builder that shuffles with the default [`ChaCha20Rng`]
     pub fn new(distance_fn: D) -> Self {
+        Self::with_rng(distance_fn)
+    }
+}
+
+impl<D: Distance<f32> + Sync + Send, R: RngCore + SeedableRng> IndexBuilder<D, R> {
+    /// Create a new index builder that shuffles with the generator `R`, e.g.
+    /// `IndexBuilder::<_, StdRng>::with_rng(distance_fn)`
+    pub fn with_rng(distance_fn: D) -> Self {
         Self {
             distance_fn,
             config: VamanaConfig::default(),
+            progress: None,
+            _rng: std::marker::PhantomData,
         }
     }
 
@@ -708,24 +1244,46 @@ impl<D: Distance<f32> + Sync + Send> IndexBuilder<D> {
         self
     }
 
+    /// Register a progress callback invoked with `(inserted, total)` during `build`/`build_parallel`
+    ///
+    /// `total` is set to the vector count up front. Calls are throttled to
+    /// roughly every [`PROGRESS_THROTTLE`] points (always including the last
+    /// one) so a logging or progress-bar hook stays cheap on large builds
+    /// without this crate depending on one. [`IndexBuilder::build_parallel`]
+    /// aggregates `inserted` across worker threads with an atomic counter and
+    /// reports once per completed batch rather than per point.
+    pub fn on_progress(mut self, callback: impl Fn(usize, usize) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
     /// Build the index from a collection of vectors
     pub fn build(self, vectors: Vec<(VectorId, Vec<f32>)>) -> DiskAnnResult<VamanaIndex<D>> {
+        let progress = self.progress.clone();
         let mut index = VamanaIndex::new(self.distance_fn, self.config.clone());
-        
+
         // Use deterministic ordering based on seed
-        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut rng = R::seed_from_u64(self.config.seed);
         let mut shuffled_vectors = vectors;
-        
+
         // Shuffle for better construction quality
         for i in (1..shuffled_vectors.len()).rev() {
             let j = rng.gen_range(0..=i);
             shuffled_vectors.swap(i, j);
         }
 
+        let total = shuffled_vectors.len();
+
         // Sequential insertion during construction for simplicity
         // In practice, we could use parallel construction with careful synchronization
-        for (id, vector) in shuffled_vectors {
+        for (inserted, (id, vector)) in shuffled_vectors.into_iter().enumerate() {
             index.insert_node(id, vector)?;
+            let inserted = inserted + 1;
+            if let Some(callback) = &progress {
+                if inserted == total || inserted % PROGRESS_THROTTLE == 0 {
+                    callback(inserted, total);
+                }
+            }
         }
 
         #[cfg(feature = "unsafe_opt")]
@@ -739,68 +1297,358 @@ impl<D: Distance<f32> + Sync + Send> IndexBuilder<D> {
         Ok(index)
     }
 
-    /// Build the index with parallel edge refinement (available with unsafe_opt feature)
+    /// Build the index with genuine concurrent construction (available with unsafe_opt feature)
+    ///
+    /// Each node's adjacency lives behind its own [`std::sync::RwLock`], so workers
+    /// insert their assigned points across a rayon pool without a global lock:
+    /// a read lock lets a worker run [`VamanaIndex::greedy_search`]-style traversal
+    /// and [`VamanaIndex::robust_prune`]-style occlusion pruning against whatever
+    /// edges are currently installed, and write locks install the new forward edges
+    /// plus reciprocal back-edges on the chosen neighbors (re-pruning any neighbor
+    /// that overflows `max_degree`). Points are dealt round-robin across
+    /// `rayon::current_num_threads()` batches (point *i* goes to batch
+    /// `i % threads`) rather than contiguous chunks, so a batch never ends up
+    /// stuck with a single pathologically dense, contiguous region of the
+    /// (already seed-shuffled) insertion order; each batch runs as one rayon
+    /// task per point.
+    ///
+    /// Because two points inserted concurrently can each read the graph before the
+    /// other's edges are installed, the exact edge set a point prunes against can
+    /// still depend on scheduling. What *is* independent of scheduling is the
+    /// tie-break order `concurrent_robust_prune` uses for same-distance candidates,
+    /// which is derived purely from `config.seed`, the batch index and the
+    /// candidate ids rather than from insertion order.
     #[cfg(feature = "unsafe_opt")]
     pub fn build_parallel(self, vectors: Vec<(VectorId, Vec<f32>)>) -> DiskAnnResult<VamanaIndex<D>> {
         use rayon::prelude::*;
-        
-        let mut index = VamanaIndex::new(self.distance_fn, self.config.clone());
-        
-        // Use deterministic ordering based on seed
-        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        use std::sync::RwLock;
+
+        let config = self.config.clone();
+        let progress = self.progress.clone();
+        let distance_fn = self.distance_fn;
+
+        // Use deterministic ordering based on seed, same as `build`.
+        let mut rng = R::seed_from_u64(config.seed);
         let mut shuffled_vectors = vectors;
-        
-        // Shuffle for better construction quality with deterministic seed
         for i in (1..shuffled_vectors.len()).rev() {
             let j = rng.gen_range(0..=i);
             shuffled_vectors.swap(i, j);
         }
 
-        // For parallel construction, we need to be more careful about synchronization
-        // This is a simplified version - in practice, you'd use more sophisticated
-        // parallel graph construction algorithms
-        
-        // First pass: add all nodes sequentially
-        for (id, vector) in &shuffled_vectors {
-            let node = GraphNode::new(*id, vector.clone());
-            index.nodes.insert(*id, node);
+        if shuffled_vectors.is_empty() {
+            return Ok(VamanaIndex::new(distance_fn, config));
         }
 
-        // Second pass: compute edges in parallel batches with deterministic seeds
+        // Every node starts with empty adjacency behind its own lock, so workers
+        // never contend over any node but the ones they're actively touching.
+        let nodes: HashMap<VectorId, RwLock<GraphNode>> = shuffled_vectors.iter()
+            .map(|(id, vector)| (*id, RwLock::new(GraphNode::new(*id, vector.clone()))))
+            .collect();
+
         let node_ids: Vec<VectorId> = shuffled_vectors.iter().map(|(id, _)| *id).collect();
-        let batch_size = std::cmp::max(1, node_ids.len() / rayon::current_num_threads());
-        
-        for (batch_idx, batch) in node_ids.chunks(batch_size).enumerate() {
-            // Use batch-specific seed for determinism
-            let batch_seed = self.config.seed.wrapping_add(batch_idx as u64);
-            
-            // Process batch in parallel
-            let edge_updates: Vec<(VectorId, Vec<VectorId>)> = batch.par_iter()
-                .map(|&node_id| {
-                    // Create a thread-local RNG with deterministic seed
-                    let _thread_rng = StdRng::seed_from_u64(
-                        batch_seed.wrapping_add(node_id as u64)
-                    );
-                    
-                    // In a real implementation, this would compute optimal edges
-                    // For now, we'll just return empty edges as a placeholder
-                    (node_id, Vec::new())
-                })
-                .collect();
-                
-            // Apply edge updates sequentially to maintain consistency
-            for (node_id, new_edges) in edge_updates {
-                if let Some(node) = index.nodes.get_mut(&node_id) {
-                    node.neighbors = new_edges;
+
+        // Deal the shuffled order round-robin across `threads` batches (point `i`
+        // goes to batch `i % threads`) instead of contiguous `chunks`, so each
+        // batch gets an interleaved slice of the dataset rather than being stuck
+        // with a single, possibly pathologically dense, contiguous region.
+        let threads = rayon::current_num_threads();
+        let mut batches: Vec<Vec<VectorId>> = vec![Vec::new(); threads];
+        for (i, &id) in node_ids.iter().enumerate() {
+            batches[i % threads].push(id);
+        }
+
+        let progress_total = node_ids.len();
+        let inserted_counter = std::sync::atomic::AtomicUsize::new(0);
+
+        for (batch_idx, batch) in batches.iter().enumerate() {
+            // Batch-specific seed, independent of thread scheduling, used to
+            // tie-break equal-distance candidates during pruning.
+            let batch_seed = config.seed.wrapping_add(batch_idx as u64);
+
+            batch.par_iter().for_each(|&id| {
+                let vector = nodes[&id].read().unwrap().vector.clone();
+
+                let candidates = Self::concurrent_greedy_search(
+                    &nodes, &distance_fn, &vector, config.search_list_size, config.search_list_size, id,
+                );
+                let neighbors = Self::concurrent_robust_prune(&nodes, &distance_fn, &candidates, &config, batch_seed);
+
+                nodes[&id].write().unwrap().neighbors = neighbors.clone();
+
+                // Reciprocal back-edges, re-pruning any neighbor that overflows `max_degree`.
+                for neighbor_id in neighbors {
+                    let needs_pruning = {
+                        let mut neighbor = nodes[&neighbor_id].write().unwrap();
+                        if !neighbor.neighbors.contains(&id) {
+                            neighbor.neighbors.push(id);
+                        }
+                        neighbor.neighbors.len() > config.max_degree
+                    };
+
+                    if needs_pruning {
+                        let (neighbor_vector, neighbor_edges) = {
+                            let neighbor = nodes[&neighbor_id].read().unwrap();
+                            (neighbor.vector.clone(), neighbor.neighbors.clone())
+                        };
+                        let neighbor_candidates: Vec<Candidate> = neighbor_edges.iter()
+                            .map(|&nb_id| Candidate {
+                                id: nb_id,
+                                distance: nodes.get(&nb_id)
+                                    .map(|nb| distance_fn.distance(&neighbor_vector, &nb.read().unwrap().vector))
+                                    .unwrap_or(f32::INFINITY),
+                            })
+                            .collect();
+                        let pruned = Self::concurrent_robust_prune(
+                            &nodes, &distance_fn, &neighbor_candidates, &config, batch_seed,
+                        );
+                        nodes[&neighbor_id].write().unwrap().neighbors = pruned;
+                    }
                 }
+
+                inserted_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            });
+
+            if let Some(callback) = &progress {
+                let inserted = inserted_counter.load(std::sync::atomic::Ordering::Relaxed);
+                callback(inserted, progress_total);
             }
         }
 
-        // Update start node
+        let final_nodes: HashMap<VectorId, GraphNode> = nodes.into_iter()
+            .map(|(id, lock)| (id, lock.into_inner().unwrap()))
+            .collect();
+
+        let mut index = VamanaIndex::new(distance_fn, config);
+        index.nodes = final_nodes;
         index.start_node = index.find_medoid();
-        
+
         Ok(index)
     }
+
+    /// [`VamanaIndex::greedy_search`], but reading node adjacency through the
+    /// per-node `RwLock`s that [`IndexBuilder::build_parallel`] builds against
+    /// instead of a plain `&self.nodes` map.
+    #[cfg(feature = "unsafe_opt")]
+    fn concurrent_greedy_search(
+        nodes: &HashMap<VectorId, std::sync::RwLock<GraphNode>>,
+        distance_fn: &D,
+        query: &[f32],
+        k: usize,
+        beam_width: usize,
+        start_id: VectorId,
+    ) -> Vec<Candidate> {
+        let actual_start_id = if nodes.contains_key(&start_id) {
+            start_id
+        } else if let Some(&first_id) = nodes.keys().next() {
+            first_id
+        } else {
+            return Vec::new();
+        };
+
+        let capacity = beam_width.max(k);
+        let mut list: Vec<Candidate> = Vec::with_capacity(capacity + 1);
+        let mut expanded: Vec<bool> = Vec::with_capacity(capacity + 1);
+        let mut seen = HashSet::new();
+
+        let start_distance = distance_fn.distance(query, &nodes[&actual_start_id].read().unwrap().vector);
+        insert_sorted_capped(&mut list, &mut expanded, Candidate { id: actual_start_id, distance: start_distance }, capacity);
+        seen.insert(actual_start_id);
+
+        while let Some(next_idx) = expanded.iter().position(|&is_expanded| !is_expanded) {
+            expanded[next_idx] = true;
+            let current_id = list[next_idx].id;
+
+            let neighbor_ids = match nodes.get(&current_id) {
+                Some(node) => node.read().unwrap().neighbors.clone(),
+                None => continue,
+            };
+
+            for neighbor_id in neighbor_ids {
+                if seen.insert(neighbor_id) {
+                    if let Some(neighbor_lock) = nodes.get(&neighbor_id) {
+                        let distance = distance_fn.distance(query, &neighbor_lock.read().unwrap().vector);
+                        insert_sorted_capped(&mut list, &mut expanded, Candidate { id: neighbor_id, distance }, capacity);
+                    }
+                }
+            }
+        }
+
+        list
+    }
+
+    /// [`VamanaIndex::robust_prune_scheduled`], but reading node vectors through
+    /// the per-node `RwLock`s `build_parallel` builds against, and breaking ties
+    /// between equal-distance candidates using a hash of `(tie_break_seed, id)`
+    /// instead of insertion order, so the chosen neighbor set doesn't depend on
+    /// which worker happened to install a competing edge first.
+    #[cfg(feature = "unsafe_opt")]
+    fn concurrent_robust_prune(
+        nodes: &HashMap<VectorId, std::sync::RwLock<GraphNode>>,
+        distance_fn: &D,
+        candidates: &[Candidate],
+        config: &VamanaConfig,
+        tie_break_seed: u64,
+    ) -> Vec<VectorId> {
+        let mut result: Vec<VectorId> = Vec::new();
+        for round_alpha in config.alpha_schedule() {
+            if result.len() >= config.max_degree {
+                break;
+            }
+            for id in Self::concurrent_robust_prune_once(
+                nodes, distance_fn, candidates, round_alpha, config.max_degree, tie_break_seed,
+            ) {
+                if result.len() >= config.max_degree {
+                    break;
+                }
+                if !result.contains(&id) {
+                    result.push(id);
+                }
+            }
+        }
+        result
+    }
+
+    #[cfg(feature = "unsafe_opt")]
+    fn concurrent_robust_prune_once(
+        nodes: &HashMap<VectorId, std::sync::RwLock<GraphNode>>,
+        distance_fn: &D,
+        candidates: &[Candidate],
+        alpha: f32,
+        max_degree: usize,
+        tie_break_seed: u64,
+    ) -> Vec<VectorId> {
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        let mut remaining: Vec<Candidate> = candidates.to_vec();
+        remaining.sort_by(|a, b| {
+            a.distance.partial_cmp(&b.distance)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| tie_break_hash(tie_break_seed, a.id).cmp(&tie_break_hash(tie_break_seed, b.id)))
+        });
+
+        let mut pruned = Vec::new();
+        while !remaining.is_empty() && pruned.len() < max_degree {
+            let best = remaining.remove(0);
+            pruned.push(best.id);
+
+            let Some(best_vector) = nodes.get(&best.id).map(|n| n.read().unwrap().vector.clone()) else { continue };
+
+            remaining.retain(|candidate| {
+                let Some(candidate_vector) = nodes.get(&candidate.id).map(|n| n.read().unwrap().vector.clone()) else { return false };
+                let dist_best_to_candidate = distance_fn.distance(&best_vector, &candidate_vector);
+                alpha * dist_best_to_candidate > candidate.distance
+            });
+        }
+
+        pruned
+    }
+}
+
+/// Deterministic, scheduling-independent ordering key for breaking ties between
+/// equal-distance candidates during concurrent pruning (splitmix64-style mix of
+/// the seed and the id)
+#[cfg(feature = "unsafe_opt")]
+fn tie_break_hash(seed: u64, id: VectorId) -> u64 {
+    let mut z = seed.wrapping_add(id as u64).wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Read-only, cache-friendly view of a [`VamanaIndex`] produced by [`VamanaIndex::compact`]
+///
+/// Every node's neighbor list lives in one shared `edge_data` vector, sliced
+/// per node by `edge_ranges`, instead of each node owning its own `Vec`. See
+/// [`VamanaIndex::compact`] for the tradeoff this makes.
+pub struct CompactVamanaIndex<D> {
+    vectors: HashMap<VectorId, Vec<f32>>,
+    edge_data: Vec<VectorId>,
+    edge_ranges: HashMap<VectorId, (usize, usize)>,
+    distance_fn: D,
+    config: VamanaConfig,
+    start_node: Option<VectorId>,
+}
+
+impl<D: Distance<f32> + Sync + Send> CompactVamanaIndex<D> {
+    /// Number of vectors in the index
+    pub fn size(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn neighbors(&self, id: VectorId) -> &[VectorId] {
+        match self.edge_ranges.get(&id) {
+            Some(&(start, end)) => &self.edge_data[start..end],
+            None => &[],
+        }
+    }
+
+    /// Same GreedySearch as [`VamanaIndex::greedy_search`], but reading
+    /// neighbors out of the flat `edge_data` slice instead of a per-node `Vec`
+    fn greedy_search(&self, query: &[f32], k: usize, beam_width: usize, start_id: VectorId) -> Vec<Candidate> {
+        let actual_start_id = if self.vectors.contains_key(&start_id) {
+            start_id
+        } else if let Some(&first_id) = self.vectors.keys().next() {
+            first_id
+        } else {
+            return Vec::new();
+        };
+
+        let capacity = beam_width.max(k);
+        let mut list: Vec<Candidate> = Vec::with_capacity(capacity + 1);
+        let mut expanded: Vec<bool> = Vec::with_capacity(capacity + 1);
+        let mut seen = HashSet::new();
+
+        let start_distance = self.distance_fn.distance(query, &self.vectors[&actual_start_id]);
+        insert_sorted_capped(&mut list, &mut expanded, Candidate { id: actual_start_id, distance: start_distance }, capacity);
+        seen.insert(actual_start_id);
+
+        while let Some(next_idx) = expanded.iter().position(|&is_expanded| !is_expanded) {
+            expanded[next_idx] = true;
+            let current_id = list[next_idx].id;
+
+            for &neighbor_id in self.neighbors(current_id) {
+                if seen.insert(neighbor_id) {
+                    if let Some(neighbor_vector) = self.vectors.get(&neighbor_id) {
+                        let distance = self.distance_fn.distance(query, neighbor_vector);
+                        insert_sorted_capped(&mut list, &mut expanded, Candidate { id: neighbor_id, distance }, capacity);
+                    }
+                }
+            }
+        }
+
+        list.truncate(k);
+        list
+    }
+}
+
+impl<D: Distance<f32> + Sync + Send> Search<f32> for CompactVamanaIndex<D> {
+    fn search(&self, query: &[f32], k: usize) -> DiskAnnResult<Vec<SearchResult>> {
+        self.search_with_beam(query, k, self.config.search_list_size)
+    }
+
+    fn search_with_beam(&self, query: &[f32], k: usize, beam_width: usize) -> DiskAnnResult<Vec<SearchResult>> {
+        if self.vectors.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let start_id = match self.start_node {
+            Some(start) if self.vectors.contains_key(&start) => start,
+            _ => *self.vectors.keys().next().unwrap(),
+        };
+
+        let candidates = self.greedy_search(query, k, beam_width, start_id);
+
+        let results = candidates.into_iter()
+            .map(|candidate| SearchResult {
+                id: candidate.id,
+                distance: candidate.distance,
+            })
+            .collect();
+
+        Ok(results)
+    }
 }
 
 /// Basic graph-based index implementation (legacy)
@@ -830,11 +1678,21 @@ impl Index<f32> for GraphIndex {
         Ok(())
     }
     
+    /// Remove a node and strip it out of every other node's neighbor list
+    ///
+    /// This is the plain, no-frills removal for the legacy, edgeless
+    /// `GraphIndex`; it has no beam search to repair connectivity with and no
+    /// tombstone/consolidation pass. For FreshVamana-style incremental
+    /// inserts, lazy deletes, and batched consolidation, use
+    /// [`VamanaIndex::delete`] and [`VamanaIndex::consolidate`] instead.
     fn remove(&mut self, id: VectorId) -> DiskAnnResult<()> {
         self.nodes.remove(&id);
+        for node in self.nodes.values_mut() {
+            node.neighbors.retain(|&neighbor_id| neighbor_id != id);
+        }
         Ok(())
     }
-    
+
     fn size(&self) -> usize {
         self.nodes.len()
     }
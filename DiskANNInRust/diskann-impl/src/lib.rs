@@ -9,11 +9,28 @@ pub mod graph;
 /// Search algorithm implementations
 pub mod search;
 
-pub use graph::{VamanaIndex, IndexBuilder, VamanaConfig};
+/// Ground-truth computation and recall evaluation
+pub mod eval;
+
+/// On-disk, memory-mapped companion to [`VamanaIndex`] for indices larger than memory
+pub mod disk_graph;
+
+/// Sharded, lock-striped concurrent index (see [`ShardedConcurrentIndex`])
+pub mod concurrent_index;
+
+/// Hand-optimized, architecture-specific hot path implementations
+pub mod optimized;
+
+pub use graph::{VamanaIndex, IndexBuilder, VamanaConfig, CompactVamanaIndex};
+pub use eval::{compute_groundtruth, recall_at_k, recall_at_k_from_ids, GroundTruthNeighbor};
+pub use disk_graph::{DiskVamanaIndex, DiskVamanaConfig, MetricTag, peek_metric_tag};
+pub use concurrent_index::ShardedConcurrentIndex;
+pub use optimized::{l2_distance, current_backend, DistanceBackend};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use diskann_core::vectors::VectorId;
     use diskann_traits::{distance::EuclideanDistance, index::Index, search::Search};
     use rand::{Rng, SeedableRng};
     use rand::rngs::StdRng;
@@ -255,6 +272,112 @@ mod tests {
         assert_eq!(index1.average_degree(), index2.average_degree());
     }
 
+    #[test]
+    fn test_lazy_delete_and_consolidate() {
+        let distance_fn = EuclideanDistance;
+
+        let vectors = vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![0.0, 1.0]),
+            (3, vec![1.0, 1.0]),
+            (4, vec![0.5, 0.5]),
+        ];
+
+        let mut index = IndexBuilder::new(distance_fn)
+            .max_degree(3)
+            .search_list_size(5)
+            .seed(42)
+            .build(vectors)
+            .unwrap();
+
+        assert_eq!(index.size(), 5);
+
+        // Lazy delete: the node is gone from search results but the size
+        // (and the node itself) is unaffected until consolidation
+        index.delete(4).unwrap();
+        assert!(index.is_tombstoned(4));
+        assert_eq!(index.size(), 5);
+
+        let query = vec![0.4, 0.4];
+        let results = index.search(&query, 5).unwrap();
+        assert!(results.iter().all(|r| r.id != 4));
+
+        // Consolidation repairs the edges and reclaims the tombstoned slot
+        index.consolidate().unwrap();
+        assert_eq!(index.size(), 4);
+        assert!(!index.is_tombstoned(4));
+
+        let results = index.search(&query, 4).unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.id != 4));
+    }
+
+    #[test]
+    fn test_tombstoned_node_still_traversed_but_excluded() {
+        // A chain where the only path from the start node to the far end passes
+        // through a tombstoned node: beam_search must still walk through it to
+        // reach the far node, even though the tombstoned node itself is never
+        // returned as a result.
+        let distance_fn = EuclideanDistance;
+        let vectors = vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![2.0, 0.0]),
+        ];
+
+        let mut index = IndexBuilder::new(distance_fn)
+            .max_degree(2)
+            .search_list_size(4)
+            .seed(42)
+            .build(vectors)
+            .unwrap();
+
+        index.delete(1).unwrap();
+        assert!(index.is_tombstoned(1));
+
+        let query = vec![2.0, 0.0];
+        let results = index.search(&query, 3).unwrap();
+        assert!(results.iter().any(|r| r.id == 2), "node 2 should still be reachable through tombstoned node 1");
+        assert!(results.iter().all(|r| r.id != 1), "tombstoned node 1 must not appear in results");
+    }
+
+    #[cfg(feature = "unsafe_opt")]
+    #[test]
+    fn test_search_batch_matches_sequential_search() {
+        let distance_fn = EuclideanDistance;
+
+        let vectors = vec![
+            (0, vec![1.0, 0.0]),
+            (1, vec![0.0, 1.0]),
+            (2, vec![-1.0, 0.0]),
+            (3, vec![0.0, -1.0]),
+            (4, vec![0.5, 0.5]),
+        ];
+
+        let index = IndexBuilder::new(distance_fn)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(42)
+            .build(vectors)
+            .unwrap();
+
+        let queries = vec![
+            vec![0.9, 0.1],
+            vec![-0.9, 0.1],
+            vec![0.1, -0.9],
+        ];
+
+        let batch_results = index.search_batch(&queries, 2, 8, Some(2)).unwrap();
+        assert_eq!(batch_results.len(), queries.len());
+
+        for (query, rows) in queries.iter().zip(batch_results.iter()) {
+            let sequential = index.search(query, 2).unwrap();
+            assert_eq!(rows.len(), sequential.len());
+            assert_eq!(rows[0].id, sequential[0].id);
+        }
+    }
+
     #[cfg(feature = "unsafe_opt")]
     #[test]
     fn test_parallel_construction() {
@@ -279,10 +402,197 @@ mod tests {
             .unwrap();
 
         assert_eq!(index.size(), 8);
-        
+
         // Parallel construction should produce a valid index
         let query = vec![0.1, 0.1];
         let results = index.search(&query, 3).unwrap();
         assert!(!results.is_empty());
     }
+
+    #[cfg(feature = "unsafe_opt")]
+    #[test]
+    fn test_build_batch_produces_valid_index() {
+        let distance_fn = EuclideanDistance;
+
+        let vectors = vec![
+            (0, vec![1.0, 0.0]),
+            (1, vec![0.0, 1.0]),
+            (2, vec![-1.0, 0.0]),
+            (3, vec![0.0, -1.0]),
+            (4, vec![0.5, 0.5]),
+            (5, vec![-0.5, 0.5]),
+            (6, vec![0.5, -0.5]),
+            (7, vec![-0.5, -0.5]),
+        ];
+
+        let config = VamanaConfig {
+            max_degree: 4,
+            search_list_size: 8,
+            seed: 42,
+            alpha: 1.2,
+        };
+        let mut index = VamanaIndex::new(distance_fn, config);
+        index.build_batch(vectors).unwrap();
+
+        assert_eq!(index.size(), 8);
+
+        // Every node should have picked up some neighbors during refinement
+        let degrees = index.degree_distribution();
+        assert!(degrees.iter().all(|&d| d <= 4));
+        assert!(degrees.iter().any(|&d| d > 0));
+
+        let query = vec![0.1, 0.1];
+        let results = index.search(&query, 3).unwrap();
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_alpha_schedule_adds_edges_over_single_round() {
+        // With alpha > 1.0, robust_prune_scheduled runs an alpha = 1.0 round plus the
+        // configured-alpha round and unions both, so it should never produce fewer edges
+        // than a single round at alpha = 1.0 would.
+        let points = vec![
+            (0, vec![0.0, 0.0]),
+            (1, vec![1.0, 0.0]),
+            (2, vec![2.0, 0.0]),
+            (3, vec![3.0, 0.0]),
+            (4, vec![4.0, 0.0]),
+            (5, vec![5.0, 0.0]),
+        ];
+
+        let scheduled_config = VamanaConfig {
+            max_degree: 4,
+            search_list_size: 8,
+            seed: 7,
+            alpha: 2.0,
+        };
+        let mut scheduled_index = VamanaIndex::new(EuclideanDistance, scheduled_config);
+        for (id, vector) in &points {
+            scheduled_index.add(*id, vector.clone()).unwrap();
+        }
+
+        let single_round_config = VamanaConfig {
+            max_degree: 4,
+            search_list_size: 8,
+            seed: 7,
+            alpha: 1.0,
+        };
+        let mut single_round_index = VamanaIndex::new(EuclideanDistance, single_round_config);
+        for (id, vector) in &points {
+            single_round_index.add(*id, vector.clone()).unwrap();
+        }
+
+        let scheduled_edges: usize = scheduled_index.degree_distribution().iter().sum();
+        let single_round_edges: usize = single_round_index.degree_distribution().iter().sum();
+        assert!(scheduled_edges >= single_round_edges);
+
+        // Every degree must still respect max_degree even with two unioned rounds
+        assert!(scheduled_index.degree_distribution().iter().all(|&d| d <= 4));
+    }
+
+    #[test]
+    fn test_colocated_duplicates_stay_reachable() {
+        let distance_fn = EuclideanDistance;
+        let config = VamanaConfig {
+            max_degree: 8,
+            search_list_size: 16,
+            seed: 42,
+            alpha: 1.2,
+        };
+        let mut index = VamanaIndex::new(distance_fn, config);
+
+        // A handful of distinct anchor points, followed by a large cluster of
+        // exact duplicates at a single location.
+        let anchors = vec![
+            vec![10.0, 0.0],
+            vec![-10.0, 0.0],
+            vec![0.0, 10.0],
+            vec![0.0, -10.0],
+        ];
+        let mut next_id: VectorId = 0;
+        for vector in anchors {
+            index.add(next_id, vector).unwrap();
+            next_id += 1;
+        }
+
+        let duplicate_vector = vec![1.0, 1.0];
+        for _ in 0..300 {
+            index.add(next_id, duplicate_vector.clone()).unwrap();
+            next_id += 1;
+        }
+
+        let pivot_id = 0;
+        for target_id in 0..next_id {
+            assert!(
+                index.is_reachable_within_k_hops(target_id, pivot_id, next_id as usize),
+                "node {} is unreachable from pivot {} — co-located cluster got disconnected",
+                target_id,
+                pivot_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_compact_index_matches_search_results() {
+        let distance_fn = EuclideanDistance;
+        let config = VamanaConfig {
+            max_degree: 8,
+            search_list_size: 16,
+            seed: 99,
+            alpha: 1.2,
+        };
+        let mut index = VamanaIndex::new(distance_fn, config);
+
+        let mut rng = StdRng::seed_from_u64(123);
+        let points: Vec<Vec<f32>> = (0..100)
+            .map(|_| vec![rng.gen_range(-50.0..50.0), rng.gen_range(-50.0..50.0)])
+            .collect();
+        for (id, vector) in points.iter().enumerate() {
+            index.add(id as VectorId, vector.clone()).unwrap();
+        }
+
+        let query = vec![3.0, -7.0];
+        let expected = index.search(&query, 5).unwrap();
+
+        let compact = index.compact();
+        assert_eq!(compact.size(), 100);
+        let actual = compact.search(&query, 5).unwrap();
+
+        let expected_ids: Vec<VectorId> = expected.iter().map(|r| r.id).collect();
+        let actual_ids: Vec<VectorId> = actual.iter().map(|r| r.id).collect();
+        assert_eq!(expected_ids, actual_ids);
+    }
+
+    #[test]
+    fn test_build_reports_progress_and_final_total() {
+        use std::sync::{Arc, Mutex};
+
+        let calls: Arc<Mutex<Vec<(usize, usize)>>> = Arc::new(Mutex::new(Vec::new()));
+        let calls_clone = calls.clone();
+
+        let vectors: Vec<(VectorId, Vec<f32>)> = (0..10)
+            .map(|i| (i as VectorId, vec![i as f32, 0.0]))
+            .collect();
+
+        let index = IndexBuilder::new(EuclideanDistance)
+            .on_progress(move |inserted, total| calls_clone.lock().unwrap().push((inserted, total)))
+            .build(vectors)
+            .unwrap();
+
+        assert_eq!(index.size(), 10);
+        let recorded = calls.lock().unwrap();
+        assert_eq!(recorded.last(), Some(&(10, 10)));
+    }
+
+    #[test]
+    fn test_graph_index_remove_drops_the_node() {
+        let mut index = graph::GraphIndex::new();
+        index.add(0, vec![0.0, 0.0]).unwrap();
+        index.add(1, vec![1.0, 0.0]).unwrap();
+        index.add(2, vec![2.0, 0.0]).unwrap();
+
+        index.remove(0).unwrap();
+
+        assert_eq!(index.size(), 2);
+    }
 }
\ No newline at end of file
@@ -5,6 +5,217 @@
 //! documented with safety invariants and performance justification.
 
 use std::arch::x86_64::*;
+use std::sync::OnceLock;
+
+/// The SIMD kernel [`l2_distance`] dispatches to, chosen once per process by
+/// [`current_backend`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceBackend {
+    /// `_mm512_*` intrinsics, 16 floats/iteration (x86_64 only)
+    Avx512,
+    /// `_mm256_*` intrinsics, 8 floats/iteration (x86_64 only)
+    Avx2,
+    /// `vld1q_f32`/`vaddvq_f32` intrinsics, 4 floats/iteration (aarch64 only)
+    Neon,
+    /// Portable scalar fallback, used when no faster backend is available
+    Scalar,
+}
+
+impl DistanceBackend {
+    /// Probe the host CPU for the fastest backend [`l2_distance`] can use
+    fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return DistanceBackend::Avx512;
+            }
+            if is_x86_feature_detected!("avx2") {
+                return DistanceBackend::Avx2;
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return DistanceBackend::Neon;
+            }
+        }
+        DistanceBackend::Scalar
+    }
+}
+
+static BACKEND: OnceLock<DistanceBackend> = OnceLock::new();
+
+/// The backend [`l2_distance`] will dispatch to on this host, detected once
+/// on first use and cached for the remainder of the process
+pub fn current_backend() -> DistanceBackend {
+    *BACKEND.get_or_init(DistanceBackend::detect)
+}
+
+/// Runtime-dispatched Euclidean (L2) distance
+///
+/// Selects the fastest kernel available on the host CPU once via
+/// [`current_backend`] (AVX-512, AVX2, ARM NEON, or a portable scalar
+/// fallback) and caches the choice, so callers get the best implementation
+/// without recompiling per target. Each SIMD kernel handles a tail loop for
+/// lengths not divisible by its lane width.
+///
+/// # Panics
+///
+/// Panics if `a.len() != b.len()`.
+pub fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "Vectors must have same length");
+
+    match current_backend() {
+        DistanceBackend::Avx512 => {
+            #[cfg(target_arch = "x86_64")]
+            {
+                // Safety: `Avx512` is only ever returned by `detect` after
+                // `is_x86_feature_detected!("avx512f")` succeeded.
+                return unsafe { l2_distance_avx512(a, b) };
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            unreachable!("AVX-512 backend can only be selected on x86_64")
+        }
+        DistanceBackend::Avx2 => {
+            #[cfg(target_arch = "x86_64")]
+            {
+                // Safety: `Avx2` is only ever returned by `detect` after
+                // `is_x86_feature_detected!("avx2")` succeeded.
+                return unsafe { l2_distance_avx2(a, b) };
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            unreachable!("AVX2 backend can only be selected on x86_64")
+        }
+        DistanceBackend::Neon => {
+            #[cfg(target_arch = "aarch64")]
+            {
+                // Safety: `Neon` is only ever returned by `detect` after
+                // `is_aarch64_feature_detected!("neon")` succeeded.
+                return unsafe { l2_distance_neon(a, b) };
+            }
+            #[cfg(not(target_arch = "aarch64"))]
+            unreachable!("NEON backend can only be selected on aarch64")
+        }
+        DistanceBackend::Scalar => l2_distance_scalar(a, b),
+    }
+}
+
+/// Portable scalar fallback used by [`l2_distance`] when no SIMD backend is
+/// available on the host
+fn l2_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        let diff = x - y;
+        sum += diff * diff;
+    }
+    sum.sqrt()
+}
+
+/// AVX-512 Euclidean distance kernel, 16 floats/iteration with a scalar tail
+/// loop for the remainder
+///
+/// # Safety
+///
+/// Caller must ensure the host supports AVX-512F (checked at runtime by
+/// [`DistanceBackend::detect`] via `is_x86_feature_detected!("avx512f")`
+/// before this backend is ever selected).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn l2_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let mut sum = _mm512_setzero_ps();
+
+    let chunks = len / 16;
+    for i in 0..chunks {
+        let offset = i * 16;
+        // Safety: `offset + 16 <= len` for every iteration of this loop.
+        let va = _mm512_loadu_ps(a.as_ptr().add(offset));
+        let vb = _mm512_loadu_ps(b.as_ptr().add(offset));
+        let diff = _mm512_sub_ps(va, vb);
+        sum = _mm512_add_ps(sum, _mm512_mul_ps(diff, diff));
+    }
+
+    let mut total = _mm512_reduce_add_ps(sum);
+    for i in (chunks * 16)..len {
+        let diff = a[i] - b[i];
+        total += diff * diff;
+    }
+    total.sqrt()
+}
+
+/// AVX2 Euclidean distance kernel, 8 floats/iteration with a scalar tail
+/// loop for the remainder. Unlike [`euclidean_distance_avx2_unsafe`], this
+/// accepts any vector length, not only multiples of 8.
+///
+/// # Safety
+///
+/// Caller must ensure the host supports AVX2 (checked at runtime by
+/// [`DistanceBackend::detect`] via `is_x86_feature_detected!("avx2")` before
+/// this backend is ever selected).
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn l2_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let mut sum = _mm256_setzero_ps();
+
+    let chunks = len / 8;
+    for i in 0..chunks {
+        let offset = i * 8;
+        // Safety: `offset + 8 <= len` for every iteration of this loop.
+        let va = _mm256_loadu_ps(a.as_ptr().add(offset));
+        let vb = _mm256_loadu_ps(b.as_ptr().add(offset));
+        let diff = _mm256_sub_ps(va, vb);
+        sum = _mm256_add_ps(sum, _mm256_mul_ps(diff, diff));
+    }
+
+    let sum_low = _mm256_castps256_ps128(sum);
+    let sum_high = _mm256_extractf128_ps(sum, 1);
+    let sum_quad = _mm_add_ps(sum_low, sum_high);
+    let sum_dual = _mm_add_ps(sum_quad, _mm_movehl_ps(sum_quad, sum_quad));
+    let sum_single = _mm_add_ss(sum_dual, _mm_shuffle_ps(sum_dual, sum_dual, 1));
+
+    let mut total = _mm_cvtss_f32(sum_single);
+    for i in (chunks * 8)..len {
+        let diff = a[i] - b[i];
+        total += diff * diff;
+    }
+    total.sqrt()
+}
+
+/// ARM NEON Euclidean distance kernel, 4 floats/iteration with a scalar tail
+/// loop for the remainder
+///
+/// # Safety
+///
+/// Caller must ensure the host supports NEON (checked at runtime by
+/// [`DistanceBackend::detect`] via
+/// `std::arch::is_aarch64_feature_detected!("neon")` before this backend is
+/// ever selected).
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn l2_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    use std::arch::aarch64::*;
+
+    let len = a.len();
+    let mut sum = vdupq_n_f32(0.0);
+
+    let chunks = len / 4;
+    for i in 0..chunks {
+        let offset = i * 4;
+        // Safety: `offset + 4 <= len` for every iteration of this loop.
+        let va = vld1q_f32(a.as_ptr().add(offset));
+        let vb = vld1q_f32(b.as_ptr().add(offset));
+        let diff = vsubq_f32(va, vb);
+        sum = vaddq_f32(sum, vmulq_f32(diff, diff));
+    }
+
+    let mut total = vaddvq_f32(sum);
+    for i in (chunks * 4)..len {
+        let diff = a[i] - b[i];
+        total += diff * diff;
+    }
+    total.sqrt()
+}
 
 /// SIMD-optimized Euclidean distance calculation using AVX2
 /// 
@@ -82,6 +293,133 @@ pub unsafe fn euclidean_distance_avx2_unsafe(a: &[f32], b: &[f32]) -> f32 {
     sum_scalar.sqrt()
 }
 
+/// Horizontal-sum an `__m256` the same way [`euclidean_distance_avx2_unsafe`]
+/// does, shared by the dot-product and cosine kernels below
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn horizontal_sum_avx2(v: __m256) -> f32 {
+    let sum_low = _mm256_castps256_ps128(v);
+    let sum_high = _mm256_extractf128_ps(v, 1);
+    let sum_quad = _mm_add_ps(sum_low, sum_high);
+
+    let sum_dual = _mm_add_ps(sum_quad, _mm_movehl_ps(sum_quad, sum_quad));
+    let sum_single = _mm_add_ss(sum_dual, _mm_shuffle_ps(sum_dual, sum_dual, 1));
+
+    _mm_cvtss_f32(sum_single)
+}
+
+/// SIMD-optimized dot product calculation using AVX2 + FMA
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// 1. Uses raw SIMD intrinsics that require proper memory alignment
+/// 2. Assumes vectors have the same length (undefined behavior if not)
+/// 3. Requires AVX2 and FMA support (checked at runtime with
+///    `is_x86_feature_detected!`)
+///
+/// # Performance Justification
+///
+/// Fuses the multiply and accumulate into a single `_mm256_fmadd_ps`
+/// instruction per 8-float chunk, halving the instruction count of the
+/// equivalent separate multiply+add used by [`euclidean_distance_avx2_unsafe`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+pub unsafe fn dot_product_avx2_unsafe(a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len(), "Vectors must have same length");
+    debug_assert!(a.len() % 8 == 0, "Vector length must be multiple of 8 for AVX2");
+
+    let len = a.len();
+    let mut acc = _mm256_setzero_ps();
+
+    let chunks = len / 8;
+    for i in 0..chunks {
+        let offset = i * 8;
+        // Safety: We've verified length compatibility and alignment
+        let va = _mm256_loadu_ps(a.as_ptr().add(offset));
+        let vb = _mm256_loadu_ps(b.as_ptr().add(offset));
+        acc = _mm256_fmadd_ps(va, vb, acc);
+    }
+
+    horizontal_sum_avx2(acc)
+}
+
+/// SIMD-optimized cosine distance calculation using AVX2 + FMA
+///
+/// Computes `1 - dot(a, b) / (‖a‖ * ‖b‖)` in a single pass: the dot product
+/// and both squared norms are accumulated into three separate registers in
+/// the same loop, then reduced with [`horizontal_sum_avx2`] (the same
+/// reduction [`euclidean_distance_avx2_unsafe`] uses).
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// 1. Uses raw SIMD intrinsics that require proper memory alignment
+/// 2. Assumes vectors have the same length (undefined behavior if not)
+/// 3. Requires AVX2 and FMA support (checked at runtime with
+///    `is_x86_feature_detected!`)
+///
+/// # Performance Justification
+///
+/// Fuses the multiply and accumulate into `_mm256_fmadd_ps` for all three
+/// accumulators (dot product, `‖a‖²`, `‖b‖²`), computing the dot product
+/// and both norms in one pass over the data.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+pub unsafe fn cosine_distance_avx2_unsafe(a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len(), "Vectors must have same length");
+    debug_assert!(a.len() % 8 == 0, "Vector length must be multiple of 8 for AVX2");
+
+    let len = a.len();
+    let mut dot_acc = _mm256_setzero_ps();
+    let mut norm_a_acc = _mm256_setzero_ps();
+    let mut norm_b_acc = _mm256_setzero_ps();
+
+    let chunks = len / 8;
+    for i in 0..chunks {
+        let offset = i * 8;
+        // Safety: We've verified length compatibility and alignment
+        let va = _mm256_loadu_ps(a.as_ptr().add(offset));
+        let vb = _mm256_loadu_ps(b.as_ptr().add(offset));
+        dot_acc = _mm256_fmadd_ps(va, vb, dot_acc);
+        norm_a_acc = _mm256_fmadd_ps(va, va, norm_a_acc);
+        norm_b_acc = _mm256_fmadd_ps(vb, vb, norm_b_acc);
+    }
+
+    let dot = horizontal_sum_avx2(dot_acc);
+    let norm_a = horizontal_sum_avx2(norm_a_acc).sqrt();
+    let norm_b = horizontal_sum_avx2(norm_b_acc).sqrt();
+
+    let denom = norm_a * norm_b;
+    if denom == 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / denom
+}
+
+/// Scalar fallback for [`dot_product_avx2_unsafe`], used on hosts without
+/// AVX2/FMA support
+pub fn dot_product_scalar(a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len(), "Vectors must have same length");
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+/// Scalar fallback for [`cosine_distance_avx2_unsafe`], used on hosts
+/// without AVX2/FMA support. Guards against a zero norm by returning 1.0.
+pub fn cosine_distance_scalar(a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len(), "Vectors must have same length");
+
+    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    let denom = norm_a * norm_b;
+    if denom == 0.0 {
+        return 1.0;
+    }
+    1.0 - dot / denom
+}
+
 /// Prefetch memory for graph traversal optimization
 /// 
 /// # Safety
@@ -234,7 +572,47 @@ mod tests {
             assert!((scalar_dist - simd_dist).abs() < 1e-6);
         }
     }
-    
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_dot_product_avx2_accuracy() {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            let a: Vec<f32> = (0..128).map(|i| i as f32 * 0.1).collect();
+            let b: Vec<f32> = (0..128).map(|i| (i as f32 * 0.2) + 0.5).collect();
+
+            let expected = dot_product_scalar(&a, &b);
+            let actual = unsafe { dot_product_avx2_unsafe(&a, &b) };
+
+            assert!((expected - actual).abs() < 1e-3, "expected {}, got {}", expected, actual);
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_cosine_distance_avx2_accuracy() {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            let a: Vec<f32> = (0..128).map(|i| i as f32 * 0.1).collect();
+            let b: Vec<f32> = (0..128).map(|i| (i as f32 * 0.2) + 0.5).collect();
+
+            let expected = cosine_distance_scalar(&a, &b);
+            let actual = unsafe { cosine_distance_avx2_unsafe(&a, &b) };
+
+            assert!((expected - actual).abs() < 1e-5, "expected {}, got {}", expected, actual);
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_cosine_distance_avx2_zero_norm() {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            let a = vec![0.0f32; 8];
+            let b = vec![1.0f32; 8];
+
+            assert_eq!(unsafe { cosine_distance_avx2_unsafe(&a, &b) }, 1.0);
+            assert_eq!(cosine_distance_scalar(&a, &b), 1.0);
+        }
+    }
+
     #[test]
     fn test_unrolled_distance_accuracy() {
         let a = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
@@ -262,4 +640,37 @@ mod tests {
         assert_eq!(idx, 3);
         assert!((dist - 0.9).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_l2_distance_backends_agree() {
+        let a: Vec<f32> = (0..131).map(|i| i as f32 * 0.37).collect();
+        let b: Vec<f32> = (0..131).map(|i| (i as f32 * 0.19) + 1.5).collect();
+
+        let scalar = l2_distance_scalar(&a, &b);
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                let avx2 = unsafe { l2_distance_avx2(&a, &b) };
+                assert!((scalar - avx2).abs() < 1e-5, "AVX2 disagrees with scalar: {} vs {}", avx2, scalar);
+            }
+            if is_x86_feature_detected!("avx512f") {
+                let avx512 = unsafe { l2_distance_avx512(&a, &b) };
+                assert!((scalar - avx512).abs() < 1e-5, "AVX-512 disagrees with scalar: {} vs {}", avx512, scalar);
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                let neon = unsafe { l2_distance_neon(&a, &b) };
+                assert!((scalar - neon).abs() < 1e-5, "NEON disagrees with scalar: {} vs {}", neon, scalar);
+            }
+        }
+
+        // The dispatched entry point must agree with the scalar reference too,
+        // whichever backend the host actually selects.
+        let dispatched = l2_distance(&a, &b);
+        assert!((scalar - dispatched).abs() < 1e-5, "dispatched backend ({:?}) disagrees with scalar: {} vs {}", current_backend(), dispatched, scalar);
+    }
 }
\ No newline at end of file
@@ -1,27 +1,235 @@
 //! Search algorithm implementations
 
-use diskann_core::DiskAnnResult;
-use diskann_traits::search::{Search, SearchResult};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
-/// Greedy search implementation
-pub struct GreedySearch;
+use diskann_core::{structures::GraphNode, vectors::VectorId, DiskAnnResult};
+use diskann_traits::{
+    distance::Distance,
+    search::{Search, SearchResult},
+};
 
-impl GreedySearch {
-    /// Create a new greedy search instance
-    pub fn new() -> Self {
-        Self
+/// A candidate held in the bounded candidate list during greedy search
+#[derive(Debug, Clone)]
+struct Candidate {
+    id: VectorId,
+    distance: f32,
+}
+
+/// Greedy Vamana-style search over an externally supplied graph
+///
+/// Unlike [`crate::graph::VamanaIndex`], which owns its graph and handles
+/// insertion/deletion as well as search, `GreedySearch` is a read-only search
+/// front-end: it takes a fixed set of graph nodes (vectors + adjacency) and a
+/// distance function, and runs the canonical DiskANN greedy beam search
+/// against them. This is useful when the graph is built or loaded elsewhere
+/// (e.g. from disk) and only search behaviour is needed.
+pub struct GreedySearch<D> {
+    nodes: HashMap<VectorId, GraphNode>,
+    distance_fn: D,
+    entry_point: VectorId,
+    search_list_size: usize,
+    /// Reused across calls to avoid per-query allocation
+    visited: RefCell<HashSet<VectorId>>,
+}
+
+impl<D: Distance<f32>> GreedySearch<D> {
+    /// Create a new greedy search instance over the given graph nodes
+    ///
+    /// The entry point is chosen as the medoid of `nodes`: the node with the
+    /// smallest total distance to all other nodes. Returns `None` if `nodes`
+    /// is empty.
+    pub fn new(nodes: HashMap<VectorId, GraphNode>, distance_fn: D, search_list_size: usize) -> Option<Self> {
+        let entry_point = Self::find_medoid(&nodes, &distance_fn)?;
+        Some(Self {
+            nodes,
+            distance_fn,
+            entry_point,
+            search_list_size,
+            visited: RefCell::new(HashSet::new()),
+        })
+    }
+
+    /// Create a new greedy search instance with an explicit entry point
+    pub fn with_entry_point(
+        nodes: HashMap<VectorId, GraphNode>,
+        distance_fn: D,
+        entry_point: VectorId,
+        search_list_size: usize,
+    ) -> Self {
+        Self {
+            nodes,
+            distance_fn,
+            entry_point,
+            search_list_size,
+            visited: RefCell::new(HashSet::new()),
+        }
+    }
+
+    fn find_medoid(nodes: &HashMap<VectorId, GraphNode>, distance_fn: &D) -> Option<VectorId> {
+        let ids: Vec<VectorId> = nodes.keys().cloned().collect();
+        if ids.is_empty() {
+            return None;
+        }
+        if ids.len() == 1 {
+            return Some(ids[0]);
+        }
+
+        let mut best_id = ids[0];
+        let mut best_total = f32::INFINITY;
+        for &candidate_id in &ids {
+            let candidate_vector = &nodes[&candidate_id].vector;
+            let total: f32 = ids
+                .iter()
+                .map(|&other_id| {
+                    if other_id == candidate_id {
+                        0.0
+                    } else {
+                        distance_fn.distance(candidate_vector, &nodes[&other_id].vector)
+                    }
+                })
+                .sum();
+            if total < best_total {
+                best_total = total;
+                best_id = candidate_id;
+            }
+        }
+        Some(best_id)
+    }
+
+    /// Number of nodes in the underlying graph
+    pub fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Run the greedy beam search, returning the closest `k` candidates
+    ///
+    /// Maintains a bounded candidate list of size `list_size` ordered by
+    /// distance to `query`, starting from the fixed entry point. At each step
+    /// the closest unvisited candidate is expanded: its neighbors are scored
+    /// and merged into the candidate list, which is then truncated back to
+    /// `list_size`. The search terminates once every candidate currently in
+    /// the list has been visited.
+    fn greedy_search(&self, query: &[f32], k: usize, list_size: usize) -> Vec<Candidate> {
+        if self.nodes.is_empty() {
+            return Vec::new();
+        }
+
+        let list_size = list_size.max(k).max(1);
+        let mut visited = self.visited.borrow_mut();
+        visited.clear();
+
+        let mut candidates: Vec<Candidate> = Vec::with_capacity(list_size + 1);
+        let entry_distance = self
+            .distance_fn
+            .distance(query, &self.nodes[&self.entry_point].vector);
+        candidates.push(Candidate {
+            id: self.entry_point,
+            distance: entry_distance,
+        });
+
+        loop {
+            let next_unvisited = candidates
+                .iter()
+                .find(|candidate| !visited.contains(&candidate.id))
+                .cloned();
+
+            let Some(current) = next_unvisited else {
+                break;
+            };
+            visited.insert(current.id);
+
+            if let Some(node) = self.nodes.get(&current.id) {
+                for &neighbor_id in &node.neighbors {
+                    if visited.contains(&neighbor_id)
+                        || candidates.iter().any(|c| c.id == neighbor_id)
+                    {
+                        continue;
+                    }
+                    if let Some(neighbor_node) = self.nodes.get(&neighbor_id) {
+                        let distance = self.distance_fn.distance(query, &neighbor_node.vector);
+                        candidates.push(Candidate {
+                            id: neighbor_id,
+                            distance,
+                        });
+                    }
+                }
+            }
+
+            candidates.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+            candidates.truncate(list_size);
+        }
+
+        candidates.truncate(k);
+        candidates
     }
 }
 
-impl Default for GreedySearch {
-    fn default() -> Self {
-        Self::new()
+impl<D: Distance<f32>> Search<f32> for GreedySearch<D> {
+    fn search(&self, query: &[f32], k: usize) -> DiskAnnResult<Vec<SearchResult>> {
+        let candidates = self.greedy_search(query, k, self.search_list_size);
+        Ok(candidates
+            .into_iter()
+            .map(|candidate| SearchResult {
+                id: candidate.id,
+                distance: candidate.distance,
+            })
+            .collect())
+    }
+
+    fn search_with_beam(&self, query: &[f32], k: usize, beam_width: usize) -> DiskAnnResult<Vec<SearchResult>> {
+        let candidates = self.greedy_search(query, k, beam_width);
+        Ok(candidates
+            .into_iter()
+            .map(|candidate| SearchResult {
+                id: candidate.id,
+                distance: candidate.distance,
+            })
+            .collect())
     }
 }
 
-impl Search<f32> for GreedySearch {
-    fn search(&self, _query: &[f32], _k: usize) -> DiskAnnResult<Vec<SearchResult>> {
-        // TODO: Implement actual greedy search algorithm
-        Ok(vec![])
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diskann_traits::distance::EuclideanDistance;
+
+    fn node(id: VectorId, vector: Vec<f32>, neighbors: Vec<VectorId>) -> GraphNode {
+        let mut node = GraphNode::new(id, vector);
+        node.neighbors = neighbors;
+        node
+    }
+
+    #[test]
+    fn finds_nearest_neighbor_along_graph_edges() {
+        let mut nodes = HashMap::new();
+        nodes.insert(0, node(0, vec![0.0, 0.0], vec![1]));
+        nodes.insert(1, node(1, vec![1.0, 0.0], vec![0, 2]));
+        nodes.insert(2, node(2, vec![2.0, 0.0], vec![1]));
+
+        let search = GreedySearch::with_entry_point(nodes, EuclideanDistance, 0, 10);
+        let results = search.search(&[2.1, 0.0], 1).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, 2);
+    }
+
+    #[test]
+    fn empty_graph_returns_no_results() {
+        let search = GreedySearch::with_entry_point(HashMap::new(), EuclideanDistance, 0, 10);
+        let results = search.search(&[0.0, 0.0], 5).unwrap();
+        assert!(results.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn medoid_entry_point_is_computed_when_not_specified() {
+        let mut nodes = HashMap::new();
+        nodes.insert(0, node(0, vec![0.0], vec![1, 2]));
+        nodes.insert(1, node(1, vec![1.0], vec![0]));
+        nodes.insert(2, node(2, vec![-1.0], vec![0]));
+
+        let search = GreedySearch::new(nodes, EuclideanDistance, 10).unwrap();
+        assert_eq!(search.entry_point, 0);
+    }
+}
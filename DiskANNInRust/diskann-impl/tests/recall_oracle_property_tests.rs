@@ -0,0 +1,111 @@
+//! Recall-oracle property test: approximate search vs. brute-force ground truth
+//!
+//! `diskann-core`'s property tests only check algebraic invariants of the distance math;
+//! they never exercise whether the index actually finds near-correct neighbors. This compares
+//! `IndexBuilder`'s beam search against an exhaustive brute-force scan of the same corpus, and
+//! checks both that recall stays above a floor and that widening the beam never makes it worse
+//! for the same corpus/query/seed.
+
+use diskann_impl::IndexBuilder;
+use diskann_traits::distance::{Distance, EuclideanDistance};
+use diskann_traits::search::Search;
+use proptest::prelude::*;
+
+/// Beam widths to probe, smallest to largest -- recall is asserted to be monotonically
+/// non-decreasing across this sequence
+const BEAM_WIDTHS: [usize; 4] = [4, 8, 16, 32];
+
+/// Minimum acceptable recall@k at the largest beam width in [`BEAM_WIDTHS`]
+const MIN_RECALL_AT_MAX_BEAM: f32 = 0.6;
+
+/// Slack added to the k-th nearest distance when deciding whether a returned neighbor
+/// "counts" as correct, so exact distance ties don't produce a false failure just because the
+/// index returned a tied vector the brute-force scan's arbitrary tie-break didn't pick
+const TIE_EPSILON: f32 = 1e-4;
+
+fn arb_corpus_and_query() -> impl Strategy<Value = (Vec<(u32, Vec<f32>)>, Vec<f32>, usize)> {
+    (2usize..=6, 8usize..=24).prop_flat_map(|(dim, corpus_size)| {
+        let component = prop::num::f32::ANY
+            .prop_filter("finite", |x| x.is_finite())
+            .prop_map(|x| x.clamp(-100.0, 100.0));
+        let vector = prop::collection::vec(component, dim..=dim);
+        let corpus = prop::collection::vec(vector.clone(), corpus_size..=corpus_size)
+            .prop_map(|vecs| vecs.into_iter().enumerate().map(|(i, v)| (i as u32, v)).collect());
+        let query = vector;
+        let k = 1usize..=4;
+        (corpus, query, k)
+    })
+}
+
+/// Recall@k of `returned` against the brute-force ground truth over `corpus`, treating any
+/// vector within [`TIE_EPSILON`] of the k-th nearest distance as a correct answer
+fn recall_at_k(
+    corpus: &[(u32, Vec<f32>)],
+    query: &[f32],
+    k: usize,
+    returned: &[u32],
+) -> f32 {
+    let distance_fn = EuclideanDistance;
+
+    let mut distances: Vec<f32> = corpus
+        .iter()
+        .map(|(_, vector)| distance_fn.distance(query, vector))
+        .collect();
+    distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let kth_distance = distances[k.min(distances.len()) - 1];
+    let threshold = kth_distance + TIE_EPSILON;
+
+    let true_neighbor_ids: Vec<u32> = corpus
+        .iter()
+        .filter(|(_, vector)| distance_fn.distance(query, vector) <= threshold)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let hits = returned.iter().filter(|id| true_neighbor_ids.contains(id)).count();
+    hits as f32 / k as f32
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// Recall stays above a floor at the largest beam width, and never regresses as the beam
+    /// widens for the same corpus/query/seed
+    #[test]
+    fn test_recall_meets_floor_and_is_monotonic_in_beam_width(
+        (corpus, query, k) in arb_corpus_and_query()
+    ) {
+        let k = k.min(corpus.len());
+        let max_degree = corpus.len().min(16).max(2);
+
+        let index = IndexBuilder::new(EuclideanDistance)
+            .max_degree(max_degree)
+            .search_list_size(corpus.len())
+            .seed(42)
+            .build(corpus.clone())
+            .unwrap();
+
+        let mut previous_recall = 0.0f32;
+        for (i, &beam_width) in BEAM_WIDTHS.iter().enumerate() {
+            let results = index.search_with_beam(&query, k, beam_width).unwrap();
+            let returned_ids: Vec<u32> = results.iter().map(|r| r.id).collect();
+            let recall = recall_at_k(&corpus, &query, k, &returned_ids);
+
+            prop_assert!(
+                recall >= previous_recall - TIE_EPSILON,
+                "recall regressed when widening the beam: beam={} recall={} < previous_recall={}",
+                beam_width, recall, previous_recall
+            );
+
+            if i == BEAM_WIDTHS.len() - 1 {
+                prop_assert!(
+                    recall >= MIN_RECALL_AT_MAX_BEAM,
+                    "recall@{} at beam_width={} fell below the floor: {} < {}",
+                    k, beam_width, recall, MIN_RECALL_AT_MAX_BEAM
+                );
+            }
+
+            previous_recall = recall;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Apache Arrow IPC import/export for vector datasets
+//!
+//! Encodes a vector dataset as a single `FixedSizeList<Float32>` column
+//! (alongside a `UInt32` id column) in an Arrow IPC stream, so DiskANN can
+//! interoperate with the broader columnar ecosystem (Arrow Flight, Parquet
+//! pipelines, Polars/DataFusion producers) without a bespoke interchange
+//! format. The raw `.bin` format from [`crate::format`] remains the default
+//! for on-disk index storage; this is an import/export path.
+
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use arrow::array::{Array, ArrayRef, FixedSizeListArray, Float32Array, UInt32Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+
+use diskann_core::vectors::Vector;
+
+const ID_FIELD_NAME: &str = "id";
+const VECTOR_FIELD_NAME: &str = "vector";
+const VECTOR_ITEM_FIELD_NAME: &str = "item";
+
+/// Encode `(id, vector)` pairs as a single-batch Arrow IPC stream
+///
+/// All vectors must share the same dimension, matching the
+/// dimension-consistency the raw `.bin` writer enforces.
+pub fn write_vectors_arrow<W: Write>(writer: &mut W, vectors: &[(u32, Vector)]) -> Result<usize> {
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
+    }
+
+    let num_dimensions = vectors[0].1.len();
+    for (id, vector) in vectors {
+        if vector.len() != num_dimensions {
+            anyhow::bail!(
+                "Vector {} has {} dimensions, expected {}",
+                id,
+                vector.len(),
+                num_dimensions
+            );
+        }
+    }
+
+    let ids: UInt32Array = vectors.iter().map(|(id, _)| *id).collect();
+    let flat: Float32Array = vectors
+        .iter()
+        .flat_map(|(_, vector)| vector.iter().copied())
+        .collect();
+
+    let item_field = Arc::new(Field::new(VECTOR_ITEM_FIELD_NAME, DataType::Float32, false));
+    let list_array = FixedSizeListArray::try_new(
+        item_field.clone(),
+        num_dimensions as i32,
+        Arc::new(flat),
+        None,
+    )
+    .context("Failed to build FixedSizeList array")?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new(ID_FIELD_NAME, DataType::UInt32, false),
+        Field::new(
+            VECTOR_FIELD_NAME,
+            DataType::FixedSizeList(item_field, num_dimensions as i32),
+            false,
+        ),
+    ]));
+
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(ids) as ArrayRef, Arc::new(list_array) as ArrayRef],
+    )
+    .context("Failed to build Arrow record batch")?;
+
+    let mut stream_writer =
+        StreamWriter::try_new(writer, &schema).context("Failed to create Arrow IPC stream writer")?;
+    stream_writer
+        .write(&batch)
+        .context("Failed to write Arrow record batch")?;
+    stream_writer
+        .finish()
+        .context("Failed to finish Arrow IPC stream")?;
+
+    Ok(vectors.len() * (4 + num_dimensions * 4))
+}
+
+/// Decode an Arrow IPC stream written by [`write_vectors_arrow`] back into `(id, vector)` pairs
+pub fn read_vectors_arrow<R: Read>(reader: R) -> Result<Vec<(u32, Vector)>> {
+    let stream_reader =
+        StreamReader::try_new(reader, None).context("Failed to create Arrow IPC stream reader")?;
+
+    let mut result = Vec::new();
+    for batch in stream_reader {
+        let batch = batch.context("Failed to read Arrow record batch")?;
+
+        let id_col = batch
+            .column_by_name(ID_FIELD_NAME)
+            .context("Missing id column")?
+            .as_any()
+            .downcast_ref::<UInt32Array>()
+            .context("id column has unexpected type")?;
+
+        let vector_col = batch
+            .column_by_name(VECTOR_FIELD_NAME)
+            .context("Missing vector column")?
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .context("vector column has unexpected type")?;
+
+        for row in 0..batch.num_rows() {
+            let id = id_col.value(row);
+            let list_value = vector_col.value(row);
+            let floats = list_value
+                .as_any()
+                .downcast_ref::<Float32Array>()
+                .context("vector element has unexpected type")?;
+            let vector: Vector = floats.values().to_vec();
+            result.push((id, vector));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrow_vectors_round_trip() {
+        let vectors = vec![
+            (0u32, vec![1.0f32, 2.0, 3.0]),
+            (1u32, vec![4.0, 5.0, 6.0]),
+            (2u32, vec![7.0, 8.0, 9.0]),
+        ];
+
+        let mut buffer = Vec::new();
+        write_vectors_arrow(&mut buffer, &vectors).unwrap();
+
+        let read_back = read_vectors_arrow(buffer.as_slice()).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_arrow_rejects_empty_dataset() {
+        let vectors: Vec<(u32, Vector)> = vec![];
+        let mut buffer = Vec::new();
+        assert!(write_vectors_arrow(&mut buffer, &vectors).is_err());
+    }
+
+    #[test]
+    fn test_arrow_rejects_ragged_rows() {
+        let vectors = vec![(0u32, vec![1.0f32, 2.0]), (1u32, vec![3.0, 4.0, 5.0])];
+        let mut buffer = Vec::new();
+        assert!(write_vectors_arrow(&mut buffer, &vectors).is_err());
+    }
+}
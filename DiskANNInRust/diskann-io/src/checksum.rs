@@ -0,0 +1,132 @@
+//! CRC32C (Castagnoli) integrity checking for the binary formats in
+//! [`crate::format`]
+//!
+//! [`ChecksumWriter`]/[`ChecksumReader`] are thin `Write`/`Read` wrappers
+//! that accumulate a running CRC32C as bytes pass through them, so a footer
+//! covering everything written (or read) so far can be produced or checked
+//! without buffering the payload in memory. [`ChecksumMismatchError`] is the
+//! distinct error type returned when a stored footer disagrees with the
+//! recomputed checksum.
+
+use std::fmt;
+use std::io::{Read, Result as IoResult, Write};
+
+/// Error returned when a file's stored CRC32C footer doesn't match the
+/// checksum recomputed over its payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumMismatchError {
+    /// CRC32C stored in the file's footer
+    pub expected: u32,
+    /// CRC32C recomputed while reading the payload
+    pub computed: u32,
+}
+
+impl fmt::Display for ChecksumMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "checksum mismatch: footer says {:#010x}, recomputed {:#010x}",
+            self.expected, self.computed
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatchError {}
+
+/// A `Write` wrapper that accumulates a running CRC32C over every byte
+/// written through it
+pub struct ChecksumWriter<W> {
+    inner: W,
+    crc: u32,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    /// Wrap `inner`, starting the running checksum from zero
+    pub fn new(inner: W) -> Self {
+        Self { inner, crc: 0 }
+    }
+
+    /// The CRC32C of every byte written through this wrapper so far
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let n = self.inner.write(buf)?;
+        self.crc = crc32c::crc32c_append(self.crc, &buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.inner.flush()
+    }
+}
+
+/// A `Read` wrapper that accumulates a running CRC32C over every byte read
+/// through it
+pub struct ChecksumReader<R> {
+    inner: R,
+    crc: u32,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    /// Wrap `inner`, starting the running checksum from zero
+    pub fn new(inner: R) -> Self {
+        Self { inner, crc: 0 }
+    }
+
+    /// The CRC32C of every byte read through this wrapper so far
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.inner.read(buf)?;
+        self.crc = crc32c::crc32c_append(self.crc, &buf[..n]);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn writer_and_reader_agree_on_checksum() {
+        let mut buffer = Vec::new();
+        let mut writer = ChecksumWriter::new(&mut buffer);
+        writer.write_all(b"hello, diskann").unwrap();
+        let write_crc = writer.crc();
+
+        let mut reader = ChecksumReader::new(Cursor::new(&buffer));
+        let mut read_back = Vec::new();
+        reader.read_to_end(&mut read_back).unwrap();
+
+        assert_eq!(read_back, b"hello, diskann");
+        assert_eq!(reader.crc(), write_crc);
+    }
+
+    #[test]
+    fn checksum_changes_with_payload() {
+        let mut a = ChecksumWriter::new(Vec::new());
+        a.write_all(b"abc").unwrap();
+
+        let mut b = ChecksumWriter::new(Vec::new());
+        b.write_all(b"abd").unwrap();
+
+        assert_ne!(a.crc(), b.crc());
+    }
+
+    #[test]
+    fn mismatch_error_formats_both_values() {
+        let err = ChecksumMismatchError { expected: 1, computed: 2 };
+        let message = err.to_string();
+        assert!(message.contains("0x00000001"));
+        assert!(message.contains("0x00000002"));
+    }
+}
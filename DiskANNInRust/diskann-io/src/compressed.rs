@@ -0,0 +1,319 @@
+//! Zstd-compressed block container with a trailing offset table
+//!
+//! [`crate::format::read_vectors_f32`] always scans a file start-to-end, so
+//! loading even a handful of vectors out of a multi-gigabyte dataset means
+//! decoding everything that precedes them. This module adds an alternative
+//! on-disk layout, selected by a leading [`MAGIC`] in place of the raw
+//! format's `num_points` header: vectors are chunked into fixed-size blocks, each block is
+//! zstd-compressed independently, and a table of per-block byte offsets is
+//! appended after the last block. [`CompressedVectorReader`] memory-maps the
+//! file, reads only that trailing table, and decompresses just the block(s)
+//! covering a requested vector range — a subset load is O(blocks touched),
+//! not O(file size).
+//!
+//! Layout: `MAGIC` | `block_size: u32` | `num_points: u32` |
+//! `num_dimensions: u32` | one zstd frame per block | one `u32` byte offset
+//! per block | `table_len: u32`.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use diskann_core::vectors::Vector;
+
+use crate::mmap::MappingStrategy;
+
+/// Magic bytes identifying a [`write_compressed`] container. Read as the raw
+/// [`crate::format::BinaryHeader`] format's leading little-endian `i32`, these
+/// bytes are a valid positive `num_points` (1,129,860,676), so the magic alone
+/// does not tell a container from a raw file of that many points.
+const MAGIC: [u8; 4] = *b"DNZC";
+
+/// Options controlling how [`write_compressed`] chunks and compresses data
+#[derive(Debug, Clone, Copy)]
+pub struct WriterOpts {
+    /// zstd compression level (1-22; higher is slower but smaller)
+    pub compress_lvl: i32,
+    /// Number of vectors packed into each independently-compressed block
+    pub block_size: usize,
+}
+
+impl Default for WriterOpts {
+    /// zstd level 3 (the library's own default trade-off) and 4096-vector
+    /// blocks, small enough to keep random-access decompression cheap
+    fn default() -> Self {
+        Self {
+            compress_lvl: 3,
+            block_size: 4096,
+        }
+    }
+}
+
+/// Write `vectors` to `writer` as a compressed block container (see the
+/// module docs for the on-disk layout)
+pub fn write_compressed<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    opts: WriterOpts,
+) -> Result<usize> {
+    if vectors.is_empty() {
+        bail!("Cannot write empty vector array");
+    }
+    if opts.block_size == 0 {
+        bail!("block_size must be nonzero");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    // Buffered into a data cursor so the offset table can record each
+    // block's starting position before it's known where the file ends.
+    let mut data = Vec::new();
+    data.extend_from_slice(&MAGIC);
+    data.extend_from_slice(&(opts.block_size as u32).to_le_bytes());
+    data.extend_from_slice(&(num_points as u32).to_le_bytes());
+    data.extend_from_slice(&(num_dimensions as u32).to_le_bytes());
+
+    let mut block_offsets = Vec::new();
+    for block in vectors.chunks(opts.block_size) {
+        block_offsets.push(data.len() as u32);
+
+        let mut raw = Vec::with_capacity(block.len() * num_dimensions * std::mem::size_of::<f32>());
+        for vector in block {
+            for &value in vector {
+                raw.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let compressed = zstd::encode_all(raw.as_slice(), opts.compress_lvl)
+            .context("Failed to zstd-compress block")?;
+        data.extend_from_slice(&compressed);
+    }
+
+    for &offset in &block_offsets {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(&(block_offsets.len() as u32).to_le_bytes());
+
+    writer.write_all(&data).context("Failed to write compressed container")?;
+    Ok(data.len())
+}
+
+/// Random-access reader over a [`write_compressed`] container
+///
+/// Parses only the header and trailing offset table at construction time;
+/// [`read_range`](Self::read_range) decompresses solely the block(s)
+/// covering the requested vector ids. Blocks are fixed-size, so the block
+/// holding id `n` is `n / block_size` directly — the offset table exists
+/// only so each block's compressed byte span on disk can be located without
+/// decompressing everything before it.
+pub struct CompressedVectorReader {
+    mapping: MappingStrategy,
+    block_size: usize,
+    num_points: usize,
+    num_dimensions: usize,
+    /// Byte offset of the start of each compressed block, in file order
+    block_offsets: Vec<u32>,
+    /// Byte offset one-past-the-end of the last block (start of the offset table)
+    table_start: usize,
+}
+
+impl CompressedVectorReader {
+    /// Open a compressed container, memory-mapping it when available and
+    /// falling back to a buffered read otherwise (see [`MappingStrategy`])
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mapping = MappingStrategy::new(path)?;
+        let len = mapping.len();
+        if len < 16 {
+            bail!("File too small to contain a compressed container header");
+        }
+
+        let magic = mapping.slice(0, 4)?;
+        if magic != MAGIC {
+            bail!("Not a compressed container: bad magic bytes");
+        }
+        let block_size = read_u32(mapping.slice(4, 4)?) as usize;
+        let num_points = read_u32(mapping.slice(8, 4)?) as usize;
+        let num_dimensions = read_u32(mapping.slice(12, 4)?) as usize;
+        if block_size == 0 {
+            bail!("Compressed container has zero block_size");
+        }
+
+        let table_len = read_u32(mapping.slice(len - 4, 4)?) as usize;
+        let table_bytes_len = table_len * 4;
+        if len < 4 + table_bytes_len {
+            bail!("Truncated compressed container: offset table runs past start of file");
+        }
+        let table_start = len - 4 - table_bytes_len;
+        let table_bytes = mapping.slice(table_start, table_bytes_len)?;
+        let block_offsets = table_bytes
+            .chunks_exact(4)
+            .map(read_u32)
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            mapping,
+            block_size,
+            num_points,
+            num_dimensions,
+            block_offsets,
+            table_start,
+        })
+    }
+
+    /// Number of vectors available in the underlying file
+    pub fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    /// Dimensionality of each vector
+    pub fn num_dimensions(&self) -> usize {
+        self.num_dimensions
+    }
+
+    fn block_byte_range(&self, block_idx: usize) -> (usize, usize) {
+        let start = self.block_offsets[block_idx] as usize;
+        let end = self
+            .block_offsets
+            .get(block_idx + 1)
+            .map(|&o| o as usize)
+            .unwrap_or(self.table_start);
+        (start, end)
+    }
+
+    fn decompress_block(&self, block_idx: usize) -> Result<Vec<Vector>> {
+        let (start, end) = self.block_byte_range(block_idx);
+        let compressed = self.mapping.slice(start, end - start)?;
+        let raw = zstd::decode_all(compressed).context("Failed to decompress block")?;
+
+        let mut vectors = Vec::with_capacity(self.block_size);
+        for chunk in raw.chunks_exact(self.num_dimensions * std::mem::size_of::<f32>()) {
+            let mut vector = Vector::with_capacity(self.num_dimensions);
+            for bytes in chunk.chunks_exact(4) {
+                vector.push(f32::from_le_bytes(bytes.try_into().unwrap()));
+            }
+            vectors.push(vector);
+        }
+        Ok(vectors)
+    }
+
+    /// Read `count` vectors starting at `start`, decompressing only the
+    /// block(s) that cover the requested range
+    pub fn read_range(&self, start: usize, count: usize) -> Result<Vec<Vector>> {
+        if start >= self.num_points {
+            bail!("Start index {} exceeds vector count {}", start, self.num_points);
+        }
+        let end = (start + count).min(self.num_points);
+
+        let first_block = start / self.block_size;
+        let last_block = (end - 1) / self.block_size;
+
+        let mut result = Vec::with_capacity(end - start);
+        for block_idx in first_block..=last_block {
+            let block_vectors = self.decompress_block(block_idx)?;
+            let block_start_id = block_idx * self.block_size;
+            for (i, vector) in block_vectors.into_iter().enumerate() {
+                let id = block_start_id + i;
+                if id >= start && id < end {
+                    result.push(vector);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().expect("caller passes exactly 4 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn sample_vectors(n: usize, dim: usize) -> Vec<Vector> {
+        (0..n)
+            .map(|i| (0..dim).map(|d| (i * dim + d) as f32).collect())
+            .collect()
+    }
+
+    #[test]
+    fn write_compressed_rejects_empty_input() {
+        let mut buffer = Vec::new();
+        let result = write_compressed(&mut buffer, &[], WriterOpts::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_compressed_rejects_zero_block_size() {
+        let vectors = sample_vectors(4, 3);
+        let mut buffer = Vec::new();
+        let opts = WriterOpts { compress_lvl: 3, block_size: 0 };
+        assert!(write_compressed(&mut buffer, &vectors, opts).is_err());
+    }
+
+    #[test]
+    fn round_trip_full_range_single_block() {
+        let vectors = sample_vectors(10, 4);
+        let mut file = NamedTempFile::new().unwrap();
+        write_compressed(file.as_file_mut(), &vectors, WriterOpts::default()).unwrap();
+
+        let reader = CompressedVectorReader::open(file.path()).unwrap();
+        assert_eq!(reader.num_points(), 10);
+        assert_eq!(reader.num_dimensions(), 4);
+
+        let read_back = reader.read_range(0, 10).unwrap();
+        assert_eq!(read_back, vectors);
+    }
+
+    #[test]
+    fn subset_read_spans_multiple_blocks() {
+        let vectors = sample_vectors(50, 4);
+        let opts = WriterOpts { compress_lvl: 3, block_size: 8 };
+
+        let mut file = NamedTempFile::new().unwrap();
+        write_compressed(file.as_file_mut(), &vectors, opts).unwrap();
+
+        let reader = CompressedVectorReader::open(file.path()).unwrap();
+        // Range [5, 20) straddles three 8-vector blocks.
+        let subset = reader.read_range(5, 15).unwrap();
+        assert_eq!(subset, vectors[5..20]);
+    }
+
+    #[test]
+    fn read_range_clamps_to_num_points() {
+        let vectors = sample_vectors(6, 2);
+        let mut file = NamedTempFile::new().unwrap();
+        write_compressed(file.as_file_mut(), &vectors, WriterOpts::default()).unwrap();
+
+        let reader = CompressedVectorReader::open(file.path()).unwrap();
+        let subset = reader.read_range(4, 100).unwrap();
+        assert_eq!(subset, vectors[4..6]);
+    }
+
+    #[test]
+    fn read_range_rejects_out_of_bounds_start() {
+        let vectors = sample_vectors(3, 2);
+        let mut file = NamedTempFile::new().unwrap();
+        write_compressed(file.as_file_mut(), &vectors, WriterOpts::default()).unwrap();
+
+        let reader = CompressedVectorReader::open(file.path()).unwrap();
+        assert!(reader.read_range(3, 1).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut buffer = vec![0u8; 32];
+        buffer[0..4].copy_from_slice(b"NOPE");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&buffer).unwrap();
+
+        assert!(CompressedVectorReader::open(file.path()).is_err());
+    }
+}
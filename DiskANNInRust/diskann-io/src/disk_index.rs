@@ -2,13 +2,103 @@
 //!
 //! This module provides functionality for building and searching indices
 //! that are stored on disk rather than in memory, enabling search on
-//! datasets larger than available RAM.
+//! datasets larger than available RAM. `vectors.bin` and `graph.bin` are
+//! memory-mapped once at [`DiskIndex::new`] time via [`MappingStrategy`], so
+//! `load_vector`/`load_neighbors` serve zero-copy slices out of the mapped
+//! region instead of reopening and seeking the file on every call. Both
+//! records are little-endian and naturally aligned (`u32`/`f32` only), which
+//! `MappingStrategy::typed_slice` checks before handing back a cast slice;
+//! on platforms without mmap support it transparently falls back to reading
+//! the whole file into a buffer up front. Each section is hashed at build
+//! time and stamped with a `format_version`; [`DiskIndex::new`] rejects an
+//! index whose version it doesn't understand and sanity-checks section
+//! lengths before serving any query, while [`DiskIndex::verify`] re-hashes
+//! the files on demand to catch corruption the length check would miss.
+//! [`DiskIndexBuilder::build_from_vectors`] constructs the Vamana graph
+//! itself rather than requiring a pre-built one, fanning the per-node
+//! GreedySearch + RobustPrune work out across a rayon thread pool with each
+//! node's neighbor list behind its own lock.
 
+use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write, Seek, SeekFrom};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use anyhow::{Result, Context};
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
 use serde::{Serialize, Deserialize};
+use sha3::{Digest, Sha3_256};
+
+use diskann_traits::distance::{CosineDistance, Distance, EuclideanDistance, InnerProductDistance};
+
+use crate::mmap::MappingStrategy;
+use crate::pq::PqCodebook;
+
+/// Current on-disk layout version, bumped whenever `config.json`, `graph.bin`
+/// or `vectors.bin`'s shape changes incompatibly. [`DiskIndex::new`] refuses
+/// to open an index stamped with a version it doesn't understand rather than
+/// silently mis-parsing an old (or newer) layout.
+const CURRENT_FORMAT_VERSION: u32 = 1;
+
+/// `config.json` predates per-section checksums and format versioning;
+/// existing indices on disk are all version 1.
+fn default_format_version() -> u32 {
+    1
+}
+
+/// Hex-encoded SHA3-256 digest of a file's full contents, used both when
+/// [`DiskIndexBuilder::build`] records each section's checksum and when
+/// [`DiskIndex::verify`] re-hashes it for comparison.
+fn hash_file(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let digest = Sha3_256::digest(&bytes);
+    Ok(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Distance metric an index was built with, persisted in [`DiskIndexConfig`]
+/// so `DiskIndex::search` always scores candidates the same way the index
+/// was intended for, regardless of which metric the caller last used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DistanceMetric {
+    /// Euclidean (L2) distance
+    Euclidean,
+    /// Cosine distance
+    Cosine,
+    /// Inner-product ("MIPS") distance
+    InnerProduct,
+}
+
+impl DistanceMetric {
+    fn distance(self, a: &[f32], b: &[f32]) -> f32 {
+        match self {
+            DistanceMetric::Euclidean => EuclideanDistance.distance(a, b),
+            DistanceMetric::Cosine => CosineDistance.distance(a, b),
+            DistanceMetric::InnerProduct => InnerProductDistance.distance(a, b),
+        }
+    }
+}
+
+impl Default for DistanceMetric {
+    fn default() -> Self {
+        DistanceMetric::Euclidean
+    }
+}
+
+/// Bytes in the `vectors.bin` header (`num_vectors: u32`, `dimension: u32`)
+/// preceding the vector data
+const VECTORS_HEADER_BYTES: usize = 8;
+/// Bytes in the `graph.bin` header (`num_nodes: u32`, `max_degree: u32`)
+/// preceding the adjacency records
+const GRAPH_HEADER_BYTES: usize = 8;
+
+/// Lloyd iterations run per subspace when [`DiskIndexBuilder::with_pq`] trains
+/// a codebook at build time
+const PQ_TRAINING_ITERATIONS: usize = 25;
+/// Fixed seed for build-time PQ training, so repeated builds over the same
+/// vectors produce byte-identical `pq.bin` files
+const PQ_TRAINING_SEED: u64 = 42;
 
 /// Configuration for disk index
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +115,19 @@ pub struct DiskIndexConfig {
     pub alpha: f32,
     /// Version of the index format
     pub index_version: String,
+    /// Fixed entry point (medoid) id used to seed beam search
+    #[serde(default)]
+    pub entry_point: u32,
+    /// Distance metric to score candidates with during search
+    #[serde(default)]
+    pub metric: DistanceMetric,
+    /// On-disk layout version; see [`CURRENT_FORMAT_VERSION`]
+    #[serde(default = "default_format_version")]
+    pub format_version: u32,
+    /// SHA3-256 hex digest of each section file, keyed by file name
+    /// (`vectors.bin`, `graph.bin`, `pq.bin`), checked by [`DiskIndex::verify`]
+    #[serde(default)]
+    pub checksums: HashMap<String, String>,
 }
 
 /// Disk-based index for memory-efficient storage and search
@@ -33,6 +136,18 @@ pub struct DiskIndex {
     graph_path: PathBuf,
     vectors_path: PathBuf,
     metadata_path: Option<PathBuf>,
+    pq_path: Option<PathBuf>,
+    vectors_mapping: MappingStrategy,
+    graph_mapping: MappingStrategy,
+    /// `max_degree` read back from `graph.bin`'s own header, used to compute
+    /// each node's adjacency record offset
+    graph_max_degree: usize,
+}
+
+#[derive(Debug, Clone)]
+struct ScoredCandidate {
+    id: u32,
+    distance: f32,
 }
 
 impl DiskIndex {
@@ -45,6 +160,10 @@ impl DiskIndex {
             let path = index_dir.join("metadata.bin");
             if path.exists() { Some(path) } else { None }
         };
+        let pq_path = {
+            let path = index_dir.join("pq.bin");
+            if path.exists() { Some(path) } else { None }
+        };
 
         // Load configuration
         let config_file = File::open(&config_path)
@@ -52,88 +171,302 @@ impl DiskIndex {
         let config: DiskIndexConfig = serde_json::from_reader(config_file)
             .context("Failed to parse config")?;
 
+        if config.format_version != CURRENT_FORMAT_VERSION {
+            anyhow::bail!(
+                "Index at {} has format_version {}, but this build only understands version {}",
+                index_dir.display(),
+                config.format_version,
+                CURRENT_FORMAT_VERSION
+            );
+        }
+
+        let vectors_mapping = MappingStrategy::new(&vectors_path)
+            .with_context(|| format!("Failed to map {}", vectors_path.display()))?;
+        let graph_mapping = MappingStrategy::new(&graph_path)
+            .with_context(|| format!("Failed to map {}", graph_path.display()))?;
+        let graph_header = graph_mapping
+            .typed_slice::<u32>(0, 2)
+            .context("Failed to read graph.bin header")?;
+        let graph_max_degree = graph_header[1] as usize;
+
+        let expected_vectors_len = VECTORS_HEADER_BYTES + config.num_vectors * config.dimension * 4;
+        let actual_vectors_len = vectors_path.metadata()?.len() as usize;
+        if actual_vectors_len != expected_vectors_len {
+            anyhow::bail!(
+                "vectors.bin is {} bytes, expected {} for {} vectors of dimension {}",
+                actual_vectors_len,
+                expected_vectors_len,
+                config.num_vectors,
+                config.dimension
+            );
+        }
+
+        let expected_graph_len = GRAPH_HEADER_BYTES + config.num_vectors * (4 + graph_max_degree * 4);
+        let actual_graph_len = graph_path.metadata()?.len() as usize;
+        if actual_graph_len != expected_graph_len {
+            anyhow::bail!(
+                "graph.bin is {} bytes, expected {} for {} nodes of max degree {}",
+                actual_graph_len,
+                expected_graph_len,
+                config.num_vectors,
+                graph_max_degree
+            );
+        }
+
         Ok(Self {
             config,
             graph_path,
             vectors_path,
             metadata_path,
+            pq_path,
+            vectors_mapping,
+            graph_mapping,
+            graph_max_degree,
+        })
+    }
+
+    /// Load the PQ codebook and per-vector codes, if this index was built with one
+    fn load_pq(&self) -> Result<Option<(PqCodebook, Vec<Vec<u8>>)>> {
+        let Some(pq_path) = &self.pq_path else {
+            return Ok(None);
+        };
+        let mut file = BufReader::new(File::open(pq_path).context("Failed to open pq.bin")?);
+        let codebook = PqCodebook::deserialize(&mut file).context("Failed to read PQ codebook")?;
+
+        let num_subspaces = codebook.num_subspaces();
+        let mut codes = Vec::with_capacity(self.config.num_vectors);
+        for _ in 0..self.config.num_vectors {
+            let mut code = vec![0u8; num_subspaces];
+            file.read_exact(&mut code).context("Failed to read PQ code")?;
+            codes.push(code);
+        }
+
+        Ok(Some((codebook, codes)))
+    }
+
+    /// Load this index's per-vector PQ codes, if it was built with
+    /// [`DiskIndexBuilder::with_pq`]
+    ///
+    /// Each code is `num_subspaces` bytes (one centroid index per subspace) —
+    /// small enough to keep RAM- or mmap-resident even while the
+    /// full-precision `vectors.bin` stays on disk. Returns `Ok(None)` if this
+    /// index has no `pq.bin`.
+    pub fn load_pq_codes(&self) -> Result<Option<Vec<Vec<u8>>>> {
+        Ok(self.load_pq()?.map(|(_, codes)| codes))
+    }
+
+    /// Asymmetric PQ distance from `query` to vector `id`
+    ///
+    /// Builds a query-to-centroid lookup table (`num_subspaces *
+    /// CENTROIDS_PER_SUBSPACE` floats) and sums `num_subspaces` table lookups
+    /// against `id`'s PQ code — the same cheap approximation
+    /// [`DiskIndex::search`] ranks candidates with. Pair this with
+    /// [`DiskIndex::load_vector`] for a final exact rerank of the top
+    /// candidates. Returns `Ok(None)` if this index has no `pq.bin`.
+    pub fn pq_distance(&self, query: &[f32], id: u32) -> Result<Option<f32>> {
+        let Some((codebook, codes)) = self.load_pq()? else {
+            return Ok(None);
+        };
+        if id as usize >= codes.len() {
+            anyhow::bail!("Vector ID {} out of range", id);
+        }
+        let table = codebook.build_distance_table(query)?;
+        Ok(Some(table.distance(&codes[id as usize])))
+    }
+
+    /// Score `query` against vector `id` with this index's configured
+    /// [`DistanceMetric`]
+    fn exact_distance(&self, query: &[f32], id: u32) -> Result<f32> {
+        let vector = self.vector_slice(id as usize)?;
+        Ok(self.config.metric.distance(query, vector))
+    }
+
+    /// Disk-resident greedy beam search (Vamana-style)
+    ///
+    /// When a `pq.bin` is present, traversal is ranked with the cheap PQ
+    /// asymmetric distance table (keeping only the compact codes resident),
+    /// but PQ distances are approximations -- returning them directly could
+    /// both misorder and misselect the final top-`k`. So the full
+    /// `search_list_size`-wide candidate set the traversal settles on is
+    /// treated as a full-precision reorder queue: every candidate's exact
+    /// vector is loaded from `vectors.bin` via [`DiskIndex::exact_distance`],
+    /// rescored under the index's configured [`DistanceMetric`], re-sorted,
+    /// and only then truncated to the exact top-`k`. Without a `pq.bin`,
+    /// traversal already scores with exact distances, so no separate rerank
+    /// pass is needed. See [`DiskIndex::greedy_beam_search`] for the
+    /// traversal itself.
+    pub fn search(&self, query: &[f32], k: usize, search_list_size: usize) -> Result<Vec<(u32, f32)>> {
+        if query.len() != self.config.dimension {
+            anyhow::bail!(
+                "Query has {} dimensions, index expects {}",
+                query.len(),
+                self.config.dimension
+            );
+        }
+        if self.config.num_vectors == 0 {
+            return Ok(Vec::new());
+        }
+
+        let entry = self.config.entry_point.min(self.config.num_vectors as u32 - 1);
+        let k = k.max(1);
+
+        let Some((codebook, codes)) = self.load_pq()? else {
+            return self.greedy_beam_search(k, search_list_size, entry, |id| self.exact_distance(query, id));
+        };
+
+        let adc_table = codebook.build_distance_table(query)?;
+        let candidates = self.greedy_beam_search(search_list_size, search_list_size, entry, |id| {
+            Ok(adc_table.distance(&codes[id as usize]))
+        })?;
+
+        let mut reranked = candidates
+            .into_iter()
+            .map(|(id, _)| Ok((id, self.exact_distance(query, id)?)))
+            .collect::<Result<Vec<(u32, f32)>>>()?;
+        reranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        reranked.truncate(k);
+        Ok(reranked)
+    }
+
+    /// Disk-resident greedy beam search with a caller-supplied distance function
+    ///
+    /// Identical traversal to [`DiskIndex::search`], but scores every
+    /// candidate with `distance_fn(query, vector)` instead of PQ/exact L2, so
+    /// cosine or inner-product metrics can be swapped in without a PQ
+    /// codebook in the loop. `distance_fn`'s output is returned as-is (no
+    /// implicit `sqrt`), since its units are whatever the caller chose.
+    pub fn search_with_distance<F>(
+        &self,
+        query: &[f32],
+        k: usize,
+        search_list_size: usize,
+        distance_fn: F,
+    ) -> Result<Vec<(u32, f32)>>
+    where
+        F: Fn(&[f32], &[f32]) -> f32,
+    {
+        if query.len() != self.config.dimension {
+            anyhow::bail!(
+                "Query has {} dimensions, index expects {}",
+                query.len(),
+                self.config.dimension
+            );
+        }
+        if self.config.num_vectors == 0 {
+            return Ok(Vec::new());
+        }
+
+        let entry = self.config.entry_point.min(self.config.num_vectors as u32 - 1);
+        self.greedy_beam_search(k, search_list_size, entry, |id| {
+            let vector = self.load_vector(id as usize)?;
+            Ok(distance_fn(query, &vector))
         })
     }
 
+    /// Shared greedy-search traversal backing [`DiskIndex::search`] and
+    /// [`DiskIndex::search_with_distance`]
+    ///
+    /// Maintains a candidate list `L` (`candidates`), sorted by ascending
+    /// `score` and capped at `search_list_size`, alongside a `visited` set so
+    /// each node is scored at most once. Each iteration expands the closest
+    /// *unexpanded* candidate — fetching its neighbors via
+    /// [`DiskIndex::load_neighbors`] and scoring the unvisited ones — then
+    /// truncates `L` back to `search_list_size`. The search stops once the
+    /// closest unexpanded candidate scores worse than the current worst of
+    /// the top-`k`, since expanding it cannot improve the answer.
+    fn greedy_beam_search<F>(
+        &self,
+        k: usize,
+        search_list_size: usize,
+        entry: u32,
+        mut score: F,
+    ) -> Result<Vec<(u32, f32)>>
+    where
+        F: FnMut(u32) -> Result<f32>,
+    {
+        let k = k.max(1);
+        let search_list_size = search_list_size.max(k);
+
+        let mut visited = HashSet::new();
+        let mut expanded: HashSet<u32> = HashSet::new();
+        let mut candidates: Vec<ScoredCandidate> = Vec::with_capacity(search_list_size + 1);
+
+        let entry_distance = score(entry)?;
+        candidates.push(ScoredCandidate { id: entry, distance: entry_distance });
+        visited.insert(entry);
+
+        while let Some(current) = candidates.iter().find(|c| !expanded.contains(&c.id)).cloned() {
+            if candidates.len() >= k && current.distance > candidates[k - 1].distance {
+                break;
+            }
+            expanded.insert(current.id);
+
+            for &neighbor in self.neighbor_slice(current.id as usize)? {
+                if visited.contains(&neighbor) {
+                    continue;
+                }
+                visited.insert(neighbor);
+                let distance = score(neighbor)?;
+                candidates.push(ScoredCandidate { id: neighbor, distance });
+            }
+
+            candidates.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+            candidates.truncate(search_list_size);
+        }
+
+        candidates.truncate(k);
+        Ok(candidates.into_iter().map(|c| (c.id, c.distance)).collect())
+    }
+
     /// Get index configuration
     pub fn config(&self) -> &DiskIndexConfig {
         &self.config
     }
 
+    /// Zero-copy slice of a vector's `dimension` floats, borrowed directly
+    /// from the memory-mapped `vectors.bin`
+    pub fn vector_slice(&self, id: usize) -> Result<&[f32]> {
+        if id >= self.config.num_vectors {
+            anyhow::bail!("Vector ID {} out of range", id);
+        }
+
+        let offset = VECTORS_HEADER_BYTES + id * self.config.dimension * 4;
+        self.vectors_mapping
+            .typed_slice::<f32>(offset, self.config.dimension)
+            .context("Failed to read vector slice")
+    }
+
     /// Load a specific vector from disk
+    ///
+    /// Convenience copy of [`DiskIndex::vector_slice`] for callers that need
+    /// an owned `Vec`; prefer `vector_slice` on hot paths.
     pub fn load_vector(&self, id: usize) -> Result<Vec<f32>> {
+        Ok(self.vector_slice(id)?.to_vec())
+    }
+
+    /// Zero-copy slice of a node's neighbor ids, borrowed directly from the
+    /// memory-mapped `graph.bin`
+    pub fn neighbor_slice(&self, id: usize) -> Result<&[u32]> {
         if id >= self.config.num_vectors {
-            anyhow::bail!("Vector ID {} out of range", id);
+            anyhow::bail!("Node ID {} out of range", id);
         }
 
-        let mut file = File::open(&self.vectors_path)?;
-        
-        // Skip header (num_vectors, dimension)
-        file.seek(SeekFrom::Start(8))?;
-        
-        // Seek to vector position
-        let vector_size = self.config.dimension * 4; // f32 = 4 bytes
-        let offset = 8 + (id * vector_size) as u64;
-        file.seek(SeekFrom::Start(offset))?;
-        
-        // Read vector
-        let mut buffer = vec![0u8; vector_size];
-        file.read_exact(&mut buffer)?;
-        
-        // Convert to f32
-        let vector: Vec<f32> = buffer
-            .chunks_exact(4)
-            .map(|chunk| {
-                let bytes = [chunk[0], chunk[1], chunk[2], chunk[3]];
-                f32::from_le_bytes(bytes)
-            })
-            .collect();
-        
-        Ok(vector)
+        // Each node stores: degree (u32) + neighbors (graph_max_degree * u32)
+        let node_size = 4 + self.graph_max_degree * 4;
+        let offset = GRAPH_HEADER_BYTES + id * node_size;
+
+        let degree = self.graph_mapping.typed_slice::<u32>(offset, 1)?[0] as usize;
+        self.graph_mapping
+            .typed_slice::<u32>(offset + 4, degree)
+            .context("Failed to read neighbor slice")
     }
 
     /// Load neighbors for a specific node from the graph
+    ///
+    /// Convenience copy of [`DiskIndex::neighbor_slice`] for callers that
+    /// need an owned `Vec`; prefer `neighbor_slice` on hot paths.
     pub fn load_neighbors(&self, id: usize) -> Result<Vec<u32>> {
-        let file = File::open(&self.graph_path)?;
-        let mut reader = BufReader::new(file);
-        
-        // Read graph header
-        let mut header = [0u8; 8];
-        reader.read_exact(&mut header)?;
-        let num_nodes = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
-        let max_degree = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
-        
-        if id >= num_nodes as usize {
-            anyhow::bail!("Node ID {} out of range", id);
-        }
-        
-        // Seek to node's neighbor list
-        // Each node stores: degree (u32) + neighbors (max_degree * u32)
-        let node_size = 4 + (max_degree as usize * 4);
-        let offset = 8 + (id * node_size) as u64;
-        
-        let mut file = reader.into_inner();
-        file.seek(SeekFrom::Start(offset))?;
-        
-        // Read degree
-        let mut degree_bytes = [0u8; 4];
-        file.read_exact(&mut degree_bytes)?;
-        let degree = u32::from_le_bytes(degree_bytes) as usize;
-        
-        // Read neighbors
-        let mut neighbors = Vec::with_capacity(degree);
-        for _ in 0..degree {
-            let mut neighbor_bytes = [0u8; 4];
-            file.read_exact(&mut neighbor_bytes)?;
-            neighbors.push(u32::from_le_bytes(neighbor_bytes));
-        }
-        
-        Ok(neighbors)
+        Ok(self.neighbor_slice(id)?.to_vec())
     }
 
     /// Get total index size in bytes
@@ -154,6 +487,37 @@ impl DiskIndex {
         
         Ok(total)
     }
+
+    /// Re-hash `vectors.bin`, `graph.bin`, and `pq.bin` (if present) and
+    /// compare against the digests [`DiskIndexBuilder::build`] recorded in
+    /// `config.json`, catching truncation or corruption that the header
+    /// sanity check in [`DiskIndex::new`] wouldn't notice.
+    pub fn verify(&self) -> Result<()> {
+        let sections: &[(&str, &Path)] = &[
+            ("vectors.bin", &self.vectors_path),
+            ("graph.bin", &self.graph_path),
+        ];
+
+        for (name, path) in sections {
+            let expected = self.config.checksums.get(*name)
+                .ok_or_else(|| anyhow::anyhow!("config.json has no checksum recorded for {}", name))?;
+            let actual = hash_file(path)?;
+            if &actual != expected {
+                anyhow::bail!("{} failed checksum verification (expected {}, got {})", name, expected, actual);
+            }
+        }
+
+        if let Some(pq_path) = &self.pq_path {
+            if let Some(expected) = self.config.checksums.get("pq.bin") {
+                let actual = hash_file(pq_path)?;
+                if &actual != expected {
+                    anyhow::bail!("pq.bin failed checksum verification (expected {}, got {})", expected, actual);
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Builder for creating disk indices
@@ -162,6 +526,9 @@ pub struct DiskIndexBuilder {
     max_degree: usize,
     search_list_size: usize,
     alpha: f32,
+    entry_point: u32,
+    pq_subspaces: Option<usize>,
+    metric: DistanceMetric,
 }
 
 impl DiskIndexBuilder {
@@ -172,6 +539,9 @@ impl DiskIndexBuilder {
             max_degree: 32,
             search_list_size: 64,
             alpha: 1.2,
+            entry_point: 0,
+            pq_subspaces: None,
+            metric: DistanceMetric::default(),
         }
     }
 
@@ -193,6 +563,204 @@ impl DiskIndexBuilder {
         self
     }
 
+    /// Set the fixed entry point (medoid) id used to seed beam search
+    pub fn entry_point(mut self, entry_point: u32) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+
+    /// Enable product quantization, splitting each vector into `num_subspaces`
+    /// contiguous subspaces and training a [`PqCodebook`] over the build-time
+    /// vectors. The codebook and one code per subspace per vector are written
+    /// to `pq.bin`, which [`DiskIndex::search`] prefers over exact distances
+    /// once present. Requires at least [`crate::pq::CENTROIDS_PER_SUBSPACE`]
+    /// vectors and `num_subspaces` to evenly divide the vector dimension.
+    pub fn with_pq(mut self, num_subspaces: usize) -> Self {
+        self.pq_subspaces = Some(num_subspaces);
+        self
+    }
+
+    /// Set the distance metric [`DiskIndex::search`] scores candidates with.
+    /// Defaults to [`DistanceMetric::Euclidean`].
+    pub fn metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Construct the Vamana adjacency lists from raw vectors in parallel, then
+    /// hand them to [`DiskIndexBuilder::build`] just like a caller who built
+    /// the graph themselves
+    ///
+    /// Picks the medoid as the fixed entry point (see
+    /// [`DiskIndexBuilder::medoid`]), then runs one rayon task per node:
+    /// `greedy_search_in_memory` from the medoid collects up to
+    /// `search_list_size` candidates, and `robust_prune` (single-pass, at the
+    /// builder's configured `alpha`) keeps up to `max_degree` of them. Every
+    /// node's neighbor list lives behind its own [`std::sync::Mutex`] so workers never
+    /// contend over any node but the one they're actively touching, and after
+    /// a node picks its forward edges it installs the reciprocal back-edge on
+    /// each neighbor, re-pruning any neighbor whose list overflows
+    /// `max_degree`. Progress is reported on the same `indicatif` progress
+    /// bar style the demo crate uses for embedding generation.
+    pub fn build_from_vectors(mut self, vectors: &[Vec<f32>]) -> Result<DiskIndex> {
+        let (graph, medoid) = self.construct_graph(vectors)?;
+        self.entry_point = medoid;
+        self.build(vectors, &graph)
+    }
+
+    /// Sum-of-distances medoid: the vector minimizing total distance to every
+    /// other vector, used as [`DiskIndexBuilder::build_from_vectors`]'s fixed
+    /// search entry point. `O(n^2)` distance computations, parallelized
+    /// across a rayon pool one row at a time.
+    fn medoid(vectors: &[Vec<f32>], metric: DistanceMetric) -> usize {
+        (0..vectors.len())
+            .into_par_iter()
+            .map(|candidate| {
+                let total: f32 = vectors.iter()
+                    .map(|other| metric.distance(&vectors[candidate], other))
+                    .sum();
+                (candidate, total)
+            })
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(id, _)| id)
+            .unwrap_or(0)
+    }
+
+    /// In-memory GreedySearch used only during [`DiskIndexBuilder::construct_graph`],
+    /// reading neighbor lists through the per-node `Mutex`es workers are
+    /// concurrently installing edges into rather than a finished [`DiskIndex`]
+    fn greedy_search_in_memory(
+        vectors: &[Vec<f32>],
+        nodes: &[Mutex<Vec<u32>>],
+        metric: DistanceMetric,
+        query: &[f32],
+        search_list_size: usize,
+        start: u32,
+    ) -> Vec<ScoredCandidate> {
+        let mut visited = HashSet::new();
+        let mut expanded: HashSet<u32> = HashSet::new();
+        let mut candidates: Vec<ScoredCandidate> = Vec::with_capacity(search_list_size + 1);
+
+        let start_distance = metric.distance(query, &vectors[start as usize]);
+        candidates.push(ScoredCandidate { id: start, distance: start_distance });
+        visited.insert(start);
+
+        while let Some(current) = candidates.iter().find(|c| !expanded.contains(&c.id)).cloned() {
+            expanded.insert(current.id);
+
+            let neighbors = nodes[current.id as usize].lock().unwrap().clone();
+            for neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let distance = metric.distance(query, &vectors[neighbor as usize]);
+                candidates.push(ScoredCandidate { id: neighbor, distance });
+            }
+
+            candidates.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+            candidates.truncate(search_list_size);
+        }
+
+        candidates
+    }
+
+    /// Single-pass RobustPrune: sort `candidates` by distance to the node
+    /// being pruned for, repeatedly keep the closest surviving candidate
+    /// `p*`, and discard every remaining candidate `p'` for which
+    /// `alpha * d(p*, p') <= d(p, p')` (already occluded by `p*`), until
+    /// `max_degree` neighbors are kept
+    fn robust_prune(
+        vectors: &[Vec<f32>],
+        metric: DistanceMetric,
+        candidates: &[ScoredCandidate],
+        alpha: f32,
+        max_degree: usize,
+    ) -> Vec<u32> {
+        let mut remaining = candidates.to_vec();
+        remaining.sort_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap_or(Ordering::Equal));
+
+        let mut pruned = Vec::new();
+        while !remaining.is_empty() && pruned.len() < max_degree {
+            let best = remaining.remove(0);
+            pruned.push(best.id);
+
+            let best_vector = &vectors[best.id as usize];
+            remaining.retain(|candidate| {
+                let dist_best_to_candidate = metric.distance(best_vector, &vectors[candidate.id as usize]);
+                alpha * dist_best_to_candidate > candidate.distance
+            });
+        }
+
+        pruned
+    }
+
+    /// Build the Vamana adjacency lists for `vectors` in parallel
+    ///
+    /// See [`DiskIndexBuilder::build_from_vectors`] for the algorithm; this
+    /// just owns the rayon fan-out and progress reporting, returning a plain
+    /// `graph` in the same shape [`DiskIndexBuilder::build`] already expects,
+    /// together with the medoid id the construction searched from.
+    fn construct_graph(&self, vectors: &[Vec<f32>]) -> Result<(Vec<Vec<u32>>, u32)> {
+        if vectors.is_empty() {
+            return Ok((Vec::new(), self.entry_point));
+        }
+
+        let medoid = Self::medoid(vectors, self.metric);
+        let nodes: Vec<Mutex<Vec<u32>>> = (0..vectors.len()).map(|_| Mutex::new(Vec::new())).collect();
+
+        let pb = ProgressBar::new(vectors.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+                .unwrap()
+                .progress_chars("=>-")
+        );
+        pb.set_message("Building Vamana graph");
+
+        (0..vectors.len()).into_par_iter().for_each(|id| {
+            // Concurrently installed back-edges can route a node's own search
+            // back to itself before its forward pass runs; drop the self-match
+            // so a node never ends up its own neighbor.
+            let candidates: Vec<ScoredCandidate> = Self::greedy_search_in_memory(
+                vectors, &nodes, self.metric, &vectors[id], self.search_list_size, medoid as u32,
+            )
+            .into_iter()
+            .filter(|candidate| candidate.id != id as u32)
+            .collect();
+            let neighbors = Self::robust_prune(vectors, self.metric, &candidates, self.alpha, self.max_degree);
+
+            *nodes[id].lock().unwrap() = neighbors.clone();
+
+            for neighbor_id in neighbors {
+                let needs_pruning = {
+                    let mut neighbor = nodes[neighbor_id as usize].lock().unwrap();
+                    if !neighbor.contains(&(id as u32)) {
+                        neighbor.push(id as u32);
+                    }
+                    neighbor.len() > self.max_degree
+                };
+
+                if needs_pruning {
+                    let neighbor_edges = nodes[neighbor_id as usize].lock().unwrap().clone();
+                    let neighbor_candidates: Vec<ScoredCandidate> = neighbor_edges.iter()
+                        .map(|&nb_id| ScoredCandidate {
+                            id: nb_id,
+                            distance: self.metric.distance(&vectors[neighbor_id as usize], &vectors[nb_id as usize]),
+                        })
+                        .collect();
+                    let pruned = Self::robust_prune(vectors, self.metric, &neighbor_candidates, self.alpha, self.max_degree);
+                    *nodes[neighbor_id as usize].lock().unwrap() = pruned;
+                }
+            }
+
+            pb.inc(1);
+        });
+
+        pb.finish_with_message("Vamana graph built");
+
+        Ok((nodes.into_iter().map(|lock| lock.into_inner().unwrap()).collect(), medoid as u32))
+    }
+
     /// Build disk index from vectors and graph
     pub fn build(
         self,
@@ -207,20 +775,6 @@ impl DiskIndexBuilder {
             .ok_or_else(|| anyhow::anyhow!("No vectors provided"))?
             .len();
 
-        // Write configuration
-        let config = DiskIndexConfig {
-            num_vectors,
-            dimension,
-            max_degree: self.max_degree,
-            search_list_size: self.search_list_size,
-            alpha: self.alpha,
-            index_version: "0.1.0".to_string(),
-        };
-
-        let config_path = self.index_dir.join("config.json");
-        let config_file = File::create(&config_path)?;
-        serde_json::to_writer_pretty(config_file, &config)?;
-
         // Write vectors
         let vectors_path = self.index_dir.join("vectors.bin");
         let vectors_file = File::create(&vectors_path)?;
@@ -264,6 +818,48 @@ impl DiskIndexBuilder {
         }
         writer.flush()?;
 
+        // Train and write PQ codes, if requested
+        let pq_path = self.index_dir.join("pq.bin");
+        if let Some(num_subspaces) = self.pq_subspaces {
+            let codebook = PqCodebook::train(vectors, num_subspaces, PQ_TRAINING_ITERATIONS, PQ_TRAINING_SEED)
+                .context("Failed to train PQ codebook")?;
+            let codes = codebook.encode_all(vectors).context("Failed to encode PQ codes")?;
+
+            let pq_file = File::create(&pq_path)?;
+            let mut writer = BufWriter::new(pq_file);
+            codebook.serialize(&mut writer).context("Failed to write PQ codebook")?;
+            for code in &codes {
+                writer.write_all(code).context("Failed to write PQ code")?;
+            }
+            writer.flush()?;
+        }
+
+        // Hash each section now that all files are fully written, then write
+        // config.json last so it can record the digests and format version.
+        let mut checksums = HashMap::new();
+        checksums.insert("vectors.bin".to_string(), hash_file(&vectors_path)?);
+        checksums.insert("graph.bin".to_string(), hash_file(&graph_path)?);
+        if self.pq_subspaces.is_some() {
+            checksums.insert("pq.bin".to_string(), hash_file(&pq_path)?);
+        }
+
+        let config = DiskIndexConfig {
+            num_vectors,
+            dimension,
+            max_degree: self.max_degree,
+            search_list_size: self.search_list_size,
+            alpha: self.alpha,
+            index_version: "0.1.0".to_string(),
+            entry_point: self.entry_point,
+            metric: self.metric,
+            format_version: CURRENT_FORMAT_VERSION,
+            checksums,
+        };
+
+        let config_path = self.index_dir.join("config.json");
+        let config_file = File::create(&config_path)?;
+        serde_json::to_writer_pretty(config_file, &config)?;
+
         // Return disk index
         DiskIndex::new(&self.index_dir)
     }
@@ -315,4 +911,328 @@ mod tests {
         let size = index.size_bytes().unwrap();
         assert!(size > 0);
     }
+
+    #[test]
+    fn test_zero_copy_slices_match_owned_loads() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let vectors = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let graph = vec![
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        assert_eq!(index.vector_slice(1).unwrap(), &[0.0, 1.0, 0.0]);
+        assert_eq!(index.vector_slice(1).unwrap().to_vec(), index.load_vector(1).unwrap());
+
+        assert_eq!(index.neighbor_slice(2).unwrap(), &[0, 1]);
+        assert_eq!(index.neighbor_slice(2).unwrap().to_vec(), index.load_neighbors(2).unwrap());
+
+        assert!(index.vector_slice(3).is_err());
+        assert!(index.neighbor_slice(3).is_err());
+    }
+
+    #[test]
+    fn test_search_finds_nearest_neighbor() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let vectors = vec![
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![5.0, 5.0],
+        ];
+        let graph = vec![
+            vec![1, 2],
+            vec![0, 3],
+            vec![0, 3],
+            vec![1, 2],
+        ];
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        let results = index.search(&[0.1, 0.1], 2, 10).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 0);
+    }
+
+    #[test]
+    fn test_search_with_distance_uses_custom_metric() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let vectors = vec![
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![-1.0, 0.0],
+        ];
+        let graph = vec![
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        // Inner product: vector 0 (same direction) should score highest.
+        let results = index
+            .search_with_distance(&[1.0, 0.0], 1, 10, |a, b| {
+                -a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum::<f32>()
+            })
+            .unwrap();
+        assert_eq!(results[0].0, 0);
+    }
+
+    #[test]
+    fn test_build_with_pq_writes_codes_and_speeds_search() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Two well-separated clusters, replicated to clear the minimum
+        // training-set size for a 256-centroid codebook.
+        let mut vectors = Vec::new();
+        let mut graph = Vec::new();
+        for i in 0..300 {
+            if i % 2 == 0 {
+                vectors.push(vec![0.0, 0.0]);
+            } else {
+                vectors.push(vec![10.0, 10.0]);
+            }
+            graph.push(vec![(i + 1) % 300, (i + 299) % 300]);
+        }
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .with_pq(2)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        assert!(temp_dir.path().join("pq.bin").exists());
+
+        let codes = index.load_pq_codes().unwrap().unwrap();
+        assert_eq!(codes.len(), vectors.len());
+        assert_eq!(codes[0].len(), 2);
+
+        let distance_to_self = index.pq_distance(&vectors[0], 0).unwrap().unwrap();
+        let distance_to_far = index.pq_distance(&vectors[0], 1).unwrap().unwrap();
+        assert!(distance_to_self < distance_to_far);
+
+        let results = index.search(&[0.0, 0.0], 2, 20).unwrap();
+        assert_eq!(results[0].0 % 2, 0);
+    }
+
+    #[test]
+    fn test_search_with_pq_returns_exact_reranked_distances() {
+        // With a pq.bin present, search() must rerank its PQ-approximate
+        // candidates against the sector-resident vectors before returning,
+        // so the reported distance should equal the true Euclidean distance
+        // to the query -- not the PQ table's squared-distance approximation.
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut vectors = Vec::new();
+        let mut graph = Vec::new();
+        for i in 0..300 {
+            if i % 2 == 0 {
+                vectors.push(vec![0.0, 0.0]);
+            } else {
+                vectors.push(vec![10.0, 10.0]);
+            }
+            graph.push(vec![(i + 1) % 300, (i + 299) % 300]);
+        }
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .with_pq(2)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        let results = index.search(&[0.0, 0.0], 1, 20).unwrap();
+        assert_eq!(results[0].0 % 2, 0);
+        assert!((results[0].1 - 0.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cosine_metric_is_persisted_and_used_by_search() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Vector 0 is nearly collinear with the query (cosine-nearest) but
+        // farther away in raw Euclidean distance than vector 1.
+        let vectors = vec![
+            vec![10.0, 0.1],
+            vec![1.0, 0.9],
+            vec![-1.0, 0.0],
+        ];
+        let graph = vec![
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .metric(DistanceMetric::Cosine)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        assert_eq!(index.config().metric, DistanceMetric::Cosine);
+
+        let results = index.search(&[1.0, 0.0], 1, 10).unwrap();
+        assert_eq!(results[0].0, 0);
+    }
+
+    #[test]
+    fn test_load_pq_codes_absent_without_pq() {
+        let temp_dir = TempDir::new().unwrap();
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let graph = vec![vec![1], vec![0]];
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        assert!(index.load_pq_codes().unwrap().is_none());
+        assert!(index.pq_distance(&vectors[0], 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_verify_passes_on_untouched_index() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut vectors = Vec::new();
+        let mut graph = Vec::new();
+        for i in 0..300 {
+            vectors.push(if i % 2 == 0 { vec![0.0, 0.0] } else { vec![10.0, 10.0] });
+            graph.push(vec![(i + 1) % 300, (i + 299) % 300]);
+        }
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .with_pq(2)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        assert!(index.config().checksums.contains_key("vectors.bin"));
+        assert!(index.config().checksums.contains_key("graph.bin"));
+        assert!(index.config().checksums.contains_key("pq.bin"));
+        assert_eq!(index.config().format_version, CURRENT_FORMAT_VERSION);
+        index.verify().unwrap();
+    }
+
+    #[test]
+    fn test_verify_detects_corrupted_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let graph = vec![vec![1], vec![0]];
+
+        DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        // Flip a byte in the vectors payload without touching its length, so
+        // the header sanity check in `new()` still passes.
+        let vectors_path = temp_dir.path().join("vectors.bin");
+        let mut bytes = std::fs::read(&vectors_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        std::fs::write(&vectors_path, &bytes).unwrap();
+
+        let index = DiskIndex::new(temp_dir.path()).unwrap();
+        assert!(index.verify().is_err());
+    }
+
+    #[test]
+    fn test_new_rejects_unknown_format_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let graph = vec![vec![1], vec![0]];
+
+        DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        let config_path = temp_dir.path().join("config.json");
+        let mut config: serde_json::Value =
+            serde_json::from_reader(File::open(&config_path).unwrap()).unwrap();
+        config["format_version"] = serde_json::json!(CURRENT_FORMAT_VERSION + 1);
+        serde_json::to_writer_pretty(File::create(&config_path).unwrap(), &config).unwrap();
+
+        assert!(DiskIndex::new(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_new_rejects_truncated_graph_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let vectors = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let graph = vec![vec![1], vec![0]];
+
+        DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build(&vectors, &graph)
+            .unwrap();
+
+        let graph_path = temp_dir.path().join("graph.bin");
+        let mut bytes = std::fs::read(&graph_path).unwrap();
+        bytes.truncate(bytes.len() - 4);
+        std::fs::write(&graph_path, &bytes).unwrap();
+
+        assert!(DiskIndex::new(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_build_from_vectors_constructs_searchable_graph() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let vectors: Vec<Vec<f32>> = (0..20)
+            .map(|i| vec![i as f32, (i * 2) as f32])
+            .collect();
+
+        let index = DiskIndexBuilder::new(temp_dir.path())
+            .max_degree(4)
+            .search_list_size(10)
+            .build_from_vectors(&vectors)
+            .unwrap();
+
+        assert_eq!(index.config().num_vectors, 20);
+
+        // Every node should have picked up at least one neighbor, and none
+        // should exceed the configured max_degree.
+        for id in 0..20 {
+            let neighbors = index.load_neighbors(id).unwrap();
+            assert!(!neighbors.is_empty());
+            assert!(neighbors.len() <= 4);
+        }
+
+        // The graph should be connected enough to find a vector's closest
+        // match starting from any entry point.
+        let results = index.search(&vectors[5], 1, 10).unwrap();
+        assert_eq!(results[0].0, 5);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,259 @@
+//! Cross-platform advisory file locking for concurrent index readers/writers
+//!
+//! Wraps the platform's native advisory-lock primitive -- `flock(2)` on Unix,
+//! `LockFileEx` on Windows, and a no-op on anything else -- behind a single
+//! [`LockGuard`] type with [`LockGuard::lock_shared`]/
+//! [`LockGuard::try_lock_exclusive`] constructors. The lock is released when
+//! the guard is dropped, so callers can't forget to unlock it.
+//!
+//! The lock belongs to the open file handle, so two handles opened
+//! separately on the same path conflict even inside one process, while
+//! threads sharing a single handle share its lock. It is not a substitute for
+//! in-process synchronization (`Mutex`/`RwLock`) between such threads.
+
+use std::fs::File;
+
+use anyhow::{Context, Result};
+
+/// An advisory lock guard over an open [`File`], released when dropped
+///
+/// Holds the `File` alive for as long as the lock is held, since closing the
+/// underlying descriptor releases the lock out from under the guard on every
+/// backend this module supports.
+pub struct LockGuard {
+    file: File,
+}
+
+impl LockGuard {
+    /// Take a shared (read) advisory lock on `file`, blocking until it's
+    /// available
+    ///
+    /// Compatible with any number of other shared locks, so concurrent
+    /// readers never contend with each other -- only with an exclusive
+    /// writer.
+    pub fn lock_shared(file: File) -> Result<Self> {
+        backend::lock_shared(&file).context("Failed to acquire shared file lock")?;
+        Ok(Self { file })
+    }
+
+    /// Take an exclusive (write) advisory lock on `file`
+    ///
+    /// Returns an error immediately rather than blocking if another process
+    /// already holds a conflicting lock, so a writer can report a clear
+    /// "index busy" error instead of either blocking forever or -- worse --
+    /// letting a reader that skipped locking see a half-written file.
+    pub fn try_lock_exclusive(file: File) -> Result<Self> {
+        backend::try_lock_exclusive(&file)
+            .context("Index file is locked by another process")?;
+        Ok(Self { file })
+    }
+
+    /// Borrow the locked file
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Mutably borrow the locked file
+    pub fn file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let _ = backend::unlock(&self.file);
+    }
+}
+
+#[cfg(unix)]
+mod backend {
+    use std::fs::File;
+    use std::os::unix::io::AsRawFd;
+
+    use anyhow::{bail, Result};
+
+    pub fn lock_shared(file: &File) -> Result<()> {
+        let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH) };
+        if ret != 0 {
+            bail!("flock(LOCK_SH) failed: {}", std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn try_lock_exclusive(file: &File) -> Result<()> {
+        let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+        if ret != 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::WouldBlock {
+                bail!("file is already locked by another process");
+            }
+            bail!("flock(LOCK_EX) failed: {}", err);
+        }
+        Ok(())
+    }
+
+    pub fn unlock(file: &File) -> Result<()> {
+        let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) };
+        if ret != 0 {
+            bail!("flock(LOCK_UN) failed: {}", std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod backend {
+    use std::ffi::c_void;
+    use std::fs::File;
+    use std::os::windows::io::AsRawHandle;
+
+    use anyhow::{bail, Result};
+
+    const LOCKFILE_FAIL_IMMEDIATELY: u32 = 0x1;
+    const LOCKFILE_EXCLUSIVE_LOCK: u32 = 0x2;
+
+    #[repr(C)]
+    struct Overlapped {
+        internal: usize,
+        internal_high: usize,
+        offset: u32,
+        offset_high: u32,
+        h_event: *mut c_void,
+    }
+
+    fn overlapped() -> Overlapped {
+        Overlapped {
+            internal: 0,
+            internal_high: 0,
+            offset: 0,
+            offset_high: 0,
+            h_event: std::ptr::null_mut(),
+        }
+    }
+
+    extern "system" {
+        fn LockFileEx(
+            h_file: *mut c_void,
+            flags: u32,
+            reserved: u32,
+            bytes_low: u32,
+            bytes_high: u32,
+            overlapped: *mut Overlapped,
+        ) -> i32;
+
+        fn UnlockFileEx(
+            h_file: *mut c_void,
+            reserved: u32,
+            bytes_low: u32,
+            bytes_high: u32,
+            overlapped: *mut Overlapped,
+        ) -> i32;
+    }
+
+    pub fn lock_shared(file: &File) -> Result<()> {
+        let mut ov = overlapped();
+        let ok = unsafe {
+            LockFileEx(file.as_raw_handle() as *mut c_void, 0, 0, u32::MAX, u32::MAX, &mut ov)
+        };
+        if ok == 0 {
+            bail!("LockFileEx(shared) failed: {}", std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn try_lock_exclusive(file: &File) -> Result<()> {
+        let mut ov = overlapped();
+        let flags = LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY;
+        let ok = unsafe {
+            LockFileEx(file.as_raw_handle() as *mut c_void, flags, 0, u32::MAX, u32::MAX, &mut ov)
+        };
+        if ok == 0 {
+            bail!("file is already locked by another process");
+        }
+        Ok(())
+    }
+
+    pub fn unlock(file: &File) -> Result<()> {
+        let mut ov = overlapped();
+        let ok = unsafe {
+            UnlockFileEx(file.as_raw_handle() as *mut c_void, 0, u32::MAX, u32::MAX, &mut ov)
+        };
+        if ok == 0 {
+            bail!("UnlockFileEx failed: {}", std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod backend {
+    use std::fs::File;
+
+    use anyhow::Result;
+
+    pub fn lock_shared(_file: &File) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn try_lock_exclusive(_file: &File) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn unlock(_file: &File) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn shared_locks_do_not_conflict_with_each_other() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let a = File::open(temp_file.path()).unwrap();
+        let b = File::open(temp_file.path()).unwrap();
+
+        let guard_a = LockGuard::lock_shared(a).unwrap();
+        let guard_b = LockGuard::lock_shared(b).unwrap();
+
+        drop(guard_a);
+        drop(guard_b);
+    }
+
+    #[test]
+    fn exclusive_lock_rejects_a_second_exclusive_lock() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let a = File::open(temp_file.path()).unwrap();
+        let b = File::open(temp_file.path()).unwrap();
+
+        let _guard = LockGuard::try_lock_exclusive(a).unwrap();
+        assert!(LockGuard::try_lock_exclusive(b).is_err());
+    }
+
+    #[test]
+    fn exclusive_lock_rejects_a_shared_lock() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let a = File::open(temp_file.path()).unwrap();
+        let b = File::open(temp_file.path()).unwrap();
+
+        let _guard = LockGuard::try_lock_exclusive(a).unwrap();
+        assert!(LockGuard::lock_shared(b).is_err());
+    }
+
+    #[test]
+    fn lock_is_released_on_drop() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let a = File::open(temp_file.path()).unwrap();
+        let guard = LockGuard::try_lock_exclusive(a).unwrap();
+        drop(guard);
+
+        let b = File::open(temp_file.path()).unwrap();
+        assert!(LockGuard::try_lock_exclusive(b).is_ok());
+    }
+}
@@ -1,14 +1,203 @@
 //! Binary format definitions for DiskANN index files
-//! 
+//!
 //! This module implements binary compatibility with the C++ DiskANN format:
 //! - 4 bytes: number of points (i32)
-//! - 4 bytes: number of dimensions (i32)  
-//! - data: points * dimensions * sizeof(T) bytes
+//! - 4 bytes: number of dimensions (i32)
+//! - 4 bytes: element dtype code (i32)
+//! - data: points * dimensions * dtype.element_size() bytes
 
-use std::io::{Read, Write, Result as IoResult};
+use std::io::{Read, Write, Seek, SeekFrom, Result as IoResult};
 use anyhow::{Result, Context};
+use half::f16;
 use diskann_core::vectors::{Vector, VectorId};
 use diskann_core::structures::GraphNode;
+use crate::checksum::{ChecksumMismatchError, ChecksumReader, ChecksumWriter};
+use crate::lz4_blocks::{read_payload_lz4_blocks, write_payload_lz4_blocks};
+use crate::block_checksum::{read_checksummed_blocks, write_checksummed_blocks, CompressionType};
+
+/// Element dtype of the vector data following a [`BinaryHeader`]
+///
+/// Mirrors the quantized dtypes upstream DiskANN routinely stores base
+/// vectors as to cut disk footprint, alongside the plain f32 path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum VectorDtype {
+    /// 4-byte IEEE-754 single precision float
+    F32 = 0,
+    /// 2-byte IEEE-754 half precision float
+    F16 = 1,
+    /// 1-byte signed integer
+    I8 = 2,
+    /// 1-byte unsigned integer
+    U8 = 3,
+}
+
+/// Bit of [`BinaryHeader::dtype`] repurposed as a flag marking that a
+/// trailing CRC32C footer follows the payload (see
+/// [`write_vectors_f32_checksummed`])
+///
+/// Real [`VectorDtype`] codes only ever occupy values 0-3, so this high bit
+/// is free to commandeer without growing the header past its on-disk 12
+/// bytes, keeping files written before this flag existed readable unchanged.
+const CHECKSUM_FLAG: i32 = 1 << 30;
+
+/// Bit of [`BinaryHeader::dtype`] repurposed as a flag marking that the
+/// payload following this header (and, for [`write_graph_nodes_lz4`], the
+/// graph adjacency section after it) is stored as LZ4 blocks written by
+/// [`crate::lz4_blocks::write_payload_lz4_blocks`] rather than raw bytes
+///
+/// Distinct from [`CHECKSUM_FLAG`] so a file can set either, both, or
+/// neither independently.
+const COMPRESSION_FLAG: i32 = 1 << 29;
+
+/// Bit of [`BinaryHeader::dtype`] repurposed as a flag marking that the
+/// payload following this header is stored by
+/// [`write_vectors_f32_block_checksummed`] -- a [`crate::block_checksum`]
+/// container with a selectable per-block codec and a per-block xxh3-64
+/// checksum -- rather than the plain or [`COMPRESSION_FLAG`] layouts
+///
+/// Kept distinct from [`COMPRESSION_FLAG`] rather than reusing it, since the
+/// two containers have incompatible byte layouts (this one carries a leading
+/// codec tag and per-block checksums that [`crate::lz4_blocks`] does not).
+const BLOCK_CHECKSUM_FLAG: i32 = 1 << 28;
+
+impl VectorDtype {
+    /// Size in bytes of a single element of this dtype
+    pub fn element_size(self) -> usize {
+        match self {
+            VectorDtype::F32 => 4,
+            VectorDtype::F16 => 2,
+            VectorDtype::I8 => 1,
+            VectorDtype::U8 => 1,
+        }
+    }
+
+    fn from_code(code: i32) -> Result<Self> {
+        match code {
+            0 => Ok(VectorDtype::F32),
+            1 => Ok(VectorDtype::F16),
+            2 => Ok(VectorDtype::I8),
+            3 => Ok(VectorDtype::U8),
+            other => anyhow::bail!("Unknown dtype code: {}", other),
+        }
+    }
+}
+
+/// A scalar type that can be stored as DiskANN vector elements on disk
+///
+/// [`write_vectors_typed`]/[`read_vectors_typed`] always widen to/from f32,
+/// which is lossy for `F16`/`I8`/`U8` data round-tripped through them. The
+/// generic [`write_vectors`]/[`read_vectors`] pair instead stores and loads
+/// `T` directly — the representation the C++ tooling's quantized index
+/// files actually use on disk — so no precision is lost.
+pub trait StoredScalar: Copy {
+    /// Byte width of a single element on disk
+    const SIZE: usize;
+    /// The [`VectorDtype`] tag this type corresponds to
+    const DTYPE: VectorDtype;
+
+    /// Encode as exactly `SIZE` little-endian bytes
+    fn to_le_bytes(self) -> Vec<u8>;
+    /// Decode from exactly `SIZE` little-endian bytes
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+}
+
+impl StoredScalar for f32 {
+    const SIZE: usize = 4;
+    const DTYPE: VectorDtype = VectorDtype::F32;
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        f32::to_le_bytes(self).to_vec()
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        f32::from_le_bytes(bytes.try_into().expect("caller passes exactly SIZE bytes"))
+    }
+}
+
+impl StoredScalar for f16 {
+    const SIZE: usize = 2;
+    const DTYPE: VectorDtype = VectorDtype::F16;
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        f16::to_le_bytes(self).to_vec()
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        f16::from_le_bytes(bytes.try_into().expect("caller passes exactly SIZE bytes"))
+    }
+}
+
+impl StoredScalar for i8 {
+    const SIZE: usize = 1;
+    const DTYPE: VectorDtype = VectorDtype::I8;
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        vec![self as u8]
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        bytes[0] as i8
+    }
+}
+
+impl StoredScalar for u8 {
+    const SIZE: usize = 1;
+    const DTYPE: VectorDtype = VectorDtype::U8;
+
+    fn to_le_bytes(self) -> Vec<u8> {
+        vec![self]
+    }
+
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        bytes[0]
+    }
+}
+
+/// Byte order of an on-disk DiskANN file
+///
+/// The native C++ DiskANN tooling writes headers and vector data in whatever
+/// endianness the host happened to have, so files seen in the wild are not
+/// guaranteed to be little-endian. [`BinaryHeader::read_from_endian`] and
+/// [`read_vectors_f32_endian`] let callers parse either layout explicitly;
+/// the plain `_from`/`_f32` entry points default to little-endian, the
+/// overwhelmingly common case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Least-significant byte first (x86/ARM default)
+    Little,
+    /// Most-significant byte first
+    Big,
+}
+
+/// Read exactly 4 bytes and decode them as `i32` per `endianness`
+///
+/// `offset` is the byte position of this field within its containing
+/// structure; it is folded into the error message on truncation so callers
+/// can tell which field a malformed file failed on, rather than just
+/// "unexpected end of file".
+fn read_i32_checked<R: Read>(reader: &mut R, offset: usize, endianness: Endianness) -> IoResult<i32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("truncated binary header at byte offset {}: {}", offset, e),
+        )
+    })?;
+    Ok(match endianness {
+        Endianness::Little => i32::from_le_bytes(bytes),
+        Endianness::Big => i32::from_be_bytes(bytes),
+    })
+}
+
+/// Encode `value` as 4 bytes per `endianness` and write them
+fn write_i32_checked<W: Write>(writer: &mut W, value: i32, endianness: Endianness) -> IoResult<()> {
+    let bytes = match endianness {
+        Endianness::Little => value.to_le_bytes(),
+        Endianness::Big => value.to_be_bytes(),
+    };
+    writer.write_all(&bytes)
+}
 
 /// Binary file header for DiskANN format
 #[repr(C)]
@@ -18,50 +207,139 @@ pub struct BinaryHeader {
     pub num_points: i32,
     /// Number of dimensions per vector
     pub num_dimensions: i32,
+    /// Element dtype code, see [`VectorDtype`]
+    pub dtype: i32,
 }
 
 impl BinaryHeader {
-    /// Create a new binary header
+    /// Create a new binary header for f32 (the default on-disk dtype)
     pub fn new(num_points: usize, num_dimensions: usize) -> Self {
+        Self::with_dtype(num_points, num_dimensions, VectorDtype::F32)
+    }
+
+    /// Create a new binary header for an arbitrary [`VectorDtype`]
+    pub fn with_dtype(num_points: usize, num_dimensions: usize, dtype: VectorDtype) -> Self {
         Self {
             num_points: num_points as i32,
             num_dimensions: num_dimensions as i32,
+            dtype: dtype as i32,
         }
     }
-    
-    /// Read header from reader
+
+    /// Read a little-endian header from `reader`
     pub fn read_from<R: Read>(reader: &mut R) -> IoResult<Self> {
-        let mut header = Self { num_points: 0, num_dimensions: 0 };
-        reader.read_exact(unsafe { 
-            std::slice::from_raw_parts_mut(
-                &mut header as *mut Self as *mut u8,
-                std::mem::size_of::<Self>()
-            )
-        })?;
-        Ok(header)
+        Self::read_from_endian(reader, Endianness::Little)
     }
-    
-    /// Write header to writer
+
+    /// Read a header from `reader`, decoding each field with an explicit
+    /// [`Endianness`] rather than assuming the host's native byte order
+    pub fn read_from_endian<R: Read>(reader: &mut R, endianness: Endianness) -> IoResult<Self> {
+        let num_points = read_i32_checked(reader, 0, endianness)?;
+        let num_dimensions = read_i32_checked(reader, 4, endianness)?;
+        let dtype = read_i32_checked(reader, 8, endianness)?;
+        Ok(Self { num_points, num_dimensions, dtype })
+    }
+
+    /// Write a little-endian header to `writer`
     pub fn write_to<W: Write>(&self, writer: &mut W) -> IoResult<()> {
-        writer.write_all(unsafe {
-            std::slice::from_raw_parts(
-                self as *const Self as *const u8,
-                std::mem::size_of::<Self>()
-            )
-        })
+        self.write_to_endian(writer, Endianness::Little)
     }
-    
+
+    /// Write a header to `writer`, encoding each field with an explicit
+    /// [`Endianness`]
+    pub fn write_to_endian<W: Write>(&self, writer: &mut W, endianness: Endianness) -> IoResult<()> {
+        write_i32_checked(writer, self.num_points, endianness)?;
+        write_i32_checked(writer, self.num_dimensions, endianness)?;
+        write_i32_checked(writer, self.dtype, endianness)
+    }
+
     /// Get the expected data size in bytes for f32 vectors
     pub fn data_size_f32(&self) -> usize {
-        (self.num_points * self.num_dimensions) as usize * std::mem::size_of::<f32>()
+        (self.num_points as i64 * self.num_dimensions as i64) as usize * std::mem::size_of::<f32>()
     }
-    
-    /// Get the total file size including header
+
+    /// Get the total file size including header, assuming f32 data
     pub fn total_file_size_f32(&self) -> usize {
         std::mem::size_of::<Self>() + self.data_size_f32()
     }
-    
+
+    /// Decode this header's dtype code, ignoring [`CHECKSUM_FLAG`],
+    /// [`COMPRESSION_FLAG`], and [`BLOCK_CHECKSUM_FLAG`]
+    pub fn dtype(&self) -> Result<VectorDtype> {
+        VectorDtype::from_code(self.dtype & !(CHECKSUM_FLAG | COMPRESSION_FLAG | BLOCK_CHECKSUM_FLAG))
+    }
+
+    /// Whether [`CHECKSUM_FLAG`] is set, meaning a trailing `u32` CRC32C
+    /// footer follows this header's payload
+    pub fn has_checksum(&self) -> bool {
+        self.dtype & CHECKSUM_FLAG != 0
+    }
+
+    /// Return a copy of this header with [`CHECKSUM_FLAG`] set or cleared
+    pub fn with_checksum_flag(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.dtype |= CHECKSUM_FLAG;
+        } else {
+            self.dtype &= !CHECKSUM_FLAG;
+        }
+        self
+    }
+
+    /// Whether [`COMPRESSION_FLAG`] is set, meaning the payload following
+    /// this header is stored as LZ4 blocks (see [`write_vectors_f32_lz4`])
+    /// rather than raw bytes
+    pub fn is_lz4_compressed(&self) -> bool {
+        self.dtype & COMPRESSION_FLAG != 0
+    }
+
+    /// Return a copy of this header with [`COMPRESSION_FLAG`] set or cleared
+    pub fn with_lz4_flag(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.dtype |= COMPRESSION_FLAG;
+        } else {
+            self.dtype &= !COMPRESSION_FLAG;
+        }
+        self
+    }
+
+    /// Whether [`BLOCK_CHECKSUM_FLAG`] is set, meaning the payload following
+    /// this header is a [`crate::block_checksum`] container (see
+    /// [`write_vectors_f32_block_checksummed`]) rather than raw bytes or the
+    /// [`COMPRESSION_FLAG`] layout
+    pub fn is_block_checksummed(&self) -> bool {
+        self.dtype & BLOCK_CHECKSUM_FLAG != 0
+    }
+
+    /// Return a copy of this header with [`BLOCK_CHECKSUM_FLAG`] set or cleared
+    pub fn with_block_checksum_flag(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.dtype |= BLOCK_CHECKSUM_FLAG;
+        } else {
+            self.dtype &= !BLOCK_CHECKSUM_FLAG;
+        }
+        self
+    }
+
+    /// Get the expected data size in bytes for this header's dtype
+    pub fn data_size(&self) -> Result<usize> {
+        let element_size = self.dtype()?.element_size();
+        Ok((self.num_points as i64 * self.num_dimensions as i64) as usize * element_size)
+    }
+
+    /// Get the total file size including header, scaled by this header's dtype
+    pub fn total_file_size(&self) -> Result<usize> {
+        Ok(std::mem::size_of::<Self>() + self.data_size()?)
+    }
+
     /// Validate header values
+    ///
+    /// Both fields come straight from an untrusted file header with no
+    /// magnitude cap, so a crafted/corrupt header can make `num_points *
+    /// num_dimensions` overflow `i32` -- checked here (before any of
+    /// [`Self::data_size_f32`]/[`Self::total_file_size_f32`]/
+    /// [`Self::data_size`]/[`Self::total_file_size`] run) so a malformed
+    /// header is rejected outright instead of silently wrapping into a
+    /// too-small expected size downstream.
     pub fn validate(&self) -> Result<()> {
         if self.num_points < 0 {
             anyhow::bail!("Invalid num_points: {}", self.num_points);
@@ -69,173 +347,1573 @@ impl BinaryHeader {
         if self.num_dimensions <= 0 {
             anyhow::bail!("Invalid num_dimensions: {}", self.num_dimensions);
         }
+        self.dtype().context("Invalid dtype")?;
+        self.num_points.checked_mul(self.num_dimensions).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Header's num_points ({}) * num_dimensions ({}) overflows i32",
+                self.num_points,
+                self.num_dimensions
+            )
+        })?;
         Ok(())
     }
 }
 
-/// Write vector data in DiskANN binary format
+/// Header describing the graph section that follows a vector block in
+/// [`write_graph_nodes`]'s on-disk layout
+///
+/// Written right after the vector data (so adjacency lives alongside the
+/// vectors it indexes rather than in a separate file), followed by a `u32`
+/// `max_degree` (R) and then, per node in id order, a `u32` `neighbor_count`
+/// plus that many `u32` neighbor ids. When `fixed_stride` is set each node's
+/// neighbor ids are padded out to R entries so every node occupies the same
+/// `4 + R * 4` bytes, matching the fixed-stride layout `disk_index`'s
+/// `graph.bin` uses for O(1) random access; when unset, nodes are packed
+/// back-to-back with no padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphHeader {
+    /// Id of the fixed entry point (medoid) used to seed beam search
+    pub entry_point: u32,
+    /// Whether each node's neighbor list is padded to `max_degree` entries
+    pub fixed_stride: bool,
+}
+
+impl GraphHeader {
+    /// Create a new graph header
+    pub fn new(entry_point: u32, fixed_stride: bool) -> Self {
+        Self { entry_point, fixed_stride }
+    }
+
+    /// Read a graph header from `reader`
+    pub fn read_from<R: Read>(reader: &mut R) -> IoResult<Self> {
+        let entry_point = read_i32_checked(reader, 0, Endianness::Little)? as u32;
+        let fixed_stride_flag = read_i32_checked(reader, 4, Endianness::Little)?;
+        Ok(Self {
+            entry_point,
+            fixed_stride: fixed_stride_flag != 0,
+        })
+    }
+
+    /// Write a graph header to `writer`
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        write_i32_checked(writer, self.entry_point as i32, Endianness::Little)?;
+        write_i32_checked(writer, self.fixed_stride as i32, Endianness::Little)
+    }
+}
+
+/// Write vector data in DiskANN binary format, defaulting to the f32 dtype
+/// and little-endian byte order
 pub fn write_vectors_f32<W: Write>(
     writer: &mut W,
     vectors: &[Vector],
+) -> Result<usize> {
+    write_vectors_f32_endian(writer, vectors, Endianness::Little)
+}
+
+/// Write vector data in DiskANN binary format (f32 dtype) with an explicit
+/// [`Endianness`], for producing files compatible with a specific host
+pub fn write_vectors_f32_endian<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    endianness: Endianness,
 ) -> Result<usize> {
     if vectors.is_empty() {
         anyhow::bail!("Cannot write empty vector array");
     }
-    
+
     let num_points = vectors.len();
     let num_dimensions = vectors[0].len();
-    
-    // Validate all vectors have same dimension
+
     for (i, vector) in vectors.iter().enumerate() {
         if vector.len() != num_dimensions {
             anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
         }
     }
-    
+
     let header = BinaryHeader::new(num_points, num_dimensions);
     header.validate()?;
-    
+
+    header.write_to_endian(writer, endianness)
+        .context("Failed to write binary header")?;
+
+    for vector in vectors {
+        for &value in vector {
+            let bits = value.to_bits() as i32;
+            write_i32_checked(writer, bits, endianness)
+                .context("Failed to write vector data")?;
+        }
+    }
+
+    Ok(header.total_file_size_f32())
+}
+
+/// Write vector data in DiskANN binary format using an explicit element dtype
+///
+/// `F16`/`I8`/`U8` are lossy: each f32 component is rounded to the nearest
+/// representable value of the target dtype (via `half::f16` for f16, integer
+/// rounding and saturation for i8/u8), so a round-trip through a quantized
+/// dtype does not preserve exact values, only the quantized approximation.
+pub fn write_vectors_typed<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    dtype: VectorDtype,
+) -> Result<usize> {
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+
+    // Validate all vectors have same dimension
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let header = BinaryHeader::with_dtype(num_points, num_dimensions, dtype);
+    header.validate()?;
+
     // Write header
     header.write_to(writer)
         .context("Failed to write binary header")?;
-    
+
     // Write vector data
     for vector in vectors {
         for &value in vector {
-            writer.write_all(&value.to_le_bytes())
-                .context("Failed to write vector data")?;
+            match dtype {
+                VectorDtype::F32 => writer.write_all(&value.to_le_bytes()),
+                VectorDtype::F16 => writer.write_all(&f16::from_f32(value).to_le_bytes()),
+                VectorDtype::I8 => writer.write_all(&(value.round() as i8).to_le_bytes()),
+                VectorDtype::U8 => writer.write_all(&(value.round() as u8).to_le_bytes()),
+            }
+            .context("Failed to write vector data")?;
         }
     }
-    
-    Ok(header.total_file_size_f32())
+
+    header.total_file_size().context("Failed to compute total file size")
 }
 
-/// Read vector data from DiskANN binary format
+/// Read vector data from DiskANN binary format, assuming little-endian byte
+/// order
 pub fn read_vectors_f32<R: Read>(reader: &mut R) -> Result<Vec<Vector>> {
-    let header = BinaryHeader::read_from(reader)
+    read_vectors_f32_endian(reader, Endianness::Little)
+}
+
+/// Read vector data from DiskANN binary format (f32 dtype) with an explicit
+/// [`Endianness`], for parsing files produced on a big-endian host
+pub fn read_vectors_f32_endian<R: Read>(reader: &mut R, endianness: Endianness) -> Result<Vec<Vector>> {
+    let header = BinaryHeader::read_from_endian(reader, endianness)
         .context("Failed to read binary header")?;
-    
+
     header.validate()?;
-    
+
     let num_points = header.num_points as usize;
     let num_dimensions = header.num_dimensions as usize;
-    
+
     let mut vectors = Vec::with_capacity(num_points);
-    
-    for _ in 0..num_points {
+
+    for point in 0..num_points {
         let mut vector = Vector::with_capacity(num_dimensions);
-        for _ in 0..num_dimensions {
-            let mut bytes = [0u8; 4];
-            reader.read_exact(&mut bytes)
+        for dim in 0..num_dimensions {
+            let offset = std::mem::size_of::<BinaryHeader>()
+                + (point * num_dimensions + dim) * std::mem::size_of::<f32>();
+            let bits = read_i32_checked(reader, offset, endianness)
                 .context("Failed to read vector component")?;
-            vector.push(f32::from_le_bytes(bytes));
+            vector.push(f32::from_bits(bits as u32));
         }
         vectors.push(vector);
     }
-    
+
     Ok(vectors)
 }
 
-/// Write graph nodes to binary format (vector data + adjacency lists)
-pub fn write_graph_nodes<W: Write>(
+/// Write vector data in DiskANN binary format (f32 dtype, little-endian),
+/// appending a trailing CRC32C footer covering the header and every vector
+/// byte and setting [`CHECKSUM_FLAG`] so [`read_vectors_f32_checksummed`]
+/// knows to expect and verify it
+pub fn write_vectors_f32_checksummed<W: Write>(
     writer: &mut W,
-    nodes: &[GraphNode],
+    vectors: &[Vector],
 ) -> Result<usize> {
-    if nodes.is_empty() {
-        anyhow::bail!("Cannot write empty graph nodes array");
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
     }
-    
-    // Extract vectors from nodes
-    let vectors: Vec<Vector> = nodes.iter().map(|node| node.vector.clone()).collect();
-    
-    // Write vector data first
-    let bytes_written = write_vectors_f32(writer, &vectors)?;
-    
-    // TODO: Add adjacency list writing when graph format is defined
-    
-    Ok(bytes_written)
-}
 
-/// Read graph nodes from binary format
-pub fn read_graph_nodes<R: Read>(reader: &mut R) -> Result<Vec<GraphNode>> {
-    let vectors = read_vectors_f32(reader)?;
-    
-    // Convert vectors to graph nodes
-    let nodes = vectors
-        .into_iter()
-        .enumerate()
-        .map(|(i, vector)| {
-            GraphNode::new(i as VectorId, vector)
-        })
-        .collect();
-        
-    Ok(nodes)
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let header = BinaryHeader::new(num_points, num_dimensions).with_checksum_flag(true);
+    header.validate()?;
+
+    let crc = {
+        let mut checksum_writer = ChecksumWriter::new(&mut *writer);
+        header.write_to(&mut checksum_writer).context("Failed to write binary header")?;
+        for vector in vectors {
+            for &value in vector {
+                checksum_writer.write_all(&value.to_le_bytes())
+                    .context("Failed to write vector data")?;
+            }
+        }
+        checksum_writer.crc()
+    };
+
+    writer.write_all(&crc.to_le_bytes()).context("Failed to write checksum footer")?;
+
+    Ok(header.total_file_size_f32() + 4)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
-    
-    #[test]
-    fn test_binary_header_round_trip() {
-        let header = BinaryHeader::new(100, 128);
-        let mut buffer = Vec::new();
-        header.write_to(&mut buffer).unwrap();
-        
-        let mut cursor = Cursor::new(buffer);
-        let read_header = BinaryHeader::read_from(&mut cursor).unwrap();
-        
-        assert_eq!(header.num_points, read_header.num_points);
-        assert_eq!(header.num_dimensions, read_header.num_dimensions);
-    }
-    
-    #[test]
-    fn test_vectors_round_trip() {
-        let vectors = vec![
-            vec![1.0, 2.0, 3.0],
-            vec![4.0, 5.0, 6.0],
-            vec![7.0, 8.0, 9.0],
-        ];
-        
-        let mut buffer = Vec::new();
-        let bytes_written = write_vectors_f32(&mut buffer, &vectors).unwrap();
-        
-        let mut cursor = Cursor::new(buffer);
-        let read_vectors = read_vectors_f32(&mut cursor).unwrap();
-        
-        assert_eq!(vectors.len(), read_vectors.len());
-        for (orig, read) in vectors.iter().zip(read_vectors.iter()) {
-            assert_eq!(orig.len(), read.len());
-            for (&orig_val, &read_val) in orig.iter().zip(read.iter()) {
-                assert!((orig_val - read_val).abs() < f32::EPSILON);
+/// Read vector data written by either [`write_vectors_f32`] or
+/// [`write_vectors_f32_checksummed`]
+///
+/// Recomputes the CRC32C over the header and payload as they're read; if the
+/// header's [`CHECKSUM_FLAG`] is set, the trailing footer is compared against
+/// it and a [`ChecksumMismatchError`] is returned on mismatch. Files without
+/// the flag (including every file written before this function existed) load
+/// exactly as [`read_vectors_f32`] would, with no footer expected.
+pub fn read_vectors_f32_checksummed<R: Read>(reader: &mut R) -> Result<Vec<Vector>> {
+    let (vectors, has_checksum, crc) = {
+        let mut checksum_reader = ChecksumReader::new(&mut *reader);
+        let header = BinaryHeader::read_from(&mut checksum_reader)
+            .context("Failed to read binary header")?;
+        header.validate()?;
+
+        let num_points = header.num_points as usize;
+        let num_dimensions = header.num_dimensions as usize;
+
+        let mut vectors = Vec::with_capacity(num_points);
+        for _ in 0..num_points {
+            let mut vector = Vector::with_capacity(num_dimensions);
+            for _ in 0..num_dimensions {
+                let mut bytes = [0u8; 4];
+                checksum_reader.read_exact(&mut bytes)
+                    .context("Failed to read vector component")?;
+                vector.push(f32::from_le_bytes(bytes));
             }
+            vectors.push(vector);
+        }
+
+        (vectors, header.has_checksum(), checksum_reader.crc())
+    };
+
+    if has_checksum {
+        let mut footer = [0u8; 4];
+        reader.read_exact(&mut footer).context("Failed to read checksum footer")?;
+        let stored = u32::from_le_bytes(footer);
+        if stored != crc {
+            return Err(ChecksumMismatchError { expected: stored, computed: crc }.into());
         }
-        
-        // Check bytes written calculation
-        let expected_bytes = 8 + (3 * 3 * 4); // header + data
-        assert_eq!(bytes_written, expected_bytes);
     }
-    
-    #[test]
-    fn test_graph_nodes_round_trip() {
-        let nodes = vec![
-            GraphNode::new(0, vec![1.0, 2.0]),
-            GraphNode::new(1, vec![3.0, 4.0]),
-        ];
-        
-        let mut buffer = Vec::new();
-        write_graph_nodes(&mut buffer, &nodes).unwrap();
+
+    Ok(vectors)
+}
+
+/// Write vector data in DiskANN binary format (f32 dtype, little-endian),
+/// partitioning the payload into `block_size`-byte blocks and LZ4-compressing
+/// each one independently (see [`crate::lz4_blocks`]), and setting
+/// [`COMPRESSION_FLAG`] so [`read_vectors_f32_lz4`] knows to expect and
+/// decompress them
+///
+/// Trades a one-time compression pass for a smaller file; unlike
+/// [`write_compressed`](crate::compressed::write_compressed), the result is
+/// still a single [`BinaryHeader`]-prefixed file, so it round-trips through
+/// the same loader code path as an uncompressed one.
+pub fn write_vectors_f32_lz4<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    block_size: usize,
+) -> Result<usize> {
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let header = BinaryHeader::new(num_points, num_dimensions).with_lz4_flag(true);
+    header.validate()?;
+    header.write_to(writer).context("Failed to write binary header")?;
+
+    let mut raw = Vec::with_capacity(num_points * num_dimensions * std::mem::size_of::<f32>());
+    for vector in vectors {
+        for &value in vector {
+            raw.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let body_bytes = write_payload_lz4_blocks(writer, &raw, block_size)
+        .context("Failed to write LZ4-compressed vector payload")?;
+
+    Ok(std::mem::size_of::<BinaryHeader>() + body_bytes)
+}
+
+/// Read vector data written by either [`write_vectors_f32`] or
+/// [`write_vectors_f32_lz4`]
+///
+/// Auto-detects which via the header's [`COMPRESSION_FLAG`] (see
+/// [`BinaryHeader::is_lz4_compressed`]), so callers don't need to know up
+/// front whether a given file was written compressed.
+pub fn read_vectors_f32_lz4<R: Read>(reader: &mut R) -> Result<Vec<Vector>> {
+    let header = BinaryHeader::read_from(reader).context("Failed to read binary header")?;
+    header.validate()?;
+
+    let raw = if header.is_lz4_compressed() {
+        read_payload_lz4_blocks(reader).context("Failed to read LZ4-compressed vector payload")?
+    } else {
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).context("Failed to read vector payload")?;
+        rest
+    };
+
+    let num_points = header.num_points as usize;
+    let num_dimensions = header.num_dimensions as usize;
+
+    let mut vectors = Vec::with_capacity(num_points);
+    let mut cursor = raw.as_slice();
+    for _ in 0..num_points {
+        let mut vector = Vector::with_capacity(num_dimensions);
+        for _ in 0..num_dimensions {
+            let mut bytes = [0u8; 4];
+            cursor.read_exact(&mut bytes).context("Failed to read vector component")?;
+            vector.push(f32::from_le_bytes(bytes));
+        }
+        vectors.push(vector);
+    }
+
+    Ok(vectors)
+}
+
+/// Write vector data in DiskANN binary format (f32 dtype, little-endian),
+/// modeled on LSM-tree segment blocks: the payload is split into
+/// `block_size`-byte blocks, each independently compressed with
+/// `compression` and checksummed with xxh3-64 (see [`crate::block_checksum`])
+///
+/// `CompressionType::None` skips the block container entirely and writes a
+/// plain [`write_vectors_f32`] file with no flag set, so uncompressed
+/// callers keep the existing zero-copy mmap fast path
+/// ([`crate::loader::MmapIndexLoader`] only takes the block-decoding path
+/// when [`BinaryHeader::is_block_checksummed`] is set).
+pub fn write_vectors_f32_block_checksummed<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    compression: CompressionType,
+    block_size: usize,
+) -> Result<usize> {
+    if compression == CompressionType::None {
+        return write_vectors_f32(writer, vectors);
+    }
+
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let header = BinaryHeader::new(num_points, num_dimensions).with_block_checksum_flag(true);
+    header.validate()?;
+    header.write_to(writer).context("Failed to write binary header")?;
+
+    let mut raw = Vec::with_capacity(num_points * num_dimensions * std::mem::size_of::<f32>());
+    for vector in vectors {
+        for &value in vector {
+            raw.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let body_bytes = write_checksummed_blocks(writer, &raw, compression, block_size)
+        .context("Failed to write block-checksummed vector payload")?;
+
+    Ok(std::mem::size_of::<BinaryHeader>() + body_bytes)
+}
+
+/// Read vector data written by either [`write_vectors_f32`] or
+/// [`write_vectors_f32_block_checksummed`]
+///
+/// Auto-detects which via the header's [`BinaryHeader::is_block_checksummed`];
+/// each block's xxh3-64 checksum is verified while decompressing, failing
+/// with the offending block's index folded into the error context if a
+/// mismatch is found.
+pub fn read_vectors_f32_block_checksummed<R: Read>(reader: &mut R) -> Result<Vec<Vector>> {
+    let header = BinaryHeader::read_from(reader).context("Failed to read binary header")?;
+    header.validate()?;
+
+    let raw = if header.is_block_checksummed() {
+        read_checksummed_blocks(reader).context("Failed to read block-checksummed vector payload")?
+    } else {
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).context("Failed to read vector payload")?;
+        rest
+    };
+
+    let num_points = header.num_points as usize;
+    let num_dimensions = header.num_dimensions as usize;
+
+    let mut vectors = Vec::with_capacity(num_points);
+    let mut cursor = raw.as_slice();
+    for _ in 0..num_points {
+        let mut vector = Vector::with_capacity(num_dimensions);
+        for _ in 0..num_dimensions {
+            let mut bytes = [0u8; 4];
+            cursor.read_exact(&mut bytes).context("Failed to read vector component")?;
+            vector.push(f32::from_le_bytes(bytes));
+        }
+        vectors.push(vector);
+    }
+
+    Ok(vectors)
+}
+
+/// Append `vectors` to an existing DiskANN binary file in place, rewriting
+/// only the header's `num_points` field rather than the whole file
+///
+/// `file` must already contain a valid little-endian [`BinaryHeader`] and f32
+/// payload (as written by [`write_vectors_f32`]); every appended vector must
+/// match the existing `num_dimensions`. Checksummed files (see
+/// [`write_vectors_f32_checksummed`]) are rejected, since appending would
+/// invalidate their footer without updating it. Returns the file's new total
+/// vector count.
+pub fn append_vectors_f32<F: Read + Write + Seek>(
+    file: &mut F,
+    vectors: &[Vector],
+) -> Result<usize> {
+    file.seek(SeekFrom::Start(0)).context("Failed to seek to header")?;
+    let mut header = BinaryHeader::read_from(file).context("Failed to read binary header")?;
+    header.validate()?;
+
+    if header.has_checksum() {
+        anyhow::bail!("Cannot append to a checksummed file; its footer would no longer match");
+    }
+    if header.is_lz4_compressed() {
+        anyhow::bail!("Cannot append to an LZ4-compressed file; its block layout would no longer match");
+    }
+    if header.is_block_checksummed() {
+        anyhow::bail!("Cannot append to a block-checksummed file; its block layout would no longer match");
+    }
+
+    let num_dimensions = header.num_dimensions as usize;
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    if vectors.is_empty() {
+        return Ok(header.num_points as usize);
+    }
+
+    let data_end = std::mem::size_of::<BinaryHeader>() as u64 + header.data_size_f32() as u64;
+    file.seek(SeekFrom::Start(data_end)).context("Failed to seek to end of data region")?;
+
+    for vector in vectors {
+        for &value in vector {
+            file.write_all(&value.to_le_bytes()).context("Failed to write vector data")?;
+        }
+    }
+
+    header.num_points += vectors.len() as i32;
+    file.seek(SeekFrom::Start(0)).context("Failed to seek back to header")?;
+    header.write_to(file).context("Failed to rewrite binary header")?;
+
+    Ok(header.num_points as usize)
+}
+
+/// Read vector data from DiskANN binary format, dispatching on the header's dtype
+///
+/// Quantized elements (`F16`/`I8`/`U8`) are widened back to f32 so callers
+/// work with a single [`Vector`] representation regardless of on-disk dtype.
+pub fn read_vectors_typed<R: Read>(reader: &mut R) -> Result<Vec<Vector>> {
+    let header = BinaryHeader::read_from(reader)
+        .context("Failed to read binary header")?;
+
+    header.validate()?;
+    let dtype = header.dtype()?;
+
+    let num_points = header.num_points as usize;
+    let num_dimensions = header.num_dimensions as usize;
+
+    let mut vectors = Vec::with_capacity(num_points);
+
+    for _ in 0..num_points {
+        let mut vector = Vector::with_capacity(num_dimensions);
+        for _ in 0..num_dimensions {
+            let value = match dtype {
+                VectorDtype::F32 => {
+                    let mut bytes = [0u8; 4];
+                    reader.read_exact(&mut bytes)
+                        .context("Failed to read vector component")?;
+                    f32::from_le_bytes(bytes)
+                }
+                VectorDtype::F16 => {
+                    let mut bytes = [0u8; 2];
+                    reader.read_exact(&mut bytes)
+                        .context("Failed to read vector component")?;
+                    f16::from_le_bytes(bytes).to_f32()
+                }
+                VectorDtype::I8 => {
+                    let mut bytes = [0u8; 1];
+                    reader.read_exact(&mut bytes)
+                        .context("Failed to read vector component")?;
+                    i8::from_le_bytes(bytes) as f32
+                }
+                VectorDtype::U8 => {
+                    let mut bytes = [0u8; 1];
+                    reader.read_exact(&mut bytes)
+                        .context("Failed to read vector component")?;
+                    u8::from_le_bytes(bytes) as f32
+                }
+            };
+            vector.push(value);
+        }
+        vectors.push(vector);
+    }
+
+    Ok(vectors)
+}
+
+/// Write vector data whose elements are natively `T` (see [`StoredScalar`])
+///
+/// Unlike [`write_vectors_typed`], which always quantizes down from f32,
+/// this stores already-typed data (e.g. `Vec<i8>` codes a caller computed
+/// itself) losslessly.
+pub fn write_vectors<W: Write, T: StoredScalar>(
+    writer: &mut W,
+    vectors: &[Vec<T>],
+) -> Result<usize> {
+    if vectors.is_empty() {
+        anyhow::bail!("Cannot write empty vector array");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            anyhow::bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let header = BinaryHeader::with_dtype(num_points, num_dimensions, T::DTYPE);
+    header.validate()?;
+
+    header.write_to(writer).context("Failed to write binary header")?;
+
+    for vector in vectors {
+        for &value in vector {
+            writer.write_all(&value.to_le_bytes())
+                .context("Failed to write vector data")?;
+        }
+    }
+
+    header.total_file_size().context("Failed to compute total file size")
+}
+
+/// Read vector data whose elements are natively `T` (see [`StoredScalar`])
+///
+/// Rejects the file if its header declares a dtype other than `T::DTYPE`
+/// rather than silently reinterpreting the bytes, so a caller requesting
+/// `i8` codes never gets handed back misdecoded `f16`/`f32` data.
+pub fn read_vectors<R: Read, T: StoredScalar>(reader: &mut R) -> Result<Vec<Vec<T>>> {
+    let header = BinaryHeader::read_from(reader)
+        .context("Failed to read binary header")?;
+
+    header.validate()?;
+    let dtype = header.dtype()?;
+    if dtype != T::DTYPE {
+        anyhow::bail!(
+            "File declares dtype {:?}, but caller requested {:?}",
+            dtype,
+            T::DTYPE
+        );
+    }
+
+    let num_points = header.num_points as usize;
+    let num_dimensions = header.num_dimensions as usize;
+
+    let mut vectors = Vec::with_capacity(num_points);
+    for _ in 0..num_points {
+        let mut vector = Vec::with_capacity(num_dimensions);
+        for _ in 0..num_dimensions {
+            let mut bytes = vec![0u8; T::SIZE];
+            reader.read_exact(&mut bytes)
+                .context("Failed to read vector component")?;
+            vector.push(T::from_le_bytes(&bytes));
+        }
+        vectors.push(vector);
+    }
+
+    Ok(vectors)
+}
+
+/// Write graph nodes to binary format: a vector block (see
+/// [`write_vectors_f32`]) followed by the adjacency section described by
+/// [`GraphHeader`], with `entry_point` 0 and variable-length (unpadded)
+/// neighbor lists
+pub fn write_graph_nodes<W: Write>(
+    writer: &mut W,
+    nodes: &[GraphNode],
+) -> Result<usize> {
+    write_graph_nodes_with_opts(writer, nodes, GraphHeader::new(0, false))
+}
+
+/// Write graph nodes to binary format using an explicit [`GraphHeader`],
+/// e.g. to record the real medoid id or request fixed-stride padding for
+/// O(1) random access to a node's neighbor list
+pub fn write_graph_nodes_with_opts<W: Write>(
+    writer: &mut W,
+    nodes: &[GraphNode],
+    header: GraphHeader,
+) -> Result<usize> {
+    if nodes.is_empty() {
+        anyhow::bail!("Cannot write empty graph nodes array");
+    }
+
+    // Write vector data first
+    let vectors: Vec<Vector> = nodes.iter().map(|node| node.vector.clone()).collect();
+    let mut bytes_written = write_vectors_f32(writer, &vectors)?;
+
+    header.write_to(writer).context("Failed to write graph header")?;
+    bytes_written += 8;
+
+    let max_degree = nodes.iter().map(|node| node.neighbors.len()).max().unwrap_or(0) as u32;
+    writer.write_all(&max_degree.to_le_bytes())
+        .context("Failed to write graph max_degree")?;
+    bytes_written += 4;
+
+    for node in nodes {
+        writer.write_all(&(node.neighbors.len() as u32).to_le_bytes())
+            .context("Failed to write neighbor_count")?;
+        bytes_written += 4;
+
+        for &neighbor in &node.neighbors {
+            writer.write_all(&neighbor.to_le_bytes())
+                .context("Failed to write neighbor id")?;
+            bytes_written += 4;
+        }
+
+        if header.fixed_stride {
+            for _ in node.neighbors.len()..max_degree as usize {
+                writer.write_all(&0u32.to_le_bytes())
+                    .context("Failed to write neighbor padding")?;
+                bytes_written += 4;
+            }
+        }
+    }
+
+    Ok(bytes_written)
+}
+
+/// Read graph nodes from binary format written by [`write_graph_nodes`],
+/// reconstructing each node's vector and neighbor list
+pub fn read_graph_nodes<R: Read>(reader: &mut R) -> Result<Vec<GraphNode>> {
+    let (nodes, _header) = read_graph_nodes_with_header(reader)?;
+    Ok(nodes)
+}
+
+/// Read graph nodes along with the [`GraphHeader`] that precedes the
+/// adjacency section, for callers that need the entry point/medoid id
+pub fn read_graph_nodes_with_header<R: Read>(reader: &mut R) -> Result<(Vec<GraphNode>, GraphHeader)> {
+    let vectors = read_vectors_f32(reader)?;
+
+    let header = GraphHeader::read_from(reader).context("Failed to read graph header")?;
+
+    let mut max_degree_bytes = [0u8; 4];
+    reader.read_exact(&mut max_degree_bytes)
+        .context("Failed to read graph max_degree")?;
+    let max_degree = u32::from_le_bytes(max_degree_bytes) as usize;
+
+    let mut nodes = Vec::with_capacity(vectors.len());
+    for (i, vector) in vectors.into_iter().enumerate() {
+        let mut count_bytes = [0u8; 4];
+        reader.read_exact(&mut count_bytes)
+            .context("Failed to read neighbor_count")?;
+        let neighbor_count = u32::from_le_bytes(count_bytes) as usize;
+
+        let mut neighbors = Vec::with_capacity(neighbor_count);
+        for _ in 0..neighbor_count {
+            let mut id_bytes = [0u8; 4];
+            reader.read_exact(&mut id_bytes)
+                .context("Failed to read neighbor id")?;
+            neighbors.push(u32::from_le_bytes(id_bytes));
+        }
+
+        if header.fixed_stride {
+            for _ in neighbor_count..max_degree {
+                let mut pad_bytes = [0u8; 4];
+                reader.read_exact(&mut pad_bytes)
+                    .context("Failed to read neighbor padding")?;
+            }
+        }
+
+        let mut node = GraphNode::new(i as VectorId, vector);
+        node.neighbors = neighbors;
+        nodes.push(node);
+    }
+
+    Ok((nodes, header))
+}
+
+/// Write graph nodes to binary format using an explicit [`GraphHeader`],
+/// LZ4-compressing everything after the leading [`BinaryHeader`] (vectors and
+/// adjacency alike) as fixed-size blocks (see [`crate::lz4_blocks`]) and
+/// setting [`COMPRESSION_FLAG`] so [`read_graph_nodes_lz4_with_header`] knows
+/// to expect and decompress them
+pub fn write_graph_nodes_lz4<W: Write>(
+    writer: &mut W,
+    nodes: &[GraphNode],
+    graph_header: GraphHeader,
+    block_size: usize,
+) -> Result<usize> {
+    if nodes.is_empty() {
+        anyhow::bail!("Cannot write empty graph nodes array");
+    }
+
+    let mut uncompressed = Vec::new();
+    write_graph_nodes_with_opts(&mut uncompressed, nodes, graph_header)?;
+
+    let header_size = std::mem::size_of::<BinaryHeader>();
+    let body = &uncompressed[header_size..];
+
+    let num_points = nodes.len();
+    let num_dimensions = nodes[0].vector.len();
+    let header = BinaryHeader::new(num_points, num_dimensions).with_lz4_flag(true);
+    header.validate()?;
+    header.write_to(writer).context("Failed to write binary header")?;
+
+    let body_bytes = write_payload_lz4_blocks(writer, body, block_size)
+        .context("Failed to write LZ4-compressed graph payload")?;
+
+    Ok(header_size + body_bytes)
+}
+
+/// Read graph nodes written by either [`write_graph_nodes`] or
+/// [`write_graph_nodes_lz4`], discarding the [`GraphHeader`]
+///
+/// Auto-detects compression via [`BinaryHeader::is_lz4_compressed`]; see
+/// [`read_graph_nodes_lz4_with_header`].
+pub fn read_graph_nodes_lz4<R: Read>(reader: &mut R) -> Result<Vec<GraphNode>> {
+    let (nodes, _header) = read_graph_nodes_lz4_with_header(reader)?;
+    Ok(nodes)
+}
+
+/// Read graph nodes along with the [`GraphHeader`], written by either
+/// [`write_graph_nodes`]/[`write_graph_nodes_with_opts`] or
+/// [`write_graph_nodes_lz4`]
+///
+/// Reads the leading [`BinaryHeader`] to auto-detect
+/// [`BinaryHeader::is_lz4_compressed`], decompresses the rest of the file if
+/// so, then re-serializes the header in front of the (now plain) payload and
+/// parses it exactly as [`read_graph_nodes_with_header`] would, so the two
+/// functions never duplicate the adjacency-decoding logic.
+pub fn read_graph_nodes_lz4_with_header<R: Read>(reader: &mut R) -> Result<(Vec<GraphNode>, GraphHeader)> {
+    let header = BinaryHeader::read_from(reader).context("Failed to read binary header")?;
+    header.validate()?;
+
+    let body = if header.is_lz4_compressed() {
+        read_payload_lz4_blocks(reader).context("Failed to read LZ4-compressed graph payload")?
+    } else {
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).context("Failed to read graph payload")?;
+        rest
+    };
+
+    let mut reconstructed = Vec::with_capacity(std::mem::size_of::<BinaryHeader>() + body.len());
+    header
+        .with_lz4_flag(false)
+        .write_to(&mut reconstructed)
+        .context("Failed to re-serialize binary header")?;
+    reconstructed.extend_from_slice(&body);
+
+    read_graph_nodes_with_header(&mut reconstructed.as_slice())
+}
+
+/// Seek-based random-access reader over a DiskANN `.bin` file
+///
+/// Unlike [`read_vectors_f32`], which loads every vector into memory up
+/// front, this wraps any seekable source (a file, a `Cursor`, an mmap) and
+/// fetches individual vectors on demand by seeking directly to their byte
+/// offset, `size_of::<BinaryHeader>() + id * num_dimensions * 4`. The header
+/// is parsed once at construction and `num_dimensions`/`num_points` are
+/// cached, so repeated reads avoid re-parsing it. Only the f32 dtype is
+/// supported; use [`read_vectors_typed`] for quantized files.
+pub struct VectorReader<R> {
+    reader: R,
+    num_points: usize,
+    num_dimensions: usize,
+}
+
+impl<R: Read + Seek> VectorReader<R> {
+    /// Wrap a seekable source, parsing its [`BinaryHeader`] once up front
+    pub fn new(mut reader: R) -> Result<Self> {
+        let header = BinaryHeader::read_from(&mut reader)
+            .context("Failed to read binary header")?;
+        header.validate()?;
+        if header.dtype()? != VectorDtype::F32 {
+            anyhow::bail!("VectorReader only supports the f32 dtype");
+        }
+
+        Ok(Self {
+            reader,
+            num_points: header.num_points as usize,
+            num_dimensions: header.num_dimensions as usize,
+        })
+    }
+
+    /// Number of vectors available in the underlying file
+    pub fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    /// Dimensionality of each vector
+    pub fn num_dimensions(&self) -> usize {
+        self.num_dimensions
+    }
+
+    /// Byte offset of vector `id`, including the header
+    fn offset_of(&self, id: u32) -> u64 {
+        std::mem::size_of::<BinaryHeader>() as u64 + (id as u64) * (self.num_dimensions as u64) * 4
+    }
+
+    /// Read a single vector by id, seeking directly to its byte offset
+    ///
+    /// Returns an error if `id >= num_points()` rather than reading past the
+    /// end of the dataset.
+    pub fn read_vector(&mut self, id: u32) -> IoResult<Vector> {
+        if id as usize >= self.num_points {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "vector id {} out of range (num_points = {})",
+                    id, self.num_points
+                ),
+            ));
+        }
+
+        self.reader.seek(SeekFrom::Start(self.offset_of(id)))?;
+
+        let mut vector = Vector::with_capacity(self.num_dimensions);
+        for _ in 0..self.num_dimensions {
+            let mut bytes = [0u8; 4];
+            self.reader.read_exact(&mut bytes)?;
+            vector.push(f32::from_le_bytes(bytes));
+        }
+        Ok(vector)
+    }
+
+    /// Read `len` consecutive vectors starting at id `start`
+    pub fn read_range(&mut self, start: u32, len: u32) -> IoResult<Vec<Vector>> {
+        let mut vectors = Vec::with_capacity(len as usize);
+        for id in start..start.saturating_add(len) {
+            vectors.push(self.read_vector(id)?);
+        }
+        Ok(vectors)
+    }
+}
+
+/// Streaming reader over a `.fvecs`-format file
+///
+/// Each record is a little-endian `int32` dimension count followed by that
+/// many `float32` components, the layout used by SIFT/GIST/DEEP and other
+/// standard ANN benchmark datasets. Records are read one at a time so large
+/// files never need to be fully loaded into memory.
+pub struct FvecsReader<R> {
+    reader: R,
+}
+
+impl<R: Read> FvecsReader<R> {
+    /// Wrap a reader over `.fvecs`-formatted bytes
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for FvecsReader<R> {
+    type Item = Result<Vector>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut dim_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut dim_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e).context("Failed to read fvecs dimension prefix")),
+        }
+
+        let dim = i32::from_le_bytes(dim_bytes) as usize;
+        let mut vector = Vector::with_capacity(dim);
+        for _ in 0..dim {
+            let mut bytes = [0u8; 4];
+            if let Err(e) = self.reader.read_exact(&mut bytes) {
+                return Some(Err(e).context("Failed to read fvecs component"));
+            }
+            vector.push(f32::from_le_bytes(bytes));
+        }
+        Some(Ok(vector))
+    }
+}
+
+/// Streaming reader over a `.bvecs`-format file (dimension prefix followed by
+/// that many `uint8` components)
+pub struct BvecsReader<R> {
+    reader: R,
+}
+
+impl<R: Read> BvecsReader<R> {
+    /// Wrap a reader over `.bvecs`-formatted bytes
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for BvecsReader<R> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut dim_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut dim_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e).context("Failed to read bvecs dimension prefix")),
+        }
+
+        let dim = i32::from_le_bytes(dim_bytes) as usize;
+        let mut vector = vec![0u8; dim];
+        if let Err(e) = self.reader.read_exact(&mut vector) {
+            return Some(Err(e).context("Failed to read bvecs component"));
+        }
+        Some(Ok(vector))
+    }
+}
+
+/// Streaming reader over an `.ivecs`-format file (dimension prefix followed
+/// by that many `int32` components), typically used for ground-truth
+/// neighbor id lists
+pub struct IvecsReader<R> {
+    reader: R,
+}
+
+impl<R: Read> IvecsReader<R> {
+    /// Wrap a reader over `.ivecs`-formatted bytes
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for IvecsReader<R> {
+    type Item = Result<Vec<i32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut dim_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut dim_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return None,
+            Err(e) => return Some(Err(e).context("Failed to read ivecs dimension prefix")),
+        }
+
+        let dim = i32::from_le_bytes(dim_bytes) as usize;
+        let mut vector = Vec::with_capacity(dim);
+        for _ in 0..dim {
+            let mut bytes = [0u8; 4];
+            if let Err(e) = self.reader.read_exact(&mut bytes) {
+                return Some(Err(e).context("Failed to read ivecs component"));
+            }
+            vector.push(i32::from_le_bytes(bytes));
+        }
+        Some(Ok(vector))
+    }
+}
+
+/// Write vectors in `.fvecs` format: each record is a little-endian `int32`
+/// dimension count followed by that many `float32` components
+pub fn write_fvecs<W: Write>(writer: &mut W, vectors: &[Vector]) -> Result<usize> {
+    let mut bytes_written = 0;
+    for vector in vectors {
+        let dim = vector.len() as i32;
+        writer.write_all(&dim.to_le_bytes())
+            .context("Failed to write fvecs dimension prefix")?;
+        bytes_written += std::mem::size_of::<i32>();
+        for &value in vector {
+            writer.write_all(&value.to_le_bytes())
+                .context("Failed to write fvecs component")?;
+            bytes_written += std::mem::size_of::<f32>();
+        }
+    }
+    Ok(bytes_written)
+}
+
+/// Write vectors in `.bvecs` format: each record is a little-endian `int32`
+/// dimension count followed by that many `uint8` components
+pub fn write_bvecs<W: Write>(writer: &mut W, vectors: &[Vec<u8>]) -> Result<usize> {
+    let mut bytes_written = 0;
+    for vector in vectors {
+        let dim = vector.len() as i32;
+        writer.write_all(&dim.to_le_bytes())
+            .context("Failed to write bvecs dimension prefix")?;
+        bytes_written += std::mem::size_of::<i32>();
+        writer.write_all(vector)
+            .context("Failed to write bvecs component")?;
+        bytes_written += vector.len();
+    }
+    Ok(bytes_written)
+}
+
+/// Write vectors in `.ivecs` format: each record is a little-endian `int32`
+/// dimension count followed by that many `int32` components
+pub fn write_ivecs<W: Write>(writer: &mut W, vectors: &[Vec<i32>]) -> Result<usize> {
+    let mut bytes_written = 0;
+    for vector in vectors {
+        let dim = vector.len() as i32;
+        writer.write_all(&dim.to_le_bytes())
+            .context("Failed to write ivecs dimension prefix")?;
+        bytes_written += std::mem::size_of::<i32>();
+        for &value in vector {
+            writer.write_all(&value.to_le_bytes())
+                .context("Failed to write ivecs component")?;
+            bytes_written += std::mem::size_of::<i32>();
+        }
+    }
+    Ok(bytes_written)
+}
+
+/// Vector dataset container formats supported by [`convert`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VecFormat {
+    /// `.fvecs`: dimension-prefixed records of `float32` components
+    Fvecs,
+    /// `.bvecs`: dimension-prefixed records of `uint8` components
+    Bvecs,
+    /// `.ivecs`: dimension-prefixed records of `int32` components
+    Ivecs,
+    /// DiskANN native `.bin`: leading `num_points`/`num_dimensions` header
+    /// followed by a row-major `float32` matrix
+    Bin,
+}
+
+/// Transcode a vector dataset between the `.fvecs`/`.bvecs`/`.ivecs` family
+/// and the native DiskANN `.bin` format, mirroring upstream DiskANN's
+/// `fvecs_to_bin`/`float_bin_to_int8` conversion tools so SIFT/GIST/DEEP
+/// benchmark datasets can be loaded directly.
+pub fn convert<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    from: VecFormat,
+    to: VecFormat,
+) -> Result<usize> {
+    let vectors: Vec<Vector> = match from {
+        VecFormat::Fvecs => FvecsReader::new(&mut *reader).collect::<Result<Vec<_>>>()?,
+        VecFormat::Bvecs => BvecsReader::new(&mut *reader)
+            .map(|record| record.map(|bytes| bytes.into_iter().map(|b| b as f32).collect()))
+            .collect::<Result<Vec<_>>>()?,
+        VecFormat::Ivecs => IvecsReader::new(&mut *reader)
+            .map(|record| record.map(|ints| ints.into_iter().map(|i| i as f32).collect()))
+            .collect::<Result<Vec<_>>>()?,
+        VecFormat::Bin => read_vectors_f32(reader)?,
+    };
+
+    match to {
+        VecFormat::Fvecs => write_fvecs(writer, &vectors),
+        VecFormat::Bvecs => {
+            let byte_vectors: Vec<Vec<u8>> = vectors.iter()
+                .map(|vector| vector.iter().map(|&value| value as u8).collect())
+                .collect();
+            write_bvecs(writer, &byte_vectors)
+        }
+        VecFormat::Ivecs => {
+            let int_vectors: Vec<Vec<i32>> = vectors.iter()
+                .map(|vector| vector.iter().map(|&value| value as i32).collect())
+                .collect();
+            write_ivecs(writer, &int_vectors)
+        }
+        VecFormat::Bin => write_vectors_f32(writer, &vectors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    
+    #[test]
+    fn test_binary_header_round_trip() {
+        let header = BinaryHeader::new(100, 128);
+        let mut buffer = Vec::new();
+        header.write_to(&mut buffer).unwrap();
         
         let mut cursor = Cursor::new(buffer);
-        let read_nodes = read_graph_nodes(&mut cursor).unwrap();
+        let read_header = BinaryHeader::read_from(&mut cursor).unwrap();
+        
+        assert_eq!(header.num_points, read_header.num_points);
+        assert_eq!(header.num_dimensions, read_header.num_dimensions);
+    }
+    
+    #[test]
+    fn test_binary_header_validate_rejects_overflowing_product() {
+        // 100_000 * 100_000 overflows i32 (and wraps to a much smaller,
+        // wrong value under plain `i32` multiplication) -- `validate()`
+        // must reject it outright rather than let a size method silently
+        // wrap.
+        let header = BinaryHeader::new(100_000, 100_000);
+        assert!(header.validate().is_err());
+    }
+
+    #[test]
+    fn test_vectors_round_trip() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+        
+        let mut buffer = Vec::new();
+        let bytes_written = write_vectors_f32(&mut buffer, &vectors).unwrap();
+        
+        let mut cursor = Cursor::new(buffer);
+        let read_vectors = read_vectors_f32(&mut cursor).unwrap();
         
+        assert_eq!(vectors.len(), read_vectors.len());
+        for (orig, read) in vectors.iter().zip(read_vectors.iter()) {
+            assert_eq!(orig.len(), read.len());
+            for (&orig_val, &read_val) in orig.iter().zip(read.iter()) {
+                assert!((orig_val - read_val).abs() < f32::EPSILON);
+            }
+        }
+        
+        // Check bytes written calculation
+        let expected_bytes = std::mem::size_of::<BinaryHeader>() + (3 * 3 * 4); // header + data
+        assert_eq!(bytes_written, expected_bytes);
+    }
+    
+    #[test]
+    fn test_vector_reader_random_access() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32(&mut buffer, &vectors).unwrap();
+
+        let mut reader = VectorReader::new(Cursor::new(buffer)).unwrap();
+        assert_eq!(reader.num_points(), 3);
+        assert_eq!(reader.num_dimensions(), 3);
+
+        // Out-of-order access should still land on the right vector
+        assert_eq!(reader.read_vector(2).unwrap(), vectors[2]);
+        assert_eq!(reader.read_vector(0).unwrap(), vectors[0]);
+        assert_eq!(reader.read_vector(1).unwrap(), vectors[1]);
+    }
+
+    #[test]
+    fn test_vector_reader_read_range() {
+        let vectors = vec![
+            vec![1.0, 2.0],
+            vec![3.0, 4.0],
+            vec![5.0, 6.0],
+            vec![7.0, 8.0],
+        ];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32(&mut buffer, &vectors).unwrap();
+
+        let mut reader = VectorReader::new(Cursor::new(buffer)).unwrap();
+        let range = reader.read_range(1, 2).unwrap();
+        assert_eq!(range, vec![vectors[1].clone(), vectors[2].clone()]);
+    }
+
+    #[test]
+    fn test_vector_reader_rejects_out_of_range_id() {
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32(&mut buffer, &vectors).unwrap();
+
+        let mut reader = VectorReader::new(Cursor::new(buffer)).unwrap();
+        assert!(reader.read_vector(2).is_err());
+    }
+
+    #[test]
+    fn test_graph_nodes_round_trip() {
+        let mut node0 = GraphNode::new(0, vec![1.0, 2.0]);
+        node0.neighbors = vec![1, 2];
+        let mut node1 = GraphNode::new(1, vec![3.0, 4.0]);
+        node1.neighbors = vec![0];
+        let node2 = GraphNode::new(2, vec![5.0, 6.0]);
+        let nodes = vec![node0, node1, node2];
+
+        let mut buffer = Vec::new();
+        write_graph_nodes(&mut buffer, &nodes).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_nodes = read_graph_nodes(&mut cursor).unwrap();
+
         assert_eq!(nodes.len(), read_nodes.len());
         for (orig, read) in nodes.iter().zip(read_nodes.iter()) {
             assert_eq!(orig.vector.len(), read.vector.len());
             for (&orig_val, &read_val) in orig.vector.iter().zip(read.vector.iter()) {
                 assert!((orig_val - read_val).abs() < f32::EPSILON);
             }
+            // Neighbor lists must survive bit-for-bit, not just vectors.
+            assert_eq!(orig.neighbors, read.neighbors);
         }
     }
+
+    #[test]
+    fn test_graph_nodes_fixed_stride_round_trip() {
+        let mut node0 = GraphNode::new(0, vec![1.0, 2.0]);
+        node0.neighbors = vec![1, 2, 3];
+        let mut node1 = GraphNode::new(1, vec![3.0, 4.0]);
+        node1.neighbors = vec![0];
+        let nodes = vec![node0, node1];
+
+        let mut buffer = Vec::new();
+        let header = GraphHeader::new(0, true);
+        write_graph_nodes_with_opts(&mut buffer, &nodes, header).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let (read_nodes, read_header) = read_graph_nodes_with_header(&mut cursor).unwrap();
+
+        assert!(read_header.fixed_stride);
+        assert_eq!(read_header.entry_point, 0);
+        assert_eq!(nodes[0].neighbors, read_nodes[0].neighbors);
+        assert_eq!(nodes[1].neighbors, read_nodes[1].neighbors);
+    }
+
+    #[test]
+    fn test_graph_header_records_entry_point() {
+        let nodes = vec![GraphNode::new(0, vec![1.0]), GraphNode::new(1, vec![2.0])];
+
+        let mut buffer = Vec::new();
+        write_graph_nodes_with_opts(&mut buffer, &nodes, GraphHeader::new(1, false)).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let (_nodes, header) = read_graph_nodes_with_header(&mut cursor).unwrap();
+        assert_eq!(header.entry_point, 1);
+        assert!(!header.fixed_stride);
+    }
+
+    #[test]
+    fn test_write_vectors_i8_round_trip() {
+        let vectors: Vec<Vec<i8>> = vec![vec![-128, 0, 127], vec![1, -1, 42]];
+
+        let mut buffer = Vec::new();
+        write_vectors(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_back: Vec<Vec<i8>> = read_vectors(&mut cursor).unwrap();
+
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_write_vectors_u8_round_trip() {
+        let vectors: Vec<Vec<u8>> = vec![vec![0, 128, 255], vec![10, 20, 30]];
+
+        let mut buffer = Vec::new();
+        write_vectors(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_back: Vec<Vec<u8>> = read_vectors(&mut cursor).unwrap();
+
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_write_vectors_f16_round_trip() {
+        let vectors: Vec<Vec<f16>> = vec![
+            vec![f16::from_f32(1.5), f16::from_f32(-2.25)],
+            vec![f16::from_f32(0.0), f16::from_f32(100.0)],
+        ];
+
+        let mut buffer = Vec::new();
+        write_vectors(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_back: Vec<Vec<f16>> = read_vectors(&mut cursor).unwrap();
+
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_read_vectors_rejects_dtype_mismatch() {
+        let vectors: Vec<Vec<i8>> = vec![vec![1, 2, 3]];
+
+        let mut buffer = Vec::new();
+        write_vectors(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let result: Result<Vec<Vec<f32>>> = read_vectors(&mut cursor);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("declares dtype"));
+    }
+
+    #[test]
+    fn test_fvecs_round_trip() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+        ];
+
+        let mut buffer = Vec::new();
+        write_fvecs(&mut buffer, &vectors).unwrap();
+
+        let read_vectors: Vec<Vector> = FvecsReader::new(Cursor::new(buffer))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(vectors, read_vectors);
+    }
+
+    #[test]
+    fn test_bvecs_round_trip() {
+        let vectors = vec![vec![1u8, 2, 3], vec![250, 251, 252]];
+
+        let mut buffer = Vec::new();
+        write_bvecs(&mut buffer, &vectors).unwrap();
+
+        let read_vectors: Vec<Vec<u8>> = BvecsReader::new(Cursor::new(buffer))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(vectors, read_vectors);
+    }
+
+    #[test]
+    fn test_ivecs_round_trip() {
+        let vectors = vec![vec![1, 2, 3], vec![-4, -5, -6]];
+
+        let mut buffer = Vec::new();
+        write_ivecs(&mut buffer, &vectors).unwrap();
+
+        let read_vectors: Vec<Vec<i32>> = IvecsReader::new(Cursor::new(buffer))
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(vectors, read_vectors);
+    }
+
+    #[test]
+    fn test_binary_header_big_endian_round_trip() {
+        let header = BinaryHeader::new(100, 128);
+        let mut buffer = Vec::new();
+        header.write_to_endian(&mut buffer, Endianness::Big).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_header = BinaryHeader::read_from_endian(&mut cursor, Endianness::Big).unwrap();
+
+        assert_eq!(header.num_points, read_header.num_points);
+        assert_eq!(header.num_dimensions, read_header.num_dimensions);
+        assert_eq!(header.dtype, read_header.dtype);
+    }
+
+    #[test]
+    fn test_binary_header_little_and_big_endian_bytes_differ() {
+        let header = BinaryHeader::new(0x0102_0304, 1);
+
+        let mut little = Vec::new();
+        header.write_to_endian(&mut little, Endianness::Little).unwrap();
+
+        let mut big = Vec::new();
+        header.write_to_endian(&mut big, Endianness::Big).unwrap();
+
+        assert_ne!(little, big);
+        assert_eq!(little[0..4], [0x04, 0x03, 0x02, 0x01]);
+        assert_eq!(big[0..4], [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn test_vectors_f32_big_endian_round_trip() {
+        let vectors = vec![vec![1.0, 2.0, 3.0], vec![-4.5, 5.5, 6.5]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_endian(&mut buffer, &vectors, Endianness::Big).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_vectors = read_vectors_f32_endian(&mut cursor, Endianness::Big).unwrap();
+
+        assert_eq!(vectors, read_vectors);
+    }
+
+    #[test]
+    fn test_binary_header_truncation_reports_offset() {
+        // Only 6 of the 12 header bytes are present, so the failure lands on
+        // the second field (num_dimensions) at byte offset 4.
+        let mut cursor = Cursor::new(vec![0u8; 6]);
+        let err = BinaryHeader::read_from(&mut cursor).unwrap_err();
+        assert!(err.to_string().contains("byte offset 4"));
+    }
+
+    #[test]
+    fn test_checksummed_vectors_round_trip() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+        ];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_checksummed(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_back = read_vectors_f32_checksummed(&mut cursor).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_checksummed_vectors_detect_corruption() {
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_checksummed(&mut buffer, &vectors).unwrap();
+
+        // Flip a byte in the middle of the payload, past the header.
+        let corrupt_at = std::mem::size_of::<BinaryHeader>() + 1;
+        buffer[corrupt_at] ^= 0xFF;
+
+        let mut cursor = Cursor::new(buffer);
+        let result = read_vectors_f32_checksummed(&mut cursor);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn test_checksummed_reader_accepts_unflagged_files() {
+        // Files written by the plain (non-checksummed) writer have no
+        // footer and must still load through the checksum-aware reader.
+        let vectors = vec![vec![1.0, 2.0, 3.0]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32(&mut buffer, &vectors).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let read_back = read_vectors_f32_checksummed(&mut cursor).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_checksum_flag_round_trips_through_header() {
+        let header = BinaryHeader::new(10, 4).with_checksum_flag(true);
+        assert!(header.has_checksum());
+        assert_eq!(header.dtype().unwrap(), VectorDtype::F32);
+
+        let header = header.with_checksum_flag(false);
+        assert!(!header.has_checksum());
+    }
+
+    #[test]
+    fn test_append_vectors_f32_grows_file_in_place() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_vectors_f32(&mut buffer, &[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+
+        let total = append_vectors_f32(&mut buffer, &[vec![5.0, 6.0]]).unwrap();
+        assert_eq!(total, 3);
+
+        buffer.set_position(0);
+        let read_back = read_vectors_f32(&mut buffer).unwrap();
+        assert_eq!(read_back, vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+    }
+
+    #[test]
+    fn test_append_vectors_f32_rejects_dimension_mismatch() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_vectors_f32(&mut buffer, &[vec![1.0, 2.0]]).unwrap();
+
+        let result = append_vectors_f32(&mut buffer, &[vec![1.0, 2.0, 3.0]]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_append_vectors_f32_rejects_checksummed_file() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_vectors_f32_checksummed(&mut buffer, &[vec![1.0, 2.0]]).unwrap();
+
+        let result = append_vectors_f32(&mut buffer, &[vec![3.0, 4.0]]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_convert_fvecs_to_bin_and_back() {
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        let mut fvecs_buffer = Vec::new();
+        write_fvecs(&mut fvecs_buffer, &vectors).unwrap();
+
+        let mut bin_buffer = Vec::new();
+        convert(
+            &mut Cursor::new(fvecs_buffer),
+            &mut bin_buffer,
+            VecFormat::Fvecs,
+            VecFormat::Bin,
+        )
+        .unwrap();
+
+        let round_tripped = read_vectors_f32(&mut Cursor::new(bin_buffer)).unwrap();
+        assert_eq!(vectors, round_tripped);
+    }
+
+    #[test]
+    fn test_vectors_f32_block_checksummed_round_trip_lz4() {
+        let vectors: Vec<Vector> = (0..200).map(|i| vec![i as f32, (i * 2) as f32, (i * 3) as f32]).collect();
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_block_checksummed(&mut buffer, &vectors, CompressionType::Lz4, 256).unwrap();
+
+        let read_back = read_vectors_f32_block_checksummed(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_vectors_f32_block_checksummed_round_trip_miniz() {
+        let vectors: Vec<Vector> = (0..200).map(|i| vec![i as f32, (i * 2) as f32, (i * 3) as f32]).collect();
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_block_checksummed(&mut buffer, &vectors, CompressionType::Miniz(6), 256).unwrap();
+
+        let header = BinaryHeader::read_from(&mut Cursor::new(buffer.clone())).unwrap();
+        assert!(header.is_block_checksummed());
+
+        let read_back = read_vectors_f32_block_checksummed(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_vectors_f32_block_checksummed_none_matches_plain_format() {
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32_block_checksummed(&mut buffer, &vectors, CompressionType::None, 256).unwrap();
+
+        let header = BinaryHeader::read_from(&mut Cursor::new(buffer.clone())).unwrap();
+        assert!(!header.is_block_checksummed());
+
+        let read_back = read_vectors_f32_block_checksummed(&mut Cursor::new(buffer)).unwrap();
+        assert_eq!(vectors, read_back);
+    }
+
+    #[test]
+    fn test_append_vectors_f32_rejects_block_checksummed_file() {
+        let mut buffer = Cursor::new(Vec::new());
+        write_vectors_f32_block_checksummed(&mut buffer, &[vec![1.0, 2.0]], CompressionType::Lz4, 256).unwrap();
+
+        let result = append_vectors_f32(&mut buffer, &[vec![3.0, 4.0]]);
+        assert!(result.is_err());
+    }
 }
\ No newline at end of file
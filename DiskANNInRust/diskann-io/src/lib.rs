@@ -24,13 +24,73 @@ pub mod loader;
 /// Disk-based index for memory-efficient storage
 pub mod disk_index;
 
+/// Product quantization compression for memory-efficient vector storage
+pub mod pq;
+
+/// Zstd-compressed block container format with O(1) subset loading
+pub mod compressed;
+
+/// Fixed-size LZ4 block container transparently layered under [`format::BinaryHeader`]
+pub mod lz4_blocks;
+
+/// Standalone LZ4 block container with a per-block raw/compressed type tag
+pub mod lz4_container;
+
+/// Block container with a selectable codec and a per-block xxh3-64 checksum
+pub mod block_checksum;
+
+/// CRC32C integrity checking for the binary formats in [`format`]
+pub mod checksum;
+
+/// Strict header/offset validation for mmapped index files
+pub mod sanitized;
+
+/// Cross-platform advisory file locking for concurrent readers/writers
+pub mod flock;
+
+/// Sharded (bucketed) index storage for horizontal scale-out
+pub mod sharded;
+
+/// Mergeable incremental index segments with a merging loader
+pub mod segmented;
+
+/// Apache Arrow IPC import/export for vector datasets
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
 // Re-export main types for convenience
 pub use writer::{IndexWriter, FileIndexWriter, IndexWriterBuilder};
 pub use loader::{IndexLoader, MmapIndexLoader, IndexLoaderBuilder, IndexMetadata};
-pub use format::{BinaryHeader, write_vectors_f32, read_vectors_f32, write_graph_nodes, read_graph_nodes};
-pub use serialization::{serialize_index, deserialize_index};
+#[cfg(feature = "mmap")]
+pub use loader::VectorRangeIter;
+pub use format::{
+    BinaryHeader, VectorDtype, Endianness, StoredScalar, write_vectors, read_vectors,
+    write_vectors_f32, write_vectors_f32_endian,
+    read_vectors_f32, read_vectors_f32_endian, write_vectors_typed,
+    read_vectors_typed, GraphHeader, write_graph_nodes, write_graph_nodes_with_opts,
+    read_graph_nodes, read_graph_nodes_with_header,
+    write_vectors_f32_checksummed, read_vectors_f32_checksummed, append_vectors_f32,
+    write_vectors_f32_lz4, read_vectors_f32_lz4,
+    write_vectors_f32_block_checksummed, read_vectors_f32_block_checksummed,
+    write_graph_nodes_lz4, read_graph_nodes_lz4, read_graph_nodes_lz4_with_header,
+    VectorReader, FvecsReader, BvecsReader, IvecsReader, write_fvecs, write_bvecs, write_ivecs,
+    VecFormat, convert,
+};
+pub use serialization::{
+    serialize_index, deserialize_index, serialize_index_with_metric, deserialize_index_with_metric,
+};
 pub use mmap::{MappingStrategy, platform_mmap_info};
-pub use disk_index::{DiskIndex, DiskIndexBuilder, DiskIndexConfig};
+pub use disk_index::{DiskIndex, DiskIndexBuilder, DiskIndexConfig, DistanceMetric};
+pub use pq::{AdcDistanceTable, PqCodebook};
+pub use compressed::{write_compressed, CompressedVectorReader, WriterOpts};
+pub use lz4_blocks::{read_payload_lz4_blocks, write_payload_lz4_blocks, DEFAULT_BLOCK_SIZE};
+pub use lz4_container::{write_lz4_container, CompressionMode, Lz4ContainerOpts, Lz4ContainerReader};
+pub use block_checksum::CompressionType as BlockCompressionType;
+pub use checksum::{ChecksumReader, ChecksumWriter, ChecksumMismatchError};
+pub use sanitized::{SanitizedMapping, LayoutDescriptor, SectionDescriptor, LayoutError};
+pub use flock::LockGuard;
+pub use sharded::{write_sharded, IndexShardConfig, ShardedIndexLoader};
+pub use segmented::{compact, deletion_sidecar_path, write_deletion_bitmap, SegmentedIndexLoader};
 
 #[cfg(feature = "mmap")]
 pub use mmap::SafeMmap;
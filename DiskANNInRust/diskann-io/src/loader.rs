@@ -6,8 +6,10 @@ use std::io::BufReader;
 use anyhow::{Result, Context};
 use diskann_core::structures::GraphNode;
 use diskann_core::vectors::{Vector, VectorId};
-use crate::mmap::{platform_mmap_info};
-use crate::format::{BinaryHeader, read_vectors_f32};
+use crate::checksum::ChecksumMismatchError;
+use crate::flock::LockGuard;
+use crate::mmap::{platform_mmap_info, MappingStrategy};
+use crate::format::{BinaryHeader, read_vectors_f32_checksummed, read_vectors_f32_block_checksummed};
 
 /// Trait for loading indices from persistent storage
 pub trait IndexLoader {
@@ -22,6 +24,69 @@ pub trait IndexLoader {
     
     /// Get file metadata without loading the full data
     fn get_metadata<P: AsRef<Path>>(&self, path: P) -> Result<IndexMetadata>;
+
+    /// Fast structural check of a file without materializing any vectors:
+    /// confirms the file's actual size matches what its header promises
+    /// (catching truncation/corruption for uncompressed formats), then, if
+    /// the checksum flag is set, verifies the trailing CRC32C footer (see
+    /// [`crate::format::write_vectors_f32_checksummed`])
+    ///
+    /// Files without the checksum flag set pass the footer check trivially,
+    /// since there's no footer to verify.
+    fn verify_only<P: AsRef<Path>>(&self, path: P) -> Result<()>;
+}
+
+/// Recompute the CRC32C over `mapping`'s first `payload_len` bytes and
+/// compare it against the trailing `u32` footer stored right after, without
+/// ever materializing the vectors/graph those bytes encode
+fn verify_checksum_footer(mapping: &MappingStrategy, payload_len: usize) -> Result<()> {
+    let len = mapping.len();
+    if len < payload_len + 4 {
+        anyhow::bail!("Truncated file: missing checksum footer after {} payload bytes", payload_len);
+    }
+
+    let payload = mapping.slice(0, payload_len)?;
+    let computed = crc32c::crc32c(payload);
+
+    let footer = mapping.slice(payload_len, 4)?;
+    let stored = u32::from_le_bytes(footer.try_into().expect("slice of length 4"));
+
+    if stored != computed {
+        return Err(ChecksumMismatchError { expected: stored, computed }.into());
+    }
+    Ok(())
+}
+
+/// Iterator returned by [`MmapIndexLoader::load_vectors_range_iter`], yielding
+/// one [`Vector`] at a time directly from the underlying memory map rather
+/// than pre-loading the whole requested range
+#[cfg(feature = "mmap")]
+pub struct VectorRangeIter {
+    mapping: MappingStrategy,
+    data_offset: usize,
+    num_dimensions: usize,
+    next_index: usize,
+    end_index: usize,
+}
+
+#[cfg(feature = "mmap")]
+impl Iterator for VectorRangeIter {
+    type Item = Result<Vector>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_index >= self.end_index {
+            return None;
+        }
+
+        let offset = self.data_offset + self.next_index * self.num_dimensions * 4;
+        let result = self
+            .mapping
+            .typed_slice::<f32>(offset, self.num_dimensions)
+            .map(|slice| slice.to_vec());
+
+        self.next_index += 1;
+        Some(result)
+    }
 }
 
 /// Metadata about an index file
@@ -35,12 +100,52 @@ pub struct IndexMetadata {
     pub file_size: usize,
     /// Strategy being used (mmap or buffered)
     pub strategy: String,
+    /// Expected vector-data payload size in bytes (`num_vectors * dimension
+    /// * 4` for f32), computed from the header alone -- independent of
+    /// `file_size`, so callers can spot a truncated/corrupt file (whose
+    /// `file_size` won't match what the header promises) without loading
+    /// any vectors
+    pub expected_body_bytes: usize,
+}
+
+/// Expected total on-disk size for an uncompressed, non-block-checksummed
+/// file: header + `num_points * num_dimensions * 4` data bytes, plus a
+/// 4-byte CRC32C footer if [`BinaryHeader::has_checksum`] is set
+///
+/// Returns `None` for LZ4/block-checksummed files, whose body length isn't a
+/// fixed function of the header alone -- those formats validate their own
+/// framing as they decode instead.
+fn expected_file_size(header: &BinaryHeader) -> Option<usize> {
+    if header.is_lz4_compressed() || header.is_block_checksummed() {
+        return None;
+    }
+    let footer = if header.has_checksum() { 4 } else { 0 };
+    Some(header.total_file_size_f32() + footer)
+}
+
+/// Bail with a descriptive error naming the expected vs. actual byte count
+/// if `actual` doesn't match what `header` promises, per [`expected_file_size`]
+fn check_not_truncated(path: &Path, header: &BinaryHeader, actual: usize) -> Result<()> {
+    if let Some(expected) = expected_file_size(header) {
+        if actual != expected {
+            anyhow::bail!(
+                "Truncated or corrupt file {}: expected {} bytes, found {}",
+                path.display(),
+                expected,
+                actual
+            );
+        }
+    }
+    Ok(())
 }
 
 /// Memory-mapped index loader with fallback to buffered I/O
 pub struct MmapIndexLoader {
     /// Whether to prefer memory mapping when available
     prefer_mmap: bool,
+    /// Whether to take an advisory shared lock around [`Self::load_vectors`]/
+    /// [`Self::load_graph`] (see [`crate::flock`])
+    locking: bool,
 }
 
 impl MmapIndexLoader {
@@ -48,16 +153,36 @@ impl MmapIndexLoader {
     pub fn new() -> Self {
         Self {
             prefer_mmap: true,
+            locking: true,
         }
     }
-    
+
     /// Create a loader that always uses buffered I/O
     pub fn buffered_only() -> Self {
         Self {
             prefer_mmap: false,
+            locking: true,
         }
     }
-    
+
+    /// Run `body` with an advisory shared lock held on `path` for its
+    /// duration, unless locking has been disabled via
+    /// [`IndexLoaderBuilder::locking`]
+    ///
+    /// Guards against a concurrent writer (see [`crate::writer::FileIndexWriter`])
+    /// swapping the file's contents out from under a reader mid-load; readers
+    /// never contend with each other since the underlying lock is shared.
+    fn with_shared_lock<T>(&self, path: &Path, body: impl FnOnce() -> Result<T>) -> Result<T> {
+        if !self.locking {
+            return body();
+        }
+
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open file for locking: {}", path.display()))?;
+        let _guard = LockGuard::lock_shared(file)?;
+        body()
+    }
+
     /// Load vectors using the optimal strategy for the platform
     fn load_vectors_with_strategy<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Vector>> {
         #[cfg(feature = "mmap")]
@@ -72,24 +197,57 @@ impl MmapIndexLoader {
     #[cfg(feature = "mmap")]
     #[allow(dead_code)]
     fn load_vectors_mmap<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Vector>> {
-        use crate::mmap::MappingStrategy;
-        
         let mapping = MappingStrategy::new(path.as_ref())?;
-        
+
         // Read header first
         let header_bytes = mapping.slice(0, std::mem::size_of::<BinaryHeader>())?;
-        let header = unsafe { 
+        let header = unsafe {
             *(header_bytes.as_ptr() as *const BinaryHeader)
         };
         header.validate()?;
-        
+        check_not_truncated(path.as_ref(), &header, mapping.len())?;
+
+        if header.has_checksum() {
+            verify_checksum_footer(&mapping, header.total_file_size_f32())?;
+        }
+
         let num_points = header.num_points as usize;
         let num_dimensions = header.num_dimensions as usize;
-        
-        // Read vector data using zero-copy when possible
         let data_offset = std::mem::size_of::<BinaryHeader>();
+
+        // Block-checksummed files aren't laid out as a flat f32 matrix, so they can't take the
+        // zero-copy path below; decompress and checksum-verify them block by block instead.
+        if header.is_block_checksummed() {
+            let body = mapping.slice(data_offset, mapping.len() - data_offset)?;
+            let mut cursor = body;
+            let raw = crate::block_checksum::read_checksummed_blocks(&mut cursor)
+                .with_context(|| {
+                    format!(
+                        "Failed to read block-checksummed vector payload from {}",
+                        path.as_ref().display()
+                    )
+                })?;
+
+            let mut vectors = Vec::with_capacity(num_points);
+            let mut raw_cursor = raw.as_slice();
+            for _ in 0..num_points {
+                let mut vector = Vector::with_capacity(num_dimensions);
+                for _ in 0..num_dimensions {
+                    let mut bytes = [0u8; 4];
+                    std::io::Read::read_exact(&mut raw_cursor, &mut bytes)
+                        .context("Failed to read vector component")?;
+                    vector.push(f32::from_le_bytes(bytes));
+                }
+                vectors.push(vector);
+            }
+
+            tracing::info!("Loaded {} vectors using block-checksummed strategy", vectors.len());
+            return Ok(vectors);
+        }
+
+        // Read vector data using zero-copy when possible
         let f32_data = mapping.typed_slice::<f32>(data_offset, num_points * num_dimensions)?;
-        
+
         // Convert to Vector format
         let mut vectors = Vec::with_capacity(num_points);
         for i in 0..num_points {
@@ -97,10 +255,10 @@ impl MmapIndexLoader {
             let end_idx = start_idx + num_dimensions;
             vectors.push(f32_data[start_idx..end_idx].to_vec());
         }
-        
-        tracing::info!("Loaded {} vectors using {} strategy", 
+
+        tracing::info!("Loaded {} vectors using {} strategy",
                       vectors.len(), mapping.strategy_info());
-        
+
         Ok(vectors)
     }
     
@@ -111,19 +269,133 @@ impl MmapIndexLoader {
         tracing::warn!("Memory mapping not available, falling back to buffered I/O");
         self.load_vectors_buffered(path)
     }
-    
+
+    /// Compute `header`/`mapping` for `path` and the clamped `[start, end)`
+    /// index range, shared by [`Self::load_vectors_subset_mmap`] and
+    /// [`Self::load_vectors_range_iter`]
+    #[cfg(feature = "mmap")]
+    fn open_subset_mapping(
+        path: &Path,
+        start: usize,
+        count: usize,
+    ) -> Result<(MappingStrategy, BinaryHeader, usize)> {
+        let mapping = MappingStrategy::new(path)?;
+        let header_bytes = mapping.slice(0, std::mem::size_of::<BinaryHeader>())?;
+        let header = unsafe { *(header_bytes.as_ptr() as *const BinaryHeader) };
+        header.validate()?;
+
+        let num_points = header.num_points as usize;
+        if start >= num_points {
+            anyhow::bail!("Start index {} exceeds vector count {}", start, num_points);
+        }
+        let end = std::cmp::min(start + count, num_points);
+
+        Ok((mapping, header, end))
+    }
+
+    /// True partial load: compute the exact byte range covering
+    /// `[start, start+count)` from the header alone and hand it to
+    /// [`MappingStrategy::typed_slice`], so only the requested vectors' pages
+    /// are ever touched -- unlike [`Self::load_vectors`] followed by slicing,
+    /// which pages in and materializes the entire file first.
+    ///
+    /// Compressed/block-checksummed files can't be decoded piecemeal, so
+    /// this falls back to a full load-then-slice for them, same as before
+    /// this method existed.
+    #[cfg(feature = "mmap")]
+    fn load_vectors_subset_mmap<P: AsRef<Path>>(&self, path: P, start: usize, count: usize) -> Result<Vec<Vector>> {
+        let (mapping, header, end) = Self::open_subset_mapping(path.as_ref(), start, count)?;
+
+        if header.is_lz4_compressed() || header.is_block_checksummed() {
+            let all_vectors = self.load_vectors(path.as_ref())?;
+            return Ok(all_vectors[start..end].to_vec());
+        }
+
+        if header.has_checksum() {
+            verify_checksum_footer(&mapping, header.total_file_size_f32())?;
+        }
+
+        let num_dimensions = header.num_dimensions as usize;
+        let data_offset = std::mem::size_of::<BinaryHeader>() + start * num_dimensions * 4;
+        let f32_data = mapping.typed_slice::<f32>(data_offset, (end - start) * num_dimensions)?;
+
+        let mut vectors = Vec::with_capacity(end - start);
+        for i in 0..(end - start) {
+            let idx_start = i * num_dimensions;
+            let idx_end = idx_start + num_dimensions;
+            vectors.push(f32_data[idx_start..idx_end].to_vec());
+        }
+        Ok(vectors)
+    }
+
+    /// Stream `[start, start+count)` one vector at a time via direct mmap
+    /// offsets computed from the header, rather than materializing the whole
+    /// range into a `Vec` up front (see [`Self::load_vectors_subset_mmap`]).
+    /// Intended for callers walking a dataset far larger than RAM, where even
+    /// one subset's worth of vectors shouldn't all be resident at once.
+    ///
+    /// Only supported for uncompressed files -- compressed/block-checksummed
+    /// payloads can't be decoded piecemeal, so this rejects them up front
+    /// instead of silently materializing the whole file.
+    #[cfg(feature = "mmap")]
+    pub fn load_vectors_range_iter<P: AsRef<Path>>(
+        &self,
+        path: P,
+        start: usize,
+        count: usize,
+    ) -> Result<VectorRangeIter> {
+        let (mapping, header, end) = Self::open_subset_mapping(path.as_ref(), start, count)?;
+
+        if header.is_lz4_compressed() || header.is_block_checksummed() {
+            anyhow::bail!(
+                "load_vectors_range_iter requires an uncompressed file; {} uses a compressed payload",
+                path.as_ref().display()
+            );
+        }
+
+        if header.has_checksum() {
+            verify_checksum_footer(&mapping, header.total_file_size_f32())?;
+        }
+
+        Ok(VectorRangeIter {
+            mapping,
+            data_offset: std::mem::size_of::<BinaryHeader>(),
+            num_dimensions: header.num_dimensions as usize,
+            next_index: start,
+            end_index: end,
+        })
+    }
+
+
     /// Load vectors using buffered I/O
     fn load_vectors_buffered<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Vector>> {
-        let file = File::open(path.as_ref())
+        use std::io::{Seek, SeekFrom};
+
+        let mut file = File::open(path.as_ref())
             .with_context(|| format!("Failed to open file: {}", path.as_ref().display()))?;
-        
+
+        // Peek the header to decide which payload layout follows, then rewind so the real read
+        // below sees the file from the start.
+        let header = BinaryHeader::read_from(&mut file).context("Failed to read binary header")?;
+        header.validate()?;
+
+        let actual_size = file.metadata().context("Failed to stat file")?.len() as usize;
+        check_not_truncated(path.as_ref(), &header, actual_size)?;
+
+        file.seek(SeekFrom::Start(0)).context("Failed to rewind after reading header")?;
+
         let mut reader = BufReader::with_capacity(64 * 1024 * 1024, file); // 64MB buffer
-        
-        let vectors = read_vectors_f32(&mut reader)
-            .context("Failed to read vector data")?;
-        
+
+        let vectors = if header.is_block_checksummed() {
+            read_vectors_f32_block_checksummed(&mut reader)
+                .context("Failed to read block-checksummed vector data")?
+        } else {
+            read_vectors_f32_checksummed(&mut reader)
+                .context("Failed to read vector data")?
+        };
+
         tracing::info!("Loaded {} vectors using buffered I/O", vectors.len());
-        
+
         Ok(vectors)
     }
 }
@@ -136,36 +408,62 @@ impl Default for MmapIndexLoader {
 
 impl IndexLoader for MmapIndexLoader {
     fn load_vectors<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Vector>> {
-        self.load_vectors_with_strategy(path)
+        let path = path.as_ref();
+        self.with_shared_lock(path, || self.load_vectors_with_strategy(path))
     }
-    
+
     fn load_graph<P: AsRef<Path>>(&self, path: P) -> Result<Vec<GraphNode>> {
-        // For now, just load as vectors and convert to graph nodes
-        // TODO: Add proper graph adjacency list loading
-        let vectors = self.load_vectors(path)?;
-        
-        let nodes = vectors
-            .into_iter()
-            .enumerate()
-            .map(|(i, vector)| GraphNode::new(i as VectorId, vector))
-            .collect();
-            
-        Ok(nodes)
+        let path = path.as_ref();
+        self.with_shared_lock(path, || {
+            // For now, just load as vectors and convert to graph nodes
+            // TODO: Add proper graph adjacency list loading
+            let vectors = self.load_vectors_with_strategy(path)?;
+
+            let nodes = vectors
+                .into_iter()
+                .enumerate()
+                .map(|(i, vector)| GraphNode::new(i as VectorId, vector))
+                .collect();
+
+            Ok(nodes)
+        })
     }
     
     fn load_vectors_subset<P: AsRef<Path>>(&self, path: P, start: usize, count: usize) -> Result<Vec<Vector>> {
-        // For simplicity, load all vectors then take subset
-        // TODO: Optimize to only load required portion
+        #[cfg(feature = "mmap")]
+        if self.prefer_mmap {
+            return self.load_vectors_subset_mmap(path, start, count);
+        }
+
         let all_vectors = self.load_vectors(path)?;
-        
+
         if start >= all_vectors.len() {
             anyhow::bail!("Start index {} exceeds vector count {}", start, all_vectors.len());
         }
-        
+
         let end = std::cmp::min(start + count, all_vectors.len());
         Ok(all_vectors[start..end].to_vec())
     }
     
+    fn verify_only<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mapping = MappingStrategy::new(path.as_ref())?;
+        let header_size = std::mem::size_of::<BinaryHeader>();
+        if mapping.len() < header_size {
+            anyhow::bail!("File too small to contain a binary header");
+        }
+
+        let header_bytes = mapping.slice(0, header_size)?;
+        let header = unsafe { *(header_bytes.as_ptr() as *const BinaryHeader) };
+        header.validate()?;
+        check_not_truncated(path.as_ref(), &header, mapping.len())?;
+
+        if !header.has_checksum() {
+            return Ok(());
+        }
+
+        verify_checksum_footer(&mapping, header.total_file_size_f32())
+    }
+
     fn get_metadata<P: AsRef<Path>>(&self, path: P) -> Result<IndexMetadata> {
         let file = File::open(path.as_ref())
             .with_context(|| format!("Failed to open file: {}", path.as_ref().display()))?;
@@ -184,11 +482,12 @@ impl IndexLoader for MmapIndexLoader {
             num_vectors: header.num_points as usize,
             dimension: header.num_dimensions as usize,
             file_size,
-            strategy: if self.prefer_mmap { 
+            strategy: if self.prefer_mmap {
                 format!("mmap-preferred ({})", platform_mmap_info())
-            } else { 
-                "buffered-only".to_string() 
+            } else {
+                "buffered-only".to_string()
             },
+            expected_body_bytes: header.data_size_f32(),
         })
     }
 }
@@ -196,6 +495,7 @@ impl IndexLoader for MmapIndexLoader {
 /// Builder for creating index loaders with different configurations
 pub struct IndexLoaderBuilder {
     prefer_mmap: bool,
+    locking: bool,
 }
 
 impl IndexLoaderBuilder {
@@ -203,22 +503,36 @@ impl IndexLoaderBuilder {
     pub fn new() -> Self {
         Self {
             prefer_mmap: true,
+            locking: true,
         }
     }
-    
+
     /// Set whether to prefer memory mapping when available
     pub fn prefer_mmap(mut self, prefer: bool) -> Self {
         self.prefer_mmap = prefer;
         self
     }
-    
+
+    /// Set whether to take an advisory shared lock around reads (see
+    /// [`crate::flock`])
+    ///
+    /// Defaults to `true`. Disable for single-process use where no other
+    /// process can concurrently rewrite the index, to skip the lock
+    /// syscall on every load.
+    pub fn locking(mut self, enabled: bool) -> Self {
+        self.locking = enabled;
+        self
+    }
+
     /// Build a memory-mapped index loader
     pub fn build_mmap_loader(self) -> MmapIndexLoader {
-        if self.prefer_mmap {
+        let mut loader = if self.prefer_mmap {
             MmapIndexLoader::new()
         } else {
             MmapIndexLoader::buffered_only()
-        }
+        };
+        loader.locking = self.locking;
+        loader
     }
 }
 
@@ -279,7 +593,7 @@ mod tests {
         
         assert_eq!(metadata.num_vectors, 2);
         assert_eq!(metadata.dimension, 4);
-        assert_eq!(metadata.file_size, 8 + (2 * 4 * 4)); // header + data
+        assert_eq!(metadata.file_size, std::mem::size_of::<BinaryHeader>() + (2 * 4 * 4)); // header + data
     }
     
     #[test]
@@ -304,6 +618,205 @@ mod tests {
         assert_eq!(subset[1], vec![5.0, 6.0]);
     }
     
+    #[test]
+    fn test_checksummed_round_trip_and_verify() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+        ];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors_checksummed(temp_file.path(), &vectors).unwrap();
+
+        loader.verify_only(temp_file.path()).unwrap();
+
+        let loaded = loader.load_vectors(temp_file.path()).unwrap();
+        assert_eq!(loaded, vectors);
+    }
+
+    #[test]
+    fn test_verify_only_detects_corruption() {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors_checksummed(temp_file.path(), &vectors).unwrap();
+
+        // Corrupt a payload byte in place, after the header.
+        let mut file = std::fs::OpenOptions::new().write(true).open(temp_file.path()).unwrap();
+        file.seek(SeekFrom::Start(std::mem::size_of::<BinaryHeader>() as u64)).unwrap();
+        file.write_all(&[0xFF]).unwrap();
+        drop(file);
+
+        let result = loader.verify_only(temp_file.path());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("checksum mismatch"));
+
+        assert!(loader.load_vectors(temp_file.path()).is_err());
+    }
+
+    #[test]
+    fn test_verify_only_passes_for_unflagged_files() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        loader.verify_only(temp_file.path()).unwrap();
+    }
+
+    #[test]
+    fn test_load_vectors_rejects_truncated_file() {
+        let writer = FileIndexWriter::new();
+
+        let vectors = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        // Chop off the last 4 bytes, as if the write was interrupted mid-vector.
+        let full_len = std::fs::metadata(temp_file.path()).unwrap().len();
+        let file = std::fs::OpenOptions::new().write(true).open(temp_file.path()).unwrap();
+        file.set_len(full_len - 4).unwrap();
+        drop(file);
+
+        let mmap_err = MmapIndexLoader::new().load_vectors(temp_file.path()).unwrap_err();
+        assert!(mmap_err.to_string().contains("Truncated or corrupt file"));
+
+        let buffered_err = MmapIndexLoader::buffered_only().load_vectors(temp_file.path()).unwrap_err();
+        assert!(buffered_err.to_string().contains("Truncated or corrupt file"));
+
+        let verify_err = MmapIndexLoader::new().verify_only(temp_file.path()).unwrap_err();
+        assert!(verify_err.to_string().contains("Truncated or corrupt file"));
+    }
+
+    #[test]
+    fn test_get_metadata_reports_expected_body_bytes() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors = vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0, 7.0, 8.0]];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let metadata = loader.get_metadata(temp_file.path()).unwrap();
+        assert_eq!(metadata.expected_body_bytes, 2 * 4 * 4);
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_vectors_subset_mmap_matches_full_load() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors: Vec<Vector> = (0..20).map(|i| vec![i as f32, (i * 2) as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let subset = loader.load_vectors_subset(temp_file.path(), 5, 7).unwrap();
+        assert_eq!(subset, vectors[5..12].to_vec());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_vectors_range_iter_yields_requested_vectors_one_at_a_time() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors: Vec<Vector> = (0..20).map(|i| vec![i as f32, (i * 2) as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let collected: Vec<Vector> = loader
+            .load_vectors_range_iter(temp_file.path(), 3, 10)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(collected, vectors[3..13].to_vec());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn test_vectors_range_iter_rejects_block_checksummed_file() {
+        use crate::block_checksum::CompressionType;
+
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors: Vec<Vector> = (0..10).map(|i| vec![i as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer
+            .write_vectors_block_checksummed(temp_file.path(), &vectors, CompressionType::Lz4, 64)
+            .unwrap();
+
+        assert!(loader.load_vectors_range_iter(temp_file.path(), 0, 5).is_err());
+    }
+
+    #[test]
+    fn test_block_checksummed_round_trip() {
+        use crate::block_checksum::CompressionType;
+
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors: Vec<Vector> = (0..80).map(|i| vec![i as f32, (i * 3) as f32, (i * 5) as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer
+            .write_vectors_block_checksummed(temp_file.path(), &vectors, CompressionType::Lz4, 128)
+            .unwrap();
+
+        let loaded = loader.load_vectors(temp_file.path()).unwrap();
+        assert_eq!(loaded, vectors);
+
+        let loaded_buffered = MmapIndexLoader::buffered_only().load_vectors(temp_file.path()).unwrap();
+        assert_eq!(loaded_buffered, vectors);
+    }
+
+    #[test]
+    fn test_block_checksummed_detects_corruption() {
+        use crate::block_checksum::CompressionType;
+        use std::io::{Seek, SeekFrom, Write};
+
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::buffered_only();
+
+        let vectors: Vec<Vector> = (0..80).map(|i| vec![i as f32, (i * 3) as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer
+            .write_vectors_block_checksummed(temp_file.path(), &vectors, CompressionType::Lz4, 64)
+            .unwrap();
+
+        // Flip the file's last byte, inside the last block's payload. An LZ4 block always
+        // ends in literal bytes, so that block still decompresses, just to the wrong bytes.
+        let file_len = std::fs::metadata(temp_file.path()).unwrap().len();
+        let mut file = std::fs::OpenOptions::new().write(true).open(temp_file.path()).unwrap();
+        file.seek(SeekFrom::Start(file_len - 1)).unwrap();
+        file.write_all(&[0xFF]).unwrap();
+        drop(file);
+
+        let result = loader.load_vectors(temp_file.path());
+        assert!(result.is_err());
+        // The block-level message sits under the loader's context, so check the whole chain.
+        assert!(format!("{:#}", result.unwrap_err()).contains("checksum mismatch in block"));
+    }
+
     #[test]
     fn test_buffered_only_loader() {
         let loader = MmapIndexLoader::buffered_only();
@@ -315,7 +828,51 @@ mod tests {
         let loader = IndexLoaderBuilder::new()
             .prefer_mmap(false)
             .build_mmap_loader();
-        
+
         assert!(!loader.prefer_mmap);
     }
+
+    #[test]
+    fn test_builder_defaults_to_locking_enabled() {
+        let loader = IndexLoaderBuilder::new().build_mmap_loader();
+        assert!(loader.locking);
+    }
+
+    #[test]
+    fn test_builder_can_disable_locking() {
+        let loader = IndexLoaderBuilder::new().locking(false).build_mmap_loader();
+        assert!(!loader.locking);
+    }
+
+    #[test]
+    fn test_load_vectors_still_works_with_locking_enabled() {
+        let writer = FileIndexWriter::new();
+        let loader = IndexLoaderBuilder::new().build_mmap_loader();
+
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let loaded = loader.load_vectors(temp_file.path()).unwrap();
+        assert_eq!(loaded, vectors);
+
+        let graph = loader.load_graph(temp_file.path()).unwrap();
+        assert_eq!(graph.len(), vectors.len());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_load_vectors_fails_when_another_process_holds_an_exclusive_lock() {
+        let writer = FileIndexWriter::new();
+        let loader = MmapIndexLoader::new();
+
+        let vectors = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let lock_file = File::open(temp_file.path()).unwrap();
+        let _guard = LockGuard::try_lock_exclusive(lock_file).unwrap();
+
+        assert!(loader.load_vectors(temp_file.path()).is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,187 @@
+//! Fixed-size LZ4 block container used to transparently compress the
+//! payload that follows a [`crate::format::BinaryHeader`]
+//!
+//! Layout (written immediately after the caller's header): `block_count: u32`
+//! | one index entry per block (`offset: u64`, `compressed_len: u32`,
+//! `uncompressed_len: u32`) | the compressed blocks themselves, back to back.
+//! `offset` is relative to the first byte *after* the index table, so a
+//! future seek-based reader could jump straight to one block's bytes without
+//! decompressing anything else; [`read_payload_lz4_blocks`] always
+//! decompresses every block since its callers need the whole reconstructed
+//! payload regardless.
+
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+
+/// Default block size (2 MiB) used by [`crate::writer::IndexWriterBuilder`]
+/// when LZ4 compression is enabled -- large enough for LZ4 to find
+/// cross-vector redundancy in clustered embeddings, small enough to keep a
+/// single block's decompression cost low for random-access reads
+pub const DEFAULT_BLOCK_SIZE: usize = 2 * 1024 * 1024;
+
+struct BlockIndexEntry {
+    offset: u64,
+    compressed_len: u32,
+    uncompressed_len: u32,
+}
+
+impl BlockIndexEntry {
+    const ENCODED_SIZE: usize = 16;
+
+    fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.offset.to_le_bytes())?;
+        writer.write_all(&self.compressed_len.to_le_bytes())?;
+        writer.write_all(&self.uncompressed_len.to_le_bytes())?;
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut offset_bytes = [0u8; 8];
+        reader.read_exact(&mut offset_bytes)?;
+        let mut compressed_len_bytes = [0u8; 4];
+        reader.read_exact(&mut compressed_len_bytes)?;
+        let mut uncompressed_len_bytes = [0u8; 4];
+        reader.read_exact(&mut uncompressed_len_bytes)?;
+        Ok(Self {
+            offset: u64::from_le_bytes(offset_bytes),
+            compressed_len: u32::from_le_bytes(compressed_len_bytes),
+            uncompressed_len: u32::from_le_bytes(uncompressed_len_bytes),
+        })
+    }
+}
+
+/// Partition `payload` into `block_size`-byte blocks, LZ4-compress each
+/// independently, and write `block_count` | index table | compressed blocks
+///
+/// Returns the number of bytes written by this call (the block count, index
+/// table, and every compressed block; not including any header the caller
+/// already wrote).
+///
+/// # Errors
+///
+/// Returns an error if `block_size` is zero.
+pub fn write_payload_lz4_blocks<W: Write>(
+    writer: &mut W,
+    payload: &[u8],
+    block_size: usize,
+) -> Result<usize> {
+    anyhow::ensure!(block_size > 0, "block_size must be nonzero");
+
+    let chunks: Vec<&[u8]> = if payload.is_empty() {
+        Vec::new()
+    } else {
+        payload.chunks(block_size).collect()
+    };
+
+    let mut index = Vec::with_capacity(chunks.len());
+    let mut compressed_blocks = Vec::with_capacity(chunks.len());
+    let mut offset = 0u64;
+    for chunk in &chunks {
+        let compressed = lz4_flex::block::compress(chunk);
+        index.push(BlockIndexEntry {
+            offset,
+            compressed_len: compressed.len() as u32,
+            uncompressed_len: chunk.len() as u32,
+        });
+        offset += compressed.len() as u64;
+        compressed_blocks.push(compressed);
+    }
+
+    let mut bytes_written = 0usize;
+
+    writer
+        .write_all(&(chunks.len() as u32).to_le_bytes())
+        .context("Failed to write LZ4 block count")?;
+    bytes_written += 4;
+
+    for entry in &index {
+        entry
+            .write_to(writer)
+            .context("Failed to write LZ4 block index entry")?;
+        bytes_written += BlockIndexEntry::ENCODED_SIZE;
+    }
+
+    for block in &compressed_blocks {
+        writer
+            .write_all(block)
+            .context("Failed to write LZ4 compressed block")?;
+        bytes_written += block.len();
+    }
+
+    Ok(bytes_written)
+}
+
+/// Read a container written by [`write_payload_lz4_blocks`] back into the
+/// original, uncompressed payload bytes
+pub fn read_payload_lz4_blocks<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut count_bytes = [0u8; 4];
+    reader
+        .read_exact(&mut count_bytes)
+        .context("Failed to read LZ4 block count")?;
+    let block_count = u32::from_le_bytes(count_bytes) as usize;
+
+    let mut index = Vec::with_capacity(block_count);
+    for _ in 0..block_count {
+        index.push(
+            BlockIndexEntry::read_from(reader).context("Failed to read LZ4 block index entry")?,
+        );
+    }
+
+    let mut payload = Vec::new();
+    for entry in &index {
+        let mut compressed = vec![0u8; entry.compressed_len as usize];
+        reader
+            .read_exact(&mut compressed)
+            .context("Failed to read LZ4 compressed block")?;
+        let decompressed =
+            lz4_flex::block::decompress(&compressed, entry.uncompressed_len as usize)
+                .context("Failed to LZ4-decompress block")?;
+        payload.extend_from_slice(&decompressed);
+    }
+
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_multi_block_payload() {
+        let payload: Vec<u8> = (0..10_000u32).flat_map(|v| v.to_le_bytes()).collect();
+        let mut buf = Vec::new();
+        write_payload_lz4_blocks(&mut buf, &payload, 1024).unwrap();
+
+        let mut cursor = buf.as_slice();
+        let round_tripped = read_payload_lz4_blocks(&mut cursor).unwrap();
+        assert_eq!(round_tripped, payload);
+    }
+
+    #[test]
+    fn round_trips_empty_payload() {
+        let mut buf = Vec::new();
+        write_payload_lz4_blocks(&mut buf, &[], 1024).unwrap();
+
+        let mut cursor = buf.as_slice();
+        let round_tripped = read_payload_lz4_blocks(&mut cursor).unwrap();
+        assert!(round_tripped.is_empty());
+    }
+
+    #[test]
+    fn rejects_zero_block_size() {
+        let mut buf = Vec::new();
+        assert!(write_payload_lz4_blocks(&mut buf, &[1, 2, 3], 0).is_err());
+    }
+
+    #[test]
+    fn single_block_payload_smaller_than_block_size() {
+        let payload = b"hello world, this is a small payload".to_vec();
+        let mut buf = Vec::new();
+        write_payload_lz4_blocks(&mut buf, &payload, 4096).unwrap();
+
+        let mut cursor = buf.as_slice();
+        let round_tripped = read_payload_lz4_blocks(&mut cursor).unwrap();
+        assert_eq!(round_tripped, payload);
+    }
+}
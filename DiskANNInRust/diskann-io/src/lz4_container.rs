@@ -0,0 +1,402 @@
+//! LZ4-compressed block container with a trailing block-descriptor table
+//!
+//! Sibling to [`crate::compressed`]'s zstd container, built the same way (fixed-size vector
+//! blocks, each compressed independently, with a trailing table so a reader can locate any
+//! block without decompressing the ones before it) but for LZ4 instead of zstd, and with two
+//! differences driven by incremental writes:
+//!
+//! - Every block carries its own [`BlockType`] tag rather than the file committing to one
+//!   codec up front. A block whose compressed form isn't actually smaller than its raw form
+//!   (rare, but possible for already-dense or incompressible data) is stored as
+//!   [`BlockType::Raw`] instead, so compression never costs more than it saves.
+//! - That per-block tag is also what would let a future writer append new raw blocks to an
+//!   existing file cheaply (no need to recompress or touch earlier blocks) and compress them
+//!   in the background later; this module doesn't implement that append path yet, but the
+//!   on-disk format already supports it.
+//!
+//! Layout: `MAGIC` | `version: u8` | `block_size: u32` | `num_points: u32` |
+//! `num_dimensions: u32` | blocks back to back | one `(type_tag: u8, offset: u32,
+//! compressed_len: u32, uncompressed_len: u32)` descriptor per block | `table_len: u32`.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use diskann_core::vectors::Vector;
+
+use crate::mmap::MappingStrategy;
+
+/// Magic bytes identifying an [`write_lz4_container`] file. Distinct from
+/// [`crate::compressed`]'s `DNZC` magic; read as the raw [`crate::format::BinaryHeader`]
+/// format's leading little-endian `i32`, these bytes are a valid positive `num_points`.
+const MAGIC: [u8; 4] = *b"DNL4";
+
+/// On-disk format version, bumped if the layout ever changes incompatibly
+const VERSION: u8 = 1;
+
+/// Per-block codec tag, recorded in each block's descriptor rather than once for the whole
+/// file, since [`write_lz4_container`] falls back to storing a block raw when compressing it
+/// wouldn't actually shrink it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockType {
+    /// Stored as raw little-endian f32 bytes, uncompressed
+    Raw,
+    /// Stored as an LZ4 block (see [`CompressionMode::Lz4`]/[`CompressionMode::Lz4Hc`])
+    Lz4,
+}
+
+impl BlockType {
+    fn to_u8(self) -> u8 {
+        match self {
+            BlockType::Raw => 0,
+            BlockType::Lz4 => 1,
+        }
+    }
+
+    fn from_u8(tag: u8) -> Result<Self> {
+        match tag {
+            0 => Ok(BlockType::Raw),
+            1 => Ok(BlockType::Lz4),
+            other => bail!("Unknown block type tag {}", other),
+        }
+    }
+}
+
+/// Compression effort requested for a [`write_lz4_container`] call, wired to the CLI's
+/// `--compress {none,lz4,lz4hc}` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMode {
+    /// Store every block raw, skipping compression entirely
+    None,
+    /// LZ4-compress each block at the standard (fast) effort level
+    Lz4,
+    /// LZ4-compress each block at a higher effort level for a better compression ratio
+    ///
+    /// The pure-Rust `lz4_flex` backend this module uses doesn't expose a distinct
+    /// high-compression codec the way `liblz4`'s HC mode does, so this currently runs the
+    /// same compressor as [`CompressionMode::Lz4`]. It's kept as its own variant so the file
+    /// format and CLI flag are already in place for a future backend that does differentiate.
+    Lz4Hc,
+}
+
+/// Options controlling how [`write_lz4_container`] chunks and compresses data
+#[derive(Debug, Clone, Copy)]
+pub struct Lz4ContainerOpts {
+    /// Compression effort to apply to each block
+    pub mode: CompressionMode,
+    /// Number of vectors packed into each independently-compressed block
+    pub block_size: usize,
+}
+
+impl Default for Lz4ContainerOpts {
+    /// [`CompressionMode::Lz4`] and 4096-vector blocks, matching
+    /// [`crate::compressed::WriterOpts`]'s default block size
+    fn default() -> Self {
+        Self {
+            mode: CompressionMode::Lz4,
+            block_size: 4096,
+        }
+    }
+}
+
+struct BlockDescriptor {
+    block_type: BlockType,
+    offset: u32,
+    compressed_len: u32,
+    uncompressed_len: u32,
+}
+
+/// Write `vectors` to `writer` as an LZ4 block container (see the module docs for the
+/// on-disk layout)
+pub fn write_lz4_container<W: Write>(
+    writer: &mut W,
+    vectors: &[Vector],
+    opts: Lz4ContainerOpts,
+) -> Result<usize> {
+    if vectors.is_empty() {
+        bail!("Cannot write empty vector array");
+    }
+    if opts.block_size == 0 {
+        bail!("block_size must be nonzero");
+    }
+
+    let num_points = vectors.len();
+    let num_dimensions = vectors[0].len();
+    for (i, vector) in vectors.iter().enumerate() {
+        if vector.len() != num_dimensions {
+            bail!("Vector {} has {} dimensions, expected {}", i, vector.len(), num_dimensions);
+        }
+    }
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&MAGIC);
+    data.push(VERSION);
+    data.extend_from_slice(&(opts.block_size as u32).to_le_bytes());
+    data.extend_from_slice(&(num_points as u32).to_le_bytes());
+    data.extend_from_slice(&(num_dimensions as u32).to_le_bytes());
+
+    let mut descriptors = Vec::new();
+    for block in vectors.chunks(opts.block_size) {
+        let mut raw = Vec::with_capacity(block.len() * num_dimensions * std::mem::size_of::<f32>());
+        for vector in block {
+            for &value in vector {
+                raw.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        let offset = data.len() as u32;
+        let uncompressed_len = raw.len() as u32;
+
+        let (block_type, stored) = if opts.mode == CompressionMode::None {
+            (BlockType::Raw, raw)
+        } else {
+            let compressed = lz4_flex::block::compress(&raw);
+            if compressed.len() < raw.len() {
+                (BlockType::Lz4, compressed)
+            } else {
+                (BlockType::Raw, raw)
+            }
+        };
+
+        descriptors.push(BlockDescriptor {
+            block_type,
+            offset,
+            compressed_len: stored.len() as u32,
+            uncompressed_len,
+        });
+        data.extend_from_slice(&stored);
+    }
+
+    for descriptor in &descriptors {
+        data.push(descriptor.block_type.to_u8());
+        data.extend_from_slice(&descriptor.offset.to_le_bytes());
+        data.extend_from_slice(&descriptor.compressed_len.to_le_bytes());
+        data.extend_from_slice(&descriptor.uncompressed_len.to_le_bytes());
+    }
+    data.extend_from_slice(&(descriptors.len() as u32).to_le_bytes());
+
+    writer.write_all(&data).context("Failed to write LZ4 container")?;
+    Ok(data.len())
+}
+
+const DESCRIPTOR_SIZE: usize = 1 + 4 + 4 + 4;
+
+/// Random-access reader over a [`write_lz4_container`] file
+///
+/// Parses only the header and trailing descriptor table at construction time;
+/// [`read_range`](Self::read_range) decompresses solely the block(s) covering the requested
+/// vector range, skipping decompression entirely for any block already stored as
+/// [`BlockType::Raw`].
+pub struct Lz4ContainerReader {
+    mapping: MappingStrategy,
+    block_size: usize,
+    num_points: usize,
+    num_dimensions: usize,
+    descriptors: Vec<BlockDescriptor>,
+}
+
+impl Lz4ContainerReader {
+    /// Open an LZ4 container, memory-mapping it when available and falling back to a
+    /// buffered read otherwise (see [`MappingStrategy`])
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mapping = MappingStrategy::new(path)?;
+        let len = mapping.len();
+        if len < 17 {
+            bail!("File too small to contain an LZ4 container header");
+        }
+
+        let magic = mapping.slice(0, 4)?;
+        if magic != MAGIC {
+            bail!("Not an LZ4 container: bad magic bytes");
+        }
+        let version = mapping.slice(4, 1)?[0];
+        if version != VERSION {
+            bail!("Unsupported LZ4 container version {}", version);
+        }
+        let block_size = read_u32(mapping.slice(5, 4)?) as usize;
+        let num_points = read_u32(mapping.slice(9, 4)?) as usize;
+        let num_dimensions = read_u32(mapping.slice(13, 4)?) as usize;
+        if block_size == 0 {
+            bail!("LZ4 container has zero block_size");
+        }
+
+        let table_len = read_u32(mapping.slice(len - 4, 4)?) as usize;
+        let table_bytes_len = table_len * DESCRIPTOR_SIZE;
+        if len < 4 + table_bytes_len {
+            bail!("Truncated LZ4 container: descriptor table runs past start of file");
+        }
+        let table_start = len - 4 - table_bytes_len;
+        let table_bytes = mapping.slice(table_start, table_bytes_len)?;
+
+        let descriptors = table_bytes
+            .chunks_exact(DESCRIPTOR_SIZE)
+            .map(|entry| {
+                Ok(BlockDescriptor {
+                    block_type: BlockType::from_u8(entry[0])?,
+                    offset: read_u32(&entry[1..5]),
+                    compressed_len: read_u32(&entry[5..9]),
+                    uncompressed_len: read_u32(&entry[9..13]),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            mapping,
+            block_size,
+            num_points,
+            num_dimensions,
+            descriptors,
+        })
+    }
+
+    /// Number of vectors available in the underlying file
+    pub fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    /// Dimensionality of each vector
+    pub fn num_dimensions(&self) -> usize {
+        self.num_dimensions
+    }
+
+    fn decode_block(&self, block_idx: usize) -> Result<Vec<Vector>> {
+        let descriptor = &self.descriptors[block_idx];
+        let stored = self
+            .mapping
+            .slice(descriptor.offset as usize, descriptor.compressed_len as usize)?;
+
+        let raw = match descriptor.block_type {
+            BlockType::Raw => stored.to_vec(),
+            BlockType::Lz4 => lz4_flex::block::decompress(stored, descriptor.uncompressed_len as usize)
+                .context("Failed to LZ4-decompress block")?,
+        };
+
+        let mut vectors = Vec::with_capacity(self.block_size);
+        for chunk in raw.chunks_exact(self.num_dimensions * std::mem::size_of::<f32>()) {
+            let mut vector = Vector::with_capacity(self.num_dimensions);
+            for bytes in chunk.chunks_exact(4) {
+                vector.push(f32::from_le_bytes(bytes.try_into().unwrap()));
+            }
+            vectors.push(vector);
+        }
+        Ok(vectors)
+    }
+
+    /// Read `count` vectors starting at `start`, decoding only the block(s) that cover the
+    /// requested range
+    pub fn read_range(&self, start: usize, count: usize) -> Result<Vec<Vector>> {
+        if start >= self.num_points {
+            bail!("Start index {} exceeds vector count {}", start, self.num_points);
+        }
+        let end = (start + count).min(self.num_points);
+
+        let first_block = start / self.block_size;
+        let last_block = (end - 1) / self.block_size;
+
+        let mut result = Vec::with_capacity(end - start);
+        for block_idx in first_block..=last_block {
+            let block_vectors = self.decode_block(block_idx)?;
+            let block_start_id = block_idx * self.block_size;
+            for (i, vector) in block_vectors.into_iter().enumerate() {
+                let id = block_start_id + i;
+                if id >= start && id < end {
+                    result.push(vector);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes.try_into().expect("caller passes exactly 4 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    fn sample_vectors(n: usize, dim: usize) -> Vec<Vector> {
+        (0..n)
+            .map(|i| (0..dim).map(|d| (i * dim + d) as f32).collect())
+            .collect()
+    }
+
+    #[test]
+    fn write_lz4_container_rejects_empty_input() {
+        let mut buffer = Vec::new();
+        let result = write_lz4_container(&mut buffer, &[], Lz4ContainerOpts::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_lz4_container_rejects_zero_block_size() {
+        let vectors = sample_vectors(4, 3);
+        let mut buffer = Vec::new();
+        let opts = Lz4ContainerOpts { mode: CompressionMode::Lz4, block_size: 0 };
+        assert!(write_lz4_container(&mut buffer, &vectors, opts).is_err());
+    }
+
+    #[test]
+    fn round_trip_full_range_single_block() {
+        let vectors = sample_vectors(10, 4);
+        let mut file = NamedTempFile::new().unwrap();
+        write_lz4_container(file.as_file_mut(), &vectors, Lz4ContainerOpts::default()).unwrap();
+
+        let reader = Lz4ContainerReader::open(file.path()).unwrap();
+        assert_eq!(reader.num_points(), 10);
+        assert_eq!(reader.num_dimensions(), 4);
+
+        let read_back = reader.read_range(0, 10).unwrap();
+        assert_eq!(read_back, vectors);
+    }
+
+    #[test]
+    fn subset_read_spans_multiple_blocks() {
+        let vectors = sample_vectors(50, 4);
+        let opts = Lz4ContainerOpts { mode: CompressionMode::Lz4, block_size: 8 };
+
+        let mut file = NamedTempFile::new().unwrap();
+        write_lz4_container(file.as_file_mut(), &vectors, opts).unwrap();
+
+        let reader = Lz4ContainerReader::open(file.path()).unwrap();
+        let subset = reader.read_range(5, 15).unwrap();
+        assert_eq!(subset, vectors[5..20]);
+    }
+
+    #[test]
+    fn compression_mode_none_round_trips_without_compressing() {
+        let vectors = sample_vectors(6, 2);
+        let opts = Lz4ContainerOpts { mode: CompressionMode::None, block_size: 4 };
+
+        let mut file = NamedTempFile::new().unwrap();
+        write_lz4_container(file.as_file_mut(), &vectors, opts).unwrap();
+
+        let reader = Lz4ContainerReader::open(file.path()).unwrap();
+        assert_eq!(reader.read_range(0, 6).unwrap(), vectors);
+        assert!(reader.descriptors.iter().all(|d| d.block_type == BlockType::Raw));
+    }
+
+    #[test]
+    fn lz4hc_mode_round_trips() {
+        let vectors = sample_vectors(20, 4);
+        let opts = Lz4ContainerOpts { mode: CompressionMode::Lz4Hc, block_size: 8 };
+
+        let mut file = NamedTempFile::new().unwrap();
+        write_lz4_container(file.as_file_mut(), &vectors, opts).unwrap();
+
+        let reader = Lz4ContainerReader::open(file.path()).unwrap();
+        assert_eq!(reader.read_range(0, 20).unwrap(), vectors);
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut buffer = vec![0u8; 32];
+        buffer[0..4].copy_from_slice(b"NOPE");
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&buffer).unwrap();
+
+        assert!(Lz4ContainerReader::open(file.path()).is_err());
+    }
+}
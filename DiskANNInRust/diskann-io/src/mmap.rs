@@ -4,13 +4,35 @@
 //! alignment guarantees and lifetime management.
 
 use std::path::Path;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::Read;
 use anyhow::{Result, Context};
 use diskann_core::utils;
 
 #[cfg(feature = "mmap")]
-use memmap2::Mmap;
+use memmap2::{Mmap, MmapMut};
+
+/// Marker for types that are valid for any bit pattern of their size -- the
+/// guarantee [`SafeMmap::typed_slice`] and [`MappingStrategy::typed_slice`]
+/// need before reinterpreting raw mapped/buffered bytes as `&[T]` without a
+/// copy. Implemented only for the scalar vector-element types this crate
+/// stores in DiskANN's binary formats; not implemented for composite types,
+/// where padding bytes or a field with its own validity invariants would
+/// make the reinterpretation unsound.
+///
+/// # Safety
+/// Implementors must be valid for any bit pattern of their size: no padding
+/// bytes, and no value ranges narrower than the full bit pattern.
+pub unsafe trait FromBytes: Copy {}
+
+unsafe impl FromBytes for f32 {}
+unsafe impl FromBytes for u8 {}
+unsafe impl FromBytes for i8 {}
+unsafe impl FromBytes for u16 {}
+// `u32` isn't in DiskANN's float/byte vector element set, but the on-disk
+// graph format's degree and adjacency-list entries ([`crate::disk_index`])
+// are `u32`s read through this same `typed_slice` path.
+unsafe impl FromBytes for u32 {}
 
 /// A safe wrapper around memory-mapped data with alignment guarantees
 pub struct SafeMmap {
@@ -69,19 +91,39 @@ impl SafeMmap {
     }
     
     /// Get a typed slice with alignment checking
-    pub fn typed_slice<T>(&self, offset: usize, count: usize) -> Result<&[T]> {
+    ///
+    /// Only types implementing [`FromBytes`] can be reinterpreted this way --
+    /// without that bound, a type with padding bytes or a restricted value
+    /// range (an enum discriminant, a reference) could be materialized from
+    /// arbitrary mapped bytes, which is unsound.
+    pub fn typed_slice<T: FromBytes>(&self, offset: usize, count: usize) -> Result<&[T]> {
         let type_size = std::mem::size_of::<T>();
         let type_align = std::mem::align_of::<T>();
-        let byte_len = count * type_size;
-        
+        let byte_len = count
+            .checked_mul(type_size)
+            .context("typed_slice byte length overflowed usize")?;
+
+        if byte_len % type_size != 0 {
+            anyhow::bail!(
+                "Typed slice byte length {} is not a multiple of size_of::<{}>() = {}",
+                byte_len,
+                std::any::type_name::<T>(),
+                type_size
+            );
+        }
+
         // Check alignment
         if (self.data as usize + offset) % type_align != 0 {
-            anyhow::bail!("Misaligned access: offset {} is not aligned to {} bytes for type {}", 
+            anyhow::bail!("Misaligned access: offset {} is not aligned to {} bytes for type {}",
                          offset, type_align, std::any::type_name::<T>());
         }
-        
+
         let bytes = self.slice(offset, byte_len)?;
-        
+
+        // SAFETY: `bytes` is exactly `count * size_of::<T>()` bytes (checked
+        // above), starts at a pointer aligned to `align_of::<T>()` (checked
+        // above), and `T: FromBytes` guarantees every bit pattern in those
+        // bytes is a valid `T`.
         Ok(unsafe {
             std::slice::from_raw_parts(bytes.as_ptr() as *const T, count)
         })
@@ -101,6 +143,209 @@ impl SafeMmap {
     pub fn alignment(&self) -> usize {
         self.alignment
     }
+
+    /// Whether this mapping's reported [`Self::alignment`] is at least
+    /// `lane_bytes` -- callers writing SIMD-width chunks can check this
+    /// before picking an aligned-store path instead of a scalar fallback,
+    /// since a poorly-aligned mmap region can't satisfy one.
+    pub fn is_simd_aligned(&self, lane_bytes: usize) -> bool {
+        self.alignment >= lane_bytes
+    }
+}
+
+/// A safe, writable counterpart to [`SafeMmap`], for builders that mutate
+/// index regions in place instead of buffering a whole file through RAM
+#[cfg(feature = "mmap")]
+pub struct SafeMmapMut {
+    file: File,
+    mmap: MmapMut,
+    data: *mut u8,
+    len: usize,
+    alignment: usize,
+}
+
+#[cfg(feature = "mmap")]
+unsafe impl Send for SafeMmapMut {}
+#[cfg(feature = "mmap")]
+unsafe impl Sync for SafeMmapMut {}
+
+#[cfg(feature = "mmap")]
+impl SafeMmapMut {
+    /// Open `file` for a writable memory map
+    pub fn new(file: File) -> Result<Self> {
+        let mut mmap = unsafe {
+            MmapMut::map_mut(&file)
+                .context("Failed to create writable memory map")?
+        };
+
+        let data = mmap.as_mut_ptr();
+        let len = mmap.len();
+        let alignment = utils::get_alignment(data as usize);
+        if alignment < 4 {
+            tracing::warn!("Writable memory map has poor alignment: {} bytes", alignment);
+        }
+
+        Ok(Self { file, mmap, data, len, alignment })
+    }
+
+    /// Open (creating if necessary) `path` as a writable memory map of
+    /// exactly `len` bytes, growing or truncating the underlying file to
+    /// match via `set_len`
+    pub fn create<P: AsRef<Path>>(path: P, len: usize) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path.as_ref())
+            .with_context(|| format!("Failed to open file for writable mapping: {}", path.as_ref().display()))?;
+
+        file.set_len(len as u64)
+            .with_context(|| format!("Failed to set length of {} to {} bytes", path.as_ref().display(), len))?;
+
+        Self::new(file)
+    }
+
+    /// Get a mutable slice of the mapped data with bounds checking
+    pub fn slice_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8]> {
+        if offset + len > self.len {
+            anyhow::bail!("Slice bounds exceed mapped region: offset={}, len={}, total={}",
+                         offset, len, self.len);
+        }
+
+        Ok(unsafe {
+            std::slice::from_raw_parts_mut(self.data.add(offset), len)
+        })
+    }
+
+    /// Get a mutable typed slice, with the same bounds and alignment checks
+    /// as [`SafeMmap::typed_slice`]
+    pub fn typed_slice_mut<T: FromBytes>(&mut self, offset: usize, count: usize) -> Result<&mut [T]> {
+        let type_size = std::mem::size_of::<T>();
+        let type_align = std::mem::align_of::<T>();
+        let byte_len = count
+            .checked_mul(type_size)
+            .context("typed_slice_mut byte length overflowed usize")?;
+
+        if byte_len % type_size != 0 {
+            anyhow::bail!(
+                "Typed slice byte length {} is not a multiple of size_of::<{}>() = {}",
+                byte_len,
+                std::any::type_name::<T>(),
+                type_size
+            );
+        }
+
+        if (self.data as usize + offset) % type_align != 0 {
+            anyhow::bail!("Misaligned access: offset {} is not aligned to {} bytes for type {}",
+                         offset, type_align, std::any::type_name::<T>());
+        }
+
+        let bytes = self.slice_mut(offset, byte_len)?;
+
+        // SAFETY: `bytes` is exactly `count * size_of::<T>()` bytes (checked
+        // above), starts at a pointer aligned to `align_of::<T>()` (checked
+        // above), and `T: FromBytes` guarantees every bit pattern in those
+        // bytes is a valid `T`.
+        Ok(unsafe {
+            std::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, count)
+        })
+    }
+
+    /// Force every dirty page in the mapping to disk
+    pub fn flush(&self) -> Result<()> {
+        self.mmap.flush().context("Failed to flush memory map")
+    }
+
+    /// Force dirty pages in `[offset, offset + len)` to disk
+    pub fn flush_range(&self, offset: usize, len: usize) -> Result<()> {
+        if offset + len > self.len {
+            anyhow::bail!("Flush range exceeds mapped region: offset={}, len={}, total={}",
+                         offset, len, self.len);
+        }
+        self.mmap
+            .flush_range(offset, len)
+            .context("Failed to flush memory map range")
+    }
+
+    /// Grow the mapping to `new_len` bytes: flushes and unmaps the current
+    /// region, extends the backing file with `File::set_len`, and re-maps.
+    /// Errors if `new_len` is smaller than the current length -- this is a
+    /// growth-only operation, not a general resize. Any slices borrowed from
+    /// `slice_mut`/`typed_slice_mut` before calling this are invalidated;
+    /// the borrow checker already enforces this, since those slices borrow
+    /// `&mut self` and so can't outlive a `grow` call.
+    pub fn grow(&mut self, new_len: usize) -> Result<()> {
+        if new_len < self.len {
+            anyhow::bail!(
+                "Cannot shrink a writable mapping: current len={}, requested={}",
+                self.len, new_len
+            );
+        }
+        if new_len == self.len {
+            return Ok(());
+        }
+
+        self.flush().context("Failed to flush before growing the mapping")?;
+        self.file
+            .set_len(new_len as u64)
+            .with_context(|| format!("Failed to grow backing file to {new_len} bytes"))?;
+
+        // Dropping the old `mmap` unmaps the current region before we
+        // create a new one over the grown file.
+        let mut mmap = unsafe {
+            MmapMut::map_mut(&self.file)
+                .context("Failed to re-map after growing")?
+        };
+
+        self.data = mmap.as_mut_ptr();
+        self.len = new_len;
+        self.alignment = utils::get_alignment(self.data as usize);
+        self.mmap = mmap;
+
+        Ok(())
+    }
+
+    /// Ensure at least `additional` more bytes are mapped beyond the current
+    /// length, growing the mapping if needed. A hint for callers writing in
+    /// passes (e.g. adjacency lists) who know roughly how much more they're
+    /// about to append and want to `grow` once rather than after every
+    /// write.
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        let new_len = self
+            .len
+            .checked_add(additional)
+            .context("reserve length overflowed usize")?;
+        self.grow(new_len)
+    }
+
+    /// The mapping's current total length in bytes -- the same quantity
+    /// [`Self::len`] reports, exposed under this name for callers that think
+    /// in terms of "how much room is mapped" rather than "how big is the
+    /// region".
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Get the total length of the mapped region
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the mapping is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the alignment of the mapped data
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Whether this mapping's reported [`Self::alignment`] is at least
+    /// `lane_bytes`; see [`SafeMmap::is_simd_aligned`].
+    pub fn is_simd_aligned(&self, lane_bytes: usize) -> bool {
+        self.alignment >= lane_bytes
+    }
 }
 
 /// Memory mapping strategy that can fall back to buffered I/O
@@ -163,27 +408,45 @@ impl MappingStrategy {
     }
     
     /// Get a typed slice with alignment checking
-    pub fn typed_slice<T>(&self, offset: usize, count: usize) -> Result<&[T]> {
+    ///
+    /// See [`SafeMmap::typed_slice`] for why `T` is bounded on [`FromBytes`].
+    pub fn typed_slice<T: FromBytes>(&self, offset: usize, count: usize) -> Result<&[T]> {
         match self {
             #[cfg(feature = "mmap")]
             Self::MemoryMapped(mmap) => mmap.typed_slice(offset, count),
             Self::Buffered(buffer) => {
                 let type_size = std::mem::size_of::<T>();
                 let type_align = std::mem::align_of::<T>();
-                let byte_len = count * type_size;
-                
+                let byte_len = count
+                    .checked_mul(type_size)
+                    .context("typed_slice byte length overflowed usize")?;
+
+                if byte_len % type_size != 0 {
+                    anyhow::bail!(
+                        "Typed slice byte length {} is not a multiple of size_of::<{}>() = {}",
+                        byte_len,
+                        std::any::type_name::<T>(),
+                        type_size
+                    );
+                }
+
                 if offset + byte_len > buffer.len() {
-                    anyhow::bail!("Typed slice bounds exceed buffer: offset={}, byte_len={}, total={}", 
+                    anyhow::bail!("Typed slice bounds exceed buffer: offset={}, byte_len={}, total={}",
                                  offset, byte_len, buffer.len());
                 }
-                
+
                 // Check alignment
                 let ptr = buffer.as_ptr() as usize + offset;
                 if ptr % type_align != 0 {
-                    anyhow::bail!("Misaligned access: offset {} results in misaligned pointer for type {}", 
+                    anyhow::bail!("Misaligned access: offset {} results in misaligned pointer for type {}",
                                  offset, std::any::type_name::<T>());
                 }
-                
+
+                // SAFETY: `byte_len` is exactly `count * size_of::<T>()`
+                // bytes and within `buffer`'s bounds (checked above), `ptr`
+                // is aligned to `align_of::<T>()` (checked above), and `T:
+                // FromBytes` guarantees every bit pattern in those bytes is
+                // a valid `T`.
                 Ok(unsafe {
                     std::slice::from_raw_parts(
                         (buffer.as_ptr() as usize + offset) as *const T,
@@ -216,6 +479,224 @@ impl MappingStrategy {
             Self::Buffered(_) => "buffered",
         }
     }
+
+    /// Get the alignment of the underlying data: the mapped region's real
+    /// alignment for [`Self::MemoryMapped`], or the buffer's allocator-given
+    /// alignment for [`Self::Buffered`] (best-effort, since a plain `Vec<u8>`
+    /// isn't guaranteed to be aligned at all -- see
+    /// `diskann_core::alignment`).
+    pub fn alignment(&self) -> usize {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.alignment(),
+            Self::Buffered(buffer) => utils::get_alignment(buffer.as_ptr() as usize),
+        }
+    }
+
+    /// Whether [`Self::alignment`] is at least `lane_bytes`; see
+    /// [`SafeMmap::is_simd_aligned`].
+    pub fn is_simd_aligned(&self, lane_bytes: usize) -> bool {
+        self.alignment() >= lane_bytes
+    }
+}
+
+/// Writable counterpart to [`MappingStrategy`], for opening an index region
+/// for in-place mutation rather than read-only zero-copy access. The
+/// buffered fallback keeps the file path around so [`Self::flush`] can write
+/// the in-memory buffer back, mirroring what [`SafeMmapMut::flush`] does for
+/// the real mapping.
+pub enum MappingStrategyMut {
+    /// Use a writable memory mapping
+    #[cfg(feature = "mmap")]
+    MemoryMapped(SafeMmapMut),
+    /// Use an in-memory buffer, written back to `path` on flush
+    Buffered {
+        /// The buffered bytes
+        buffer: Vec<u8>,
+        /// Where `buffer` is written back to on flush
+        path: std::path::PathBuf,
+    },
+}
+
+impl MappingStrategyMut {
+    /// Open (creating or truncating/growing to `len` if necessary) the best
+    /// available writable mapping strategy for `path`
+    pub fn open_rw<P: AsRef<Path>>(path: P, len: usize) -> Result<Self> {
+        #[cfg(feature = "mmap")]
+        {
+            match SafeMmapMut::create(path.as_ref(), len) {
+                Ok(mmap) => {
+                    tracing::debug!("Using writable memory-mapped I/O for {}", path.as_ref().display());
+                    return Ok(Self::MemoryMapped(mmap));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to create writable memory map, falling back to buffered I/O: {}", e);
+                }
+            }
+        }
+
+        #[cfg(not(feature = "mmap"))]
+        {
+            tracing::info!("Memory mapping not available, using buffered I/O");
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path.as_ref())
+            .with_context(|| format!("Failed to open file for buffered writable I/O: {}", path.as_ref().display()))?;
+        file.set_len(len as u64)
+            .with_context(|| format!("Failed to set length of {} to {} bytes", path.as_ref().display(), len))?;
+
+        let mut buffer = vec![0u8; len];
+        file.read_exact(&mut buffer)
+            .context("Failed to read file into buffer")?;
+
+        Ok(Self::Buffered { buffer, path: path.as_ref().to_path_buf() })
+    }
+
+    /// Get a mutable slice of the data
+    pub fn slice_mut(&mut self, offset: usize, len: usize) -> Result<&mut [u8]> {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.slice_mut(offset, len),
+            Self::Buffered { buffer, .. } => {
+                if offset + len > buffer.len() {
+                    anyhow::bail!("Slice bounds exceed buffer: offset={}, len={}, total={}",
+                                 offset, len, buffer.len());
+                }
+                Ok(&mut buffer[offset..offset + len])
+            }
+        }
+    }
+
+    /// Get a mutable typed slice with alignment checking
+    ///
+    /// See [`SafeMmap::typed_slice`] for why `T` is bounded on [`FromBytes`].
+    pub fn typed_slice_mut<T: FromBytes>(&mut self, offset: usize, count: usize) -> Result<&mut [T]> {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.typed_slice_mut(offset, count),
+            Self::Buffered { buffer, .. } => {
+                let type_size = std::mem::size_of::<T>();
+                let type_align = std::mem::align_of::<T>();
+                let byte_len = count
+                    .checked_mul(type_size)
+                    .context("typed_slice_mut byte length overflowed usize")?;
+
+                if byte_len % type_size != 0 {
+                    anyhow::bail!(
+                        "Typed slice byte length {} is not a multiple of size_of::<{}>() = {}",
+                        byte_len,
+                        std::any::type_name::<T>(),
+                        type_size
+                    );
+                }
+
+                if offset + byte_len > buffer.len() {
+                    anyhow::bail!("Typed slice bounds exceed buffer: offset={}, byte_len={}, total={}",
+                                 offset, byte_len, buffer.len());
+                }
+
+                let ptr = buffer.as_ptr() as usize + offset;
+                if ptr % type_align != 0 {
+                    anyhow::bail!("Misaligned access: offset {} results in misaligned pointer for type {}",
+                                 offset, std::any::type_name::<T>());
+                }
+
+                // SAFETY: see `MappingStrategy::typed_slice`'s matching
+                // buffered branch.
+                Ok(unsafe {
+                    std::slice::from_raw_parts_mut(
+                        (buffer.as_mut_ptr() as usize + offset) as *mut T,
+                        count
+                    )
+                })
+            }
+        }
+    }
+
+    /// Force any dirty data to disk: flushes mapped pages for the
+    /// memory-mapped variant, or rewrites the whole backing file for the
+    /// buffered fallback
+    pub fn flush(&self) -> Result<()> {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.flush(),
+            Self::Buffered { buffer, path } => {
+                std::fs::write(path, buffer)
+                    .with_context(|| format!("Failed to flush buffered mapping to {}", path.display()))
+            }
+        }
+    }
+
+    /// Grow the mapping to `new_len` bytes. See [`SafeMmapMut::grow`] for the
+    /// memory-mapped path; the buffered fallback simply `resize`s the
+    /// `Vec<u8>`, zero-filling the new bytes. Errors if `new_len` is smaller
+    /// than the current length. Any previously handed-out slices are
+    /// invalidated.
+    pub fn grow(&mut self, new_len: usize) -> Result<()> {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.grow(new_len),
+            Self::Buffered { buffer, .. } => {
+                if new_len < buffer.len() {
+                    anyhow::bail!(
+                        "Cannot shrink a writable mapping: current len={}, requested={}",
+                        buffer.len(), new_len
+                    );
+                }
+                buffer.resize(new_len, 0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Ensure at least `additional` more bytes are available beyond the
+    /// current length, growing if needed
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        let new_len = self
+            .len()
+            .checked_add(additional)
+            .context("reserve length overflowed usize")?;
+        self.grow(new_len)
+    }
+
+    /// The mapping's current total length in bytes
+    pub fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    /// Get the total length
+    pub fn len(&self) -> usize {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.len(),
+            Self::Buffered { buffer, .. } => buffer.len(),
+        }
+    }
+
+    /// Check if empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the alignment of the underlying data; see
+    /// [`MappingStrategy::alignment`].
+    pub fn alignment(&self) -> usize {
+        match self {
+            #[cfg(feature = "mmap")]
+            Self::MemoryMapped(mmap) => mmap.alignment(),
+            Self::Buffered { buffer, .. } => utils::get_alignment(buffer.as_ptr() as usize),
+        }
+    }
+
+    /// Whether [`Self::alignment`] is at least `lane_bytes`; see
+    /// [`SafeMmap::is_simd_aligned`].
+    pub fn is_simd_aligned(&self, lane_bytes: usize) -> bool {
+        self.alignment() >= lane_bytes
+    }
 }
 
 /// Emit compile-time warnings when falling back to buffered I/O
@@ -280,11 +761,95 @@ mod tests {
         let mut temp_file = NamedTempFile::new().unwrap();
         temp_file.write_all(b"short").unwrap();
         temp_file.flush().unwrap();
-        
+
         let strategy = MappingStrategy::new(temp_file.path()).unwrap();
-        
+
         // This should fail
         assert!(strategy.slice(0, 10).is_err());
         assert!(strategy.slice(3, 10).is_err());
     }
+
+    #[test]
+    fn test_typed_slice_overflow_is_rejected() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(b"short").unwrap();
+        temp_file.flush().unwrap();
+
+        let strategy = MappingStrategy::new(temp_file.path()).unwrap();
+
+        // `count * size_of::<f32>()` overflows `usize` here, which must be
+        // reported as an error rather than wrapping into a too-small,
+        // silently-truncated byte length.
+        assert!(strategy.typed_slice::<f32>(0, usize::MAX / 2).is_err());
+    }
+
+    #[test]
+    fn test_mapping_strategy_mut_roundtrips_and_flushes() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        {
+            let mut strategy = MappingStrategyMut::open_rw(temp_file.path(), 16).unwrap();
+            assert_eq!(strategy.len(), 16);
+
+            let f32_slice = strategy.typed_slice_mut::<f32>(0, 4).unwrap();
+            f32_slice.copy_from_slice(&[1.0, 2.0, 3.0, 4.0]);
+            strategy.flush().unwrap();
+        }
+
+        let readback = MappingStrategy::new(temp_file.path()).unwrap();
+        let f32_slice = readback.typed_slice::<f32>(0, 4).unwrap();
+        assert_eq!(f32_slice, &[1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_mapping_strategy_mut_bounds_checking() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut strategy = MappingStrategyMut::open_rw(temp_file.path(), 8).unwrap();
+
+        assert!(strategy.slice_mut(0, 16).is_err());
+        assert!(strategy.slice_mut(4, 8).is_err());
+    }
+
+    #[test]
+    fn test_mapping_strategy_mut_grow_preserves_content_and_extends() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut strategy = MappingStrategyMut::open_rw(temp_file.path(), 8).unwrap();
+
+        strategy.slice_mut(0, 8).unwrap().copy_from_slice(b"original");
+        assert_eq!(strategy.capacity(), 8);
+
+        strategy.grow(16).unwrap();
+        assert_eq!(strategy.len(), 16);
+        assert_eq!(strategy.capacity(), 16);
+        assert_eq!(&strategy.slice_mut(0, 8).unwrap(), b"original");
+    }
+
+    #[test]
+    fn test_mapping_strategy_mut_grow_rejects_shrinking() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut strategy = MappingStrategyMut::open_rw(temp_file.path(), 16).unwrap();
+
+        assert!(strategy.grow(8).is_err());
+    }
+
+    #[test]
+    fn test_mapping_strategy_mut_reserve_extends_by_requested_amount() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut strategy = MappingStrategyMut::open_rw(temp_file.path(), 8).unwrap();
+
+        strategy.reserve(24).unwrap();
+        assert_eq!(strategy.len(), 32);
+    }
+
+    #[test]
+    fn test_is_simd_aligned_reflects_alignment() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let strategy = MappingStrategyMut::open_rw(temp_file.path(), 8).unwrap();
+
+        // Whatever the real alignment turns out to be, asking for a lane
+        // width larger than it must report false, and 1-byte "alignment"
+        // must always report true.
+        assert!(strategy.is_simd_aligned(1));
+        assert!(!strategy.is_simd_aligned(strategy.alignment() * 2));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,404 @@
+//! Product quantization (PQ) compression for memory-efficient vector storage
+//!
+//! Splits each database vector into `m` contiguous subvectors and encodes each
+//! subvector as the index of its nearest of 256 centroids (trained per
+//! subspace with k-means). This shrinks a D-dimensional f32 vector down to
+//! `m` bytes, at the cost of approximate (asymmetric) distance computation
+//! via [`AdcDistanceTable`]. This is the compression layer the `disk_index`
+//! module relies on to keep search memory-efficient.
+
+use anyhow::{bail, Context, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io::{Read, Write};
+
+use crate::format::BinaryHeader;
+
+/// Number of centroids trained per subspace (one byte per code)
+pub const CENTROIDS_PER_SUBSPACE: usize = 256;
+
+/// A trained product-quantization codebook
+#[derive(Debug, Clone)]
+pub struct PqCodebook {
+    num_subspaces: usize,
+    dimension: usize,
+    /// `num_subspaces * CENTROIDS_PER_SUBSPACE` centroids, each `subspace_dim()`
+    /// floats, flattened in subspace-major order
+    centroids: Vec<f32>,
+}
+
+impl PqCodebook {
+    /// Dimensionality of the original (unquantized) vectors
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Number of subspaces the vector is split into
+    pub fn num_subspaces(&self) -> usize {
+        self.num_subspaces
+    }
+
+    /// Dimensionality of each subspace (`dimension / num_subspaces`)
+    pub fn subspace_dim(&self) -> usize {
+        self.dimension / self.num_subspaces
+    }
+
+    fn centroid(&self, subspace: usize, code: usize) -> &[f32] {
+        let dim = self.subspace_dim();
+        let start = (subspace * CENTROIDS_PER_SUBSPACE + code) * dim;
+        &self.centroids[start..start + dim]
+    }
+
+    /// Train a codebook from a set of training vectors
+    ///
+    /// Runs k-means (k-means++ initialization, then Lloyd iterations) independently
+    /// per subspace. `num_subspaces` must evenly divide the vector dimension, and
+    /// there must be at least `CENTROIDS_PER_SUBSPACE` training vectors.
+    pub fn train(
+        training_vectors: &[Vec<f32>],
+        num_subspaces: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> Result<Self> {
+        if training_vectors.is_empty() {
+            bail!("Cannot train a PQ codebook with no training vectors");
+        }
+        let dimension = training_vectors[0].len();
+        if dimension == 0 || num_subspaces == 0 || dimension % num_subspaces != 0 {
+            bail!(
+                "Vector dimension {} must be a nonzero multiple of num_subspaces {}",
+                dimension,
+                num_subspaces
+            );
+        }
+        if training_vectors.len() < CENTROIDS_PER_SUBSPACE {
+            bail!(
+                "Need at least {} training vectors, got {}",
+                CENTROIDS_PER_SUBSPACE,
+                training_vectors.len()
+            );
+        }
+        for (i, v) in training_vectors.iter().enumerate() {
+            if v.len() != dimension {
+                bail!("Training vector {} has {} dimensions, expected {}", i, v.len(), dimension);
+            }
+        }
+
+        let subspace_dim = dimension / num_subspaces;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut centroids = vec![0.0f32; num_subspaces * CENTROIDS_PER_SUBSPACE * subspace_dim];
+
+        for subspace in 0..num_subspaces {
+            let offset = subspace * subspace_dim;
+            let subvectors: Vec<&[f32]> = training_vectors
+                .iter()
+                .map(|v| &v[offset..offset + subspace_dim])
+                .collect();
+
+            let subspace_centroids = kmeans(&subvectors, CENTROIDS_PER_SUBSPACE, iterations, &mut rng);
+
+            let dst_start = subspace * CENTROIDS_PER_SUBSPACE * subspace_dim;
+            centroids[dst_start..dst_start + subspace_centroids.len()]
+                .copy_from_slice(&subspace_centroids);
+        }
+
+        Ok(Self {
+            num_subspaces,
+            dimension,
+            centroids,
+        })
+    }
+
+    /// Encode a single vector as `num_subspaces` centroid indices
+    pub fn encode(&self, vector: &[f32]) -> Result<Vec<u8>> {
+        if vector.len() != self.dimension {
+            bail!("Vector has {} dimensions, codebook expects {}", vector.len(), self.dimension);
+        }
+        let subspace_dim = self.subspace_dim();
+        let mut code = Vec::with_capacity(self.num_subspaces);
+        for subspace in 0..self.num_subspaces {
+            let offset = subspace * subspace_dim;
+            let sub = &vector[offset..offset + subspace_dim];
+            code.push(self.nearest_centroid(subspace, sub) as u8);
+        }
+        Ok(code)
+    }
+
+    /// Encode a batch of vectors
+    pub fn encode_all(&self, vectors: &[Vec<f32>]) -> Result<Vec<Vec<u8>>> {
+        vectors.iter().map(|v| self.encode(v)).collect()
+    }
+
+    fn nearest_centroid(&self, subspace: usize, sub: &[f32]) -> usize {
+        let mut best = 0;
+        let mut best_dist = f32::INFINITY;
+        for code in 0..CENTROIDS_PER_SUBSPACE {
+            let dist = squared_l2(sub, self.centroid(subspace, code));
+            if dist < best_dist {
+                best_dist = dist;
+                best = code;
+            }
+        }
+        best
+    }
+
+    /// Build an asymmetric distance table for the given query, to be reused
+    /// across every candidate's PQ code during a single search
+    pub fn build_distance_table(&self, query: &[f32]) -> Result<AdcDistanceTable> {
+        if query.len() != self.dimension {
+            bail!("Query has {} dimensions, codebook expects {}", query.len(), self.dimension);
+        }
+        let subspace_dim = self.subspace_dim();
+        let mut table = vec![0.0f32; self.num_subspaces * CENTROIDS_PER_SUBSPACE];
+        for subspace in 0..self.num_subspaces {
+            let offset = subspace * subspace_dim;
+            let sub_query = &query[offset..offset + subspace_dim];
+            for code in 0..CENTROIDS_PER_SUBSPACE {
+                table[subspace * CENTROIDS_PER_SUBSPACE + code] =
+                    squared_l2(sub_query, self.centroid(subspace, code));
+            }
+        }
+        Ok(AdcDistanceTable {
+            num_subspaces: self.num_subspaces,
+            table,
+        })
+    }
+
+    /// Serialize the codebook using a `BinaryHeader`-compatible layout: the
+    /// header's `num_points` holds the total centroid count
+    /// (`num_subspaces * CENTROIDS_PER_SUBSPACE`) and `num_dimensions` holds
+    /// the per-subspace dimension, so the original shape is fully recoverable.
+    pub fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let header = BinaryHeader::new(self.num_subspaces * CENTROIDS_PER_SUBSPACE, self.subspace_dim());
+        header.validate()?;
+        header.write_to(writer).context("Failed to write PQ codebook header")?;
+        for &value in &self.centroids {
+            writer
+                .write_all(&value.to_le_bytes())
+                .context("Failed to write PQ codebook centroids")?;
+        }
+        Ok(())
+    }
+
+    /// Deserialize a codebook previously written with [`PqCodebook::serialize`]
+    pub fn deserialize<R: Read>(reader: &mut R) -> Result<Self> {
+        let header = BinaryHeader::read_from(reader).context("Failed to read PQ codebook header")?;
+        header.validate()?;
+
+        let num_centroids = header.num_points as usize;
+        if num_centroids == 0 || num_centroids % CENTROIDS_PER_SUBSPACE != 0 {
+            bail!(
+                "PQ codebook centroid count {} is not a nonzero multiple of {}",
+                num_centroids,
+                CENTROIDS_PER_SUBSPACE
+            );
+        }
+        let subspace_dim = header.num_dimensions as usize;
+        let num_subspaces = num_centroids / CENTROIDS_PER_SUBSPACE;
+
+        let mut centroids = Vec::with_capacity(num_centroids * subspace_dim);
+        for _ in 0..num_centroids * subspace_dim {
+            let mut bytes = [0u8; 4];
+            reader
+                .read_exact(&mut bytes)
+                .context("Failed to read PQ codebook centroid component")?;
+            centroids.push(f32::from_le_bytes(bytes));
+        }
+
+        Ok(Self {
+            num_subspaces,
+            dimension: num_subspaces * subspace_dim,
+            centroids,
+        })
+    }
+}
+
+/// Asymmetric distance table (ADC) for scoring PQ-compressed candidates
+/// against a single query, without ever reconstructing the original vectors
+pub struct AdcDistanceTable {
+    num_subspaces: usize,
+    /// `num_subspaces * CENTROIDS_PER_SUBSPACE` squared distances from the
+    /// query's j-th subvector to each of that subspace's centroids
+    table: Vec<f32>,
+}
+
+impl AdcDistanceTable {
+    /// Approximate squared distance from the query to the vector encoded by `code`
+    pub fn distance(&self, code: &[u8]) -> f32 {
+        debug_assert_eq!(code.len(), self.num_subspaces);
+        code.iter()
+            .enumerate()
+            .map(|(subspace, &c)| self.table[subspace * CENTROIDS_PER_SUBSPACE + c as usize])
+            .sum()
+    }
+}
+
+fn squared_l2(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(&x, &y)| (x - y) * (x - y)).sum()
+}
+
+/// Run k-means (k-means++ init, Lloyd iterations) over `points`, returning
+/// `k` flattened centroids
+fn kmeans(points: &[&[f32]], k: usize, iterations: usize, rng: &mut StdRng) -> Vec<f32> {
+    let dim = points[0].len();
+    let mut centroids = kmeans_plus_plus_init(points, k, rng);
+
+    let mut assignments = vec![0usize; points.len()];
+    for _ in 0..iterations.max(1) {
+        // Assignment step
+        for (i, point) in points.iter().enumerate() {
+            let mut best = 0;
+            let mut best_dist = f32::INFINITY;
+            for c in 0..k {
+                let centroid = &centroids[c * dim..(c + 1) * dim];
+                let dist = squared_l2(point, centroid);
+                if dist < best_dist {
+                    best_dist = dist;
+                    best = c;
+                }
+            }
+            assignments[i] = best;
+        }
+
+        // Update step
+        let mut sums = vec![0.0f32; k * dim];
+        let mut counts = vec![0usize; k];
+        for (point, &cluster) in points.iter().zip(assignments.iter()) {
+            counts[cluster] += 1;
+            let sum = &mut sums[cluster * dim..(cluster + 1) * dim];
+            for (s, &v) in sum.iter_mut().zip(point.iter()) {
+                *s += v;
+            }
+        }
+        for c in 0..k {
+            if counts[c] == 0 {
+                // Re-seed empty clusters from a random training point to avoid
+                // dead centroids collapsing the codebook
+                let idx = rng.gen_range(0..points.len());
+                centroids[c * dim..(c + 1) * dim].copy_from_slice(points[idx]);
+                continue;
+            }
+            let centroid = &mut centroids[c * dim..(c + 1) * dim];
+            let sum = &sums[c * dim..(c + 1) * dim];
+            for (dst, &s) in centroid.iter_mut().zip(sum.iter()) {
+                *dst = s / counts[c] as f32;
+            }
+        }
+    }
+
+    centroids
+}
+
+/// k-means++ initialization: pick the first centroid uniformly at random,
+/// then each subsequent centroid with probability proportional to its
+/// squared distance to the nearest already-chosen centroid
+fn kmeans_plus_plus_init(points: &[&[f32]], k: usize, rng: &mut StdRng) -> Vec<f32> {
+    let dim = points[0].len();
+    let mut centroids = Vec::with_capacity(k * dim);
+
+    let first = rng.gen_range(0..points.len());
+    centroids.extend_from_slice(points[first]);
+
+    let mut min_dist_sq = vec![f32::INFINITY; points.len()];
+
+    while centroids.len() / dim < k {
+        let last_centroid = &centroids[centroids.len() - dim..];
+        for (i, point) in points.iter().enumerate() {
+            let dist = squared_l2(point, last_centroid);
+            if dist < min_dist_sq[i] {
+                min_dist_sq[i] = dist;
+            }
+        }
+
+        let total: f32 = min_dist_sq.iter().sum();
+        if total <= 0.0 {
+            // All remaining points coincide with chosen centroids; pick uniformly
+            let idx = rng.gen_range(0..points.len());
+            centroids.extend_from_slice(points[idx]);
+            continue;
+        }
+
+        let mut target = rng.gen::<f32>() * total;
+        let mut chosen = points.len() - 1;
+        for (i, &d) in min_dist_sq.iter().enumerate() {
+            if target <= d {
+                chosen = i;
+                break;
+            }
+            target -= d;
+        }
+        centroids.extend_from_slice(points[chosen]);
+    }
+
+    centroids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn clustered_training_set() -> Vec<Vec<f32>> {
+        // Two well-separated clusters per 4-dim vector (2 subspaces of dim 2),
+        // replicated until there are enough points to fill 256 centroids.
+        let mut vectors = Vec::new();
+        for i in 0..300 {
+            if i % 2 == 0 {
+                vectors.push(vec![0.0, 0.0, 10.0, 10.0]);
+            } else {
+                vectors.push(vec![1.0, 1.0, 11.0, 11.0]);
+            }
+        }
+        vectors
+    }
+
+    #[test]
+    fn train_and_encode_round_trip_shape() {
+        let training = clustered_training_set();
+        let codebook = PqCodebook::train(&training, 2, 5, 7).unwrap();
+
+        assert_eq!(codebook.num_subspaces(), 2);
+        assert_eq!(codebook.dimension(), 4);
+
+        let code = codebook.encode(&training[0]).unwrap();
+        assert_eq!(code.len(), 2);
+    }
+
+    #[test]
+    fn distance_table_prefers_matching_cluster() {
+        let training = clustered_training_set();
+        let codebook = PqCodebook::train(&training, 2, 5, 7).unwrap();
+
+        let near_cluster_a = codebook.encode(&[0.0, 0.0, 10.0, 10.0]).unwrap();
+        let near_cluster_b = codebook.encode(&[5.0, 5.0, 5.0, 5.0]).unwrap();
+
+        let table = codebook.build_distance_table(&[0.0, 0.0, 10.0, 10.0]).unwrap();
+        assert!(table.distance(&near_cluster_a) <= table.distance(&near_cluster_b));
+    }
+
+    #[test]
+    fn serialize_deserialize_round_trip() {
+        let training = clustered_training_set();
+        let codebook = PqCodebook::train(&training, 2, 5, 7).unwrap();
+
+        let mut buffer = Vec::new();
+        codebook.serialize(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let loaded = PqCodebook::deserialize(&mut cursor).unwrap();
+
+        assert_eq!(loaded.num_subspaces(), codebook.num_subspaces());
+        assert_eq!(loaded.dimension(), codebook.dimension());
+
+        let original_code = codebook.encode(&training[0]).unwrap();
+        let loaded_code = loaded.encode(&training[0]).unwrap();
+        assert_eq!(original_code, loaded_code);
+    }
+
+    #[test]
+    fn rejects_dimension_mismatch() {
+        let training = clustered_training_set();
+        let codebook = PqCodebook::train(&training, 2, 5, 7).unwrap();
+        assert!(codebook.encode(&[0.0, 0.0]).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! Strict header/offset validation for mmapped DiskANN index files
+//!
+//! [`MappingStrategy::typed_slice`] trusts whatever offset a caller hands
+//! it; a truncated or corrupt index file can still produce an
+//! in-bounds-but-garbage read. [`SanitizedMapping`] instead takes a
+//! [`LayoutDescriptor`] describing the file's expected sections up front,
+//! checks every invariant once at [`SanitizedMapping::open`] time -- magic
+//! bytes, section bounds, declared size vs. `point_count * dimension *
+//! dtype.element_size()`, alignment, and non-overlap -- and returns a single
+//! [`LayoutError`] enumerating whichever one failed. Callers then resolve
+//! typed slices by section name, with bounds already proven.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::format::VectorDtype;
+use crate::mmap::{FromBytes, MappingStrategy};
+
+/// Describes one named section of a mapped index file, validated against
+/// the mapping on [`SanitizedMapping::open`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDescriptor {
+    /// Section name (`"vectors"`, `"graph"`, `"pq_pivots"`, ...), resolved
+    /// by [`SanitizedMapping::typed_slice`]
+    pub name: &'static str,
+    /// Byte offset of the section's first element within the mapped file
+    pub offset: usize,
+    /// Declared byte length of the section, cross-checked against
+    /// `point_count * dimension * dtype.element_size()`
+    pub len: usize,
+    /// Element dtype stored in this section
+    pub dtype: VectorDtype,
+    /// Number of points (rows) in this section
+    pub point_count: usize,
+    /// Dimension (elements per point) of this section
+    pub dimension: usize,
+}
+
+/// Declares a mapped file's expected layout: leading magic bytes followed by
+/// a set of named, non-overlapping sections
+#[derive(Debug, Clone)]
+pub struct LayoutDescriptor {
+    /// Expected leading 4 magic bytes identifying the file format
+    pub magic: [u8; 4],
+    /// Every section the file is expected to contain
+    pub sections: Vec<SectionDescriptor>,
+}
+
+/// A layout invariant [`SanitizedMapping::open`] found violated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The file's leading 4 bytes didn't match [`LayoutDescriptor::magic`]
+    BadMagic {
+        /// Magic bytes the descriptor expected
+        expected: [u8; 4],
+        /// Magic bytes actually found
+        found: [u8; 4],
+    },
+    /// A section's `[offset, offset + len)` range falls outside the mapped
+    /// file
+    SectionOutOfBounds {
+        /// Name of the offending section
+        name: &'static str,
+        /// The section's declared offset
+        offset: usize,
+        /// The section's declared length
+        len: usize,
+        /// The mapped file's total length
+        mapped_len: usize,
+    },
+    /// A section's declared `len` doesn't match `point_count * dimension *
+    /// dtype.element_size()`
+    SectionSizeMismatch {
+        /// Name of the offending section
+        name: &'static str,
+        /// The section's declared length
+        declared_len: usize,
+        /// `point_count * dimension * dtype.element_size()`
+        computed_len: usize,
+    },
+    /// A section's offset isn't aligned to its own element size
+    MisalignedSection {
+        /// Name of the offending section
+        name: &'static str,
+        /// The section's declared offset
+        offset: usize,
+        /// The alignment the section's dtype requires
+        required_align: usize,
+    },
+    /// Two sections' byte ranges overlap
+    OverlappingSections {
+        /// The earlier (by offset) of the two overlapping sections
+        first: &'static str,
+        /// The later (by offset) of the two overlapping sections
+        second: &'static str,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::BadMagic { expected, found } => write!(
+                f,
+                "bad magic bytes: expected {expected:?}, found {found:?}"
+            ),
+            LayoutError::SectionOutOfBounds { name, offset, len, mapped_len } => write!(
+                f,
+                "section '{name}' at offset {offset} with length {len} exceeds mapped length {mapped_len}"
+            ),
+            LayoutError::SectionSizeMismatch { name, declared_len, computed_len } => write!(
+                f,
+                "section '{name}' declares length {declared_len}, but point_count * dimension * dtype size is {computed_len}"
+            ),
+            LayoutError::MisalignedSection { name, offset, required_align } => write!(
+                f,
+                "section '{name}' at offset {offset} is not aligned to {required_align} bytes"
+            ),
+            LayoutError::OverlappingSections { first, second } => write!(
+                f,
+                "sections '{first}' and '{second}' overlap"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// A [`MappingStrategy`] whose sections have already been validated against
+/// a [`LayoutDescriptor`], so [`Self::typed_slice`] can hand out typed
+/// slices without re-checking bounds on every call
+pub struct SanitizedMapping {
+    mapping: MappingStrategy,
+    sections: HashMap<&'static str, SectionDescriptor>,
+}
+
+impl SanitizedMapping {
+    /// Open `path` and validate it against `layout`, returning a
+    /// [`LayoutError`] (wrapped in the returned `anyhow::Error`) describing
+    /// the first invariant that doesn't hold
+    pub fn open<P: AsRef<Path>>(path: P, layout: &LayoutDescriptor) -> Result<Self> {
+        let mapping = MappingStrategy::new(path)
+            .context("Failed to open file for sanitized mapping")?;
+        Self::validate(&mapping, layout)?;
+
+        let sections = layout
+            .sections
+            .iter()
+            .cloned()
+            .map(|section| (section.name, section))
+            .collect();
+
+        Ok(Self { mapping, sections })
+    }
+
+    fn validate(mapping: &MappingStrategy, layout: &LayoutDescriptor) -> Result<()> {
+        let magic = mapping.slice(0, 4).context("File too short to contain magic bytes")?;
+        if magic != layout.magic {
+            let mut found = [0u8; 4];
+            found.copy_from_slice(magic);
+            return Err(LayoutError::BadMagic { expected: layout.magic, found }.into());
+        }
+
+        let mapped_len = mapping.len();
+        let mut sections: Vec<&SectionDescriptor> = layout.sections.iter().collect();
+        sections.sort_by_key(|section| section.offset);
+
+        for (i, section) in sections.iter().enumerate() {
+            if section.offset.checked_add(section.len).map(|end| end > mapped_len).unwrap_or(true) {
+                return Err(LayoutError::SectionOutOfBounds {
+                    name: section.name,
+                    offset: section.offset,
+                    len: section.len,
+                    mapped_len,
+                }
+                .into());
+            }
+
+            let computed_len = section.point_count * section.dimension * section.dtype.element_size();
+            if computed_len != section.len {
+                return Err(LayoutError::SectionSizeMismatch {
+                    name: section.name,
+                    declared_len: section.len,
+                    computed_len,
+                }
+                .into());
+            }
+
+            let required_align = section.dtype.element_size();
+            if section.offset % required_align != 0 {
+                return Err(LayoutError::MisalignedSection {
+                    name: section.name,
+                    offset: section.offset,
+                    required_align,
+                }
+                .into());
+            }
+
+            if i > 0 {
+                let prev = sections[i - 1];
+                if prev.offset + prev.len > section.offset {
+                    return Err(LayoutError::OverlappingSections {
+                        first: prev.name,
+                        second: section.name,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the named section as a typed slice, with bounds already proven at
+    /// [`Self::open`] time. Errors if `name` isn't a declared section, or if
+    /// `T`'s size doesn't match the section's declared dtype.
+    pub fn typed_slice<T: FromBytes>(&self, name: &str) -> Result<&[T]> {
+        let section = self
+            .sections
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown section: {name}"))?;
+
+        let type_size = std::mem::size_of::<T>();
+        if type_size != section.dtype.element_size() {
+            anyhow::bail!(
+                "Section '{}' holds {:?} ({} bytes/element), but requested type is {} bytes/element",
+                name,
+                section.dtype,
+                section.dtype.element_size(),
+                type_size
+            );
+        }
+
+        self.mapping
+            .typed_slice::<T>(section.offset, section.point_count * section.dimension)
+    }
+
+    /// The underlying validated mapping
+    pub fn mapping(&self) -> &MappingStrategy {
+        &self.mapping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn write_file(magic: &[u8; 4], vectors: &[f32]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(magic).unwrap();
+        file.write_all(bytemuck::cast_slice(vectors)).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn vectors_section(offset: usize, point_count: usize, dimension: usize) -> SectionDescriptor {
+        SectionDescriptor {
+            name: "vectors",
+            offset,
+            len: point_count * dimension * VectorDtype::F32.element_size(),
+            dtype: VectorDtype::F32,
+            point_count,
+            dimension,
+        }
+    }
+
+    #[test]
+    fn opens_and_resolves_a_well_formed_layout() {
+        let data: [f32; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let file = write_file(b"DANN", &data);
+        let layout = LayoutDescriptor {
+            magic: *b"DANN",
+            sections: vec![vectors_section(4, 2, 4)],
+        };
+
+        let sanitized = SanitizedMapping::open(file.path(), &layout).unwrap();
+        let slice = sanitized.typed_slice::<f32>("vectors").unwrap();
+        assert_eq!(slice, &data);
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let data: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let file = write_file(b"DANN", &data);
+        let layout = LayoutDescriptor {
+            magic: *b"NOPE",
+            sections: vec![vectors_section(4, 1, 4)],
+        };
+
+        let err = SanitizedMapping::open(file.path(), &layout).unwrap_err();
+        assert!(err.to_string().contains("bad magic bytes"));
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_section() {
+        let data: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let file = write_file(b"DANN", &data);
+        let layout = LayoutDescriptor {
+            magic: *b"DANN",
+            sections: vec![vectors_section(4, 100, 4)],
+        };
+
+        let err = SanitizedMapping::open(file.path(), &layout).unwrap_err();
+        assert!(err.to_string().contains("exceeds mapped length"));
+    }
+
+    #[test]
+    fn rejects_declared_size_mismatch() {
+        let data: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let file = write_file(b"DANN", &data);
+        let mut section = vectors_section(4, 1, 4);
+        section.len = 8; // doesn't match point_count * dimension * element_size
+        let layout = LayoutDescriptor { magic: *b"DANN", sections: vec![section] };
+
+        let err = SanitizedMapping::open(file.path(), &layout).unwrap_err();
+        assert!(err.to_string().contains("declares length"));
+    }
+
+    #[test]
+    fn rejects_overlapping_sections() {
+        let data: [f32; 8] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let file = write_file(b"DANN", &data);
+        let mut first = vectors_section(4, 2, 4);
+        first.name = "vectors";
+        let mut second = vectors_section(4 + 8, 2, 4);
+        second.name = "pq_pivots";
+        second.offset = 4 + 4; // overlaps `first`'s [4, 36) range
+        let layout = LayoutDescriptor { magic: *b"DANN", sections: vec![first, second] };
+
+        let err = SanitizedMapping::open(file.path(), &layout).unwrap_err();
+        assert!(err.to_string().contains("overlap"));
+    }
+
+    #[test]
+    fn rejects_unknown_section_name() {
+        let data: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let file = write_file(b"DANN", &data);
+        let layout = LayoutDescriptor {
+            magic: *b"DANN",
+            sections: vec![vectors_section(4, 1, 4)],
+        };
+
+        let sanitized = SanitizedMapping::open(file.path(), &layout).unwrap();
+        assert!(sanitized.typed_slice::<f32>("graph").is_err());
+    }
+}
@@ -0,0 +1,329 @@
+//! Mergeable incremental index segments with a merging loader
+//!
+//! Lets callers append new vectors as standalone, immutable segment files
+//! (each a regular [`crate::format::BinaryHeader`]-prefixed vector file)
+//! instead of rewriting one monolithic index on every ingest.
+//! [`SegmentedIndexLoader`] loads an ordered list of segments and presents
+//! them as a single [`IndexLoader`] view, reassigning globally unique
+//! [`VectorId`]s by concatenation order. [`compact`] merges several segments
+//! into one file -- dropping any ids tombstoned in a segment's
+//! deletion-bitmap sidecar -- so the segment count stays bounded, the same
+//! way an LSM tree compacts its SSTables.
+//!
+//! A segment's tombstones live in a sidecar file next to it, named
+//! `<segment>.deleted`: a flat sequence of little-endian `u32` local
+//! (per-segment) vector indices. A segment with no sidecar has no
+//! tombstones.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use diskann_core::structures::GraphNode;
+use diskann_core::vectors::{Vector, VectorId};
+
+use crate::loader::{IndexLoader, IndexMetadata, MmapIndexLoader};
+use crate::writer::{FileIndexWriter, IndexWriter};
+
+/// Sidecar path holding `segment_path`'s tombstoned local indices
+pub fn deletion_sidecar_path(segment_path: &Path) -> PathBuf {
+    let mut name = segment_path.as_os_str().to_owned();
+    name.push(".deleted");
+    PathBuf::from(name)
+}
+
+/// Write `deleted_local_ids` to `segment_path`'s deletion sidecar
+pub fn write_deletion_bitmap(segment_path: &Path, deleted_local_ids: &[u32]) -> Result<()> {
+    let sidecar = deletion_sidecar_path(segment_path);
+    let mut file = File::create(&sidecar)
+        .with_context(|| format!("Failed to create deletion sidecar: {}", sidecar.display()))?;
+    for id in deleted_local_ids {
+        file.write_all(&id.to_le_bytes())
+            .context("Failed to write tombstoned id")?;
+    }
+    Ok(())
+}
+
+/// Read `segment_path`'s tombstoned local indices, or an empty set if it has
+/// no deletion sidecar
+fn read_deletion_bitmap(segment_path: &Path) -> Result<HashSet<u32>> {
+    let sidecar = deletion_sidecar_path(segment_path);
+    if !sidecar.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let mut file = File::open(&sidecar)
+        .with_context(|| format!("Failed to open deletion sidecar: {}", sidecar.display()))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).context("Failed to read deletion sidecar")?;
+
+    let mut ids = HashSet::new();
+    for chunk in bytes.chunks_exact(4) {
+        ids.insert(u32::from_le_bytes(chunk.try_into().expect("chunk of length 4")));
+    }
+    Ok(ids)
+}
+
+/// Load `segment_path` via `loader` and drop any locally tombstoned vectors
+fn load_live_vectors(loader: &MmapIndexLoader, segment_path: &Path) -> Result<Vec<Vector>> {
+    let vectors = loader
+        .load_vectors(segment_path)
+        .with_context(|| format!("Failed to load segment {}", segment_path.display()))?;
+    let deleted = read_deletion_bitmap(segment_path)?;
+
+    if deleted.is_empty() {
+        return Ok(vectors);
+    }
+
+    Ok(vectors
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !deleted.contains(&(*i as u32)))
+        .map(|(_, vector)| vector)
+        .collect())
+}
+
+/// [`IndexLoader`] over an ordered list of immutable segment files
+pub struct SegmentedIndexLoader {
+    segment_paths: Vec<PathBuf>,
+    inner: MmapIndexLoader,
+}
+
+impl SegmentedIndexLoader {
+    /// Open a segmented index made up of `segment_paths`, merged in order
+    pub fn new(segment_paths: Vec<PathBuf>) -> Self {
+        Self {
+            segment_paths,
+            inner: MmapIndexLoader::new(),
+        }
+    }
+
+    /// Number of segments backing this view
+    pub fn segment_count(&self) -> usize {
+        self.segment_paths.len()
+    }
+}
+
+impl IndexLoader for SegmentedIndexLoader {
+    fn load_vectors<P: AsRef<Path>>(&self, _path: P) -> Result<Vec<Vector>> {
+        let mut vectors = Vec::new();
+        for segment_path in &self.segment_paths {
+            vectors.extend(load_live_vectors(&self.inner, segment_path)?);
+        }
+        Ok(vectors)
+    }
+
+    fn load_graph<P: AsRef<Path>>(&self, path: P) -> Result<Vec<GraphNode>> {
+        let vectors = self.load_vectors(path)?;
+
+        let nodes = vectors
+            .into_iter()
+            .enumerate()
+            .map(|(i, vector)| GraphNode::new(i as VectorId, vector))
+            .collect();
+
+        Ok(nodes)
+    }
+
+    fn load_vectors_subset<P: AsRef<Path>>(&self, path: P, start: usize, count: usize) -> Result<Vec<Vector>> {
+        let all_vectors = self.load_vectors(path)?;
+
+        if start >= all_vectors.len() {
+            anyhow::bail!("Start index {} exceeds vector count {}", start, all_vectors.len());
+        }
+
+        let end = std::cmp::min(start + count, all_vectors.len());
+        Ok(all_vectors[start..end].to_vec())
+    }
+
+    fn get_metadata<P: AsRef<Path>>(&self, _path: P) -> Result<IndexMetadata> {
+        let mut num_vectors = 0;
+        let mut file_size = 0;
+        let mut expected_body_bytes = 0;
+        let mut dimension = None;
+
+        for segment_path in &self.segment_paths {
+            let metadata = self.inner.get_metadata(segment_path).with_context(|| {
+                format!("Failed to read metadata for segment {}", segment_path.display())
+            })?;
+            let deleted = read_deletion_bitmap(segment_path)?;
+
+            match dimension {
+                None => dimension = Some(metadata.dimension),
+                Some(expected) if expected != metadata.dimension => {
+                    anyhow::bail!(
+                        "Segment {} has dimension {}, expected {}",
+                        segment_path.display(),
+                        metadata.dimension,
+                        expected
+                    );
+                }
+                _ => {}
+            }
+
+            // `deleted` is an external sidecar file, not a value this loader
+            // controls -- it may be stale (a segment rewritten after it was
+            // written) or simply malformed, so its raw length isn't trusted
+            // as a valid in-range, deduplicated count the way
+            // `load_live_vectors`'s `filter` already treats it. Only count
+            // entries that are actually in-range local indices, and
+            // saturate rather than underflow if there somehow end up more
+            // of those than the segment has vectors.
+            let deleted_in_range = deleted
+                .iter()
+                .filter(|&&id| (id as usize) < metadata.num_vectors)
+                .count();
+            let live_vectors = metadata.num_vectors.saturating_sub(deleted_in_range);
+            num_vectors += live_vectors;
+            file_size += metadata.file_size;
+            expected_body_bytes += live_vectors * metadata.dimension * std::mem::size_of::<f32>();
+        }
+
+        Ok(IndexMetadata {
+            num_vectors,
+            dimension: dimension.unwrap_or(0),
+            file_size,
+            strategy: format!("segmented ({} live segments)", self.segment_paths.len()),
+            expected_body_bytes,
+        })
+    }
+
+    fn verify_only<P: AsRef<Path>>(&self, _path: P) -> Result<()> {
+        for segment_path in &self.segment_paths {
+            self.inner
+                .verify_only(segment_path)
+                .with_context(|| format!("Segment {} failed verification", segment_path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Merge `segments` (in order) into a single file at `out`, dropping any
+/// locally tombstoned vectors (see [`deletion_sidecar_path`]) along the way
+/// -- the same way an LSM tree compacts several SSTables into one.
+///
+/// Returns the number of live vectors written to `out`.
+pub fn compact<P: AsRef<Path>>(segments: &[PathBuf], out: P) -> Result<usize> {
+    let inner = MmapIndexLoader::new();
+
+    let mut vectors = Vec::new();
+    for segment_path in segments {
+        vectors.extend(load_live_vectors(&inner, segment_path)?);
+    }
+
+    let writer = FileIndexWriter::new();
+    writer
+        .write_vectors(out.as_ref(), &vectors)
+        .with_context(|| format!("Failed to write compacted segment to {}", out.as_ref().display()))?;
+
+    Ok(vectors.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::FileIndexWriter;
+    use tempfile::TempDir;
+
+    fn write_segment(dir: &Path, name: &str, vectors: &[Vector]) -> PathBuf {
+        let path = dir.join(name);
+        FileIndexWriter::new().write_vectors(&path, vectors).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_segmented_loader_concatenates_segments_in_order() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0]]);
+        let seg_b = write_segment(dir.path(), "seg_b.diskann", &[vec![3.0], vec![4.0]]);
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone(), seg_b.clone()]);
+        assert_eq!(loader.segment_count(), 2);
+
+        let vectors = loader.load_vectors(&seg_a).unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0]]);
+    }
+
+    #[test]
+    fn test_segmented_loader_drops_tombstoned_vectors() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0], vec![3.0]]);
+        write_deletion_bitmap(&seg_a, &[1]).unwrap();
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone()]);
+        let vectors = loader.load_vectors(&seg_a).unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![3.0]]);
+    }
+
+    #[test]
+    fn test_segmented_loader_metadata_accounts_for_tombstones() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let seg_b = write_segment(dir.path(), "seg_b.diskann", &[vec![5.0, 6.0]]);
+        write_deletion_bitmap(&seg_a, &[0]).unwrap();
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone(), seg_b.clone()]);
+        let metadata = loader.get_metadata(&seg_a).unwrap();
+
+        assert_eq!(metadata.num_vectors, 2);
+        assert_eq!(metadata.dimension, 2);
+        assert_eq!(loader.load_vectors(&seg_a).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_segmented_loader_metadata_tolerates_out_of_range_tombstones() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0]]);
+        // A stale/malformed sidecar naming more (and out-of-range) ids than
+        // the segment actually has vectors shouldn't underflow `num_vectors`.
+        write_deletion_bitmap(&seg_a, &[0, 1, 2, 3, 4]).unwrap();
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone()]);
+        let metadata = loader.get_metadata(&seg_a).unwrap();
+
+        assert_eq!(metadata.num_vectors, 0);
+    }
+
+    #[test]
+    fn test_segmented_loader_load_graph_and_subset() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0], vec![3.0]]);
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone()]);
+
+        let nodes = loader.load_graph(&seg_a).unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[2].id, 2);
+
+        let subset = loader.load_vectors_subset(&seg_a, 1, 2).unwrap();
+        assert_eq!(subset, vec![vec![2.0], vec![3.0]]);
+    }
+
+    #[test]
+    fn test_segmented_loader_verify_only() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0]]);
+
+        let loader = SegmentedIndexLoader::new(vec![seg_a.clone()]);
+        loader.verify_only(&seg_a).unwrap();
+    }
+
+    #[test]
+    fn test_compact_merges_segments_and_drops_tombstones() {
+        let dir = TempDir::new().unwrap();
+        let seg_a = write_segment(dir.path(), "seg_a.diskann", &[vec![1.0], vec![2.0], vec![3.0]]);
+        let seg_b = write_segment(dir.path(), "seg_b.diskann", &[vec![4.0], vec![5.0]]);
+        write_deletion_bitmap(&seg_a, &[1]).unwrap();
+
+        let out_path = dir.path().join("compacted.diskann");
+        let live_count = compact(&[seg_a.clone(), seg_b.clone()], &out_path).unwrap();
+        assert_eq!(live_count, 4);
+
+        let loader = MmapIndexLoader::new();
+        let vectors = loader.load_vectors(&out_path).unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![3.0], vec![4.0], vec![5.0]]);
+    }
+}
@@ -1,40 +1,245 @@
 //! Index serialization and deserialization
+//!
+//! The on-disk layout is versioned so old files stay readable: a magic
+//! header (see [`INDEX_MAGIC`]) plus a format version and metric id
+//! precede the vector/adjacency payload in the current format, while files
+//! written before this header existed (plain [`write_vectors_f32`] output,
+//! no adjacency) are detected by that magic's absence and read back as
+//! format version 0.
 
 use diskann_core::structures::GraphNode;
 use anyhow::{Result, Context};
-use std::io::{Read, Write};
+use std::io::{Read, Write, Cursor};
 use crate::format::{write_vectors_f32, read_vectors_f32};
+use crate::disk_index::DistanceMetric;
 
-/// Serialize index to writer (simple vector-only format for now)
+/// Magic bytes identifying the adjacency-preserving index format
+///
+/// Chosen so it can't be mistaken for a plausible `num_points` value from
+/// the old vector-only format, whose first 4 bytes are `num_points` as a
+/// little-endian `i32` (see [`deserialize_index`]'s backward-compatibility
+/// handling).
+const INDEX_MAGIC: [u8; 4] = *b"DAX1";
+
+/// Current index format version, bumped whenever [`serialize_index`]'s
+/// on-disk layout changes incompatibly. Files with [`INDEX_MAGIC`] absent
+/// predate this versioning and are treated as format version 0.
+const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Encode a [`DistanceMetric`] as the stable on-disk id stored in the index
+/// header
+fn metric_to_id(metric: DistanceMetric) -> u32 {
+    match metric {
+        DistanceMetric::Euclidean => 0,
+        DistanceMetric::Cosine => 1,
+        DistanceMetric::InnerProduct => 2,
+    }
+}
+
+/// Decode a [`DistanceMetric`] from its on-disk id; see [`metric_to_id`]
+fn metric_from_id(id: u32) -> Result<DistanceMetric> {
+    match id {
+        0 => Ok(DistanceMetric::Euclidean),
+        1 => Ok(DistanceMetric::Cosine),
+        2 => Ok(DistanceMetric::InnerProduct),
+        other => anyhow::bail!("Unknown index metric id: {}", other),
+    }
+}
+
+/// Serialize index to writer, defaulting to [`DistanceMetric::Euclidean`]
+///
+/// See [`serialize_index_with_metric`] to record a different metric in the
+/// header.
 pub fn serialize_index<W: Write>(writer: &mut W, nodes: &[GraphNode]) -> Result<()> {
+    serialize_index_with_metric(writer, nodes, DistanceMetric::default())
+}
+
+/// Serialize index to writer, recording which `metric` it was built with
+///
+/// Writes [`INDEX_MAGIC`], the format version, and `metric`'s id, then each
+/// node's vector (via [`write_vectors_f32`], which has its own embedded
+/// header carrying node count and dimension) followed by its neighbor list
+/// as a length-prefixed `u32` count and that many `u32` neighbor ids.
+pub fn serialize_index_with_metric<W: Write>(
+    writer: &mut W,
+    nodes: &[GraphNode],
+    metric: DistanceMetric,
+) -> Result<()> {
     if nodes.is_empty() {
         anyhow::bail!("Cannot serialize empty index");
     }
-    
+
+    writer
+        .write_all(&INDEX_MAGIC)
+        .context("Failed to write index magic bytes")?;
+    writer
+        .write_all(&INDEX_FORMAT_VERSION.to_le_bytes())
+        .context("Failed to write index format version")?;
+    writer
+        .write_all(&metric_to_id(metric).to_le_bytes())
+        .context("Failed to write index metric id")?;
+
     // Extract vectors from nodes
     let vectors: Vec<_> = nodes.iter().map(|node| node.vector.clone()).collect();
-    
+
     // Write vectors using diskann-io format
     write_vectors_f32(writer, &vectors)
         .context("Failed to serialize index vectors")?;
-    
+
+    for node in nodes {
+        writer
+            .write_all(&(node.neighbors.len() as u32).to_le_bytes())
+            .context("Failed to write neighbor count")?;
+        for &neighbor in &node.neighbors {
+            writer
+                .write_all(&neighbor.to_le_bytes())
+                .context("Failed to write neighbor id")?;
+        }
+    }
+
     Ok(())
 }
 
-/// Deserialize index from reader (simple vector-only format for now)
+/// Deserialize index from reader
+///
+/// See [`deserialize_index_with_metric`] to also recover the metric the
+/// index was serialized with.
 pub fn deserialize_index<R: Read>(reader: &mut R) -> Result<Vec<GraphNode>> {
-    // Read vectors using diskann-io format
-    let vectors = read_vectors_f32(reader)
-        .context("Failed to deserialize index vectors")?;
-    
-    // Convert vectors to graph nodes
-    let nodes = vectors
-        .into_iter()
-        .enumerate()
-        .map(|(i, vector)| {
-            GraphNode::new(i as u32, vector)
-        })
-        .collect();
-        
+    let (nodes, _metric) = deserialize_index_with_metric(reader)?;
     Ok(nodes)
-}
\ No newline at end of file
+}
+
+/// Deserialize index from reader, along with the [`DistanceMetric`] it was
+/// serialized with
+///
+/// Detects the current (adjacency-preserving) format by [`INDEX_MAGIC`];
+/// when it's absent, the stream is assumed to be the old vector-only
+/// payload (format version 0), its 4 already-read bytes are stitched back
+/// onto the reader, and every node comes back with an empty neighbor list
+/// and [`DistanceMetric::default()`], matching this function's behavior
+/// before adjacency was persisted.
+pub fn deserialize_index_with_metric<R: Read>(reader: &mut R) -> Result<(Vec<GraphNode>, DistanceMetric)> {
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .context("Failed to read index header")?;
+
+    if magic == INDEX_MAGIC {
+        let mut version_bytes = [0u8; 4];
+        reader
+            .read_exact(&mut version_bytes)
+            .context("Failed to read index format version")?;
+        let version = u32::from_le_bytes(version_bytes);
+        if version != INDEX_FORMAT_VERSION {
+            anyhow::bail!("Unsupported index format version: {}", version);
+        }
+
+        let mut metric_bytes = [0u8; 4];
+        reader
+            .read_exact(&mut metric_bytes)
+            .context("Failed to read index metric id")?;
+        let metric = metric_from_id(u32::from_le_bytes(metric_bytes))?;
+
+        let vectors = read_vectors_f32(reader)
+            .context("Failed to deserialize index vectors")?;
+
+        let mut nodes = Vec::with_capacity(vectors.len());
+        for (i, vector) in vectors.into_iter().enumerate() {
+            let mut count_bytes = [0u8; 4];
+            reader
+                .read_exact(&mut count_bytes)
+                .context("Failed to read neighbor count")?;
+            let neighbor_count = u32::from_le_bytes(count_bytes) as usize;
+
+            let mut neighbors = Vec::with_capacity(neighbor_count);
+            for _ in 0..neighbor_count {
+                let mut id_bytes = [0u8; 4];
+                reader
+                    .read_exact(&mut id_bytes)
+                    .context("Failed to read neighbor id")?;
+                neighbors.push(u32::from_le_bytes(id_bytes));
+            }
+
+            let mut node = GraphNode::new(i as u32, vector);
+            node.neighbors = neighbors;
+            nodes.push(node);
+        }
+
+        Ok((nodes, metric))
+    } else {
+        let mut chained = Cursor::new(magic).chain(reader);
+        let vectors = read_vectors_f32(&mut chained)
+            .context("Failed to deserialize index vectors")?;
+
+        let nodes = vectors
+            .into_iter()
+            .enumerate()
+            .map(|(i, vector)| GraphNode::new(i as u32, vector))
+            .collect();
+
+        Ok((nodes, DistanceMetric::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_preserves_adjacency() {
+        let mut node0 = GraphNode::new(0, vec![1.0, 2.0, 3.0]);
+        node0.neighbors = vec![1, 2];
+        let mut node1 = GraphNode::new(1, vec![4.0, 5.0, 6.0]);
+        node1.neighbors = vec![0];
+        let node2 = GraphNode::new(2, vec![7.0, 8.0, 9.0]);
+
+        let nodes = vec![node0, node1, node2];
+
+        let mut buffer = Vec::new();
+        serialize_index_with_metric(&mut buffer, &nodes, DistanceMetric::Cosine).unwrap();
+
+        let (read_nodes, metric) = deserialize_index_with_metric(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(metric, DistanceMetric::Cosine);
+        assert_eq!(read_nodes.len(), nodes.len());
+        for (orig, read) in nodes.iter().zip(read_nodes.iter()) {
+            assert_eq!(orig.vector, read.vector);
+            assert_eq!(orig.neighbors, read.neighbors);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_falls_back_to_vector_only_format() {
+        let vectors = vec![vec![1.0f32, 2.0], vec![3.0f32, 4.0]];
+
+        let mut buffer = Vec::new();
+        write_vectors_f32(&mut buffer, &vectors).unwrap();
+
+        let (nodes, metric) = deserialize_index_with_metric(&mut buffer.as_slice()).unwrap();
+
+        assert_eq!(metric, DistanceMetric::Euclidean);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].vector, vectors[0]);
+        assert!(nodes[0].neighbors.is_empty());
+        assert_eq!(nodes[1].vector, vectors[1]);
+        assert!(nodes[1].neighbors.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_index_defaults_to_euclidean_metric() {
+        let nodes = vec![GraphNode::new(0, vec![1.0, 2.0])];
+
+        let mut buffer = Vec::new();
+        serialize_index(&mut buffer, &nodes).unwrap();
+
+        let (_, metric) = deserialize_index_with_metric(&mut buffer.as_slice()).unwrap();
+        assert_eq!(metric, DistanceMetric::Euclidean);
+    }
+
+    #[test]
+    fn test_serialize_empty_index_fails() {
+        let nodes: Vec<GraphNode> = vec![];
+        let mut buffer = Vec::new();
+        assert!(serialize_index(&mut buffer, &nodes).is_err());
+    }
+}
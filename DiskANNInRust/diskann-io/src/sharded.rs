@@ -0,0 +1,320 @@
+//! Sharded (bucketed) index storage for horizontal scale-out
+//!
+//! Splits a dataset across a power-of-two number of independent
+//! `bucket_{n}.diskann` files inside a directory, each one a regular
+//! [`crate::format::BinaryHeader`]-prefixed vector file written by
+//! [`write_sharded`]. [`ShardedIndexLoader`] opens every bucket with a plain
+//! [`MmapIndexLoader`](crate::loader::MmapIndexLoader) and presents the
+//! directory as a single [`IndexLoader`]. Each bucket stays independently
+//! memory-mappable, so capacity grows by adding buckets rather than
+//! rewriting one monolithic file.
+//!
+//! Vectors are routed to bucket `id & (num_buckets - 1)` by their position in
+//! the input slice (mirroring how [`crate::loader::MmapIndexLoader::load_graph`]
+//! assigns [`VectorId`]s), so the unified [`IndexLoader::load_vectors`] view
+//! iterates buckets in order and yields each bucket's vectors in their
+//! original relative order -- it does *not* reconstruct the original global
+//! ordering across buckets.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use diskann_core::structures::GraphNode;
+use diskann_core::vectors::{Vector, VectorId};
+
+use crate::format::BinaryHeader;
+use crate::loader::{IndexLoader, IndexMetadata, MmapIndexLoader};
+use crate::writer::{FileIndexWriter, IndexWriter};
+
+/// Bucket file name for bucket index `n`, e.g. `bucket_3.diskann`
+fn bucket_file_name(n: usize) -> String {
+    format!("bucket_{}.diskann", n)
+}
+
+/// Configuration for a [`ShardedIndexLoader`]/[`write_sharded`] layout
+#[derive(Debug, Clone, Copy)]
+pub struct IndexShardConfig {
+    num_buckets_pow2: u32,
+}
+
+impl IndexShardConfig {
+    /// Create a config with `2.pow(num_buckets_pow2)` buckets
+    pub fn new(num_buckets_pow2: u32) -> Self {
+        Self { num_buckets_pow2 }
+    }
+
+    /// Number of buckets this config routes vectors across
+    pub fn num_buckets(&self) -> usize {
+        1usize << self.num_buckets_pow2
+    }
+
+    /// Bucket index `id` routes to: `id & (num_buckets - 1)`
+    pub fn bucket_for(&self, id: VectorId) -> usize {
+        (id as usize) & (self.num_buckets() - 1)
+    }
+}
+
+/// Write `vectors` into `dir` as `config.num_buckets()` per-bucket
+/// [`crate::format::BinaryHeader`]-prefixed files, creating `dir` if it
+/// doesn't already exist
+///
+/// Each vector's position in `vectors` is its [`VectorId`] for routing
+/// purposes (see [`IndexShardConfig::bucket_for`]); within a bucket, vectors
+/// keep their relative order. A bucket that receives no vectors is not
+/// written, so [`ShardedIndexLoader`] only has to iterate buckets that
+/// actually exist. Returns the total number of vectors written.
+pub fn write_sharded<P: AsRef<Path>>(
+    dir: P,
+    vectors: &[Vector],
+    config: IndexShardConfig,
+) -> Result<usize> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create shard directory: {}", dir.display()))?;
+
+    let mut buckets: Vec<Vec<Vector>> = vec![Vec::new(); config.num_buckets()];
+    for (i, vector) in vectors.iter().enumerate() {
+        let bucket = config.bucket_for(i as VectorId);
+        buckets[bucket].push(vector.clone());
+    }
+
+    let writer = FileIndexWriter::new();
+    for (n, bucket_vectors) in buckets.iter().enumerate() {
+        if bucket_vectors.is_empty() {
+            continue;
+        }
+        let bucket_path = dir.join(bucket_file_name(n));
+        writer
+            .write_vectors(&bucket_path, bucket_vectors)
+            .with_context(|| format!("Failed to write bucket {}", n))?;
+    }
+
+    Ok(vectors.len())
+}
+
+/// Discover and numerically sort the `bucket_{n}.diskann` files inside `dir`
+fn discover_bucket_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read shard directory: {}", dir.display()))?;
+
+    let mut buckets = Vec::new();
+    for entry in entries {
+        let entry = entry.context("Failed to read directory entry")?;
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(n) = file_name
+            .strip_prefix("bucket_")
+            .and_then(|rest| rest.strip_suffix(".diskann"))
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        buckets.push((n, path));
+    }
+
+    buckets.sort_by_key(|(n, _)| *n);
+    Ok(buckets.into_iter().map(|(_, path)| path).collect())
+}
+
+/// [`IndexLoader`] over a directory of buckets written by [`write_sharded`]
+pub struct ShardedIndexLoader {
+    dir: PathBuf,
+    inner: MmapIndexLoader,
+}
+
+impl ShardedIndexLoader {
+    /// Open the sharded index rooted at `dir`
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            inner: MmapIndexLoader::new(),
+        }
+    }
+
+    fn bucket_paths(&self) -> Result<Vec<PathBuf>> {
+        discover_bucket_paths(&self.dir)
+    }
+}
+
+impl IndexLoader for ShardedIndexLoader {
+    fn load_vectors<P: AsRef<Path>>(&self, _path: P) -> Result<Vec<Vector>> {
+        let mut vectors = Vec::new();
+        for bucket_path in self.bucket_paths()? {
+            let bucket_vectors = self
+                .inner
+                .load_vectors(&bucket_path)
+                .with_context(|| format!("Failed to load bucket {}", bucket_path.display()))?;
+            vectors.extend(bucket_vectors);
+        }
+        Ok(vectors)
+    }
+
+    fn load_graph<P: AsRef<Path>>(&self, path: P) -> Result<Vec<GraphNode>> {
+        let vectors = self.load_vectors(path)?;
+
+        let nodes = vectors
+            .into_iter()
+            .enumerate()
+            .map(|(i, vector)| GraphNode::new(i as VectorId, vector))
+            .collect();
+
+        Ok(nodes)
+    }
+
+    fn load_vectors_subset<P: AsRef<Path>>(&self, path: P, start: usize, count: usize) -> Result<Vec<Vector>> {
+        let all_vectors = self.load_vectors(path)?;
+
+        if start >= all_vectors.len() {
+            anyhow::bail!("Start index {} exceeds vector count {}", start, all_vectors.len());
+        }
+
+        let end = std::cmp::min(start + count, all_vectors.len());
+        Ok(all_vectors[start..end].to_vec())
+    }
+
+    fn get_metadata<P: AsRef<Path>>(&self, _path: P) -> Result<IndexMetadata> {
+        let bucket_paths = self.bucket_paths()?;
+        if bucket_paths.is_empty() {
+            anyhow::bail!("Shard directory {} has no bucket files", self.dir.display());
+        }
+
+        let mut num_vectors = 0;
+        let mut file_size = 0;
+        let mut expected_body_bytes = 0;
+        let mut dimension = None;
+
+        for bucket_path in &bucket_paths {
+            let metadata = self.inner.get_metadata(bucket_path)
+                .with_context(|| format!("Failed to read metadata for bucket {}", bucket_path.display()))?;
+
+            match dimension {
+                None => dimension = Some(metadata.dimension),
+                Some(expected) if expected != metadata.dimension => {
+                    anyhow::bail!(
+                        "Bucket {} has dimension {}, expected {}",
+                        bucket_path.display(),
+                        metadata.dimension,
+                        expected
+                    );
+                }
+                _ => {}
+            }
+
+            num_vectors += metadata.num_vectors;
+            file_size += metadata.file_size;
+            expected_body_bytes += metadata.expected_body_bytes;
+        }
+
+        Ok(IndexMetadata {
+            num_vectors,
+            dimension: dimension.unwrap_or(0),
+            file_size,
+            strategy: format!("sharded ({} buckets)", bucket_paths.len()),
+            expected_body_bytes,
+        })
+    }
+
+    fn verify_only<P: AsRef<Path>>(&self, _path: P) -> Result<()> {
+        for bucket_path in self.bucket_paths()? {
+            self.inner
+                .verify_only(&bucket_path)
+                .with_context(|| format!("Bucket {} failed verification", bucket_path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Read a bucket file's header alone, without loading its vectors -- used by
+/// tests to sanity-check [`write_sharded`]'s routing without going through
+/// the full [`IndexLoader`] path
+#[allow(dead_code)]
+fn read_bucket_header(path: &Path) -> Result<BinaryHeader> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+    let header = BinaryHeader::read_from(&mut reader).context("Failed to read binary header")?;
+    header.validate()?;
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bucket_for_routes_by_mask() {
+        let config = IndexShardConfig::new(2); // 4 buckets
+        assert_eq!(config.num_buckets(), 4);
+        assert_eq!(config.bucket_for(0), 0);
+        assert_eq!(config.bucket_for(1), 1);
+        assert_eq!(config.bucket_for(5), 1);
+        assert_eq!(config.bucket_for(6), 2);
+    }
+
+    #[test]
+    fn test_write_sharded_creates_one_file_per_nonempty_bucket() {
+        let dir = TempDir::new().unwrap();
+        let config = IndexShardConfig::new(1); // 2 buckets
+
+        let vectors: Vec<Vector> = (0..8).map(|i| vec![i as f32]).collect();
+        let written = write_sharded(dir.path(), &vectors, config).unwrap();
+        assert_eq!(written, 8);
+
+        assert!(dir.path().join("bucket_0.diskann").exists());
+        assert!(dir.path().join("bucket_1.diskann").exists());
+
+        let header = read_bucket_header(&dir.path().join("bucket_0.diskann")).unwrap();
+        assert_eq!(header.num_points as usize, 4);
+    }
+
+    #[test]
+    fn test_sharded_loader_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let config = IndexShardConfig::new(2); // 4 buckets
+
+        let vectors: Vec<Vector> = (0..20).map(|i| vec![i as f32, (i * 2) as f32]).collect();
+        write_sharded(dir.path(), &vectors, config).unwrap();
+
+        let loader = ShardedIndexLoader::new(dir.path());
+        let loaded = loader.load_vectors(dir.path()).unwrap();
+        assert_eq!(loaded.len(), vectors.len());
+
+        let metadata = loader.get_metadata(dir.path()).unwrap();
+        assert_eq!(metadata.num_vectors, 20);
+        assert_eq!(metadata.dimension, 2);
+    }
+
+    #[test]
+    fn test_sharded_loader_load_graph_and_subset() {
+        let dir = TempDir::new().unwrap();
+        let config = IndexShardConfig::new(1); // 2 buckets
+
+        let vectors: Vec<Vector> = (0..10).map(|i| vec![i as f32]).collect();
+        write_sharded(dir.path(), &vectors, config).unwrap();
+
+        let loader = ShardedIndexLoader::new(dir.path());
+
+        let nodes = loader.load_graph(dir.path()).unwrap();
+        assert_eq!(nodes.len(), 10);
+
+        let subset = loader.load_vectors_subset(dir.path(), 2, 3).unwrap();
+        assert_eq!(subset.len(), 3);
+    }
+
+    #[test]
+    fn test_sharded_loader_verify_only() {
+        let dir = TempDir::new().unwrap();
+        let config = IndexShardConfig::new(1);
+
+        let vectors: Vec<Vector> = (0..6).map(|i| vec![i as f32]).collect();
+        write_sharded(dir.path(), &vectors, config).unwrap();
+
+        let loader = ShardedIndexLoader::new(dir.path());
+        loader.verify_only(dir.path()).unwrap();
+    }
+}
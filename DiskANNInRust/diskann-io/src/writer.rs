@@ -1,12 +1,71 @@
 //! Index writer abstractions for persistent storage
 
 use std::path::Path;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
 use anyhow::{Result, Context};
 use diskann_core::structures::GraphNode;
 use diskann_core::vectors::Vector;
-use crate::format::{write_vectors_f32, write_graph_nodes};
+use crate::format::{
+    write_vectors_f32, write_vectors_f32_checksummed, write_vectors_f32_lz4,
+    write_vectors_f32_block_checksummed,
+    write_graph_nodes, write_graph_nodes_lz4, append_vectors_f32, GraphHeader,
+};
+use crate::compressed::{write_compressed, WriterOpts};
+use crate::flock::LockGuard;
+use crate::lz4_blocks::DEFAULT_BLOCK_SIZE;
+use crate::block_checksum::CompressionType as BlockCompressionType;
+
+/// Create (or truncate) `path` and take an advisory exclusive lock on it via
+/// a second file handle, so a concurrent [`crate::loader::MmapIndexLoader`]
+/// reader blocks (or fails fast, for a racing writer) instead of observing a
+/// half-written file. The returned [`LockGuard`] must be kept alive for as
+/// long as `path` is being written to.
+///
+/// Opens without truncating and only calls [`File::set_len`] once the lock
+/// is held, so a reader racing in before the lock is acquired sees the
+/// previous file's contents (or blocks/fails outright trying to lock it)
+/// rather than an empty file truncated out from under it -- mirroring how
+/// [`FileIndexWriter::append_vectors`] opens its file before locking.
+fn create_locked<P: AsRef<Path>>(path: P) -> Result<(File, LockGuard)> {
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .open(path.as_ref())
+        .with_context(|| format!("Failed to create file: {}", path.as_ref().display()))?;
+    let lock_file = File::open(path.as_ref())
+        .with_context(|| format!("Failed to open file for locking: {}", path.as_ref().display()))?;
+    let guard = LockGuard::try_lock_exclusive(lock_file)?;
+    file.set_len(0)
+        .with_context(|| format!("Failed to truncate file: {}", path.as_ref().display()))?;
+    Ok((file, guard))
+}
+
+/// Block-compression scheme for [`FileIndexWriter::write_vectors_buffered`]/
+/// [`FileIndexWriter::write_graph`], selected via
+/// [`IndexWriterBuilder::compression`]
+///
+/// Distinct from [`FileIndexWriter::write_vectors_compressed`]'s separate
+/// zstd container format (see [`crate::compressed`]): this stays within the
+/// normal [`crate::format::BinaryHeader`]-prefixed layout, so a compressed
+/// file round-trips through the same [`crate::format::read_vectors_f32_lz4`]/
+/// [`crate::format::read_graph_nodes_lz4`] readers as an uncompressed one,
+/// auto-detected via the header rather than requiring the caller to know up
+/// front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Store the payload as-is (the historical behavior)
+    None,
+    /// Partition the payload into fixed-size blocks and LZ4-compress each
+    /// one independently (see [`IndexWriterBuilder::block_size`])
+    Lz4,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Compression::None
+    }
+}
 
 /// Trait for writing indices to persistent storage
 pub trait IndexWriter {
@@ -18,12 +77,51 @@ pub trait IndexWriter {
     
     /// Write with custom buffer size for performance tuning
     fn write_vectors_buffered<P: AsRef<Path>>(&self, path: P, vectors: &[Vector], buffer_size: usize) -> Result<usize>;
+
+    /// Write vectors as a zstd-compressed block container (see
+    /// [`crate::compressed`]) instead of the raw format `write_vectors` uses,
+    /// trading a one-time compression pass for a smaller file and O(1) subset
+    /// loads via [`crate::compressed::CompressedVectorReader`]
+    fn write_vectors_compressed<P: AsRef<Path>>(&self, path: P, vectors: &[Vector], opts: WriterOpts) -> Result<usize>;
+
+    /// Write vectors with a trailing CRC32C integrity footer (see
+    /// [`crate::format::write_vectors_f32_checksummed`]), so a truncated or
+    /// bit-rotted file is caught on load instead of silently returning
+    /// corrupt data
+    fn write_vectors_checksummed<P: AsRef<Path>>(&self, path: P, vectors: &[Vector]) -> Result<usize>;
+
+    /// Write vectors as a [`crate::block_checksum`] container: fixed-size
+    /// blocks, each compressed with `compression` and checksummed with
+    /// xxh3-64, so block-level corruption is caught (and localized) on load
+    /// instead of either silently returning bad data or failing the whole
+    /// file. `BlockCompressionType::None` falls back to the plain
+    /// [`write_vectors`](IndexWriter::write_vectors) layout, preserving its
+    /// zero-copy mmap load path.
+    fn write_vectors_block_checksummed<P: AsRef<Path>>(
+        &self,
+        path: P,
+        vectors: &[Vector],
+        compression: BlockCompressionType,
+        block_size: usize,
+    ) -> Result<usize>;
+
+    /// Append vectors to an existing file written by [`write_vectors`](IndexWriter::write_vectors),
+    /// rewriting only the header's point count instead of the whole file (see
+    /// [`crate::format::append_vectors_f32`]). If `path` doesn't yet exist (or
+    /// is empty), this bootstraps it with a regular write instead. Returns
+    /// the file's new total vector count.
+    fn append_vectors<P: AsRef<Path>>(&self, path: P, vectors: &[Vector]) -> Result<usize>;
 }
 
 /// Standard file-based index writer
 pub struct FileIndexWriter {
     /// Default buffer size for writes
     buffer_size: usize,
+    /// Block-compression scheme applied by [`write_vectors_buffered`](IndexWriter::write_vectors_buffered)
+    /// and [`write_graph`](IndexWriter::write_graph)
+    compression: Compression,
+    /// Block size used when `compression` is [`Compression::Lz4`]
+    block_size: usize,
 }
 
 impl FileIndexWriter {
@@ -31,12 +129,17 @@ impl FileIndexWriter {
     pub fn new() -> Self {
         Self {
             buffer_size: 64 * 1024 * 1024, // 64MB default buffer
+            compression: Compression::None,
+            block_size: DEFAULT_BLOCK_SIZE,
         }
     }
-    
+
     /// Create a new file index writer with custom buffer size
     pub fn with_buffer_size(buffer_size: usize) -> Self {
-        Self { buffer_size }
+        Self {
+            buffer_size,
+            ..Self::new()
+        }
     }
 }
 
@@ -52,49 +155,140 @@ impl IndexWriter for FileIndexWriter {
     }
     
     fn write_graph<P: AsRef<Path>>(&self, path: P, nodes: &[GraphNode]) -> Result<usize> {
-        let file = File::create(path.as_ref())
-            .with_context(|| format!("Failed to create file: {}", path.as_ref().display()))?;
-        
+        let (file, _lock) = create_locked(path.as_ref())?;
+
         let mut writer = BufWriter::with_capacity(self.buffer_size, file);
-        
-        let bytes_written = write_graph_nodes(&mut writer, nodes)
-            .context("Failed to write graph nodes")?;
-        
+
+        let bytes_written = match self.compression {
+            Compression::None => write_graph_nodes(&mut writer, nodes)
+                .context("Failed to write graph nodes")?,
+            Compression::Lz4 => write_graph_nodes_lz4(&mut writer, nodes, GraphHeader::new(0, false), self.block_size)
+                .context("Failed to write LZ4-compressed graph nodes")?,
+        };
+
         writer.flush()
             .context("Failed to flush writer")?;
-        
-        tracing::info!("Wrote {} graph nodes to {}, {} bytes total", 
+
+        tracing::info!("Wrote {} graph nodes to {}, {} bytes total",
                       nodes.len(), path.as_ref().display(), bytes_written);
-        
+
         Ok(bytes_written)
     }
-    
+
     fn write_vectors_buffered<P: AsRef<Path>>(&self, path: P, vectors: &[Vector], buffer_size: usize) -> Result<usize> {
         if vectors.is_empty() {
             anyhow::bail!("Cannot write empty vector array");
         }
-        
-        let file = File::create(path.as_ref())
-            .with_context(|| format!("Failed to create file: {}", path.as_ref().display()))?;
-        
+
+        let (file, _lock) = create_locked(path.as_ref())?;
+
         let mut writer = BufWriter::with_capacity(buffer_size, file);
-        
-        let bytes_written = write_vectors_f32(&mut writer, vectors)
-            .context("Failed to write vector data")?;
-        
+
+        let bytes_written = match self.compression {
+            Compression::None => write_vectors_f32(&mut writer, vectors)
+                .context("Failed to write vector data")?,
+            Compression::Lz4 => write_vectors_f32_lz4(&mut writer, vectors, self.block_size)
+                .context("Failed to write LZ4-compressed vector data")?,
+        };
+
         writer.flush()
             .context("Failed to flush writer")?;
-        
-        tracing::info!("Wrote {} vectors to {}, {} bytes total", 
+
+        tracing::info!("Wrote {} vectors to {}, {} bytes total",
                       vectors.len(), path.as_ref().display(), bytes_written);
-        
+
         Ok(bytes_written)
     }
+
+    fn write_vectors_compressed<P: AsRef<Path>>(&self, path: P, vectors: &[Vector], opts: WriterOpts) -> Result<usize> {
+        let (file, _lock) = create_locked(path.as_ref())?;
+
+        let mut writer = BufWriter::with_capacity(self.buffer_size, file);
+
+        let bytes_written = write_compressed(&mut writer, vectors, opts)
+            .context("Failed to write compressed vector data")?;
+
+        writer.flush()
+            .context("Failed to flush writer")?;
+
+        tracing::info!("Wrote {} vectors to {} as a compressed container, {} bytes total",
+                      vectors.len(), path.as_ref().display(), bytes_written);
+
+        Ok(bytes_written)
+    }
+
+    fn write_vectors_checksummed<P: AsRef<Path>>(&self, path: P, vectors: &[Vector]) -> Result<usize> {
+        let (file, _lock) = create_locked(path.as_ref())?;
+
+        let mut writer = BufWriter::with_capacity(self.buffer_size, file);
+
+        let bytes_written = write_vectors_f32_checksummed(&mut writer, vectors)
+            .context("Failed to write checksummed vector data")?;
+
+        writer.flush()
+            .context("Failed to flush writer")?;
+
+        tracing::info!("Wrote {} vectors to {} with a checksum footer, {} bytes total",
+                      vectors.len(), path.as_ref().display(), bytes_written);
+
+        Ok(bytes_written)
+    }
+
+    fn write_vectors_block_checksummed<P: AsRef<Path>>(
+        &self,
+        path: P,
+        vectors: &[Vector],
+        compression: BlockCompressionType,
+        block_size: usize,
+    ) -> Result<usize> {
+        let (file, _lock) = create_locked(path.as_ref())?;
+
+        let mut writer = BufWriter::with_capacity(self.buffer_size, file);
+
+        let bytes_written = write_vectors_f32_block_checksummed(&mut writer, vectors, compression, block_size)
+            .context("Failed to write block-checksummed vector data")?;
+
+        writer.flush()
+            .context("Failed to flush writer")?;
+
+        tracing::info!("Wrote {} vectors to {} as a block-checksummed container, {} bytes total",
+                      vectors.len(), path.as_ref().display(), bytes_written);
+
+        Ok(bytes_written)
+    }
+
+    fn append_vectors<P: AsRef<Path>>(&self, path: P, vectors: &[Vector]) -> Result<usize> {
+        let has_existing_data = path.as_ref().metadata().map(|m| m.len() > 0).unwrap_or(false);
+        if !has_existing_data {
+            self.write_vectors(path.as_ref(), vectors)?;
+            return Ok(vectors.len());
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path.as_ref())
+            .with_context(|| format!("Failed to open file: {}", path.as_ref().display()))?;
+
+        let lock_file = File::open(path.as_ref())
+            .with_context(|| format!("Failed to open file for locking: {}", path.as_ref().display()))?;
+        let _lock = LockGuard::try_lock_exclusive(lock_file)?;
+
+        let total = append_vectors_f32(&mut file, vectors)
+            .context("Failed to append vector data")?;
+
+        tracing::info!("Appended {} vectors to {}, {} total now",
+                      vectors.len(), path.as_ref().display(), total);
+
+        Ok(total)
+    }
 }
 
 /// Builder for creating index writers with different configurations
 pub struct IndexWriterBuilder {
     buffer_size: usize,
+    compression: Compression,
+    block_size: usize,
 }
 
 impl IndexWriterBuilder {
@@ -102,18 +296,39 @@ impl IndexWriterBuilder {
     pub fn new() -> Self {
         Self {
             buffer_size: 64 * 1024 * 1024,
+            compression: Compression::None,
+            block_size: DEFAULT_BLOCK_SIZE,
         }
     }
-    
+
     /// Set the buffer size for writes
     pub fn buffer_size(mut self, size: usize) -> Self {
         self.buffer_size = size;
         self
     }
-    
+
+    /// Set the block-compression scheme applied by the built writer's
+    /// [`write_vectors_buffered`](IndexWriter::write_vectors_buffered) and
+    /// [`write_graph`](IndexWriter::write_graph)
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Set the block size used when `compression` is [`Compression::Lz4`]
+    /// (see [`crate::lz4_blocks::DEFAULT_BLOCK_SIZE`])
+    pub fn block_size(mut self, size: usize) -> Self {
+        self.block_size = size;
+        self
+    }
+
     /// Build a file-based index writer
     pub fn build_file_writer(self) -> FileIndexWriter {
-        FileIndexWriter::with_buffer_size(self.buffer_size)
+        FileIndexWriter {
+            buffer_size: self.buffer_size,
+            compression: self.compression,
+            block_size: self.block_size,
+        }
     }
 }
 
@@ -149,17 +364,22 @@ mod tests {
     
     #[test]
     fn test_file_writer_graph() {
+        use crate::format::BinaryHeader;
+
         let writer = FileIndexWriter::new();
         let nodes = vec![
             GraphNode::new(0, vec![1.0, 2.0]),
             GraphNode::new(1, vec![3.0, 4.0]),
         ];
-        
+
         let temp_file = NamedTempFile::new().unwrap();
         let bytes_written = writer.write_graph(temp_file.path(), &nodes).unwrap();
-        
-        // Header (8 bytes) + data (2 * 2 * 4 = 16 bytes) = 24 bytes total
-        assert_eq!(bytes_written, 24);
+
+        // Vector block: header + data (2 * 2 * 4 = 16 bytes), then the graph
+        // section: an 8-byte GraphHeader + a 4-byte max_degree + one 4-byte
+        // neighbor_count per node (both nodes have no neighbors here).
+        let expected = std::mem::size_of::<BinaryHeader>() + 16 + 8 + 4 + nodes.len() * 4;
+        assert_eq!(bytes_written, expected);
     }
     
     #[test]
@@ -171,13 +391,174 @@ mod tests {
         assert_eq!(writer.buffer_size, 128 * 1024);
     }
     
+    #[test]
+    fn test_file_writer_compressed_vectors() {
+        use crate::compressed::CompressedVectorReader;
+
+        let writer = FileIndexWriter::new();
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors_compressed(temp_file.path(), &vectors, WriterOpts::default()).unwrap();
+
+        let reader = CompressedVectorReader::open(temp_file.path()).unwrap();
+        assert_eq!(reader.read_range(0, 3).unwrap(), vectors);
+    }
+
+    #[test]
+    fn test_file_writer_checksummed_vectors() {
+        use crate::format::read_vectors_f32_checksummed;
+
+        let writer = FileIndexWriter::new();
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+        ];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors_checksummed(temp_file.path(), &vectors).unwrap();
+
+        let mut file = std::fs::File::open(temp_file.path()).unwrap();
+        let read_back = read_vectors_f32_checksummed(&mut file).unwrap();
+        assert_eq!(read_back, vectors);
+    }
+
+    #[test]
+    fn test_file_writer_block_checksummed_vectors() {
+        use crate::format::read_vectors_f32_block_checksummed;
+
+        let writer = FileIndexWriter::new();
+        let vectors: Vec<Vector> = (0..50).map(|i| vec![i as f32, (i * 2) as f32]).collect();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer
+            .write_vectors_block_checksummed(temp_file.path(), &vectors, BlockCompressionType::Lz4, 64)
+            .unwrap();
+
+        let mut file = std::fs::File::open(temp_file.path()).unwrap();
+        let read_back = read_vectors_f32_block_checksummed(&mut file).unwrap();
+        assert_eq!(read_back, vectors);
+    }
+
+    #[test]
+    fn test_append_vectors_matches_single_shot_write() {
+        let writer = FileIndexWriter::new();
+        let batch1 = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        let batch2 = vec![vec![5.0, 6.0]];
+        let batch3 = vec![vec![7.0, 8.0], vec![9.0, 10.0]];
+
+        let appended_file = NamedTempFile::new().unwrap();
+        // The file starts out empty, so the first append bootstraps it.
+        assert_eq!(writer.append_vectors(appended_file.path(), &batch1).unwrap(), 2);
+        assert_eq!(writer.append_vectors(appended_file.path(), &batch2).unwrap(), 3);
+        assert_eq!(writer.append_vectors(appended_file.path(), &batch3).unwrap(), 5);
+
+        let mut all = batch1;
+        all.extend(batch2);
+        all.extend(batch3);
+
+        let single_shot_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(single_shot_file.path(), &all).unwrap();
+
+        let appended_bytes = std::fs::read(appended_file.path()).unwrap();
+        let single_shot_bytes = std::fs::read(single_shot_file.path()).unwrap();
+        assert_eq!(appended_bytes, single_shot_bytes);
+    }
+
+    #[test]
+    fn test_append_vectors_rejects_dimension_mismatch() {
+        let writer = FileIndexWriter::new();
+        let temp_file = NamedTempFile::new().unwrap();
+
+        writer.append_vectors(temp_file.path(), &[vec![1.0, 2.0]]).unwrap();
+        let result = writer.append_vectors(temp_file.path(), &[vec![1.0, 2.0, 3.0]]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_empty_vectors_error() {
         let writer = FileIndexWriter::new();
         let temp_file = NamedTempFile::new().unwrap();
-        
+
         let result = writer.write_vectors(temp_file.path(), &[]);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("empty vector array"));
     }
+
+    #[test]
+    fn test_builder_compression_and_block_size() {
+        let writer = IndexWriterBuilder::new()
+            .compression(Compression::Lz4)
+            .block_size(4096)
+            .build_file_writer();
+
+        assert_eq!(writer.compression, Compression::Lz4);
+        assert_eq!(writer.block_size, 4096);
+    }
+
+    #[test]
+    fn test_lz4_vectors_round_trip() {
+        use crate::format::read_vectors_f32_lz4;
+
+        let writer = IndexWriterBuilder::new()
+            .compression(Compression::Lz4)
+            .block_size(64)
+            .build_file_writer();
+        let vectors = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_vectors(temp_file.path(), &vectors).unwrap();
+
+        let mut file = std::fs::File::open(temp_file.path()).unwrap();
+        let read_back = read_vectors_f32_lz4(&mut file).unwrap();
+        assert_eq!(read_back, vectors);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_vectors_fails_while_another_process_holds_an_exclusive_lock() {
+        let writer = FileIndexWriter::new();
+        let temp_file = NamedTempFile::new().unwrap();
+        // Pre-create the file so there's something to lock.
+        std::fs::write(temp_file.path(), []).unwrap();
+
+        let lock_file = std::fs::File::open(temp_file.path()).unwrap();
+        let _guard = LockGuard::try_lock_exclusive(lock_file).unwrap();
+
+        let result = writer.write_vectors(temp_file.path(), &[vec![1.0, 2.0]]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_lz4_graph_round_trip() {
+        use crate::format::read_graph_nodes_lz4;
+
+        let writer = IndexWriterBuilder::new()
+            .compression(Compression::Lz4)
+            .block_size(64)
+            .build_file_writer();
+        let nodes = vec![
+            GraphNode::new(0, vec![1.0, 2.0]),
+            GraphNode::new(1, vec![3.0, 4.0]),
+        ];
+
+        let temp_file = NamedTempFile::new().unwrap();
+        writer.write_graph(temp_file.path(), &nodes).unwrap();
+
+        let mut file = std::fs::File::open(temp_file.path()).unwrap();
+        let read_back = read_graph_nodes_lz4(&mut file).unwrap();
+        assert_eq!(read_back.len(), nodes.len());
+        for (original, round_tripped) in nodes.iter().zip(read_back.iter()) {
+            assert_eq!(round_tripped.vector, original.vector);
+            assert_eq!(round_tripped.neighbors, original.neighbors);
+        }
+    }
 }
\ No newline at end of file
@@ -2,7 +2,10 @@
 //! These tests verify round-trip encoding/decoding invariants
 
 use proptest::prelude::*;
-use diskann_io::format::{BinaryHeader, write_vectors_f32, read_vectors_f32};
+use diskann_io::format::{
+    BinaryHeader, VectorDtype, write_vectors_f32, read_vectors_f32, write_vectors_typed,
+    read_vectors_typed,
+};
 use diskann_core::vectors::Vector;
 use std::io::Cursor;
 
@@ -12,6 +15,7 @@ fn arb_header() -> impl Strategy<Value = BinaryHeader> {
         BinaryHeader {
             num_points: points,
             num_dimensions: dims,
+            dtype: VectorDtype::F32 as i32,
         }
     })
 }
@@ -91,7 +95,7 @@ proptest! {
     /// Test header validation properties
     #[test]
     fn test_header_validation(num_points in -1000i32..10000i32, num_dimensions in -1000i32..1000i32) {
-        let header = BinaryHeader { num_points, num_dimensions };
+        let header = BinaryHeader { num_points, num_dimensions, dtype: VectorDtype::F32 as i32 };
         let result = header.validate();
         
         if num_points < 0 || num_dimensions <= 0 {
@@ -105,11 +109,83 @@ proptest! {
     #[test]
     fn test_data_size_calculations(header in arb_header()) {
         let expected_data_size = (header.num_points * header.num_dimensions) as usize * 4; // 4 bytes per f32
-        let expected_total_size = 8 + expected_data_size; // 8 bytes for header
+        let expected_total_size = std::mem::size_of::<BinaryHeader>() + expected_data_size;
         
         prop_assert_eq!(header.data_size_f32(), expected_data_size, "Data size calculation should be correct");
         prop_assert_eq!(header.total_file_size_f32(), expected_total_size, "Total size calculation should be correct");
     }
+
+    /// Test that the f16 dtype round-trips within half-precision rounding error
+    #[test]
+    fn test_f16_round_trip_within_tolerance(vectors in arb_vector_dataset()) {
+        let mut buffer = Vec::new();
+        let mut cursor = Cursor::new(&mut buffer);
+
+        write_vectors_typed(&mut cursor, &vectors, VectorDtype::F16).unwrap();
+
+        cursor.set_position(0);
+        let read_back = read_vectors_typed(&mut cursor).unwrap();
+
+        prop_assert_eq!(vectors.len(), read_back.len());
+        for (original, read) in vectors.iter().zip(read_back.iter()) {
+            for (&orig_val, &read_val) in original.iter().zip(read.iter()) {
+                // f16 has ~3 decimal digits of precision; tolerate proportional error.
+                let tolerance = (orig_val.abs() * 1e-2).max(1e-2);
+                prop_assert!((orig_val - read_val).abs() <= tolerance,
+                    "f16 round-trip {} vs {} exceeds tolerance {}", orig_val, read_val, tolerance);
+            }
+        }
+    }
+
+    /// Test that the i8 dtype round-trips within one unit of quantization error
+    #[test]
+    fn test_i8_round_trip_within_tolerance(
+        vectors in arb_vectors_with_dims(8, 16).prop_map(|dataset| {
+            dataset.into_iter()
+                .map(|vector| vector.into_iter().map(|v| v * 100.0).collect())
+                .collect::<Vec<Vector>>()
+        })
+    ) {
+        let mut buffer = Vec::new();
+        let mut cursor = Cursor::new(&mut buffer);
+
+        write_vectors_typed(&mut cursor, &vectors, VectorDtype::I8).unwrap();
+
+        cursor.set_position(0);
+        let read_back = read_vectors_typed(&mut cursor).unwrap();
+
+        for (original, read) in vectors.iter().zip(read_back.iter()) {
+            for (&orig_val, &read_val) in original.iter().zip(read.iter()) {
+                prop_assert!((orig_val.round().clamp(-128.0, 127.0) - read_val).abs() <= f32::EPSILON,
+                    "i8 round-trip {} vs {} should match the clamped/rounded source value", orig_val, read_val);
+            }
+        }
+    }
+
+    /// Test that the u8 dtype round-trips within one unit of quantization error
+    #[test]
+    fn test_u8_round_trip_within_tolerance(
+        vectors in arb_vectors_with_dims(8, 16).prop_map(|dataset| {
+            dataset.into_iter()
+                .map(|vector| vector.into_iter().map(|v| v.abs() * 100.0).collect())
+                .collect::<Vec<Vector>>()
+        })
+    ) {
+        let mut buffer = Vec::new();
+        let mut cursor = Cursor::new(&mut buffer);
+
+        write_vectors_typed(&mut cursor, &vectors, VectorDtype::U8).unwrap();
+
+        cursor.set_position(0);
+        let read_back = read_vectors_typed(&mut cursor).unwrap();
+
+        for (original, read) in vectors.iter().zip(read_back.iter()) {
+            for (&orig_val, &read_val) in original.iter().zip(read.iter()) {
+                prop_assert!((orig_val.round().clamp(0.0, 255.0) - read_val).abs() <= f32::EPSILON,
+                    "u8 round-trip {} vs {} should match the clamped/rounded source value", orig_val, read_val);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,285 @@
+//! HTTP service exposing index build/search over JSON
+//!
+//! The CLI's `build`/`search` flow rebuilds the whole index from scratch on every search,
+//! which is fine for a one-shot command but wasteful for repeated queries against the same
+//! data. This crate keeps one or more named [`DiskVamanaIndex`]es resident in memory and
+//! serves them to many concurrent clients over HTTP, so the (potentially expensive) load
+//! happens once per index instead of once per request.
+//!
+//! Routing and JSON decoding live here, decoupled from the actual HTTP transport (see
+//! [`crate::route`]), so the request-handling logic can be exercised directly in tests
+//! without binding a real socket. [`main`](../fn.main.html) in `src/main.rs` wires this up to
+//! a [`tiny_http::Server`].
+
+#![deny(warnings)]
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+use diskann_core::vectors::VectorId;
+use diskann_impl::{DiskVamanaConfig, DiskVamanaIndex};
+use serde::{Deserialize, Serialize};
+
+/// Body of a `POST /indices/{name}/load` request
+#[derive(Debug, Deserialize)]
+pub struct LoadIndexRequest {
+    /// Path to a file written by [`diskann_impl::VamanaIndex::save`]
+    pub path: String,
+}
+
+/// Response to a successful `POST /indices/{name}/load` request
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LoadIndexResponse {
+    /// The name the index was registered under
+    pub name: String,
+    /// Number of nodes in the loaded graph
+    pub num_nodes: usize,
+    /// Vector dimension of the loaded graph
+    pub dimension: usize,
+}
+
+/// Body of a `POST /indices/{name}/search` request
+#[derive(Debug, Deserialize)]
+pub struct SearchRequest {
+    /// Query vector; must match the index's dimension
+    pub query: Vec<f32>,
+    /// Number of nearest neighbors to return
+    pub k: usize,
+    /// Beam width for the search (larger = better recall, slower)
+    pub beam_width: usize,
+}
+
+/// A single neighbor in a `POST /indices/{name}/search` response
+#[derive(Debug, Serialize, PartialEq)]
+pub struct SearchResultItem {
+    /// Neighbor's vector id
+    pub id: VectorId,
+    /// Distance from the query to this neighbor
+    pub distance: f32,
+}
+
+/// A JSON error body, returned alongside a non-2xx status code
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    /// Human-readable description of what went wrong
+    pub error: String,
+}
+
+/// A request-handling failure, carrying the HTTP status code it maps to
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request body wasn't valid JSON, or failed validation (e.g. a
+    /// query/index dimension mismatch) -- maps to `400 Bad Request`
+    BadRequest(String),
+    /// No index is registered under the requested name -- maps to `404 Not Found`
+    NotFound(String),
+}
+
+impl ApiError {
+    /// The HTTP status code this error maps to
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ApiError::BadRequest(_) => 400,
+            ApiError::NotFound(_) => 404,
+        }
+    }
+
+    /// Render this error as a JSON [`ErrorResponse`] body
+    pub fn to_json(&self) -> Vec<u8> {
+        let message = match self {
+            ApiError::BadRequest(msg) => msg,
+            ApiError::NotFound(msg) => msg,
+        };
+        serde_json::to_vec(&ErrorResponse { error: message.clone() })
+            .expect("ErrorResponse serialization cannot fail")
+    }
+}
+
+/// Resident set of named, disk-backed indices shared across requests
+///
+/// Held behind a [`RwLock`] rather than a `Mutex` since searches (the overwhelmingly common
+/// request) only need read access; only loading a new index under a given name needs to
+/// write.
+#[derive(Default)]
+pub struct ServerState {
+    indices: RwLock<HashMap<String, DiskVamanaIndex>>,
+}
+
+impl ServerState {
+    /// Create an empty server state with no indices loaded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the index file at `body.path` and register it under `name`,
+    /// replacing any existing index with that name
+    pub fn load_index(&self, name: &str, body: &[u8]) -> Result<LoadIndexResponse, ApiError> {
+        let request: LoadIndexRequest = serde_json::from_slice(body)
+            .map_err(|e| ApiError::BadRequest(format!("invalid request body: {}", e)))?;
+
+        let disk_index = DiskVamanaIndex::open(Path::new(&request.path), DiskVamanaConfig::default())
+            .map_err(|e| ApiError::BadRequest(format!("failed to open index file: {}", e)))?;
+
+        let response = LoadIndexResponse {
+            name: name.to_string(),
+            num_nodes: disk_index.size(),
+            dimension: disk_index.dim(),
+        };
+
+        self.indices
+            .write()
+            .expect("index map lock poisoned")
+            .insert(name.to_string(), disk_index);
+
+        Ok(response)
+    }
+
+    /// Search the index registered under `name` for `body.query`'s nearest neighbors
+    pub fn search_index(&self, name: &str, body: &[u8]) -> Result<Vec<SearchResultItem>, ApiError> {
+        let request: SearchRequest = serde_json::from_slice(body)
+            .map_err(|e| ApiError::BadRequest(format!("invalid request body: {}", e)))?;
+
+        let indices = self.indices.read().expect("index map lock poisoned");
+        let index = indices
+            .get(name)
+            .ok_or_else(|| ApiError::NotFound(format!("no index named '{}' is loaded", name)))?;
+
+        if request.query.len() != index.dim() {
+            return Err(ApiError::BadRequest(format!(
+                "query has dimension {}, expected {}",
+                request.query.len(),
+                index.dim()
+            )));
+        }
+
+        let results = index
+            .search(&request.query, request.k, request.beam_width)
+            .map_err(|e| ApiError::BadRequest(format!("search failed: {}", e)))?;
+
+        Ok(results
+            .into_iter()
+            .map(|(id, distance)| SearchResultItem { id, distance })
+            .collect())
+    }
+}
+
+/// An HTTP method, independent of any particular server crate's own type for one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// Any method this service doesn't recognize
+    Other,
+}
+
+/// Route `(method, path)` to the matching handler and render its result as a JSON body plus
+/// status code, so `src/main.rs` only has to translate this to and from its HTTP library's
+/// own request/response types
+pub fn route(state: &ServerState, method: Method, path: &str, body: &[u8]) -> (u16, Vec<u8>) {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        (Method::Post, ["indices", name, "load"]) => match state.load_index(name, body) {
+            Ok(response) => (
+                200,
+                serde_json::to_vec(&response).expect("LoadIndexResponse serialization cannot fail"),
+            ),
+            Err(err) => (err.status_code(), err.to_json()),
+        },
+        (Method::Post, ["indices", name, "search"]) => match state.search_index(name, body) {
+            Ok(results) => (
+                200,
+                serde_json::to_vec(&results).expect("search results serialization cannot fail"),
+            ),
+            Err(err) => (err.status_code(), err.to_json()),
+        },
+        _ => (
+            404,
+            ApiError::NotFound(format!("no such route: {:?} {}", method, path)).to_json(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diskann_impl::IndexBuilder;
+    use diskann_traits::distance::EuclideanDistance;
+    use tempfile::NamedTempFile;
+
+    fn sample_index_path() -> NamedTempFile {
+        let index = IndexBuilder::new(EuclideanDistance)
+            .max_degree(4)
+            .search_list_size(8)
+            .seed(42)
+            .build(vec![
+                (0, vec![0.0, 0.0]),
+                (1, vec![1.0, 0.0]),
+                (2, vec![0.0, 1.0]),
+                (3, vec![1.0, 1.0]),
+            ])
+            .unwrap();
+
+        let file = NamedTempFile::new().unwrap();
+        index.save(file.path()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_load_then_search_round_trip() {
+        let state = ServerState::new();
+        let file = sample_index_path();
+
+        let load_body = serde_json::to_vec(&LoadIndexRequest { path: file.path().to_str().unwrap().to_string() }).unwrap();
+        let (status, body) = route(&state, Method::Post, "/indices/demo/load", &load_body);
+        assert_eq!(status, 200);
+        let loaded: LoadIndexResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(loaded.num_nodes, 4);
+        assert_eq!(loaded.dimension, 2);
+
+        let search_body = serde_json::to_vec(&SearchRequest { query: vec![0.1, 0.1], k: 2, beam_width: 8 }).unwrap();
+        let (status, body) = route(&state, Method::Post, "/indices/demo/search", &search_body);
+        assert_eq!(status, 200);
+        let results: Vec<SearchResultItem> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, 0);
+    }
+
+    #[test]
+    fn test_search_unknown_index_returns_404() {
+        let state = ServerState::new();
+        let search_body = serde_json::to_vec(&SearchRequest { query: vec![0.0], k: 1, beam_width: 4 }).unwrap();
+        let (status, _) = route(&state, Method::Post, "/indices/missing/search", &search_body);
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn test_search_dimension_mismatch_returns_400() {
+        let state = ServerState::new();
+        let file = sample_index_path();
+        let load_body = serde_json::to_vec(&LoadIndexRequest { path: file.path().to_str().unwrap().to_string() }).unwrap();
+        route(&state, Method::Post, "/indices/demo/load", &load_body);
+
+        let search_body = serde_json::to_vec(&SearchRequest { query: vec![0.1, 0.1, 0.1], k: 1, beam_width: 4 }).unwrap();
+        let (status, _) = route(&state, Method::Post, "/indices/demo/search", &search_body);
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn test_load_missing_file_returns_400() {
+        let state = ServerState::new();
+        let load_body = serde_json::to_vec(&LoadIndexRequest { path: "/no/such/file.bin".to_string() }).unwrap();
+        let (status, _) = route(&state, Method::Post, "/indices/demo/load", &load_body);
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn test_unknown_route_returns_404() {
+        let state = ServerState::new();
+        let (status, _) = route(&state, Method::Get, "/healthz", &[]);
+        assert_eq!(status, 404);
+    }
+}
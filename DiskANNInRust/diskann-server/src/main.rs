@@ -0,0 +1,69 @@
+//! `diskann-server` binary: serves [`diskann_server`]'s routes over a blocking
+//! `tiny_http` listener
+//!
+//! One thread per connection, matching the rest of this codebase's synchronous style rather
+//! than pulling in an async runtime this service doesn't otherwise need (see
+//! `diskann-demo/src/remote_model.rs` for the same call on the client side, with `ureq`).
+
+#![deny(warnings)]
+
+use std::io::Read;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use tiny_http::{Method as HttpMethod, Response, Server};
+use tracing::info;
+
+use diskann_server::{route, Method, ServerState};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: String,
+}
+
+fn to_method(method: &HttpMethod) -> Method {
+    match method {
+        HttpMethod::Get => Method::Get,
+        HttpMethod::Post => Method::Post,
+        _ => Method::Other,
+    }
+}
+
+fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let cli = Cli::parse();
+
+    let server = Server::http(&cli.listen)
+        .map_err(|e| anyhow::anyhow!("failed to bind {}: {}", cli.listen, e))
+        .context("Failed to start HTTP server")?;
+    info!("diskann-server listening on {}", cli.listen);
+
+    let state = Arc::new(ServerState::new());
+
+    for mut request in server.incoming_requests() {
+        let state = Arc::clone(&state);
+        std::thread::spawn(move || {
+            let method = to_method(request.method());
+            let path = request.url().to_string();
+
+            let mut body = Vec::new();
+            if let Err(e) = request.as_reader().read_to_end(&mut body) {
+                let response = Response::from_string(format!("{{\"error\":\"failed to read request body: {}\"}}", e))
+                    .with_status_code(400);
+                let _ = request.respond(response);
+                return;
+            }
+
+            let (status, response_body) = route(&state, method, &path, &body);
+            let response = Response::from_data(response_body).with_status_code(status);
+            let _ = request.respond(response);
+        });
+    }
+
+    Ok(())
+}
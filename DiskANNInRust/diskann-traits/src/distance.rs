@@ -1,31 +1,242 @@
 //! Distance computation traits
 
-use diskann_core::math::{dot_product, l2_norm_squared};
+use diskann_core::math::{dot_product, hamming_distance, l2_norm_squared};
+use diskann_core::simd;
+
+/// A strongly-typed metric result, distinguishing a distance from a
+/// similarity
+///
+/// Every [`Distance::distance`] implementation returns a bare `f32`, which
+/// is ambiguous: cosine returns `1 - similarity` while a caller may actually
+/// want the similarity, and search code can accidentally sort the wrong
+/// direction. `MetricResult` tags the scalar with what it represents, so
+/// e.g. max-inner-product search gets a [`MetricResult::DotProduct`] that
+/// can't be confused with a distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetricResult {
+    /// A Euclidean (L2) distance; smaller is closer
+    L2(f32),
+    /// A raw cosine similarity in `[-1, 1]`; larger is closer
+    CosineSimilarity(f32),
+    /// A cosine distance (`1 - similarity`); smaller is closer
+    CosineDistance(f32),
+    /// A raw dot product; larger is closer
+    DotProduct(f32),
+    /// A generic "smaller is closer" scalar with no specific metric
+    /// semantics attached -- the default [`Distance::measure`] wraps
+    /// [`Distance::distance`]'s output this way
+    Raw(f32),
+}
+
+impl MetricResult {
+    /// Normalize this result into a "smaller is closer" scalar suitable for
+    /// ordering candidates in a min-heap / priority queue, regardless of
+    /// which variant it is
+    pub fn as_sortable_distance(&self) -> f32 {
+        match *self {
+            MetricResult::L2(d) => d,
+            MetricResult::CosineSimilarity(s) => 1.0 - s,
+            MetricResult::CosineDistance(d) => d,
+            MetricResult::DotProduct(p) => -p,
+            MetricResult::Raw(d) => d,
+        }
+    }
+
+    /// The similarity this result represents, where larger is more similar
+    ///
+    /// Not meaningful for [`MetricResult::L2`]/[`MetricResult::Raw`], which
+    /// have no natural similarity scale; they're negated so "closer" still
+    /// means "larger" for consistency.
+    pub fn similarity(&self) -> f32 {
+        match *self {
+            MetricResult::L2(d) => -d,
+            MetricResult::CosineSimilarity(s) => s,
+            MetricResult::CosineDistance(d) => 1.0 - d,
+            MetricResult::DotProduct(p) => p,
+            MetricResult::Raw(d) => -d,
+        }
+    }
+
+    /// The distance this result represents, where smaller is closer
+    ///
+    /// An alias for [`Self::as_sortable_distance`].
+    pub fn distance(&self) -> f32 {
+        self.as_sortable_distance()
+    }
+}
+
+/// An ordering-friendly nearness value for neighbor-candidate comparisons
+///
+/// Greedy/beam search only ever need to know which of two candidates is
+/// closer, not the distance itself, until a result is actually returned to
+/// the caller. Euclidean's natural distance needs a `sqrt()` to get there,
+/// but `sqrt` is monotonic for non-negative inputs, so comparing squared
+/// distances gives the same ordering without paying for it on every
+/// candidate comparison. `NearnessValue` carries either form and orders by
+/// comparing the stored scalar directly; call [`Self::into_distance`] only
+/// once a result is actually returned, not during the search itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NearnessValue {
+    /// A squared Euclidean distance; smaller is closer. [`Self::into_distance`]
+    /// recovers the true distance with a `sqrt()`.
+    SquaredL2(f32),
+    /// A value that's already monotonic with distance and needs no further
+    /// transform -- cosine's `1 - cos`, inner product's `1 - dot`,
+    /// Manhattan, and anything else [`Distance::distance`] returns directly.
+    Monotonic(f32),
+}
+
+impl NearnessValue {
+    /// Recover the true distance, computing a `sqrt()` only if this value is
+    /// a [`Self::SquaredL2`]
+    pub fn into_distance(self) -> f32 {
+        match self {
+            NearnessValue::SquaredL2(sq) => sq.sqrt(),
+            NearnessValue::Monotonic(d) => d,
+        }
+    }
+
+    fn ordering_key(&self) -> f32 {
+        match *self {
+            NearnessValue::SquaredL2(sq) => sq,
+            NearnessValue::Monotonic(d) => d,
+        }
+    }
+}
+
+impl PartialOrd for NearnessValue {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.ordering_key().partial_cmp(&other.ordering_key())
+    }
+}
+
+// Distances are never expected to be NaN in practice, so -- matching the
+// existing candidate-ordering convention in `diskann-impl` -- this treats
+// the partial order as total, falling back to `Equal` if it ever isn't.
+impl Eq for NearnessValue {}
+
+impl Ord for NearnessValue {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.partial_cmp(other).unwrap_or(core::cmp::Ordering::Equal)
+    }
+}
 
 /// Trait for computing distances between vectors
-/// 
+///
 /// This trait provides a generic interface for different distance metrics
 /// used in nearest neighbor search algorithms.
 pub trait Distance<T> {
     /// Compute distance between two vectors
-    /// 
+    ///
     /// # Arguments
     /// * `a` - First vector
     /// * `b` - Second vector
-    /// 
+    ///
     /// # Returns
     /// The distance between the vectors as a non-negative f32 value
     fn distance(&self, a: &[T], b: &[T]) -> f32;
-    
+
     /// Get the name of this distance metric
     fn name(&self) -> &'static str;
-    
+
     /// Check if this distance metric satisfies the triangle inequality
     fn is_metric(&self) -> bool {
         true
     }
+
+    /// Whether this metric assumes its inputs are already unit-normalized
+    ///
+    /// Cosine and inner-product metrics both divide out (or are defined in
+    /// terms of) a vector's norm; if a caller has already normalized every
+    /// vector at insertion time, that division is redundant work on every
+    /// comparison. Defaults to `false` -- no [`Distance`] impl in this crate
+    /// assumes pre-normalized input today, so none currently need to
+    /// override it, but index/search code that maintains its own
+    /// normalization invariant can implement a wrapper [`Distance`] that
+    /// returns `true` here and skip re-deriving the norm downstream.
+    fn is_pre_normalized(&self) -> bool {
+        false
+    }
+
+    /// Compute this metric's result as a strongly-typed [`MetricResult`]
+    ///
+    /// Defaults to wrapping [`Self::distance`]'s output as
+    /// [`MetricResult::Raw`]. Metrics whose `distance()` output could be
+    /// confused with something else it isn't -- cosine (distance vs
+    /// similarity), inner product (distance vs raw dot product) -- override
+    /// this to return the metric-specific variant instead.
+    fn measure(&self, a: &[T], b: &[T]) -> MetricResult {
+        MetricResult::Raw(self.distance(a, b))
+    }
+
+    /// Compute this metric's nearness as an ordering-friendly [`NearnessValue`]
+    ///
+    /// Neighbor-selection and priority-queue code should call this instead
+    /// of [`Self::distance`] when all it needs is a comparison, so Euclidean
+    /// candidates can skip the `sqrt()` until a result is actually returned.
+    /// Defaults to wrapping [`Self::distance`] as [`NearnessValue::Monotonic`];
+    /// override this only for metrics with a cheaper-than-`distance()` way
+    /// to produce an equivalently-ordered value (see [`EuclideanDistance`]).
+    fn partial_nearness(&self, a: &[T], b: &[T]) -> NearnessValue {
+        NearnessValue::Monotonic(self.distance(a, b))
+    }
+
+    /// Compute this metric restricted to the first `dims` dimensions of each
+    /// vector
+    ///
+    /// Lets pruning code bound a candidate's distance without touching its
+    /// remaining dimensions -- e.g. bailing out of a comparison once a
+    /// running partial sum over a prefix already exceeds the worst
+    /// candidate kept so far, without paying for the rest of the vector.
+    /// `dims` is clamped to both slices' lengths rather than panicking, so
+    /// callers can pass a fixed prefix length across vectors of varying
+    /// size. Defaults to calling [`Self::distance`] on the truncated
+    /// slices; override only if a metric has a cheaper way to compute a
+    /// distance-consistent partial value.
+    fn partial_distance(&self, a: &[T], b: &[T], dims: usize) -> f32 {
+        let dims = dims.min(a.len()).min(b.len());
+        self.distance(&a[..dims], &b[..dims])
+    }
 }
 
+/// A possibly-asymmetric proximity measure between a query and a stored item
+///
+/// [`Distance<T>`] bakes in two assumptions that don't hold for every useful
+/// measure: that both arguments share one element type, and that the result
+/// is symmetric. Neither holds for max-inner-product search (there's no
+/// natural "distance", only a score where larger is closer) or for querying
+/// quantized items with an unquantized query vector (`K` and `V` differ).
+/// `Proximity<K, V>` drops both assumptions; every [`Distance<T>`] impl gets
+/// one for free via the blanket impl below, so existing metrics don't need
+/// to be rewritten to participate.
+pub trait Proximity<K, V = K> {
+    /// Compute this measure's result as a strongly-typed [`MetricResult`]
+    fn proximity(&self, query: &[K], item: &[V]) -> MetricResult;
+
+    /// Get the name of this proximity measure
+    fn name(&self) -> &'static str;
+}
+
+impl<T, D: Distance<T>> Proximity<T, T> for D {
+    fn proximity(&self, query: &[T], item: &[T]) -> MetricResult {
+        self.measure(query, item)
+    }
+
+    fn name(&self) -> &'static str {
+        Distance::name(self)
+    }
+}
+
+/// Marker for a [`Proximity`] that is additionally a true metric: symmetric,
+/// and satisfying the triangle inequality
+///
+/// This is the `Proximity<T, T>` counterpart to [`Distance::is_metric`]
+/// returning `true` -- implemented for [`EuclideanDistance`],
+/// [`ManhattanDistance`], and [`MinkowskiDistance`], but not for
+/// [`CosineDistance`]/[`InnerProductDistance`]/[`DotProduct`], none of which
+/// satisfy the triangle inequality.
+pub trait Metric<T>: Proximity<T, T> {}
+
 /// Trait for squared distance computation (avoids sqrt for efficiency)
 pub trait SquaredDistance<T> {
     /// Compute squared distance between two vectors
@@ -40,10 +251,18 @@ impl Distance<f32> for EuclideanDistance {
     fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
         self.squared_distance(a, b).sqrt()
     }
-    
+
     fn name(&self) -> &'static str {
         "euclidean"
     }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::L2(self.distance(a, b))
+    }
+
+    fn partial_nearness(&self, a: &[f32], b: &[f32]) -> NearnessValue {
+        NearnessValue::SquaredL2(self.squared_distance(a, b))
+    }
 }
 
 impl SquaredDistance<f32> for EuclideanDistance {
@@ -65,10 +284,18 @@ impl Distance<f64> for EuclideanDistance {
     fn distance(&self, a: &[f64], b: &[f64]) -> f32 {
         self.squared_distance(a, b).sqrt()
     }
-    
+
     fn name(&self) -> &'static str {
         "euclidean"
     }
+
+    fn measure(&self, a: &[f64], b: &[f64]) -> MetricResult {
+        MetricResult::L2(self.distance(a, b))
+    }
+
+    fn partial_nearness(&self, a: &[f64], b: &[f64]) -> NearnessValue {
+        NearnessValue::SquaredL2(self.squared_distance(a, b))
+    }
 }
 
 impl SquaredDistance<f64> for EuclideanDistance {
@@ -86,6 +313,9 @@ impl SquaredDistance<f64> for EuclideanDistance {
     }
 }
 
+impl Metric<f32> for EuclideanDistance {}
+impl Metric<f64> for EuclideanDistance {}
+
 /// Manhattan (L1) distance implementation
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ManhattanDistance;
@@ -126,6 +356,9 @@ impl Distance<f64> for ManhattanDistance {
     }
 }
 
+impl Metric<f32> for ManhattanDistance {}
+impl Metric<f64> for ManhattanDistance {}
+
 /// Cosine distance implementation
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CosineDistance;
@@ -155,14 +388,18 @@ impl Distance<f32> for CosineDistance {
         
         1.0 - cosine_sim
     }
-    
+
     fn name(&self) -> &'static str {
         "cosine"
     }
-    
+
     fn is_metric(&self) -> bool {
         false // Cosine distance doesn't satisfy triangle inequality
     }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::CosineDistance(self.distance(a, b))
+    }
 }
 
 impl Distance<f64> for CosineDistance {
@@ -190,14 +427,410 @@ impl Distance<f64> for CosineDistance {
         
         (1.0 - cosine_sim) as f32
     }
-    
+
     fn name(&self) -> &'static str {
         "cosine"
     }
-    
+
     fn is_metric(&self) -> bool {
         false // Cosine distance doesn't satisfy triangle inequality
     }
+
+    fn measure(&self, a: &[f64], b: &[f64]) -> MetricResult {
+        MetricResult::CosineDistance(self.distance(a, b))
+    }
+}
+
+/// Inner product ("MIPS") distance implementation
+///
+/// Defined as `1.0 - dot_product(a, b)` so that vectors pointing in the same
+/// direction (larger dot product) are treated as closer, matching upstream
+/// DiskANN's inner-product metric.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InnerProductDistance;
+
+impl Distance<f32> for InnerProductDistance {
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        1.0 - dot_product(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        "inner_product"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Inner product doesn't satisfy triangle inequality
+    }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::DotProduct(dot_product(a, b))
+    }
+}
+
+impl Distance<f64> for InnerProductDistance {
+    fn distance(&self, a: &[f64], b: &[f64]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        (1.0 - dot_product(a, b)) as f32
+    }
+
+    fn name(&self) -> &'static str {
+        "inner_product"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Inner product doesn't satisfy triangle inequality
+    }
+
+    fn measure(&self, a: &[f64], b: &[f64]) -> MetricResult {
+        MetricResult::DotProduct(dot_product(a, b) as f32)
+    }
+}
+
+/// SIMD-accelerated Euclidean (L2) distance
+///
+/// Dispatches to [`diskann_core::simd::l2_squared_distance_dispatch`], which
+/// picks an AVX2 or portable-SIMD kernel behind the `simd` feature (falling
+/// back to the scalar loop when the feature is off or the CPU lacks the
+/// instructions). `IndexBuilder::new` and friends are generic over
+/// `D: Distance<f32>`, so construction and search get the speedup just by
+/// passing `SimdEuclidean` instead of [`EuclideanDistance`] — no other API
+/// change is needed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimdEuclidean;
+
+impl Distance<f32> for SimdEuclidean {
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        simd::l2_squared_distance_dispatch(a, b).sqrt()
+    }
+
+    fn name(&self) -> &'static str {
+        "simd_euclidean"
+    }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::L2(self.distance(a, b))
+    }
+
+    fn partial_nearness(&self, a: &[f32], b: &[f32]) -> NearnessValue {
+        NearnessValue::SquaredL2(self.squared_distance(a, b))
+    }
+}
+
+impl SquaredDistance<f32> for SimdEuclidean {
+    fn squared_distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        simd::l2_squared_distance_dispatch(a, b)
+    }
+}
+
+/// SIMD-accelerated inner-product ("MIPS") distance
+///
+/// Dispatches to [`diskann_core::simd::inner_product_distance_dispatch`]; see
+/// [`SimdEuclidean`] for the dispatch/fallback story. Defined as
+/// `1.0 - dot_product(a, b)`, matching [`InnerProductDistance`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimdInnerProduct;
+
+impl Distance<f32> for SimdInnerProduct {
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        simd::inner_product_distance_dispatch(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        "simd_inner_product"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Inner product doesn't satisfy triangle inequality
+    }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::DotProduct(1.0 - self.distance(a, b))
+    }
+}
+
+/// SIMD-accelerated cosine distance
+///
+/// Dispatches to [`diskann_core::simd::cosine_distance_dispatch`]; see
+/// [`SimdEuclidean`] for the dispatch/fallback story. Defined as
+/// `1 - dot(a, b) / (|a| * |b|)`, matching [`CosineDistance`], including the
+/// zero-vector convention of returning a distance of `1.0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimdCosine;
+
+impl Distance<f32> for SimdCosine {
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        simd::cosine_distance_dispatch(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        "simd_cosine"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Cosine distance doesn't satisfy the triangle inequality
+    }
+
+    fn measure(&self, a: &[f32], b: &[f32]) -> MetricResult {
+        MetricResult::CosineDistance(self.distance(a, b))
+    }
+}
+
+/// SIMD-accelerated Manhattan (L1) distance
+///
+/// Dispatches to [`diskann_core::simd::l1_distance_dispatch`]; see
+/// [`SimdEuclidean`] for the dispatch/fallback story. Defined as
+/// `Σ |aᵢ - bᵢ|`, matching [`ManhattanDistance`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimdManhattan;
+
+impl Distance<f32> for SimdManhattan {
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        simd::l1_distance_dispatch(a, b)
+    }
+
+    fn name(&self) -> &'static str {
+        "simd_manhattan"
+    }
+}
+
+/// Dot product of two 8-bit quantized vectors, accumulated in a widened
+/// `i64` so the sum can't overflow `u8`'s native range even for
+/// high-dimensional vectors (e.g. 1536 dims at 255 * 255 each is already
+/// ~100M, well past `i16`)
+pub fn quantized_dot_product_u8(a: &[u8], b: &[u8]) -> i64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| x as i64 * y as i64)
+        .sum()
+}
+
+/// Dot product of two 8-bit quantized vectors (signed), accumulated in a
+/// widened `i64`; see [`quantized_dot_product_u8`]
+pub fn quantized_dot_product_i8(a: &[i8], b: &[i8]) -> i64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(&x, &y)| x as i64 * y as i64)
+        .sum()
+}
+
+/// Squared magnitude (`Σ xᵢ²`) of a quantized vector
+///
+/// Cache the result of this once per vector at insertion time rather than
+/// recomputing it on every comparison, then pass it to
+/// [`cosine_similarity_from_dot_product`].
+pub fn quantized_magnitude_squared_u8(a: &[u8]) -> u64 {
+    a.iter().map(|&x| (x as u64) * (x as u64)).sum()
+}
+
+/// Squared magnitude (`Σ xᵢ²`) of a quantized vector (signed); see
+/// [`quantized_magnitude_squared_u8`]
+pub fn quantized_magnitude_squared_i8(a: &[i8]) -> u64 {
+    a.iter().map(|&x| (x as i64 * x as i64) as u64).sum()
+}
+
+/// Cosine similarity from a precomputed dot product and precomputed squared
+/// magnitudes
+///
+/// Lets a caller that's already caching each vector's
+/// [`quantized_magnitude_squared_u8`]/[`quantized_magnitude_squared_i8`] at
+/// insertion time skip recomputing it on every comparison -- only the dot
+/// product (which necessarily depends on both vectors) needs to be computed
+/// per call. Returns `0.0`, matching an orthogonal-vectors' similarity, if
+/// either magnitude is zero, to avoid dividing by zero.
+pub fn cosine_similarity_from_dot_product(dot: i64, mag_a: u64, mag_b: u64) -> f32 {
+    if mag_a == 0 || mag_b == 0 {
+        return 0.0;
+    }
+
+    let sim = dot as f32 / ((mag_a as f32).sqrt() * (mag_b as f32).sqrt());
+    sim.max(-1.0).min(1.0)
+}
+
+/// Euclidean (L2) distance over 8-bit quantized vectors
+///
+/// Quantized values are assumed to represent the original f32 vector scaled
+/// by `1 / scale` at quantization time (i.e. `original ≈ quantized as f32 *
+/// scale`), so the integer squared-distance accumulator is dequantized back
+/// to original-vector units by multiplying by `scale * scale`. Accumulates
+/// in a widened `i64` (see [`quantized_dot_product_u8`]) to avoid
+/// overflowing `u8`/`i8`'s native range.
+#[derive(Debug, Clone, Copy)]
+pub struct QuantizedEuclideanDistance {
+    /// Dequantization scale factor: `original ≈ quantized as f32 * scale`
+    pub scale: f32,
+}
+
+impl QuantizedEuclideanDistance {
+    /// Create a quantized Euclidean distance with the given dequantization
+    /// scale factor
+    pub fn new(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
+impl Distance<u8> for QuantizedEuclideanDistance {
+    fn distance(&self, a: &[u8], b: &[u8]) -> f32 {
+        self.squared_distance(a, b).sqrt()
+    }
+
+    fn name(&self) -> &'static str {
+        "quantized_euclidean_u8"
+    }
+
+    fn measure(&self, a: &[u8], b: &[u8]) -> MetricResult {
+        MetricResult::L2(self.distance(a, b))
+    }
+
+    fn partial_nearness(&self, a: &[u8], b: &[u8]) -> NearnessValue {
+        NearnessValue::SquaredL2(self.squared_distance(a, b))
+    }
+}
+
+impl SquaredDistance<u8> for QuantizedEuclideanDistance {
+    fn squared_distance(&self, a: &[u8], b: &[u8]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        let sum: i64 = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&x, &y)| {
+                let diff = x as i64 - y as i64;
+                diff * diff
+            })
+            .sum();
+
+        sum as f32 * self.scale * self.scale
+    }
+}
+
+impl Distance<i8> for QuantizedEuclideanDistance {
+    fn distance(&self, a: &[i8], b: &[i8]) -> f32 {
+        self.squared_distance(a, b).sqrt()
+    }
+
+    fn name(&self) -> &'static str {
+        "quantized_euclidean_i8"
+    }
+
+    fn measure(&self, a: &[i8], b: &[i8]) -> MetricResult {
+        MetricResult::L2(self.distance(a, b))
+    }
+
+    fn partial_nearness(&self, a: &[i8], b: &[i8]) -> NearnessValue {
+        NearnessValue::SquaredL2(self.squared_distance(a, b))
+    }
+}
+
+impl SquaredDistance<i8> for QuantizedEuclideanDistance {
+    fn squared_distance(&self, a: &[i8], b: &[i8]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        let sum: i64 = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&x, &y)| {
+                let diff = x as i64 - y as i64;
+                diff * diff
+            })
+            .sum();
+
+        sum as f32 * self.scale * self.scale
+    }
+}
+
+/// Cosine distance over 8-bit quantized vectors
+///
+/// Recomputes both vectors' squared magnitudes on every call via
+/// [`quantized_magnitude_squared_u8`]/[`quantized_magnitude_squared_i8`]. For
+/// a hot loop comparing the same database vectors repeatedly, call
+/// [`quantized_dot_product_u8`]/[`quantized_magnitude_squared_u8`] and
+/// [`cosine_similarity_from_dot_product`] directly instead, caching each
+/// vector's squared magnitude once at insertion time rather than going
+/// through this `Distance` impl.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuantizedCosineDistance;
+
+impl Distance<u8> for QuantizedCosineDistance {
+    fn distance(&self, a: &[u8], b: &[u8]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        let dot = quantized_dot_product_u8(a, b);
+        let mag_a = quantized_magnitude_squared_u8(a);
+        let mag_b = quantized_magnitude_squared_u8(b);
+
+        1.0 - cosine_similarity_from_dot_product(dot, mag_a, mag_b)
+    }
+
+    fn name(&self) -> &'static str {
+        "quantized_cosine_u8"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Cosine distance doesn't satisfy the triangle inequality
+    }
+
+    fn measure(&self, a: &[u8], b: &[u8]) -> MetricResult {
+        MetricResult::CosineDistance(self.distance(a, b))
+    }
+}
+
+impl Distance<i8> for QuantizedCosineDistance {
+    fn distance(&self, a: &[i8], b: &[i8]) -> f32 {
+        if a.len() != b.len() {
+            return f32::INFINITY;
+        }
+
+        let dot = quantized_dot_product_i8(a, b);
+        let mag_a = quantized_magnitude_squared_i8(a);
+        let mag_b = quantized_magnitude_squared_i8(b);
+
+        1.0 - cosine_similarity_from_dot_product(dot, mag_a, mag_b)
+    }
+
+    fn name(&self) -> &'static str {
+        "quantized_cosine_i8"
+    }
+
+    fn is_metric(&self) -> bool {
+        false // Cosine distance doesn't satisfy the triangle inequality
+    }
+
+    fn measure(&self, a: &[i8], b: &[i8]) -> MetricResult {
+        MetricResult::CosineDistance(self.distance(a, b))
+    }
 }
 
 /// Minkowski distance implementation with configurable p-norm
@@ -274,6 +907,93 @@ impl Distance<f64> for MinkowskiDistance {
     }
 }
 
+impl Metric<f32> for MinkowskiDistance {}
+impl Metric<f64> for MinkowskiDistance {}
+
+/// Raw dot-product proximity ("max inner product search"), usable either
+/// between same-typed vectors or between an f32 query and `u8`-quantized
+/// database items
+///
+/// Unlike [`InnerProductDistance`], which negates the dot product into a
+/// small-is-closer `f32` so it can go through [`Distance<T>`], `DotProduct`
+/// is [`Proximity`]-only: larger is closer, with no [`Distance<T>`] bridge
+/// to fight with. It isn't a [`Metric`] -- the triangle inequality doesn't
+/// hold for a raw dot product any more than it does for
+/// [`InnerProductDistance`].
+#[derive(Debug, Clone, Copy)]
+pub struct DotProduct {
+    /// Dequantization scale applied to `u8` items: `original ≈ item as f32 *
+    /// scale`. Unused by the same-type `f32` impl.
+    pub scale: f32,
+}
+
+impl DotProduct {
+    /// Create a dot product proximity for same-typed (unquantized) vectors
+    pub fn new() -> Self {
+        Self { scale: 1.0 }
+    }
+
+    /// Create a dot product proximity for querying `u8`-quantized items with
+    /// the given dequantization scale (see [`QuantizedEuclideanDistance`])
+    pub fn with_scale(scale: f32) -> Self {
+        Self { scale }
+    }
+}
+
+impl Default for DotProduct {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Proximity<f32, f32> for DotProduct {
+    fn proximity(&self, query: &[f32], item: &[f32]) -> MetricResult {
+        MetricResult::DotProduct(dot_product(query, item))
+    }
+
+    fn name(&self) -> &'static str {
+        "dot_product"
+    }
+}
+
+impl Proximity<f32, u8> for DotProduct {
+    fn proximity(&self, query: &[f32], item: &[u8]) -> MetricResult {
+        let dot: f32 = query
+            .iter()
+            .zip(item.iter())
+            .map(|(&q, &v)| q * (v as f32 * self.scale))
+            .sum();
+        MetricResult::DotProduct(dot)
+    }
+
+    fn name(&self) -> &'static str {
+        "dot_product_quantized_u8"
+    }
+}
+
+/// Hamming distance between bit-packed vectors
+///
+/// Each `u8` holds 8 bits of the vector (see [`diskann_core::math::hamming_distance`]),
+/// matching upstream DiskANN's binary-vector support. Unlike the other metrics in this
+/// module, `DiskVamanaIndex`'s on-disk node records are always `f32`, so a `HammingDistance`
+/// index can be built and searched in memory via [`crate::distance`]'s usual
+/// `Distance<u8>` surface but can't yet be persisted through
+/// `VamanaIndex::save`/`DiskVamanaIndex::open`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HammingDistance;
+
+impl Distance<u8> for HammingDistance {
+    fn distance(&self, a: &[u8], b: &[u8]) -> f32 {
+        hamming_distance(a, b) as f32
+    }
+
+    fn name(&self) -> &'static str {
+        "hamming"
+    }
+}
+
+impl Metric<u8> for HammingDistance {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -406,6 +1126,263 @@ mod tests {
         assert!(parallel_distance < 1e-6);
     }
 
+    #[test]
+    fn test_inner_product_distance_f32() {
+        let ip = InnerProductDistance;
+
+        let a = vec![1.0f32, 0.0];
+        let b = vec![1.0f32, 0.0];
+
+        // Identical unit vectors have an inner product of 1, so distance 0
+        let distance = ip.distance(&a, &b);
+        assert!(distance < 1e-6);
+
+        let c = vec![0.0f32, 1.0];
+        // Orthogonal unit vectors have an inner product of 0, so distance 1
+        let distance = ip.distance(&a, &c);
+        assert!((distance - 1.0).abs() < 1e-6);
+
+        assert!(!ip.is_metric());
+    }
+
+    #[test]
+    fn test_metric_result_as_sortable_distance() {
+        assert_eq!(MetricResult::L2(3.0).as_sortable_distance(), 3.0);
+        assert_eq!(MetricResult::CosineSimilarity(0.8).as_sortable_distance(), 0.2);
+        assert_eq!(MetricResult::CosineDistance(0.2).as_sortable_distance(), 0.2);
+        assert_eq!(MetricResult::DotProduct(5.0).as_sortable_distance(), -5.0);
+        assert_eq!(MetricResult::Raw(1.5).as_sortable_distance(), 1.5);
+    }
+
+    #[test]
+    fn test_metric_result_similarity() {
+        assert_eq!(MetricResult::L2(3.0).similarity(), -3.0);
+        assert_eq!(MetricResult::CosineSimilarity(0.8).similarity(), 0.8);
+        assert_eq!(MetricResult::CosineDistance(0.2).similarity(), 0.8);
+        assert_eq!(MetricResult::DotProduct(5.0).similarity(), 5.0);
+        assert_eq!(MetricResult::Raw(1.5).similarity(), -1.5);
+    }
+
+    #[test]
+    fn test_metric_result_distance_is_alias_for_sortable_distance() {
+        let result = MetricResult::CosineDistance(0.3);
+        assert_eq!(result.distance(), result.as_sortable_distance());
+    }
+
+    #[test]
+    fn test_euclidean_measure_returns_l2() {
+        let euclidean = EuclideanDistance;
+        let a = vec![0.0f32, 0.0];
+        let b = vec![3.0f32, 4.0];
+
+        match euclidean.measure(&a, &b) {
+            MetricResult::L2(d) => assert!((d - 5.0).abs() < 1e-6),
+            other => panic!("expected MetricResult::L2, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cosine_measure_returns_cosine_distance() {
+        let cosine = CosineDistance;
+        let a = vec![1.0f32, 0.0];
+        let b = vec![0.0f32, 1.0];
+
+        match cosine.measure(&a, &b) {
+            MetricResult::CosineDistance(d) => assert!((d - 1.0).abs() < 1e-6),
+            other => panic!("expected MetricResult::CosineDistance, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_inner_product_measure_returns_raw_dot_product() {
+        let ip = InnerProductDistance;
+        let a = vec![1.0f32, 0.0];
+        let b = vec![0.0f32, 1.0];
+
+        // Orthogonal unit vectors: dot product is 0, but distance() is 1.0 --
+        // measure() should carry the raw dot product, not the distance form.
+        match ip.measure(&a, &b) {
+            MetricResult::DotProduct(p) => assert!(p.abs() < 1e-6),
+            other => panic!("expected MetricResult::DotProduct, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_nearness_value_orders_by_stored_scalar() {
+        assert!(NearnessValue::SquaredL2(4.0) < NearnessValue::SquaredL2(9.0));
+        assert!(NearnessValue::Monotonic(0.1) < NearnessValue::Monotonic(0.2));
+    }
+
+    #[test]
+    fn test_nearness_value_into_distance() {
+        assert_eq!(NearnessValue::SquaredL2(9.0).into_distance(), 3.0);
+        assert_eq!(NearnessValue::Monotonic(0.5).into_distance(), 0.5);
+    }
+
+    #[test]
+    fn test_euclidean_partial_nearness_matches_distance_ordering() {
+        let euclidean = EuclideanDistance;
+        let query = vec![0.0f32, 0.0];
+        let near = vec![1.0f32, 1.0];
+        let far = vec![5.0f32, 5.0];
+
+        let nearness_near = euclidean.partial_nearness(&query, &near);
+        let nearness_far = euclidean.partial_nearness(&query, &far);
+        assert!(nearness_near < nearness_far);
+
+        match nearness_near {
+            NearnessValue::SquaredL2(sq) => {
+                assert!((sq.sqrt() - euclidean.distance(&query, &near)).abs() < 1e-6);
+            }
+            other => panic!("expected NearnessValue::SquaredL2, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cosine_partial_nearness_is_monotonic() {
+        let cosine = CosineDistance;
+        let a = vec![1.0f32, 0.0];
+        let b = vec![0.0f32, 1.0];
+
+        match cosine.partial_nearness(&a, &b) {
+            NearnessValue::Monotonic(d) => assert!((d - cosine.distance(&a, &b)).abs() < 1e-6),
+            other => panic!("expected NearnessValue::Monotonic, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_measure_wraps_raw() {
+        let minkowski = MinkowskiDistance { p: 3.0 };
+        let a = vec![0.0f32, 0.0];
+        let b = vec![1.0f32, 1.0];
+
+        match minkowski.measure(&a, &b) {
+            MetricResult::Raw(d) => assert_eq!(d, minkowski.distance(&a, &b)),
+            other => panic!("expected MetricResult::Raw, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_simd_euclidean_matches_scalar_euclidean() {
+        let scalar = EuclideanDistance;
+        let simd = SimdEuclidean;
+
+        let a = vec![0.0f32, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let b = vec![3.0f32, 4.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let c = vec![1.0f32, 1.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+
+        assert!((simd.distance(&a, &b) - scalar.distance(&a, &b)).abs() < 1e-4);
+        assert_distance_properties(&simd, &a, &b, &c);
+    }
+
+    #[test]
+    fn test_simd_inner_product_matches_scalar_inner_product() {
+        let scalar = InnerProductDistance;
+        let simd = SimdInnerProduct;
+
+        let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+        assert!((simd.distance(&a, &b) - scalar.distance(&a, &b)).abs() < 1e-4);
+        assert!(!simd.is_metric());
+    }
+
+    #[test]
+    fn test_simd_cosine_matches_scalar_cosine() {
+        let scalar = CosineDistance;
+        let simd = SimdCosine;
+
+        let a = vec![1.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let b = vec![0.0f32, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+        assert!((simd.distance(&a, &b) - scalar.distance(&a, &b)).abs() < 1e-4);
+        assert!(simd.distance(&a, &a) < 1e-4);
+        assert!(!simd.is_metric());
+    }
+
+    #[test]
+    fn test_simd_manhattan_matches_scalar_manhattan() {
+        let scalar = ManhattanDistance;
+        let simd = SimdManhattan;
+
+        let a = vec![0.0f32, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let b = vec![3.0f32, 4.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let c = vec![1.0f32, 1.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+
+        assert!((simd.distance(&a, &b) - scalar.distance(&a, &b)).abs() < 1e-4);
+        assert_distance_properties(&simd, &a, &b, &c);
+    }
+
+    #[test]
+    fn test_quantized_euclidean_distance_u8() {
+        let quantized = QuantizedEuclideanDistance::new(0.1);
+
+        let a: Vec<u8> = vec![0, 0];
+        let b: Vec<u8> = vec![30, 40];
+
+        // Integer squared distance is 30^2 + 40^2 = 2500, dequantized by
+        // scale^2 = 0.01 gives 25.0, whose sqrt is 5.0.
+        let distance = quantized.distance(&a, &b);
+        assert!((distance - 5.0).abs() < 1e-4);
+
+        assert_distance_properties(&quantized, &a, &b, &vec![10u8, 10]);
+    }
+
+    #[test]
+    fn test_quantized_euclidean_distance_i8() {
+        let quantized = QuantizedEuclideanDistance::new(1.0);
+
+        let a: Vec<i8> = vec![-10, 0];
+        let b: Vec<i8> = vec![-10, 4];
+
+        let distance = quantized.distance(&a, &b);
+        assert!((distance - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_quantized_cosine_distance_u8() {
+        let cosine = QuantizedCosineDistance;
+
+        let a: Vec<u8> = vec![10, 0];
+        let b: Vec<u8> = vec![0, 10];
+
+        // Orthogonal vectors: cosine similarity 0, so distance 1.
+        let distance = cosine.distance(&a, &b);
+        assert!((distance - 1.0).abs() < 1e-4);
+
+        // Parallel vectors: cosine similarity 1, so distance 0.
+        let parallel_distance = cosine.distance(&a, &a);
+        assert!(parallel_distance < 1e-4);
+
+        assert!(!cosine.is_metric());
+    }
+
+    #[test]
+    fn test_quantized_cosine_distance_i8() {
+        let cosine = QuantizedCosineDistance;
+
+        let a: Vec<i8> = vec![10, 0];
+        let b: Vec<i8> = vec![-10, 0];
+
+        // Opposite-direction vectors: cosine similarity -1, so distance 2.
+        let distance = cosine.distance(&a, &b);
+        assert!((distance - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cosine_similarity_from_dot_product_zero_magnitude() {
+        assert_eq!(cosine_similarity_from_dot_product(0, 0, 10), 0.0);
+        assert_eq!(cosine_similarity_from_dot_product(0, 10, 0), 0.0);
+    }
+
+    #[test]
+    fn test_quantized_dot_product_widens_to_avoid_overflow() {
+        // 255 * 255 * 4 = 260100, which overflows a u16 but not an i64.
+        let a: Vec<u8> = vec![255; 4];
+        let b: Vec<u8> = vec![255; 4];
+        assert_eq!(quantized_dot_product_u8(&a, &b), 260_100);
+    }
+
     #[test]
     fn test_minkowski_distance_f32() {
         let l1 = MinkowskiDistance::manhattan();
@@ -488,4 +1465,108 @@ mod tests {
         let dist = euclidean.distance(&a, &b);
         assert!((dist * dist - squared_dist).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_distance_blanket_proximity_matches_measure() {
+        let euclidean = EuclideanDistance;
+        let a = vec![0.0f32, 0.0];
+        let b = vec![3.0f32, 4.0];
+
+        assert_eq!(
+            Proximity::proximity(&euclidean, &a, &b),
+            euclidean.measure(&a, &b)
+        );
+        assert_eq!(Proximity::name(&euclidean), Distance::<f32>::name(&euclidean));
+    }
+
+    #[test]
+    fn test_euclidean_manhattan_minkowski_are_metrics() {
+        fn assert_is_metric<T, M: Metric<T>>(_: &M) {}
+
+        assert_is_metric::<f32, _>(&EuclideanDistance);
+        assert_is_metric::<f32, _>(&ManhattanDistance);
+        assert_is_metric::<f32, _>(&MinkowskiDistance { p: 3.0 });
+    }
+
+    #[test]
+    fn test_dot_product_same_type_proximity() {
+        let dp = DotProduct::new();
+        let a = vec![1.0f32, 2.0, 3.0];
+        let b = vec![4.0f32, 5.0, 6.0];
+
+        match dp.proximity(&a, &b) {
+            MetricResult::DotProduct(p) => assert!((p - 32.0).abs() < 1e-6),
+            other => panic!("expected MetricResult::DotProduct, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dot_product_cross_type_f32_query_u8_item() {
+        let dp = DotProduct::with_scale(0.5);
+        let query = vec![1.0f32, 2.0, 3.0];
+        let item = vec![2u8, 4, 6];
+
+        // item dequantizes to [1.0, 2.0, 3.0], i.e. the same vector as the
+        // query itself.
+        match Proximity::proximity(&dp, &query, &item) {
+            MetricResult::DotProduct(p) => assert!((p - 14.0).abs() < 1e-6),
+            other => panic!("expected MetricResult::DotProduct, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hamming_distance_counts_differing_bits() {
+        let hamming = HammingDistance;
+        let a = vec![0b1111_0000u8];
+        let b = vec![0b1100_0000u8];
+        assert_eq!(hamming.distance(&a, &b), 2.0);
+    }
+
+    #[test]
+    fn test_default_is_pre_normalized_is_false() {
+        assert!(!EuclideanDistance.is_pre_normalized());
+        assert!(!CosineDistance.is_pre_normalized());
+        assert!(!InnerProductDistance.is_pre_normalized());
+    }
+
+    #[test]
+    fn test_partial_distance_matches_distance_on_full_prefix() {
+        let euclidean = EuclideanDistance;
+        let a = vec![0.0f32, 0.0, 3.0, 4.0];
+        let b = vec![3.0f32, 4.0, 0.0, 0.0];
+
+        // dims == len should be identical to the full distance
+        assert_eq!(
+            euclidean.partial_distance(&a, &b, 4),
+            euclidean.distance(&a, &b)
+        );
+
+        // A 2-dim prefix should match the distance over just that prefix
+        assert_eq!(
+            euclidean.partial_distance(&a, &b, 2),
+            euclidean.distance(&a[..2], &b[..2])
+        );
+    }
+
+    #[test]
+    fn test_partial_distance_clamps_to_shorter_length() {
+        let euclidean = EuclideanDistance;
+        let a = vec![3.0f32, 4.0];
+        let b = vec![3.0f32, 4.0];
+
+        // Requesting more dims than either vector has should clamp rather
+        // than panic.
+        assert_eq!(euclidean.partial_distance(&a, &b, 100), 0.0);
+    }
+
+    #[test]
+    fn test_hamming_distance_properties() {
+        let hamming = HammingDistance;
+        let a = vec![0b1010_1010u8, 0b0000_1111];
+        let b = vec![0b0101_0101u8, 0b1111_0000];
+        let c = vec![0b1111_1111u8, 0b1111_1111];
+
+        assert_distance_properties(&hamming, &a, &b, &c);
+        assert!(hamming.is_metric());
+    }
 }
\ No newline at end of file
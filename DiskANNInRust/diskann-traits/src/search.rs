@@ -15,13 +15,22 @@ pub struct SearchResult {
 }
 
 /// Scratch buffer for zero-allocation search operations
+///
+/// `candidates`/`distances`/`expanded` together are the GreedySearch candidate
+/// list: kept sorted by ascending distance and capped at the caller's
+/// `search_list_size`, with `expanded[i]` tracking whether `candidates[i]` has
+/// already had its own neighbors explored. `visited` is a separate, global
+/// "already inserted into the list" bitmap indexed by vector id, so a node is
+/// never queued twice even after it falls out of the capped list.
 #[derive(Debug)]
 pub struct SearchBuffer {
-    /// Candidate buffer for search operations
+    /// Candidate list, kept sorted by ascending distance and capped at `search_list_size`
     pub candidates: Vec<VectorId>,
-    /// Distance buffer for storing distances during search
+    /// Distances parallel to `candidates`
     pub distances: Vec<f32>,
-    /// Visited set buffer
+    /// Per-candidate expansion flag, parallel to `candidates`
+    pub expanded: Vec<bool>,
+    /// Global "already inserted into the candidate list" bitmap, indexed by vector id
     pub visited: Vec<bool>,
 }
 
@@ -31,6 +40,7 @@ impl SearchBuffer {
         Self {
             candidates: Vec::with_capacity(capacity),
             distances: Vec::with_capacity(capacity),
+            expanded: Vec::with_capacity(capacity),
             visited: Vec::with_capacity(capacity),
         }
     }
@@ -39,6 +49,7 @@ impl SearchBuffer {
     pub fn clear(&mut self) {
         self.candidates.clear();
         self.distances.clear();
+        self.expanded.clear();
         self.visited.clear();
     }
 
@@ -10,6 +10,8 @@ struct FuzzInput {
     // Header data
     num_points: i32,
     num_dimensions: i32,
+    // Dtype code; fuzzed independently so out-of-range codes exercise validate()
+    dtype: i32,
     // Variable length data following the header
     data: Vec<u8>,
 }
@@ -17,11 +19,12 @@ struct FuzzInput {
 fuzz_target!(|input: FuzzInput| {
     // Test binary header parsing with fuzzed data
     let mut cursor = Cursor::new(Vec::new());
-    
+
     // Write fuzzed header
     let header = BinaryHeader {
         num_points: input.num_points,
         num_dimensions: input.num_dimensions,
+        dtype: input.dtype,
     };
     
     // Try to write header - this should not panic